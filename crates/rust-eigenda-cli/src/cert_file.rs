@@ -0,0 +1,98 @@
+//! Cert files read and written by the CLI.
+//!
+//! JSON certs are read and written as is. Binary certs (RLP, ABI and bincode, all prefixed by the version byte of the
+//! cert) are written to files as raw bytes and printed as `0x` hex, and are read from either form.
+use rust_eigenda_v2_common::EigenDACert;
+
+use crate::errors::CertFileError;
+
+/// Encoding of a cert file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum CertEncoding {
+    /// The human-readable JSON representation.
+    Json,
+    /// The RLP encoding of the eigenda-proxy.
+    Rlp,
+    /// The ABI encoding of the cert verifier contracts.
+    Abi,
+    /// The bincode encoding of the Rust clients.
+    Bincode,
+}
+
+/// Encodes a cert. JSON certs are pretty-printed.
+pub fn encode_cert(cert: &EigenDACert, encoding: CertEncoding) -> Result<Vec<u8>, CertFileError> {
+    match encoding {
+        CertEncoding::Json => {
+            let mut json = serde_json::to_vec_pretty(cert)?;
+            json.push(b'\n');
+            Ok(json)
+        }
+        CertEncoding::Rlp => Ok(cert.to_rlp_bytes()),
+        CertEncoding::Abi => Ok(cert.to_abi_bytes()),
+        CertEncoding::Bincode => Ok(cert.to_bytes()?),
+    }
+}
+
+/// Decodes a cert file, whether binary certs are raw bytes or hex.
+pub fn decode_cert(contents: &[u8], encoding: CertEncoding) -> Result<EigenDACert, CertFileError> {
+    match encoding {
+        CertEncoding::Json => Ok(serde_json::from_slice(contents)?),
+        CertEncoding::Rlp => Ok(EigenDACert::from_rlp_bytes(&binary_contents(contents)?)?),
+        CertEncoding::Abi => Ok(EigenDACert::from_abi_bytes(&binary_contents(contents)?)?),
+        CertEncoding::Bincode => Ok(EigenDACert::from_bytes(&binary_contents(contents)?)?),
+    }
+}
+
+/// Returns the bytes of a binary file, decoding them if the file is `0x` hex.
+pub fn binary_contents(contents: &[u8]) -> Result<Vec<u8>, CertFileError> {
+    // raw certs cannot start with "0x", as it is not a valid version byte
+    if let Some(hex) = std::str::from_utf8(contents)
+        .ok()
+        .and_then(|text| text.trim().strip_prefix("0x"))
+    {
+        return Ok(hex::decode(hex)?);
+    }
+    Ok(contents.to_vec())
+}
+
+/// Text printed for an encoded cert: JSON as is, binary encodings as `0x` hex.
+pub fn display_cert(bytes: &[u8], encoding: CertEncoding) -> String {
+    match encoding {
+        CertEncoding::Json => String::from_utf8_lossy(bytes).into_owned(),
+        _ => format!("0x{}\n", hex::encode(bytes)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_test_eigenda_cert;
+
+    const ENCODINGS: [CertEncoding; 4] = [
+        CertEncoding::Json,
+        CertEncoding::Rlp,
+        CertEncoding::Abi,
+        CertEncoding::Bincode,
+    ];
+
+    #[test]
+    fn test_cert_file_round_trip() {
+        let cert = get_test_eigenda_cert();
+        for encoding in ENCODINGS {
+            let bytes = encode_cert(&cert, encoding).unwrap();
+            assert_eq!(decode_cert(&bytes, encoding).unwrap(), cert);
+
+            let text = display_cert(&bytes, encoding);
+            assert_eq!(decode_cert(text.as_bytes(), encoding).unwrap(), cert);
+        }
+    }
+
+    #[test]
+    fn test_cert_file_wrong_encoding() {
+        let cert = get_test_eigenda_cert();
+        let rlp = encode_cert(&cert, CertEncoding::Rlp).unwrap();
+        assert!(decode_cert(&rlp, CertEncoding::Abi).is_err());
+        assert!(decode_cert(&rlp, CertEncoding::Json).is_err());
+        assert!(decode_cert(b"0xzz", CertEncoding::Rlp).is_err());
+    }
+}
@@ -0,0 +1,434 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ethereum_types::H160;
+use rust_eigenda_client::network::Network as V1Network;
+use rust_eigenda_v2_client::{
+    cert_verifier_router::CertVerifierAddress,
+    network::{Network, NetworkConfig},
+};
+use rust_eigenda_v2_common::EigenDACertVersion;
+use url::Url;
+
+use crate::{cert_file::CertEncoding, errors::CliError, signer::CliSigner};
+
+/// Disperses, retrieves and inspects EigenDA blobs and certs.
+///
+/// Every option may also be set with its `EIGENDA_*` environment variable.
+// not Debug, as the signer args hold secrets
+#[derive(Parser)]
+#[command(name = "eigenda", version)]
+pub struct Cli {
+    #[command(flatten)]
+    pub client: ClientArgs,
+    #[command(flatten)]
+    pub signer: SignerArgs,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ClientVersion {
+    /// The v1 `EigenClient`.
+    V1,
+    /// The v2 payload disperser and retrievers.
+    V2,
+}
+
+/// Public EigenDA network, whose canonical disperser and contracts are used unless overridden.
+///
+/// The v2 disperser and contracts are only preset for Holesky, those of the other networks must be set explicitly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum NetworkArg {
+    Mainnet,
+    Holesky,
+    Sepolia,
+}
+
+impl NetworkArg {
+    /// Returns the v2 deployment of the network, if it is preset.
+    pub fn v2(self) -> Option<Network> {
+        match self {
+            NetworkArg::Holesky => Some(Network::Holesky),
+            NetworkArg::Mainnet | NetworkArg::Sepolia => None,
+        }
+    }
+
+    /// Returns the v2 deployment of the network, or an error asking for `arg` if it is not preset.
+    fn v2_config(self, arg: &'static str) -> Result<NetworkConfig, CliError> {
+        self.v2()
+            .map(|network| network.config())
+            .ok_or(CliError::NoV2Preset(self, arg))
+    }
+
+    pub fn v1(self) -> V1Network {
+        match self {
+            NetworkArg::Mainnet => V1Network::Mainnet,
+            NetworkArg::Holesky => V1Network::Holesky,
+            NetworkArg::Sepolia => V1Network::Sepolia,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CertVersion {
+    V2,
+    V3,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Disperses the contents of a file, printing the blob key (v2) or request id (v1).
+    Disperse { file: PathBuf },
+    /// Prints the status of a blob.
+    Status { blob_key: String },
+    /// Waits for a blob to be certified, printing its cert (v2) or inclusion data (v1).
+    Wait {
+        blob_key: String,
+        #[arg(long, default_value_t = 300)]
+        timeout_secs: u64,
+        #[arg(long, value_enum, default_value_t = CertEncoding::Json)]
+        encoding: CertEncoding,
+        /// File the cert is written to, instead of printing it.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Retrieves the payload of a cert (v2) or inclusion data (v1).
+    Get {
+        cert_file: PathBuf,
+        #[arg(long, value_enum, default_value_t = CertEncoding::Json)]
+        encoding: CertEncoding,
+        /// File the payload is written to, instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Verifies a cert with the cert verifier, at the latest block.
+    Verify {
+        cert_file: PathBuf,
+        #[arg(long, value_enum, default_value_t = CertEncoding::Json)]
+        encoding: CertEncoding,
+    },
+    /// Pretty-prints a cert as JSON.
+    DecodeCert {
+        cert_file: PathBuf,
+        #[arg(long, value_enum, default_value_t = CertEncoding::Rlp)]
+        encoding: CertEncoding,
+    },
+    /// Prints the payment state of the account of the signer.
+    PaymentState,
+    /// Checks the disperser, the payment state of the signer, the contracts, the relays of `--relay-keys` and the
+    /// SRS, printing the result of each check.
+    Preflight,
+    /// Inspects the relays of the relay registry.
+    Relays {
+        #[command(subcommand)]
+        command: RelaysCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RelaysCommand {
+    /// Lists the URLs of the relays, those of `--relay-keys` or else every registered one.
+    List,
+}
+
+/// Options of the clients.
+#[derive(Debug, Args)]
+pub struct ClientArgs {
+    #[arg(long, value_enum, default_value_t = ClientVersion::V2, env = "EIGENDA_CLIENT")]
+    pub client: ClientVersion,
+    /// Network whose disperser and contracts are used by default. The ETH RPC must be connected to its chain.
+    #[arg(long, value_enum, env = "EIGENDA_NETWORK")]
+    pub network: Option<NetworkArg>,
+    #[arg(long, env = "EIGENDA_DISPERSER_RPC")]
+    pub disperser_rpc: Option<String>,
+    #[arg(long, env = "EIGENDA_ETH_RPC_URL", hide_env_values = true)]
+    pub eth_rpc_url: Option<Url>,
+    /// Connects to the disperser without TLS.
+    #[arg(long, env = "EIGENDA_INSECURE_GRPC")]
+    pub insecure_grpc: bool,
+    #[arg(
+        long,
+        env = "EIGENDA_CERT_VERIFIER_ADDRESS",
+        conflicts_with = "cert_verifier_router_address"
+    )]
+    pub cert_verifier_address: Option<H160>,
+    #[arg(long, env = "EIGENDA_CERT_VERIFIER_ROUTER_ADDRESS")]
+    pub cert_verifier_router_address: Option<H160>,
+    #[arg(long, env = "EIGENDA_RELAY_REGISTRY_ADDRESS")]
+    pub relay_registry_address: Option<H160>,
+    #[arg(long, env = "EIGENDA_RELAY_KEYS", value_delimiter = ',')]
+    pub relay_keys: Vec<u32>,
+    #[arg(long, env = "EIGENDA_BLOB_VERSION", default_value_t = 0)]
+    pub blob_version: u16,
+    /// Version of the certs verified by the cert verifier. Defaults to the version of the network, or V2.
+    #[arg(long, env = "EIGENDA_CERT_VERSION", value_enum)]
+    pub cert_version: Option<CertVersion>,
+    /// Disperses payloads in evaluation form, instead of coefficient form.
+    #[arg(long, env = "EIGENDA_EVAL_FORM")]
+    pub eval_form: bool,
+    #[arg(long, env = "EIGENDA_CONFIRMATION_DEPTH", default_value_t = 0)]
+    pub confirmation_depth: u32,
+    /// Path of the G1 points of the SRS (v2).
+    #[arg(
+        long,
+        env = "EIGENDA_SRS_G1_PATH",
+        default_value = "resources/g1.point"
+    )]
+    pub srs_g1_path: PathBuf,
+    /// Number of points of the SRS the G1 points belong to (v2). Verifying length proofs needs the G1 file to hold
+    /// all of them.
+    #[arg(long, env = "EIGENDA_SRS_ORDER", default_value_t = 268435456)]
+    pub srs_order: u32,
+    /// Skips verifying the length proofs of retrieved blobs (v2), which needs a G1 file holding all the points of the
+    /// SRS: `get` fails with the truncated files otherwise. The blob length claimed by the cert is then not proven.
+    #[arg(long, env = "EIGENDA_SKIP_LENGTH_PROOF")]
+    pub skip_length_proof: bool,
+    /// Directory holding `g1.point` and `g2.point.powerOf2` (v1).
+    #[arg(long, env = "EIGENDA_SRS_PATH", default_value = "resources")]
+    pub srs_path: String,
+    /// Address of the EigenDAServiceManager (v1).
+    #[arg(long, env = "EIGENDA_SVC_MANAGER_ADDRESS")]
+    pub svc_manager_address: Option<H160>,
+    /// Waits for blobs to be finalized, not only confirmed (v1).
+    #[arg(long, env = "EIGENDA_WAIT_FOR_FINALIZATION")]
+    pub wait_for_finalization: bool,
+    /// Disperses with authenticated requests (v1).
+    #[arg(long, env = "EIGENDA_AUTHENTICATED")]
+    pub authenticated: bool,
+}
+
+impl ClientArgs {
+    /// Returns the chain id the ETH RPC must be connected to, that of `--network` if set.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.network.map(|network| network.v1().chain_id())
+    }
+
+    pub fn disperser_rpc(&self) -> Result<String, CliError> {
+        if let Some(disperser_rpc) = &self.disperser_rpc {
+            return Ok(disperser_rpc.clone());
+        }
+        match (self.network, self.client) {
+            (Some(network), ClientVersion::V1) => Ok(network.v1().config().disperser_rpc),
+            (Some(network), ClientVersion::V2) => {
+                Ok(network.v2_config("--disperser-rpc")?.disperser_rpc)
+            }
+            (None, _) => Err(CliError::MissingArg("--disperser-rpc or --network")),
+        }
+    }
+
+    pub fn eth_rpc_url(&self) -> Result<Url, CliError> {
+        self.eth_rpc_url
+            .clone()
+            .ok_or(CliError::MissingArg("--eth-rpc-url"))
+    }
+
+    pub fn cert_verifier_address(&self) -> Result<CertVerifierAddress, CliError> {
+        match (
+            self.cert_verifier_address,
+            self.cert_verifier_router_address,
+            self.network,
+        ) {
+            (Some(address), _, _) => Ok(CertVerifierAddress::Verifier(address)),
+            (None, Some(address), _) => Ok(CertVerifierAddress::Router(address)),
+            (None, None, Some(network)) => Ok(network
+                .v2_config("--cert-verifier-address or --cert-verifier-router-address")?
+                .cert_verifier_address),
+            (None, None, None) => Err(CliError::MissingArg(
+                "--cert-verifier-address, --cert-verifier-router-address or --network",
+            )),
+        }
+    }
+
+    pub fn cert_version(&self) -> EigenDACertVersion {
+        match (self.cert_version, self.network) {
+            (Some(CertVersion::V2), _) => EigenDACertVersion::V2,
+            (Some(CertVersion::V3), _) => EigenDACertVersion::V3,
+            (None, network) => network
+                .and_then(NetworkArg::v2)
+                .map_or(EigenDACertVersion::V2, |network| {
+                    network.config().cert_version
+                }),
+        }
+    }
+
+    pub fn relay_registry_address(&self) -> Result<H160, CliError> {
+        match (self.relay_registry_address, self.network) {
+            (Some(address), _) => Ok(address),
+            (None, Some(network)) => Ok(network
+                .v2_config("--relay-registry-address")?
+                .relay_registry_address),
+            (None, None) => Err(CliError::MissingArg(
+                "--relay-registry-address or --network",
+            )),
+        }
+    }
+
+    pub fn svc_manager_address(&self) -> Result<H160, CliError> {
+        self.svc_manager_address
+            .or(self
+                .network
+                .map(|network| network.v1().config().eigenda_svc_manager_address))
+            .ok_or(CliError::MissingArg("--svc-manager-address or --network"))
+    }
+}
+
+/// Signer of the requests: a private key, a keystore or Web3Signer.
+#[derive(Args)]
+pub struct SignerArgs {
+    /// Hex encoded private key.
+    #[arg(
+        long,
+        env = "EIGENDA_SIGNER_PRIVATE_KEY",
+        hide_env_values = true,
+        group = "signer_source"
+    )]
+    pub private_key: Option<String>,
+    /// Encrypted keystore holding the private key.
+    #[arg(
+        long,
+        env = "EIGENDA_KEYSTORE",
+        group = "signer_source",
+        requires = "keystore_password"
+    )]
+    pub keystore: Option<PathBuf>,
+    #[arg(long, env = "EIGENDA_KEYSTORE_PASSWORD", hide_env_values = true)]
+    pub keystore_password: Option<String>,
+    /// URL of a Web3Signer instance, see the `signer` module.
+    #[arg(
+        long,
+        env = "EIGENDA_REMOTE_SIGNER_URL",
+        hide_env_values = true,
+        group = "signer_source",
+        requires = "remote_signer_address"
+    )]
+    pub remote_signer_url: Option<Url>,
+    /// Address of the account whose key Web3Signer holds.
+    #[arg(long, env = "EIGENDA_REMOTE_SIGNER_ADDRESS")]
+    pub remote_signer_address: Option<H160>,
+}
+
+impl SignerArgs {
+    /// Loads the signer. Commands that only read fall back to a throwaway key, as their requests must still be signed.
+    ///
+    /// `chain_id` is the chain of the transactions signed by Web3Signer, mainnet if unknown.
+    pub async fn load(&self, chain_id: Option<u64>, required: bool) -> Result<CliSigner, CliError> {
+        if let Some(private_key) = &self.private_key {
+            return Ok(CliSigner::from_private_key(private_key)?);
+        }
+        if let Some(keystore) = &self.keystore {
+            let password = self
+                .keystore_password
+                .as_deref()
+                .ok_or(CliError::MissingArg("--keystore-password"))?;
+            return Ok(CliSigner::from_keystore(keystore, password)?);
+        }
+        if let Some(url) = &self.remote_signer_url {
+            let address = self
+                .remote_signer_address
+                .ok_or(CliError::MissingArg("--remote-signer-address"))?;
+            let chain_id = chain_id.unwrap_or(1);
+            return Ok(CliSigner::connect_web3signer(url.as_str(), address, chain_id).await?);
+        }
+        if required {
+            return Err(CliError::MissingArg(
+                "--private-key, --keystore or --remote-signer-url",
+            ));
+        }
+        Ok(CliSigner::random())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from([
+            "eigenda",
+            "--client",
+            "v1",
+            "--relay-keys",
+            "0,1,2",
+            "wait",
+            "0x1234",
+            "--encoding",
+            "rlp",
+        ])
+        .unwrap();
+        assert_eq!(cli.client.client, ClientVersion::V1);
+        assert_eq!(cli.client.relay_keys, vec![0, 1, 2]);
+        assert!(cli.client.disperser_rpc().is_err());
+        assert!(matches!(
+            cli.command,
+            Command::Wait {
+                encoding: CertEncoding::Rlp,
+                timeout_secs: 300,
+                ..
+            }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "eigenda",
+            "--network",
+            "holesky",
+            "--relay-registry-address",
+            "0x0000000000000000000000000000000000000001",
+            "status",
+            "0x1234",
+        ])
+        .unwrap();
+        let holesky = Network::Holesky.config();
+        assert_eq!(cli.client.chain_id(), Some(holesky.chain_id));
+        assert_eq!(cli.client.disperser_rpc().unwrap(), holesky.disperser_rpc);
+        assert_eq!(
+            cli.client.cert_verifier_address().unwrap(),
+            holesky.cert_verifier_address
+        );
+        assert_eq!(
+            cli.client.relay_registry_address().unwrap(),
+            H160::from_low_u64_be(1)
+        );
+
+        let cli = Cli::try_parse_from([
+            "eigenda",
+            "--network",
+            "mainnet",
+            "--disperser-rpc",
+            "https://disperser.example",
+            "status",
+            "0x1234",
+        ])
+        .unwrap();
+        assert_eq!(cli.client.chain_id(), Some(1));
+        assert_eq!(
+            cli.client.disperser_rpc().unwrap(),
+            "https://disperser.example"
+        );
+        assert!(matches!(
+            cli.client.cert_verifier_address(),
+            Err(CliError::NoV2Preset(NetworkArg::Mainnet, _))
+        ));
+        assert_eq!(cli.client.cert_version(), EigenDACertVersion::V2);
+
+        let cli = Cli::try_parse_from(["eigenda", "preflight"]).unwrap();
+        assert!(matches!(cli.command, Command::Preflight));
+
+        let conflicting_signers = Cli::try_parse_from([
+            "eigenda",
+            "--private-key",
+            "0x11",
+            "--remote-signer-url",
+            "http://localhost:9000",
+            "--remote-signer-address",
+            "0x0000000000000000000000000000000000000001",
+            "payment-state",
+        ]);
+        assert!(conflicting_signers.is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use rust_eigenda_client::errors::{
+    ConfigError as V1ConfigError, EigenClientError as V1EigenClientError,
+};
+use rust_eigenda_v2_client::errors::{
+    CertVerifierError, ConversionError, DisperseError, EigenClientError, PayloadDisperserError,
+    RelayClientError, RelayPayloadRetrieverError,
+};
+use rust_eigenda_v2_common::EigenDACertError;
+
+use crate::cli::NetworkArg;
+
+/// Errors of the commands of the CLI
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error("Missing {0}")]
+    MissingArg(&'static str),
+    #[error("Invalid argument: {0}")]
+    InvalidArg(String),
+    #[error("The v2 deployment of --network {0:?} is not preset, set {1}")]
+    NoV2Preset(NetworkArg, &'static str),
+    #[error("{0} is not supported by the v1 client")]
+    UnsupportedV1(&'static str),
+    #[error("Timed out waiting for blob {0}")]
+    Timeout(String),
+    #[error("{0} preflight checks failed")]
+    PreflightFailed(usize),
+    #[error("Blob not found")]
+    BlobNotFound,
+    #[error("Failed to read or write file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    CertFile(#[from] CertFileError),
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+    #[error(transparent)]
+    V1Config(#[from] V1ConfigError),
+    #[error(transparent)]
+    V1Client(#[from] V1EigenClientError),
+    #[error(transparent)]
+    Client(#[from] EigenClientError),
+    #[error(transparent)]
+    PayloadDisperser(#[from] PayloadDisperserError),
+    #[error(transparent)]
+    Disperse(#[from] DisperseError),
+    #[error(transparent)]
+    RelayClient(#[from] RelayClientError),
+    #[error(transparent)]
+    RelayPayloadRetriever(#[from] RelayPayloadRetrieverError),
+    #[error(transparent)]
+    CertVerifier(#[from] CertVerifierError),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+}
+
+/// Errors of the cert files read and written by the CLI
+#[derive(Debug, thiserror::Error)]
+pub enum CertFileError {
+    #[error("Invalid hex cert: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Invalid JSON cert: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Cert(#[from] EigenDACertError),
+    #[error("Invalid v1 inclusion data: {0}")]
+    V1InclusionData(String),
+}
+
+/// Errors of the signers of the CLI
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    #[error("Invalid private key")]
+    InvalidPrivateKey,
+    #[error("Failed to decrypt keystore: {0}")]
+    Keystore(#[from] eth_keystore::KeystoreError),
+    #[error(transparent)]
+    Local(#[from] rust_eigenda_signers::signers::private_key::Error),
+    #[error(transparent)]
+    Remote(#[from] rust_eigenda_signers::signers::web3signer::Error),
+}
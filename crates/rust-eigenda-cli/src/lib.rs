@@ -0,0 +1,180 @@
+pub mod cert_file;
+pub mod cli;
+pub mod errors;
+pub mod signer;
+pub mod v1;
+pub mod v2;
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use rust_eigenda_v2_client::core::BlobKey;
+
+use crate::{
+    cert_file::{binary_contents, decode_cert, display_cert, encode_cert, CertEncoding},
+    cli::{Cli, ClientVersion, Command, RelaysCommand},
+    errors::CliError,
+};
+
+/// Runs a command, printing its result to stdout.
+pub async fn run(cli: Cli) -> Result<(), CliError> {
+    let Cli {
+        client: args,
+        signer,
+        command,
+    } = cli;
+    let v1 = args.client == ClientVersion::V1;
+
+    match command {
+        Command::Disperse { file } => {
+            let payload = std::fs::read(file)?;
+            let signer = signer.load(args.chain_id(), true).await?;
+            if v1 {
+                println!("{}", v1::disperse(&args, signer, payload).await?);
+            } else {
+                println!("{}", v2::disperse(&args, signer, payload).await?.to_hex());
+            }
+        }
+        Command::Status { blob_key } => {
+            let signer = signer.load(args.chain_id(), false).await?;
+            if v1 {
+                let status = match v1::inclusion_data(&args, signer, &blob_key).await? {
+                    Some(_) => "CONFIRMED",
+                    None => "PROCESSING",
+                };
+                println!("{}", status);
+            } else {
+                let blob_key = parse_blob_key(&blob_key)?;
+                println!("{}", v2::status(&args, signer, &blob_key).await?);
+            }
+        }
+        Command::Wait {
+            blob_key,
+            timeout_secs,
+            encoding,
+            out,
+        } => {
+            let signer = signer.load(args.chain_id(), false).await?;
+            let timeout = Duration::from_secs(timeout_secs);
+            let (bytes, encoding) = if v1 {
+                // v1 certs are always the ABI encoded blob info
+                let inclusion_data = v1::wait(&args, signer, &blob_key, timeout).await?;
+                (inclusion_data, CertEncoding::Abi)
+            } else {
+                let blob_key = parse_blob_key(&blob_key)?;
+                let cert = v2::wait(&args, signer, &blob_key, timeout).await?;
+                (encode_cert(&cert, encoding)?, encoding)
+            };
+            match out {
+                Some(out) => std::fs::write(out, bytes)?,
+                None => print!("{}", display_cert(&bytes, encoding)),
+            }
+        }
+        Command::Get {
+            cert_file,
+            encoding,
+            out,
+        } => {
+            let contents = std::fs::read(cert_file)?;
+            let signer = signer.load(args.chain_id(), false).await?;
+            let payload = if v1 {
+                v1::get(&args, signer, &binary_contents(&contents)?).await?
+            } else {
+                v2::get(&args, signer, decode_cert(&contents, encoding)?).await?
+            };
+            write_output(out, &payload)?;
+        }
+        Command::Verify {
+            cert_file,
+            encoding,
+        } => {
+            if v1 {
+                return Err(CliError::UnsupportedV1("verify"));
+            }
+            let cert = read_cert(&cert_file, encoding)?;
+            v2::verify(&args, signer.load(args.chain_id(), false).await?, &cert).await?;
+            println!("valid");
+        }
+        Command::DecodeCert {
+            cert_file,
+            encoding,
+        } => {
+            let cert = read_cert(&cert_file, encoding)?;
+            print!(
+                "{}",
+                display_cert(&encode_cert(&cert, CertEncoding::Json)?, CertEncoding::Json)
+            );
+        }
+        Command::PaymentState => {
+            if v1 {
+                return Err(CliError::UnsupportedV1("payment-state"));
+            }
+            print!(
+                "{}",
+                v2::payment_state(&args, signer.load(args.chain_id(), true).await?).await?
+            );
+        }
+        Command::Preflight => {
+            if v1 {
+                return Err(CliError::UnsupportedV1("preflight"));
+            }
+            let report = v2::preflight(&args, signer.load(args.chain_id(), true).await?).await?;
+            print!("{}", report);
+            let failures = report.failures().count();
+            if failures > 0 {
+                return Err(CliError::PreflightFailed(failures));
+            }
+        }
+        Command::Relays {
+            command: RelaysCommand::List,
+        } => {
+            if v1 {
+                return Err(CliError::UnsupportedV1("relays"));
+            }
+            for (relay_key, url) in
+                v2::list_relays(&args, signer.load(args.chain_id(), false).await?).await?
+            {
+                println!("{}\t{}", relay_key, url);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_blob_key(blob_key: &str) -> Result<BlobKey, CliError> {
+    Ok(BlobKey::from_hex(
+        blob_key.strip_prefix("0x").unwrap_or(blob_key),
+    )?)
+}
+
+fn read_cert(
+    path: &Path,
+    encoding: CertEncoding,
+) -> Result<rust_eigenda_v2_common::EigenDACert, CliError> {
+    Ok(decode_cert(&std::fs::read(path)?, encoding)?)
+}
+
+fn write_output(out: Option<PathBuf>, bytes: &[u8]) -> Result<(), CliError> {
+    match out {
+        Some(out) => std::fs::write(out, bytes)?,
+        None => std::io::stdout().write_all(bytes)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_eigenda_v2_common::{EigenDACert, EigenDACertV2};
+
+    const EIGENDA_CERT_V2_RLP_HEX: &str =
+        include_str!("../../rust-eigenda-v2-common/testdata/eigenda_cert_v2.rlp.hex");
+
+    /// The cert of the test vectors of `rust-eigenda-v2-common`.
+    pub fn get_test_eigenda_cert() -> EigenDACert {
+        let bytes = hex::decode(EIGENDA_CERT_V2_RLP_HEX.trim()).unwrap();
+        EigenDACert::V2(EigenDACertV2::from_rlp_bytes(&bytes).unwrap())
+    }
+}
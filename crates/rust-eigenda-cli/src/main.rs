@@ -0,0 +1,16 @@
+use clap::Parser;
+use rust_eigenda_cli::cli::Cli;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() {
+    // logs go to stderr, so that they never mix with the output of the commands
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+    if let Err(e) = rust_eigenda_cli::run(Cli::parse()).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,92 @@
+//! Signers of the dispersals and requests of the CLI: a private key, an encrypted keystore or a
+//! [Web3Signer](https://github.com/Consensys/web3signer) instance.
+//!
+//! Web3Signer does not sign raw digests, so the requests that EigenDA authenticates with a signature of their digest
+//! (dispersals, payment state and relay chunk requests) fail with it, see
+//! [`rust_eigenda_signers::signers::web3signer`].
+use std::{path::Path, str::FromStr};
+
+use async_trait::async_trait;
+use ethereum_types::H160;
+use rust_eigenda_signers::{
+    signers::{private_key::Signer as PrivateKeySigner, web3signer::Signer as Web3Signer},
+    Message, PublicKey, RecoverableSignature, SecretKey, Sign,
+};
+
+use crate::errors::SignerError;
+
+/// Signer selected in the command line.
+#[derive(Clone, Debug)]
+pub enum CliSigner {
+    Local(PrivateKeySigner),
+    Remote(Box<Web3Signer>),
+}
+
+impl CliSigner {
+    /// Signer of a hex encoded private key.
+    pub fn from_private_key(private_key: &str) -> Result<Self, SignerError> {
+        let private_key = private_key.trim();
+        let secret_key = SecretKey::from_str(private_key.strip_prefix("0x").unwrap_or(private_key))
+            .map_err(|_| SignerError::InvalidPrivateKey)?;
+        Ok(CliSigner::Local(PrivateKeySigner::new(secret_key)))
+    }
+
+    /// Signer of the private key of an encrypted (Web3 Secret Storage) keystore.
+    pub fn from_keystore(path: &Path, password: &str) -> Result<Self, SignerError> {
+        let key = eth_keystore::decrypt_key(path, password)?;
+        let key: [u8; 32] = key.try_into().map_err(|_| SignerError::InvalidPrivateKey)?;
+        let secret_key = SecretKey::new(key).map_err(|_| SignerError::InvalidPrivateKey)?;
+        Ok(CliSigner::Local(PrivateKeySigner::new(secret_key)))
+    }
+
+    /// Signer of the key of `address` held by Web3Signer, see the module docs.
+    pub async fn connect_web3signer(
+        url: &str,
+        address: H160,
+        chain_id: u64,
+    ) -> Result<Self, SignerError> {
+        let signer = Web3Signer::connect(url, address, chain_id).await?;
+        Ok(CliSigner::Remote(Box::new(signer)))
+    }
+
+    /// Throwaway signer, for commands that only read and whose requests must be signed by some key.
+    pub fn random() -> Self {
+        CliSigner::Local(PrivateKeySigner::random(&mut rand::thread_rng()))
+    }
+}
+
+#[async_trait]
+impl Sign for CliSigner {
+    type Error = SignerError;
+
+    async fn sign_digest(&self, message: &Message) -> Result<RecoverableSignature, Self::Error> {
+        match self {
+            CliSigner::Local(signer) => Ok(signer.sign_digest(message).await?),
+            CliSigner::Remote(signer) => Ok(signer.sign_digest(message).await?),
+        }
+    }
+
+    fn public_key(&self) -> PublicKey {
+        match self {
+            CliSigner::Local(signer) => signer.public_key(),
+            CliSigner::Remote(signer) => signer.public_key(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_private_key() {
+        let key = "0x1111111111111111111111111111111111111111111111111111111111111111";
+        let signer = CliSigner::from_private_key(key).unwrap();
+        let unprefixed = CliSigner::from_private_key(&key[2..]).unwrap();
+        assert_eq!(signer.public_key(), unprefixed.public_key());
+        assert!(matches!(
+            CliSigner::from_private_key("0x1234"),
+            Err(SignerError::InvalidPrivateKey)
+        ));
+    }
+}
@@ -0,0 +1,222 @@
+//! Commands run with the v1 [`EigenClient`], whose certs are the ABI encoded `BlobInfo` returned as inclusion data.
+use std::{error::Error, sync::Arc, time::Duration};
+
+use ethabi::{ParamType, Token};
+use rust_eigenda_client::{
+    client::BlobProvider,
+    config::{EigenConfig, SecretUrl, SrsPointsSource},
+    EigenClient,
+};
+
+use crate::{
+    cli::ClientArgs,
+    errors::{CertFileError, CliError},
+    signer::CliSigner,
+};
+
+/// The CLI does not store blobs, so the data retrieved by the client is only checked against its commitment.
+#[derive(Debug)]
+struct NoBlobProvider;
+
+#[async_trait::async_trait]
+impl BlobProvider for NoBlobProvider {
+    async fn get_blob(
+        &self,
+        _blob_id: &str,
+    ) -> Result<Option<Vec<u8>>, Box<dyn Error + Send + Sync>> {
+        Ok(None)
+    }
+}
+
+async fn client(args: &ClientArgs, signer: CliSigner) -> Result<EigenClient<CliSigner>, CliError> {
+    let mut config = EigenConfig::new(
+        args.disperser_rpc()?,
+        SecretUrl::new(args.eth_rpc_url()?),
+        args.confirmation_depth,
+        args.svc_manager_address()?,
+        args.wait_for_finalization,
+        args.authenticated,
+        SrsPointsSource::Path(args.srs_path.clone()),
+        vec![],
+    )?;
+    if let Some(chain_id) = args.chain_id() {
+        config = config.with_chain_id(chain_id);
+    }
+    Ok(EigenClient::new(config, signer, Arc::new(NoBlobProvider)).await?)
+}
+
+/// Disperses a payload, returning the request id of the blob.
+pub async fn disperse(
+    args: &ClientArgs,
+    signer: CliSigner,
+    payload: Vec<u8>,
+) -> Result<String, CliError> {
+    Ok(client(args, signer).await?.dispatch_blob(payload).await?)
+}
+
+/// Returns the inclusion data of a blob, if it is already confirmed (or finalized, if waiting for finalization).
+pub async fn inclusion_data(
+    args: &ClientArgs,
+    signer: CliSigner,
+    request_id: &str,
+) -> Result<Option<Vec<u8>>, CliError> {
+    Ok(client(args, signer)
+        .await?
+        .get_inclusion_data(request_id)
+        .await?)
+}
+
+/// Waits for the inclusion data of a blob.
+pub async fn wait(
+    args: &ClientArgs,
+    signer: CliSigner,
+    request_id: &str,
+    timeout: Duration,
+) -> Result<Vec<u8>, CliError> {
+    let client = client(args, signer).await?;
+    let start = tokio::time::Instant::now();
+    loop {
+        if let Some(inclusion_data) = client.get_inclusion_data(request_id).await? {
+            return Ok(inclusion_data);
+        }
+        if start.elapsed() >= timeout {
+            return Err(CliError::Timeout(request_id.to_string()));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Retrieves the payload of the blob of the given inclusion data.
+pub async fn get(
+    args: &ClientArgs,
+    signer: CliSigner,
+    inclusion_data: &[u8],
+) -> Result<Vec<u8>, CliError> {
+    let (blob_index, batch_header_hash) = retrieval_params(inclusion_data)?;
+    client(args, signer)
+        .await?
+        .get_blob(blob_index, batch_header_hash)
+        .await?
+        .ok_or(CliError::BlobNotFound)
+}
+
+fn blob_info_param_type() -> ParamType {
+    let g1_commitment = ParamType::Tuple(vec![ParamType::Uint(256), ParamType::Uint(256)]);
+    let blob_quorum_param = ParamType::Tuple(vec![
+        ParamType::Uint(8),
+        ParamType::Uint(32),
+        ParamType::Uint(32),
+        ParamType::Uint(32),
+    ]);
+    let blob_header = ParamType::Tuple(vec![
+        g1_commitment,
+        ParamType::Uint(32),
+        ParamType::Array(Box::new(blob_quorum_param)),
+    ]);
+    let batch_header = ParamType::Tuple(vec![
+        ParamType::FixedBytes(32),
+        ParamType::Bytes,
+        ParamType::Bytes,
+        ParamType::Uint(32),
+    ]);
+    let batch_metadata = ParamType::Tuple(vec![
+        batch_header,
+        ParamType::FixedBytes(32),
+        ParamType::Uint(32),
+        ParamType::Bytes,
+        ParamType::Bytes,
+    ]);
+    let blob_verification_proof = ParamType::Tuple(vec![
+        ParamType::Uint(32),
+        ParamType::Uint(32),
+        batch_metadata,
+        ParamType::Bytes,
+        ParamType::Bytes,
+    ]);
+    ParamType::Tuple(vec![blob_header, blob_verification_proof])
+}
+
+/// Returns the blob index and batch header hash the blob of the given inclusion data is retrieved with.
+pub fn retrieval_params(inclusion_data: &[u8]) -> Result<(u32, Vec<u8>), CertFileError> {
+    let invalid = |e: &str| CertFileError::V1InclusionData(e.to_string());
+    let tokens = ethabi::decode(&[blob_info_param_type()], inclusion_data)
+        .map_err(|e| CertFileError::V1InclusionData(e.to_string()))?;
+    let Some(Token::Tuple(blob_info)) = tokens.into_iter().next() else {
+        return Err(invalid("expected a BlobInfo tuple"));
+    };
+    let Some(Token::Tuple(proof)) = blob_info.into_iter().nth(1) else {
+        return Err(invalid("expected a BlobVerificationProof tuple"));
+    };
+    let mut proof = proof.into_iter();
+    let (Some(_batch_id), Some(Token::Uint(blob_index)), Some(Token::Tuple(metadata))) =
+        (proof.next(), proof.next(), proof.next())
+    else {
+        return Err(invalid("expected a blob index and a BatchMetadata tuple"));
+    };
+    let Some(Token::Bytes(batch_header_hash)) = metadata.into_iter().nth(3) else {
+        return Err(invalid("expected a batch header hash"));
+    };
+    if blob_index > u32::MAX.into() {
+        return Err(invalid("blob index overflows u32"));
+    }
+    Ok((blob_index.as_u32(), batch_header_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use ethereum_types::U256;
+
+    use super::*;
+
+    fn uint(value: u64) -> Token {
+        Token::Uint(U256::from(value))
+    }
+
+    /// Inclusion data as encoded by the v1 client, see `BlobInfo::to_tokens`.
+    fn test_inclusion_data() -> Vec<u8> {
+        let blob_header = Token::Tuple(vec![
+            Token::Tuple(vec![uint(1), uint(2)]),
+            uint(1024),
+            Token::Array(vec![Token::Tuple(vec![
+                uint(0),
+                uint(33),
+                uint(55),
+                uint(4),
+            ])]),
+        ]);
+        let batch_header = Token::Tuple(vec![
+            Token::FixedBytes(vec![0x11; 32]),
+            Token::Bytes(vec![0, 1]),
+            Token::Bytes(vec![100, 100]),
+            uint(3656235),
+        ]);
+        let batch_metadata = Token::Tuple(vec![
+            batch_header,
+            Token::FixedBytes(vec![0x22; 32]),
+            uint(3656300),
+            Token::Bytes(vec![0x33; 32]),
+            Token::Bytes(vec![0]),
+        ]);
+        let proof = Token::Tuple(vec![
+            uint(42),
+            uint(7),
+            batch_metadata,
+            Token::Bytes(vec![0x44; 64]),
+            Token::Bytes(vec![0, 1]),
+        ]);
+        ethabi::encode(&[Token::Tuple(vec![blob_header, proof])])
+    }
+
+    #[test]
+    fn test_retrieval_params() {
+        let inclusion_data = test_inclusion_data();
+        assert_eq!(
+            retrieval_params(&inclusion_data).unwrap(),
+            (7, vec![0x33; 32])
+        );
+        assert!(matches!(
+            retrieval_params(&inclusion_data[..inclusion_data.len() - 32]),
+            Err(CertFileError::V1InclusionData(_))
+        ));
+    }
+}
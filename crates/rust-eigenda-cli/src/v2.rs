@@ -0,0 +1,270 @@
+//! Commands run with the v2 clients.
+use std::{collections::HashMap, fmt::Write, sync::Arc, time::Duration};
+
+use ethers::types::BlockNumber;
+use num_bigint::BigUint;
+use rust_eigenda_signers::Sign;
+use rust_eigenda_srs::{SrsFile, SrsSource};
+use rust_eigenda_v2_client::{
+    cert_verifier_router::CertVerifierResolver,
+    core::{BlobKey, BlobParams, Payload, PayloadDecodeMode, PayloadForm},
+    disperser_client::{DisperserClient, DisperserClientConfig},
+    network::DEFAULT_MAX_GRPC_MESSAGE_SIZE,
+    payload_disperser::{PayloadDisperser, PayloadDisperserConfig},
+    preflight::{PreflightConfig, PreflightReport},
+    relay_client::{RelayClient, RelayClientConfig},
+    relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig},
+    relay_registry::RelayRegistry,
+    srs::{SRSConfig, Srs},
+    utils::SecretUrl,
+};
+use rust_eigenda_v2_common::EigenDACert;
+
+use crate::{cli::ClientArgs, errors::CliError, signer::CliSigner};
+
+/// Max number of relay keys scanned when listing every registered relay.
+const MAX_RELAY_KEYS: u32 = 256;
+
+fn eth_rpc_url(args: &ClientArgs) -> Result<SecretUrl, CliError> {
+    Ok(SecretUrl::new(args.eth_rpc_url()?))
+}
+
+fn payload_form(args: &ClientArgs) -> PayloadForm {
+    if args.eval_form {
+        PayloadForm::Eval
+    } else {
+        PayloadForm::Coeff
+    }
+}
+
+fn payload_disperser_config(args: &ClientArgs) -> Result<PayloadDisperserConfig, CliError> {
+    Ok(PayloadDisperserConfig {
+        polynomial_form: payload_form(args),
+        blob_version: args.blob_version,
+        cert_verifier_address: args.cert_verifier_address()?,
+        eth_rpc_url: eth_rpc_url(args)?,
+        disperser_rpc: args.disperser_rpc()?,
+        use_secure_grpc_flag: !args.insecure_grpc,
+        settlement_layer_confirmation_depth: args.confirmation_depth,
+        cert_version: args.cert_version(),
+        chain_id: args.chain_id(),
+    })
+}
+
+fn relay_client_config(args: &ClientArgs) -> Result<RelayClientConfig, CliError> {
+    Ok(RelayClientConfig {
+        max_grpc_message_size: DEFAULT_MAX_GRPC_MESSAGE_SIZE,
+        relay_clients_keys: args.relay_keys.clone(),
+        relay_registry_address: args.relay_registry_address()?,
+        eth_rpc_url: eth_rpc_url(args)?,
+        chain_id: args.chain_id(),
+    })
+}
+
+fn srs_config(args: &ClientArgs) -> SRSConfig {
+    SRSConfig {
+        g1: SrsFile::new(SrsSource::Path(args.srs_g1_path.clone())),
+        order: args.srs_order,
+        g2_power_of_2: None,
+        cache_dir: None,
+    }
+}
+
+async fn payload_disperser(
+    args: &ClientArgs,
+    signer: CliSigner,
+) -> Result<PayloadDisperser<CliSigner>, CliError> {
+    Ok(PayloadDisperser::new(payload_disperser_config(args)?, signer).await?)
+}
+
+async fn disperser_client(
+    args: &ClientArgs,
+    signer: CliSigner,
+) -> Result<DisperserClient<CliSigner>, CliError> {
+    let config = DisperserClientConfig::new(args.disperser_rpc()?, signer, !args.insecure_grpc)?;
+    Ok(DisperserClient::new(config).await?)
+}
+
+/// Disperses a payload, returning the key of its blob.
+pub async fn disperse(
+    args: &ClientArgs,
+    signer: CliSigner,
+    payload: Vec<u8>,
+) -> Result<BlobKey, CliError> {
+    let payload_disperser = payload_disperser(args, signer).await?;
+    Ok(payload_disperser
+        .send_payload(Payload::new(payload))
+        .await?)
+}
+
+/// Returns the status of a blob, as named by the disperser API (e.g. `GATHERING_SIGNATURES`).
+pub async fn status(
+    args: &ClientArgs,
+    signer: CliSigner,
+    blob_key: &BlobKey,
+) -> Result<String, CliError> {
+    let reply = disperser_client(args, signer)
+        .await?
+        .blob_status(blob_key)
+        .await?;
+    Ok(reply.status().as_str_name().to_string())
+}
+
+/// Waits for a blob to be certified, returning its cert.
+pub async fn wait(
+    args: &ClientArgs,
+    signer: CliSigner,
+    blob_key: &BlobKey,
+    timeout: Duration,
+) -> Result<EigenDACert, CliError> {
+    let payload_disperser = payload_disperser(args, signer).await?;
+    let start = tokio::time::Instant::now();
+    loop {
+        if let Some(cert) = payload_disperser.get_inclusion_data(blob_key).await? {
+            return Ok(cert);
+        }
+        if start.elapsed() >= timeout {
+            return Err(CliError::Timeout(blob_key.to_hex()));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Retrieves the payload of a cert from the relays, verifying the cert first if a cert verifier is set.
+pub async fn get(
+    args: &ClientArgs,
+    signer: CliSigner,
+    cert: EigenDACert,
+) -> Result<Vec<u8>, CliError> {
+    if args.relay_keys.is_empty() {
+        return Err(CliError::MissingArg("--relay-keys"));
+    }
+    let srs = Srs::load(&srs_config(args)).await?;
+    let relay_client = RelayClient::new(relay_client_config(args)?, signer.clone()).await?;
+    let config = RelayPayloadRetrieverConfig {
+        payload_form: payload_form(args),
+        retrieval_timeout_secs: Duration::from_secs(30),
+        blob_params: HashMap::from([(
+            args.blob_version,
+            BlobParams {
+                num_chunks: 8192,
+                coding_rate: 8,
+            },
+        )]),
+        verify_length_proof: !args.skip_length_proof,
+        decode_mode: PayloadDecodeMode::Lenient,
+    };
+    let mut retriever = RelayPayloadRetriever::new(config, Arc::new(srs), relay_client)?;
+    if let Ok(address) = args.cert_verifier_address() {
+        let cert_verifier = CertVerifierResolver::new(address, eth_rpc_url(args)?, signer)?;
+        retriever = retriever.with_cert_verifier(Box::new(cert_verifier));
+    }
+    Ok(retriever.get_payload(cert).await?.serialize())
+}
+
+/// Verifies a cert at the latest block, with the verifier of its reference block number.
+pub async fn verify(
+    args: &ClientArgs,
+    signer: CliSigner,
+    cert: &EigenDACert,
+) -> Result<(), CliError> {
+    let cert_verifier =
+        CertVerifierResolver::new(args.cert_verifier_address()?, eth_rpc_url(args)?, signer)?;
+    Ok(cert_verifier
+        .verify_cert_v2_at_block(cert, BlockNumber::Latest)
+        .await?)
+}
+
+fn big_uint(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(bytes)
+}
+
+/// Returns a description of the payment state of the account of the signer.
+pub async fn payment_state(args: &ClientArgs, signer: CliSigner) -> Result<String, CliError> {
+    let account = signer.public_key().address();
+    let state = disperser_client(args, signer)
+        .await?
+        .payment_state()
+        .await?;
+
+    let mut out = String::new();
+    let _ = writeln!(out, "account: {:?}", account);
+    match &state.reservation {
+        Some(reservation) => {
+            let _ = writeln!(
+                out,
+                "reservation: {} symbols/s from {} to {}, quorums {:?} (splits {:?})",
+                reservation.symbols_per_second,
+                reservation.start_timestamp,
+                reservation.end_timestamp,
+                reservation.quorum_numbers,
+                reservation.quorum_splits
+            );
+        }
+        None => out.push_str("reservation: none\n"),
+    }
+    for record in &state.period_records {
+        let _ = writeln!(
+            out,
+            "reservation period {}: {} symbols used",
+            record.index, record.usage
+        );
+    }
+    let _ = writeln!(
+        out,
+        "on-demand deposit: {} wei",
+        big_uint(&state.onchain_cumulative_payment)
+    );
+    let _ = writeln!(
+        out,
+        "on-demand cumulative payment: {} wei",
+        big_uint(&state.cumulative_payment)
+    );
+    if let Some(params) = &state.payment_global_params {
+        let _ = writeln!(
+            out,
+            "global: {} symbols/s, min {} symbols, {} wei/symbol, reservation window {}s, on-demand quorums {:?}",
+            params.global_symbols_per_second,
+            params.min_num_symbols,
+            params.price_per_symbol,
+            params.reservation_window,
+            params.on_demand_quorum_numbers
+        );
+    }
+    Ok(out)
+}
+
+/// Checks the configuration against the network: the disperser and the payment state of the signer, the cert
+/// verifier, the relays of `--relay-keys` (if any) and the SRS.
+pub async fn preflight(args: &ClientArgs, signer: CliSigner) -> Result<PreflightReport, CliError> {
+    let mut config =
+        PreflightConfig::new(payload_disperser_config(args)?).with_srs(srs_config(args));
+    if !args.relay_keys.is_empty() {
+        config = config.with_relay_client(relay_client_config(args)?);
+    }
+    Ok(rust_eigenda_v2_client::preflight::preflight(&config, signer).await)
+}
+
+/// Returns the URLs of the relays of `--relay-keys`, or of every registered relay if none is given.
+pub async fn list_relays(
+    args: &ClientArgs,
+    signer: CliSigner,
+) -> Result<Vec<(u32, String)>, CliError> {
+    let registry = RelayRegistry::new(args.relay_registry_address()?, eth_rpc_url(args)?, signer)?;
+    let mut relays = vec![];
+    if !args.relay_keys.is_empty() {
+        for &relay_key in &args.relay_keys {
+            relays.push((relay_key, registry.get_url_from_relay_key(relay_key).await?));
+        }
+        return Ok(relays);
+    }
+    // relay keys are assigned sequentially, so the first unregistered one ends the list
+    for relay_key in 0..MAX_RELAY_KEYS {
+        let url = registry.get_url_from_relay_key(relay_key).await?;
+        if url == "https://" {
+            break;
+        }
+        relays.push((relay_key, url));
+    }
+    Ok(relays)
+}
@@ -10,7 +10,8 @@ mod tests {
     use crate::{
         client::BlobProvider,
         config::EigenConfig,
-        errors::{CommunicationError, EigenClientError},
+        errors::{CommunicationError, ConfigError, EigenClientError},
+        network::MAINNET_CHAIN_ID,
         test_eigenda_config, EigenClient,
     };
     use backon::{ConstantBuilder, Retryable};
@@ -295,4 +296,29 @@ mod tests {
             .unwrap();
         assert_eq!(retrieved_data.unwrap(), data);
     }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    #[serial]
+    async fn test_chain_id_mismatch() {
+        // a Holesky ETH RPC with the chain id of mainnet
+        let config = EigenConfig {
+            chain_id: Some(MAINNET_CHAIN_ID),
+            ..test_eigenda_config()
+        };
+        let pk_signer = PrivateKeySigner::new(
+            "d08aa7ae1bb5ddd46c3c2d8cdb5894ab9f54dec467233686ca42629e826ac4c6"
+                .parse()
+                .unwrap(),
+        );
+
+        let result = EigenClient::new(config, pk_signer, Arc::new(MockBlobProvider)).await;
+        assert!(matches!(
+            result,
+            Err(EigenClientError::Config(ConfigError::ChainIdMismatch {
+                expected: MAINNET_CHAIN_ID,
+                ..
+            }))
+        ));
+    }
 }
@@ -1,8 +1,12 @@
+use std::{collections::HashSet, path::Path};
+
 use ethereum_types::H160;
+use rust_eigenda_srs::{SrsFile, SrsSource};
 use secrecy::{ExposeSecret, Secret};
+use serde::{Deserialize, Deserializer};
 use url::Url;
 
-use crate::errors::ConfigError;
+use crate::{errors::ConfigError, network::Network};
 
 #[derive(Debug, Clone)]
 /// A URL stored securely using the `Secret` type from the secrecy crate
@@ -21,6 +25,19 @@ impl SecretUrl {
     }
 }
 
+impl<'de> Deserialize<'de> for SecretUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let url = String::deserialize(deserializer)?;
+        // the error does not include the URL, which may hold an API key
+        let url = Url::parse(&url)
+            .map_err(|e| serde::de::Error::custom(format!("invalid URL: {}", e)))?;
+        Ok(Self::new(url))
+    }
+}
+
 impl From<SecretUrl> for Url {
     fn from(secret_url: SecretUrl) -> Self {
         Url::parse(secret_url.inner.expose_secret()).unwrap() // Safe to unwrap, as the `new` fn ensures the URL is valid
@@ -37,12 +54,18 @@ impl PartialEq for SecretUrl {
 pub enum SrsPointsSource {
     /// Path to the SRS points file, it should have both g1 and power of g2 points
     Path(String),
-    /// Urls to g1 and power of g2 points
+    /// Urls to g1 and power of g2 points, downloaded once and cached on disk
     Url((String, String)),
+    /// g1 and power of g2 point files, from any source and optionally pinned to their SHA-256 digest
+    Files((SrsFile, SrsFile)),
 }
 
 /// Configuration for the EigenDA remote disperser client.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Built with [`EigenConfig::builder`], or read from TOML, JSON or environment variables. When read, the SRS points
+/// are set by either `srs_path` or both `srs_g1_url` and `srs_g2_url`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "EigenConfigFields")]
 pub struct EigenConfig {
     /// URL of the Disperser RPC server
     pub(crate) disperser_rpc: String,
@@ -61,10 +84,12 @@ pub struct EigenConfig {
     pub(crate) srs_points_source: SrsPointsSource,
     /// Custom quorum numbers
     pub(crate) custom_quorum_numbers: Vec<u8>,
+    /// Chain id the ETH RPC must be connected to, checked when the client is created
+    pub(crate) chain_id: Option<u64>,
 }
 
 impl EigenConfig {
-    /// Create a new EigenConfig
+    /// Create a new EigenConfig, see [`EigenConfig::builder`] for a validated one with defaults
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         disperser_rpc: String,
@@ -85,6 +110,379 @@ impl EigenConfig {
             authenticated,
             srs_points_source,
             custom_quorum_numbers,
+            chain_id: None,
         })
     }
+
+    /// Checks that the ETH RPC is connected to the chain with the given id when the client is created
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Create an EigenConfig for the disperser and service manager of a network, checking that the ETH RPC is
+    /// connected to its chain. Blobs are dispersed without authentication, to the default quorums, and are returned
+    /// once confirmed.
+    pub fn for_network(
+        network: &Network,
+        eth_rpc_url: SecretUrl,
+        srs_points_source: SrsPointsSource,
+    ) -> Self {
+        let network = network.config();
+        Self {
+            disperser_rpc: network.disperser_rpc,
+            eth_rpc_url,
+            settlement_layer_confirmation_depth: 0,
+            eigenda_svc_manager_address: network.eigenda_svc_manager_address,
+            wait_for_finalization: false,
+            authenticated: false,
+            srs_points_source,
+            custom_quorum_numbers: vec![],
+            chain_id: Some(network.chain_id),
+        }
+    }
+
+    /// Create a builder of a validated EigenConfig
+    pub fn builder() -> EigenConfigBuilder {
+        EigenConfigBuilder::default()
+    }
+
+    /// Check the URLs, the service manager address, the SRS points source and the custom quorum numbers
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        check_url("disperser_rpc", &self.disperser_rpc)?;
+        check_url("eth_rpc_url", self.eth_rpc_url.inner.expose_secret())?;
+        if self.eigenda_svc_manager_address.is_zero() {
+            return Err(invalid(
+                "eigenda_svc_manager_address",
+                "the zero address is not a contract",
+            ));
+        }
+        match &self.srs_points_source {
+            SrsPointsSource::Path(path) if path.is_empty() => {
+                return Err(invalid("srs_points_source", "empty path"))
+            }
+            SrsPointsSource::Path(_) => {}
+            SrsPointsSource::Url((g1_url, g2_url)) => {
+                check_url("srs_points_source", g1_url)?;
+                check_url("srs_points_source", g2_url)?;
+            }
+            SrsPointsSource::Files((g1, g2)) => {
+                for file in [g1, g2] {
+                    if let SrsSource::Url(url) = &file.source {
+                        check_url("srs_points_source", url)?;
+                    }
+                }
+            }
+        }
+        let unique_quorums: HashSet<_> = self.custom_quorum_numbers.iter().collect();
+        if unique_quorums.len() != self.custom_quorum_numbers.len() {
+            return Err(invalid(
+                "custom_quorum_numbers",
+                "must not repeat quorum numbers",
+            ));
+        }
+        if self.chain_id == Some(0) {
+            return Err(invalid("chain_id", "must not be 0"));
+        }
+        Ok(())
+    }
+
+    /// Read the config from TOML
+    pub fn from_toml(contents: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Read the config from JSON
+    pub fn from_json(contents: &str) -> Result<Self, ConfigError> {
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    /// Read the config from a file, as JSON if its extension is `.json` and as TOML otherwise
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            _ => Self::from_toml(&contents),
+        }
+    }
+
+    /// Read the config from the environment variables with the given prefix (e.g. `EIGENDA_DISPERSER_RPC` with the
+    /// `EIGENDA_` prefix). Custom quorum numbers are comma separated
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+        Self::from_env_iter(prefix, std::env::vars())
+    }
+
+    /// Read the config from the variables with the given prefix, ignoring any other
+    pub fn from_env_iter(
+        prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        // `envy::prefixed` strips the prefix repeatedly, which breaks fields starting with it, like
+        // `eigenda_svc_manager_address` with the `EIGENDA_` prefix
+        let vars = vars.into_iter().filter_map(|(key, value)| {
+            key.strip_prefix(prefix)
+                .map(|field| (field.to_string(), value))
+        });
+        Ok(envy::from_iter(vars)?)
+    }
+}
+
+fn invalid(field: &'static str, reason: impl Into<String>) -> ConfigError {
+    ConfigError::InvalidField {
+        field,
+        reason: reason.into(),
+    }
+}
+
+/// Check that a URL is an absolute HTTP(S) URL, without including it in the error
+fn check_url(field: &'static str, url: &str) -> Result<(), ConfigError> {
+    let url = Url::parse(url).map_err(|e| invalid(field, e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(field, "the scheme must be http or https"));
+    }
+    if url.host().is_none() {
+        return Err(invalid(field, "missing host"));
+    }
+    Ok(())
+}
+
+/// Builder of a validated [`EigenConfig`]. Blobs are dispersed without authentication, to the default quorums, and
+/// are returned once confirmed, unless set otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct EigenConfigBuilder {
+    disperser_rpc: Option<String>,
+    eth_rpc_url: Option<SecretUrl>,
+    settlement_layer_confirmation_depth: u32,
+    eigenda_svc_manager_address: Option<H160>,
+    wait_for_finalization: bool,
+    authenticated: bool,
+    srs_points_source: Option<SrsPointsSource>,
+    custom_quorum_numbers: Vec<u8>,
+    chain_id: Option<u64>,
+}
+
+impl EigenConfigBuilder {
+    /// Set the disperser, service manager and chain id of a network
+    pub fn with_network(mut self, network: &Network) -> Self {
+        let network = network.config();
+        self.disperser_rpc = Some(network.disperser_rpc);
+        self.eigenda_svc_manager_address = Some(network.eigenda_svc_manager_address);
+        self.chain_id = Some(network.chain_id);
+        self
+    }
+
+    pub fn with_disperser_rpc(mut self, disperser_rpc: impl Into<String>) -> Self {
+        self.disperser_rpc = Some(disperser_rpc.into());
+        self
+    }
+
+    pub fn with_eth_rpc_url(mut self, eth_rpc_url: SecretUrl) -> Self {
+        self.eth_rpc_url = Some(eth_rpc_url);
+        self
+    }
+
+    pub fn with_settlement_layer_confirmation_depth(mut self, depth: u32) -> Self {
+        self.settlement_layer_confirmation_depth = depth;
+        self
+    }
+
+    pub fn with_svc_manager_address(mut self, address: H160) -> Self {
+        self.eigenda_svc_manager_address = Some(address);
+        self
+    }
+
+    pub fn with_wait_for_finalization(mut self, wait_for_finalization: bool) -> Self {
+        self.wait_for_finalization = wait_for_finalization;
+        self
+    }
+
+    pub fn with_authenticated(mut self, authenticated: bool) -> Self {
+        self.authenticated = authenticated;
+        self
+    }
+
+    pub fn with_srs_points_source(mut self, srs_points_source: SrsPointsSource) -> Self {
+        self.srs_points_source = Some(srs_points_source);
+        self
+    }
+
+    pub fn with_custom_quorum_numbers(mut self, custom_quorum_numbers: Vec<u8>) -> Self {
+        self.custom_quorum_numbers = custom_quorum_numbers;
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn build(self) -> Result<EigenConfig, ConfigError> {
+        let config = EigenConfig {
+            disperser_rpc: self
+                .disperser_rpc
+                .ok_or(ConfigError::MissingField("disperser_rpc"))?,
+            eth_rpc_url: self.eth_rpc_url.ok_or(ConfigError::NoEthRpcUrl)?,
+            settlement_layer_confirmation_depth: self.settlement_layer_confirmation_depth,
+            eigenda_svc_manager_address: self
+                .eigenda_svc_manager_address
+                .ok_or(ConfigError::MissingField("eigenda_svc_manager_address"))?,
+            wait_for_finalization: self.wait_for_finalization,
+            authenticated: self.authenticated,
+            srs_points_source: self
+                .srs_points_source
+                .ok_or(ConfigError::MissingField("srs_points_source"))?,
+            custom_quorum_numbers: self.custom_quorum_numbers,
+            chain_id: self.chain_id,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Fields of an [`EigenConfig`] as read from files and environment variables
+#[derive(Deserialize)]
+struct EigenConfigFields {
+    disperser_rpc: Option<String>,
+    eth_rpc_url: Option<SecretUrl>,
+    #[serde(default)]
+    settlement_layer_confirmation_depth: u32,
+    eigenda_svc_manager_address: Option<H160>,
+    #[serde(default)]
+    wait_for_finalization: bool,
+    #[serde(default)]
+    authenticated: bool,
+    srs_path: Option<String>,
+    srs_g1_url: Option<String>,
+    srs_g2_url: Option<String>,
+    #[serde(default)]
+    custom_quorum_numbers: Vec<u8>,
+    chain_id: Option<u64>,
+}
+
+impl TryFrom<EigenConfigFields> for EigenConfig {
+    type Error = ConfigError;
+
+    fn try_from(fields: EigenConfigFields) -> Result<Self, Self::Error> {
+        let srs_points_source = match (fields.srs_path, fields.srs_g1_url, fields.srs_g2_url) {
+            (Some(path), None, None) => Some(SrsPointsSource::Path(path)),
+            (None, Some(g1_url), Some(g2_url)) => Some(SrsPointsSource::Url((g1_url, g2_url))),
+            (None, None, None) => None,
+            _ => {
+                return Err(invalid(
+                    "srs_points_source",
+                    "set either srs_path or both srs_g1_url and srs_g2_url",
+                ))
+            }
+        };
+        EigenConfigBuilder {
+            disperser_rpc: fields.disperser_rpc,
+            eth_rpc_url: fields.eth_rpc_url,
+            settlement_layer_confirmation_depth: fields.settlement_layer_confirmation_depth,
+            eigenda_svc_manager_address: fields.eigenda_svc_manager_address,
+            wait_for_finalization: fields.wait_for_finalization,
+            authenticated: fields.authenticated,
+            srs_points_source,
+            custom_quorum_numbers: fields.custom_quorum_numbers,
+            chain_id: fields.chain_id,
+        }
+        .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const TEST_TOML_CONFIG: &str = r#"
+        disperser_rpc = "https://disperser-holesky.eigenda.xyz"
+        eth_rpc_url = "https://ethereum-holesky-rpc.publicnode.com"
+        eigenda_svc_manager_address = "0xd4a7e1bd8015057293f0d0a557088c286942e84b"
+        srs_path = "../../resources"
+        custom_quorum_numbers = [0, 1]
+        chain_id = 17000
+    "#;
+
+    fn test_eth_rpc_url() -> SecretUrl {
+        SecretUrl::new(Url::from_str("https://ethereum-holesky-rpc.publicnode.com").unwrap())
+    }
+
+    #[test]
+    fn test_config_from_toml() {
+        let config = EigenConfig::from_toml(TEST_TOML_CONFIG).unwrap();
+        let expected = EigenConfig::builder()
+            .with_network(&Network::Holesky)
+            .with_eth_rpc_url(test_eth_rpc_url())
+            .with_srs_points_source(SrsPointsSource::Path("../../resources".to_string()))
+            .with_custom_quorum_numbers(vec![0, 1])
+            .build()
+            .unwrap();
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn test_config_from_env() {
+        let vars = [
+            (
+                "EIGENDA_DISPERSER_RPC",
+                "https://disperser-holesky.eigenda.xyz",
+            ),
+            (
+                "EIGENDA_ETH_RPC_URL",
+                "https://ethereum-holesky-rpc.publicnode.com",
+            ),
+            (
+                "EIGENDA_EIGENDA_SVC_MANAGER_ADDRESS",
+                "0xd4a7e1bd8015057293f0d0a557088c286942e84b",
+            ),
+            ("EIGENDA_SRS_G1_URL", "https://example.com/g1.point"),
+            (
+                "EIGENDA_SRS_G2_URL",
+                "https://example.com/g2.point.powerOf2",
+            ),
+            ("EIGENDA_WAIT_FOR_FINALIZATION", "true"),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+        let config = EigenConfig::from_env_iter("EIGENDA_", vars).unwrap();
+        assert!(config.wait_for_finalization);
+        assert!(matches!(config.srs_points_source, SrsPointsSource::Url(_)));
+        assert_eq!(config.chain_id, None);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let repeated_quorums = TEST_TOML_CONFIG.replace("[0, 1]", "[1, 1]");
+        let error = EigenConfig::from_toml(&repeated_quorums).unwrap_err();
+        assert!(error.to_string().contains("custom_quorum_numbers"));
+
+        let both_srs_sources =
+            format!("{}\nsrs_g1_url = \"https://example.com\"", TEST_TOML_CONFIG);
+        let error = EigenConfig::from_toml(&both_srs_sources).unwrap_err();
+        assert!(error.to_string().contains("srs_points_source"));
+
+        let invalid_url = EigenConfig::builder()
+            .with_network(&Network::Holesky)
+            .with_eth_rpc_url(SecretUrl::new(Url::from_str("ws://user:key@host").unwrap()))
+            .with_srs_points_source(SrsPointsSource::Path("../../resources".to_string()))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            invalid_url,
+            ConfigError::InvalidField {
+                field: "eth_rpc_url",
+                ..
+            }
+        ));
+        assert!(!invalid_url.to_string().contains("key"));
+
+        assert!(matches!(
+            EigenConfig::builder()
+                .with_network(&Network::Holesky)
+                .with_eth_rpc_url(test_eth_rpc_url())
+                .build(),
+            Err(ConfigError::MissingField("srs_points_source"))
+        ));
+    }
 }
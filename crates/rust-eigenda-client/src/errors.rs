@@ -1,4 +1,5 @@
 use ark_bn254::G1Affine;
+use ethereum_types::U256;
 use tokio::sync::mpsc::error::SendError;
 use tonic::{transport::Error as TonicError, Status};
 
@@ -27,8 +28,22 @@ pub enum ConfigError {
     PrivateKey,
     #[error("ETH RPC URL not set")]
     NoEthRpcUrl,
+    #[error("ETH RPC is connected to chain {actual}, expected chain {expected}")]
+    ChainIdMismatch { expected: u64, actual: U256 },
     #[error(transparent)]
     Tonic(#[from] TonicError),
+    #[error("Missing config field {0}")]
+    MissingField(&'static str),
+    #[error("Invalid config field {field}: {reason}")]
+    InvalidField { field: &'static str, reason: String },
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid TOML config: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid JSON config: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid config environment variables: {0}")]
+    Env(#[from] envy::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -4,6 +4,7 @@ use bytes::Bytes;
 use ethereum_types::{Address, U256};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use tracing::instrument;
 use url::Url;
 
 use crate::{config::SecretUrl, errors::EthClientError};
@@ -114,7 +115,27 @@ impl EthClient {
         }
     }
 
+    /// Gets the id of the chain the node is connected to
+    #[instrument(skip_all)]
+    pub(crate) async fn get_chain_id(&self) -> Result<U256, EthClientError> {
+        let request = RpcRequest {
+            id: RpcRequestId::Number(1),
+            jsonrpc: "2.0".to_string(),
+            method: "eth_chainId".to_string(),
+            params: None,
+        };
+
+        match self.send_request(request).await {
+            Ok(RpcResponse::Success(result)) => {
+                serde_json::from_value(result.result).map_err(EthClientError::SerdeJSON)
+            }
+            Ok(RpcResponse::Error(error_response)) => Err(EthClientError::Rpc(error_response)),
+            Err(error) => Err(error),
+        }
+    }
+
     /// Calls a contract
+    #[instrument(skip_all, fields(contract = ?to, selector = ?calldata.get(..4), block))]
     pub(crate) async fn call(
         &self,
         to: Address,
@@ -0,0 +1,63 @@
+use ethereum_types::H160;
+use hex_literal::hex;
+pub use rust_eigenda_v2_common::network::{
+    HOLESKY_CHAIN_ID, HOLESKY_V1_DISPERSER_RPC_URL as HOLESKY_DISPERSER_RPC_URL, MAINNET_CHAIN_ID,
+    MAINNET_V1_DISPERSER_RPC_URL as MAINNET_DISPERSER_RPC_URL, SEPOLIA_CHAIN_ID,
+    SEPOLIA_V1_DISPERSER_RPC_URL as SEPOLIA_DISPERSER_RPC_URL,
+};
+
+pub const MAINNET_SVC_MANAGER_ADDRESS: H160 =
+    H160(hex!("870679e138bcdf293b7ff14dd44b70fc97e12fc0"));
+pub const HOLESKY_SVC_MANAGER_ADDRESS: H160 =
+    H160(hex!("d4a7e1bd8015057293f0d0a557088c286942e84b"));
+pub const SEPOLIA_SVC_MANAGER_ADDRESS: H160 =
+    H160(hex!("3a5acf46ba6890b8536420f4900ac9bc45df4764"));
+
+/// EigenDA deployment the client connects to, with the canonical disperser and service manager of each public
+/// network.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Network {
+    Mainnet,
+    Holesky,
+    Sepolia,
+    /// Any other deployment, such as a local devnet.
+    Custom(NetworkConfig),
+}
+
+/// Chain and EigenDA deployment of a [`Network`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkConfig {
+    /// Chain id the ETH RPC must be connected to.
+    pub chain_id: u64,
+    pub disperser_rpc: String,
+    pub eigenda_svc_manager_address: H160,
+}
+
+impl Network {
+    /// Returns the chain and EigenDA deployment of the network.
+    pub fn config(&self) -> NetworkConfig {
+        match self {
+            Network::Mainnet => NetworkConfig {
+                chain_id: MAINNET_CHAIN_ID,
+                disperser_rpc: MAINNET_DISPERSER_RPC_URL.to_string(),
+                eigenda_svc_manager_address: MAINNET_SVC_MANAGER_ADDRESS,
+            },
+            Network::Holesky => NetworkConfig {
+                chain_id: HOLESKY_CHAIN_ID,
+                disperser_rpc: HOLESKY_DISPERSER_RPC_URL.to_string(),
+                eigenda_svc_manager_address: HOLESKY_SVC_MANAGER_ADDRESS,
+            },
+            Network::Sepolia => NetworkConfig {
+                chain_id: SEPOLIA_CHAIN_ID,
+                disperser_rpc: SEPOLIA_DISPERSER_RPC_URL.to_string(),
+                eigenda_svc_manager_address: SEPOLIA_SVC_MANAGER_ADDRESS,
+            },
+            Network::Custom(config) => config.clone(),
+        }
+    }
+
+    /// Returns the chain id the ETH RPC must be connected to.
+    pub fn chain_id(&self) -> u64 {
+        self.config().chain_id
+    }
+}
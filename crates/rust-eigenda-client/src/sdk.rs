@@ -19,6 +19,7 @@ use crate::{
     rust_eigenda_signers::signers::private_key::Signer as PrivateKeySigner,
 };
 use byteorder::{BigEndian, ByteOrder};
+use ethereum_types::U256;
 use rust_eigenda_signers::{Message, Sign};
 use tiny_keccak::{Hasher, Keccak};
 use tokio::sync::{mpsc, Mutex};
@@ -27,6 +28,7 @@ use tonic::{
     transport::{Channel, ClientTlsConfig, Endpoint},
     Streaming,
 };
+use tracing::{debug, debug_span, field, info, instrument, Instrument};
 
 /// Raw Client that comunicates with the disperser
 #[derive(Debug)]
@@ -43,6 +45,7 @@ pub(crate) const FIELD_ELEMENT_SIZE_BYTES: usize = 32;
 impl<S> RawEigenClient<S> {
     const BLOB_SIZE_LIMIT: usize = 1024 * 1024 * 16; // 16 MB
     /// Creates a new RawEigenClient
+    #[instrument(skip_all, fields(disperser_rpc = %config.disperser_rpc))]
     pub(crate) async fn new(
         signer: S,
         config: EigenConfig,
@@ -60,6 +63,12 @@ impl<S> RawEigenClient<S> {
 
         let url = config.eth_rpc_url.clone();
         let eth_client = eth_client::EthClient::new(url, config.eigenda_svc_manager_address);
+        if let Some(expected) = config.chain_id {
+            let actual = eth_client.get_chain_id().await?;
+            if actual != U256::from(expected) {
+                return Err(ConfigError::ChainIdMismatch { expected, actual }.into());
+            }
+        }
 
         let verifier = Verifier::new(config.clone(), eth_client).await?;
         Ok(RawEigenClient {
@@ -77,6 +86,14 @@ impl<S> RawEigenClient<S> {
     }
 
     /// Dispatches a blob to the disperser without authentication
+    #[instrument(
+        skip_all,
+        fields(
+            size = data.len(),
+            quorums = ?self.config.custom_quorum_numbers,
+            request_id = field::Empty
+        )
+    )]
     async fn dispatch_blob_non_authenticated(
         &self,
         data: Vec<u8>,
@@ -95,14 +112,17 @@ impl<S> RawEigenClient<S> {
             account_id: String::default(), // Account Id is not used in non-authenticated mode
         };
 
-        let disperse_reply = self
-            .client
-            .lock()
-            .await
-            .disperse_blob(request)
-            .await
-            .map_err(BlobStatusError::Status)?
-            .into_inner();
+        let disperse_reply = async {
+            self.client
+                .lock()
+                .await
+                .disperse_blob(request)
+                .await
+                .map_err(BlobStatusError::Status)
+        }
+        .instrument(debug_span!("disperse_blob_rpc"))
+        .await?
+        .into_inner();
 
         match disperser::BlobStatus::try_from(disperse_reply.result)
             .map_err(BlobStatusError::Prost)?
@@ -114,11 +134,24 @@ impl<S> RawEigenClient<S> {
             disperser::BlobStatus::Dispersing
             | disperser::BlobStatus::Processing
             | disperser::BlobStatus::Finalized
-            | disperser::BlobStatus::Confirmed => Ok(hex::encode(disperse_reply.request_id)),
+            | disperser::BlobStatus::Confirmed => {
+                let request_id = hex::encode(disperse_reply.request_id);
+                tracing::Span::current().record("request_id", request_id.as_str());
+                info!("dispersed blob");
+                Ok(request_id)
+            }
         }
     }
 
     /// Dispatches a blob to the disperser with authentication
+    #[instrument(
+        skip_all,
+        fields(
+            size = data.len(),
+            quorums = ?self.config.custom_quorum_numbers,
+            request_id = field::Empty
+        )
+    )]
     async fn dispatch_blob_authenticated(&self, data: Vec<u8>) -> Result<String, EigenClientError>
     where
         S: Sign,
@@ -141,15 +174,20 @@ impl<S> RawEigenClient<S> {
         let response_stream = response_stream.get_mut();
 
         // 2. receive BlobAuthHeader
-        let blob_auth_header = self.receive_blob_auth_header(response_stream).await?;
+        let blob_auth_header = self
+            .receive_blob_auth_header(response_stream)
+            .instrument(debug_span!("receive_blob_auth_header"))
+            .await?;
 
         // 3. sign and send BlobAuthHeader
         self.submit_authentication_data(blob_auth_header.clone(), &tx)
+            .instrument(debug_span!("submit_authentication_data"))
             .await?;
 
         // 4. receive DisperseBlobReply
         let reply = response_stream
             .next()
+            .instrument(debug_span!("receive_disperse_reply"))
             .await
             .ok_or(CommunicationError::NoResponseFromServer)?
             .map_err(BlobStatusError::Status)?
@@ -172,11 +210,17 @@ impl<S> RawEigenClient<S> {
             disperser::BlobStatus::Dispersing
             | disperser::BlobStatus::Processing
             | disperser::BlobStatus::Finalized
-            | disperser::BlobStatus::Confirmed => Ok(hex::encode(disperse_reply.request_id)),
+            | disperser::BlobStatus::Confirmed => {
+                let request_id = hex::encode(disperse_reply.request_id);
+                tracing::Span::current().record("request_id", request_id.as_str());
+                info!("dispersed blob");
+                Ok(request_id)
+            }
         }
     }
 
     /// Gets the blob info for a given request id
+    #[instrument(skip_all, fields(request_id))]
     pub(crate) async fn get_commitment(
         &self,
         request_id: &str,
@@ -211,12 +255,15 @@ impl<S> RawEigenClient<S> {
                 return Err(VerificationError::DataMismatch)?;
             }
         }
-        self.verifier
-            .verify_commitment(blob_info.blob_header.commitment.clone(), data)?;
+        debug_span!("verify_commitment").in_scope(|| {
+            self.verifier
+                .verify_commitment(blob_info.blob_header.commitment.clone(), data)
+        })?;
 
         let result = self
             .verifier
             .verify_inclusion_data_against_settlement_layer(blob_info.clone())
+            .instrument(debug_span!("verify_inclusion_data"))
             .await;
         if let Err(e) = result {
             match e {
@@ -347,6 +394,7 @@ impl<S> RawEigenClient<S> {
         }
     }
 
+    #[instrument(skip_all)]
     async fn try_get_inclusion_data(
         &self,
         request_id: String,
@@ -364,7 +412,10 @@ impl<S> RawEigenClient<S> {
             .map_err(BlobStatusError::Status)?
             .into_inner();
 
-        match disperser::BlobStatus::try_from(resp.status).map_err(BlobStatusError::Prost)? {
+        let status =
+            disperser::BlobStatus::try_from(resp.status).map_err(BlobStatusError::Prost)?;
+        debug!(status = ?status, "polled blob status");
+        match status {
             disperser::BlobStatus::Processing | disperser::BlobStatus::Dispersing => Ok(None),
             disperser::BlobStatus::Failed => Err(BlobStatusError::BlobDispatchedFailed)?,
             disperser::BlobStatus::InsufficientSignatures => {
@@ -391,6 +442,7 @@ impl<S> RawEigenClient<S> {
     }
 
     /// Returns the blob data
+    #[instrument(skip_all, fields(blob_index))]
     pub(crate) async fn get_blob(
         &self,
         blob_index: u32,
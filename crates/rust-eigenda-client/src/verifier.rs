@@ -1,8 +1,4 @@
-use std::{
-    collections::HashMap,
-    io::Write,
-    path::{Path, PathBuf},
-};
+use std::{collections::HashMap, path::PathBuf};
 
 use crate::{
     config::{EigenConfig, SrsPointsSource},
@@ -12,8 +8,8 @@ use crate::{
 use ark_bn254::{Fq, G1Affine};
 use ethabi::{encode, ParamType, Token};
 use ethereum_types::{U256, U64};
+use rust_eigenda_srs::{default_cache_dir, SrsFile, SrsSource};
 use rust_kzg_bn254::{blob::Blob, kzg::Kzg, polynomial::PolynomialFormat};
-use tempfile::NamedTempFile;
 use tiny_keccak::{Hasher, Keccak};
 
 use super::{
@@ -21,21 +17,6 @@ use super::{
     eth_client::EthClient,
 };
 
-#[derive(Debug)]
-enum PointFile {
-    Temp(NamedTempFile),
-    Path(PathBuf),
-}
-
-impl PointFile {
-    fn path(&self) -> &Path {
-        match self {
-            PointFile::Temp(file) => file.path(),
-            PointFile::Path(path) => path.as_path(),
-        }
-    }
-}
-
 pub(crate) fn decode_bytes(encoded: Vec<u8>) -> Result<Vec<u8>, VerificationError> {
     let output_type = [ParamType::Bytes];
     let tokens = ethabi::decode(&output_type, &encoded)
@@ -189,51 +170,26 @@ impl<T: SvcManagerClient> Verifier<T> {
     pub(crate) const G2POINT: &'static str = "g2.point.powerOf2";
     pub(crate) const POINT_SIZE: u32 = 32;
 
-    async fn download_temp_point(url: &String) -> Result<NamedTempFile, VerificationError> {
-        let response = reqwest::get(url)
+    /// Returns the local path of a point file, downloading it to the on-disk SRS cache if needed.
+    async fn local_point_path(file: &SrsFile) -> Result<PathBuf, VerificationError> {
+        file.local_path(&default_cache_dir())
             .await
-            .map_err(|e| VerificationError::PointDownloadError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(VerificationError::PointDownloadError(format!(
-                "Failed to download point from source {}",
-                url
-            )));
-        }
-
-        let content = response
-            .bytes()
-            .await
-            .map_err(|e| VerificationError::PointDownloadError(e.to_string()))?;
-
-        // Tempfile writting uses `std::fs`, so we need to spawn a blocking task
-        let temp_file = tokio::task::spawn_blocking(move || {
-            let mut file = NamedTempFile::new()
-                .map_err(|e| VerificationError::PointDownloadError(e.to_string()))?;
-
-            file.write_all(&content)
-                .map_err(|e| VerificationError::PointDownloadError(e.to_string()))?;
-
-            file.flush()
-                .map_err(|e| VerificationError::PointDownloadError(e.to_string()))?;
-
-            Ok::<NamedTempFile, VerificationError>(file)
-        })
-        .await
-        .map_err(|e| VerificationError::PointDownloadError(e.to_string()))??;
-
-        Ok::<NamedTempFile, VerificationError>(temp_file)
+            .map_err(|e| VerificationError::PointDownloadError(e.to_string()))
     }
 
-    async fn get_points(cfg: &EigenConfig) -> Result<(PointFile, PointFile), VerificationError> {
+    async fn get_points(cfg: &EigenConfig) -> Result<(PathBuf, PathBuf), VerificationError> {
         match &cfg.srs_points_source {
             SrsPointsSource::Path(path) => Ok((
-                PointFile::Path(PathBuf::from(format!("{}/{}", path, Self::G1POINT))),
-                PointFile::Path(PathBuf::from(format!("{}/{}", path, Self::G2POINT))),
+                PathBuf::from(format!("{}/{}", path, Self::G1POINT)),
+                PathBuf::from(format!("{}/{}", path, Self::G2POINT)),
             )),
             SrsPointsSource::Url((g1_url, g2_url)) => Ok((
-                PointFile::Temp(Self::download_temp_point(g1_url).await?),
-                PointFile::Temp(Self::download_temp_point(g2_url).await?),
+                Self::local_point_path(&SrsFile::new(SrsSource::Url(g1_url.clone()))).await?,
+                Self::local_point_path(&SrsFile::new(SrsSource::Url(g2_url.clone()))).await?,
+            )),
+            SrsPointsSource::Files((g1_file, g2_file)) => Ok((
+                Self::local_point_path(g1_file).await?,
+                Self::local_point_path(g2_file).await?,
             )),
         }
     }
@@ -243,10 +199,10 @@ impl<T: SvcManagerClient> Verifier<T> {
         let srs_points_to_load = RawEigenClient::<()>::blob_size_limit() as u32 / Self::POINT_SIZE;
         let (g1_point_file, g2_point_file) = Self::get_points(&cfg).await?;
         let kzg_handle = tokio::task::spawn_blocking(move || {
-            let g1_point_file_path = g1_point_file.path().to_str().ok_or(KzgError::Setup(
+            let g1_point_file_path = g1_point_file.to_str().ok_or(KzgError::Setup(
                 "Could not format point path into a valid string".to_string(),
             ))?;
-            let g2_point_file_path = g2_point_file.path().to_str().ok_or(KzgError::Setup(
+            let g2_point_file_path = g2_point_file.to_str().ok_or(KzgError::Setup(
                 "Could not format point path into a valid string".to_string(),
             ))?;
             Kzg::setup(
@@ -344,13 +300,13 @@ impl<T: SvcManagerClient> Verifier<T> {
         index: u32,
     ) -> Result<Vec<u8>, VerificationError> {
         let mut index = index;
-        if proof.len() % 32 != 0 {
+        if !proof.len().is_multiple_of(32) {
             return Err(VerificationError::WrongProof);
         }
         let mut computed_hash = leaf.to_vec();
         for i in 0..proof.len() / 32 {
             let mut buffer = [0u8; 64];
-            if index % 2 == 0 {
+            if index.is_multiple_of(2) {
                 buffer[..32].copy_from_slice(&computed_hash);
                 buffer[32..].copy_from_slice(&proof[i * 32..(i + 1) * 32]);
             } else {
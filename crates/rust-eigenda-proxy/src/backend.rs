@@ -0,0 +1,149 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use rust_eigenda_srs::{SrsFile, SrsSource};
+use rust_eigenda_v2_client::{
+    cert_verifier_router::CertVerifierResolver,
+    core::Payload,
+    errors::EigenClientError,
+    payload_disperser::{PayloadDisperser, PayloadDisperserConfig},
+    relay_client::{RelayClient, RelayClientConfig},
+    relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig},
+    rust_eigenda_signers::signers::private_key::Signer as PrivateKeySigner,
+    srs::{SRSConfig, Srs},
+};
+use rust_eigenda_v2_common::EigenDACert;
+
+use crate::{
+    config::ProxyConfig,
+    errors::{BackendError, ProxyError},
+};
+
+/// Storage the proxy serves `/put` and `/get` from.
+#[async_trait::async_trait]
+pub trait DaBackend: Send + Sync {
+    /// Stores a payload, returning its cert once it is certified.
+    async fn put(&self, payload: Vec<u8>) -> Result<EigenDACert, ProxyError>;
+    /// Verifies a cert and returns its payload.
+    async fn get(&self, cert: EigenDACert) -> Result<Vec<u8>, ProxyError>;
+    /// Max size of the payloads accepted by [`Self::put`].
+    fn max_payload_size(&self) -> usize;
+}
+
+/// [`DaBackend`] that disperses payloads to EigenDA and retrieves them from its relays.
+pub struct EigenDABackend {
+    payload_disperser: PayloadDisperser,
+    retriever_config: RelayPayloadRetrieverConfig,
+    srs: Arc<Srs>,
+    // the relay clients and the cert verifier share their connections when cloned, so each `/get`
+    // gets its own retriever and retrievals run concurrently
+    relay_client: RelayClient,
+    cert_verifier: CertVerifierResolver<PrivateKeySigner>,
+    put_timeout: Duration,
+    status_poll_interval: Duration,
+}
+
+impl EigenDABackend {
+    /// Builds the disperser, relay client, cert verifier and retriever described by the config.
+    pub async fn new(config: &ProxyConfig) -> Result<Self, BackendError> {
+        let signer = PrivateKeySigner::new(config.signer_private_key()?);
+        let eth_rpc_url = config.eth_rpc_url()?;
+        let cert_verifier_address = config.cert_verifier_address()?;
+        let payload_form = config.payload_form()?;
+
+        let payload_disperser = PayloadDisperser::new(
+            PayloadDisperserConfig {
+                polynomial_form: payload_form,
+                blob_version: config.blob_version,
+                cert_verifier_address,
+                eth_rpc_url: eth_rpc_url.clone(),
+                disperser_rpc: config.disperser_rpc.clone(),
+                use_secure_grpc_flag: config.use_secure_grpc,
+                settlement_layer_confirmation_depth: config.settlement_layer_confirmation_depth,
+                cert_version: config.cert_version,
+                chain_id: config.chain_id,
+            },
+            signer.clone(),
+        )
+        .await?;
+
+        let srs = Srs::load(&SRSConfig {
+            g1: SrsFile::new(SrsSource::Path(config.srs_g1_path.clone().into())),
+            order: config.srs_order,
+            g2_power_of_2: None,
+            cache_dir: None,
+        })
+        .await
+        .map_err(|e| BackendError::Srs(e.to_string()))?;
+        if config.verify_length_proof {
+            srs.check_length_proof_points()
+                .map_err(|e| BackendError::Srs(e.to_string()))?;
+        }
+
+        let relay_client = RelayClient::new(
+            RelayClientConfig {
+                max_grpc_message_size: config.max_grpc_message_size,
+                relay_clients_keys: config.relay_keys.clone(),
+                relay_registry_address: config.relay_registry_address,
+                eth_rpc_url: eth_rpc_url.clone(),
+                chain_id: config.chain_id,
+            },
+            signer.clone(),
+        )
+        .await?;
+        let cert_verifier = CertVerifierResolver::new(cert_verifier_address, eth_rpc_url, signer)?;
+
+        Ok(Self {
+            payload_disperser,
+            retriever_config: RelayPayloadRetrieverConfig {
+                payload_form,
+                retrieval_timeout_secs: config.retrieval_timeout(),
+                blob_params: HashMap::from([(config.blob_version, config.blob_params()?)]),
+                verify_length_proof: config.verify_length_proof,
+                decode_mode: config.decode_mode,
+            },
+            srs: Arc::new(srs),
+            relay_client,
+            cert_verifier,
+            put_timeout: config.put_timeout(),
+            status_poll_interval: config.status_poll_interval(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl DaBackend for EigenDABackend {
+    async fn put(&self, payload: Vec<u8>) -> Result<EigenDACert, ProxyError> {
+        let blob_key = self
+            .payload_disperser
+            .send_payload(Payload::new(payload))
+            .await
+            .map_err(EigenClientError::from)?;
+
+        let start = tokio::time::Instant::now();
+        loop {
+            if let Some(cert) = self.payload_disperser.get_inclusion_data(&blob_key).await? {
+                return Ok(cert);
+            }
+            if start.elapsed() >= self.put_timeout {
+                return Err(ProxyError::DispersalTimeout(blob_key.to_hex()));
+            }
+            tokio::time::sleep(self.status_poll_interval).await;
+        }
+    }
+
+    async fn get(&self, cert: EigenDACert) -> Result<Vec<u8>, ProxyError> {
+        let payload = RelayPayloadRetriever::new(
+            self.retriever_config.clone(),
+            self.srs.clone(),
+            self.relay_client.clone(),
+        )?
+        .with_cert_verifier(Box::new(self.cert_verifier.clone()))
+        .get_payload(cert)
+        .await?;
+        Ok(payload.serialize())
+    }
+
+    fn max_payload_size(&self) -> usize {
+        PayloadDisperser::<PrivateKeySigner>::blob_size_limit().unwrap_or(usize::MAX)
+    }
+}
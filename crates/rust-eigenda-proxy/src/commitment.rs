@@ -0,0 +1,153 @@
+//! Commitments returned by `/put` and received by `/get`, as the eigenda-proxy encodes them.
+//!
+//! In every mode the cert is [`EigenDACert::to_rlp_bytes`]: its version byte followed by the RLP encoded cert. The
+//! OP Stack alt-DA modes prefix it with the alt-DA commitment header.
+use std::str::FromStr;
+
+use rust_eigenda_v2_common::EigenDACert;
+
+use crate::errors::CommitmentError;
+
+/// Alt-DA commitment type of keccak256 commitments, where the commitment is the hash of the payload.
+pub const OP_KECCAK_COMMITMENT_TYPE: u8 = 0x00;
+/// Alt-DA commitment type of generic commitments, defined by each DA layer.
+pub const OP_GENERIC_COMMITMENT_TYPE: u8 = 0x01;
+/// DA layer byte of EigenDA in generic commitments.
+pub const EIGENDA_DA_LAYER_BYTE: u8 = 0x00;
+
+/// How commitments are encoded, selected with the `commitment_mode` query parameter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommitmentMode {
+    /// OP Stack alt-DA generic commitments: `[0x01, 0x00, cert...]`. The default, as op-node does not set the
+    /// query parameter.
+    #[default]
+    OptimismGeneric,
+    /// The cert alone, as used by Arbitrum Nitro.
+    Standard,
+}
+
+impl CommitmentMode {
+    /// Name of the mode in the `commitment_mode` query parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitmentMode::OptimismGeneric => "optimism_generic",
+            CommitmentMode::Standard => "standard",
+        }
+    }
+}
+
+impl FromStr for CommitmentMode {
+    type Err = CommitmentError;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "optimism_generic" => Ok(CommitmentMode::OptimismGeneric),
+            "standard" => Ok(CommitmentMode::Standard),
+            mode => Err(CommitmentError::UnknownMode(mode.to_string())),
+        }
+    }
+}
+
+/// Encodes the commitment of a cert in the given mode.
+pub fn encode_commitment(cert: &EigenDACert, mode: CommitmentMode) -> Vec<u8> {
+    match mode {
+        CommitmentMode::OptimismGeneric => {
+            let mut commitment = vec![OP_GENERIC_COMMITMENT_TYPE, EIGENDA_DA_LAYER_BYTE];
+            commitment.extend(cert.to_rlp_bytes());
+            commitment
+        }
+        CommitmentMode::Standard => cert.to_rlp_bytes(),
+    }
+}
+
+/// Decodes the cert of a commitment in the given mode, see [`encode_commitment`].
+pub fn decode_commitment(
+    commitment: &[u8],
+    mode: CommitmentMode,
+) -> Result<EigenDACert, CommitmentError> {
+    let cert_bytes = match mode {
+        CommitmentMode::OptimismGeneric => {
+            let (&commitment_type, rest) =
+                commitment.split_first().ok_or(CommitmentError::Empty)?;
+            if commitment_type != OP_GENERIC_COMMITMENT_TYPE {
+                return Err(CommitmentError::UnsupportedCommitmentType(commitment_type));
+            }
+            let (&da_layer, cert_bytes) = rest.split_first().ok_or(CommitmentError::Empty)?;
+            if da_layer != EIGENDA_DA_LAYER_BYTE {
+                return Err(CommitmentError::UnsupportedDaLayer(da_layer));
+            }
+            cert_bytes
+        }
+        CommitmentMode::Standard => commitment,
+    };
+    Ok(EigenDACert::from_rlp_bytes(cert_bytes)?)
+}
+
+/// Decodes the hex commitment of a `/get/0x...` path.
+pub fn decode_hex_commitment(
+    commitment: &str,
+    mode: CommitmentMode,
+) -> Result<EigenDACert, CommitmentError> {
+    let commitment = commitment.strip_prefix("0x").unwrap_or(commitment);
+    decode_commitment(&hex::decode(commitment)?, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_eigenda_v2_common::EigenDACertError;
+
+    use super::*;
+    use crate::tests::get_test_eigenda_cert;
+
+    #[test]
+    fn test_commitment_round_trip() {
+        let cert = get_test_eigenda_cert();
+        for mode in [CommitmentMode::OptimismGeneric, CommitmentMode::Standard] {
+            let commitment = encode_commitment(&cert, mode);
+            assert_eq!(decode_commitment(&commitment, mode).unwrap(), cert);
+
+            let hex_commitment = format!("0x{}", hex::encode(&commitment));
+            assert_eq!(decode_hex_commitment(&hex_commitment, mode).unwrap(), cert);
+            assert_eq!(mode.as_str().parse::<CommitmentMode>().unwrap(), mode);
+        }
+    }
+
+    #[test]
+    fn test_commitment_headers() {
+        let cert = get_test_eigenda_cert();
+        let commitment = encode_commitment(&cert, CommitmentMode::OptimismGeneric);
+        assert_eq!(commitment[..3], [0x01, 0x00, u8::from(cert.version())]);
+        assert_eq!(
+            encode_commitment(&cert, CommitmentMode::Standard),
+            commitment[2..]
+        );
+
+        let mut keccak_commitment = commitment.clone();
+        keccak_commitment[0] = OP_KECCAK_COMMITMENT_TYPE;
+        assert!(matches!(
+            decode_commitment(&keccak_commitment, CommitmentMode::OptimismGeneric),
+            Err(CommitmentError::UnsupportedCommitmentType(0x00))
+        ));
+
+        let mut other_da_layer = commitment.clone();
+        other_da_layer[1] = 0x01;
+        assert!(matches!(
+            decode_commitment(&other_da_layer, CommitmentMode::OptimismGeneric),
+            Err(CommitmentError::UnsupportedDaLayer(0x01))
+        ));
+
+        assert!(matches!(
+            decode_commitment(&[], CommitmentMode::OptimismGeneric),
+            Err(CommitmentError::Empty)
+        ));
+        assert!(matches!(
+            decode_commitment(
+                &commitment[..commitment.len() - 1],
+                CommitmentMode::OptimismGeneric
+            ),
+            Err(CommitmentError::Cert(EigenDACertError::RlpDecoding(_)))
+        ));
+        assert!(decode_hex_commitment("0xzz", CommitmentMode::Standard).is_err());
+        assert!("optimism_keccak".parse::<CommitmentMode>().is_err());
+    }
+}
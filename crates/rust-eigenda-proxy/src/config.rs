@@ -0,0 +1,360 @@
+use std::{net::SocketAddr, path::Path, str::FromStr, time::Duration};
+
+use ethereum_types::H160;
+use rust_eigenda_v2_client::{
+    cert_verifier_router::CertVerifierAddress,
+    core::{BlobParams, PayloadDecodeMode, PayloadForm},
+    relay_payload_retriever::BLOB_VERSION_0_PARAMS,
+    rust_eigenda_signers::SecretKey,
+    utils::SecretUrl,
+};
+use rust_eigenda_v2_common::EigenDACertVersion;
+use secrecy::{ExposeSecret, SecretString};
+use url::Url;
+
+use crate::errors::ConfigError;
+
+/// Prefix of the environment variables the config is read from, e.g. `EIGENDA_PROXY_ETH_RPC_URL`.
+pub const ENV_PREFIX: &str = "EIGENDA_PROXY_";
+
+/// Configuration of the proxy, read from a TOML file or from `EIGENDA_PROXY_*` environment variables.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyConfig {
+    /// Address the REST server listens on.
+    #[serde(default = "default_listen_addr")]
+    pub listen_addr: SocketAddr,
+    pub eth_rpc_url: SecretString,
+    /// Chain id the ETH RPC must be connected to, checked at startup. Unset skips the check.
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    pub disperser_rpc: String,
+    #[serde(default = "default_true")]
+    pub use_secure_grpc: bool,
+    /// Address of the EigenDACertVerifier. Exactly one of it and `cert_verifier_router_address` must be set.
+    pub cert_verifier_address: Option<H160>,
+    /// Address of the EigenDACertVerifierRouter.
+    pub cert_verifier_router_address: Option<H160>,
+    pub relay_registry_address: H160,
+    /// Keys of the relays to retrieve blobs from.
+    pub relay_keys: Vec<u32>,
+    /// Hex encoded private key that signs dispersals and relay requests.
+    pub signer_private_key: SecretString,
+    #[serde(default)]
+    pub blob_version: u16,
+    #[serde(default = "default_cert_version")]
+    pub cert_version: EigenDACertVersion,
+    /// Whether payloads are dispersed in coefficient (`coeff`) or evaluation (`eval`) form.
+    #[serde(default = "default_polynomial_form")]
+    pub polynomial_form: String,
+    #[serde(default)]
+    pub settlement_layer_confirmation_depth: u32,
+    /// Path of the G1 points of the SRS (`g1.point`).
+    pub srs_g1_path: String,
+    /// Number of points of the SRS the G1 points belong to.
+    #[serde(default = "default_srs_order")]
+    pub srs_order: u32,
+    #[serde(default = "default_max_grpc_message_size")]
+    pub max_grpc_message_size: usize,
+    /// Time a `/put` waits for the blob to be certified before failing.
+    #[serde(default = "default_put_timeout_secs")]
+    pub put_timeout_secs: u64,
+    /// Time between polls of the blob status while waiting for the blob to be certified.
+    #[serde(default = "default_status_poll_interval_millis")]
+    pub status_poll_interval_millis: u64,
+    /// Time a relay has to serve a blob before the next relay is tried.
+    #[serde(default = "default_retrieval_timeout_secs")]
+    pub retrieval_timeout_secs: u64,
+    /// Number of chunks of `blob_version`, as registered in the EigenDAThresholdRegistry.
+    /// Defaults to the value of blob version 0, and must be set for any other version.
+    #[serde(default)]
+    pub blob_num_chunks: Option<u32>,
+    /// Coding rate of `blob_version`, as registered in the EigenDAThresholdRegistry.
+    /// Defaults to the value of blob version 0, and must be set for any other version.
+    #[serde(default)]
+    pub blob_coding_rate: Option<u32>,
+    /// Whether to verify the length proof of retrieved blobs. It needs an SRS file holding all the `srs_order`
+    /// points, so the proxy fails to start when enabled with a truncated `g1.point`.
+    #[serde(default = "default_true")]
+    pub verify_length_proof: bool,
+    /// How strictly retrieved blobs are decoded into payloads (`lenient` or `strict`).
+    #[serde(default)]
+    pub decode_mode: PayloadDecodeMode,
+}
+
+fn default_listen_addr() -> SocketAddr {
+    SocketAddr::from(([0, 0, 0, 0], 3100))
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_cert_version() -> EigenDACertVersion {
+    EigenDACertVersion::V2
+}
+
+fn default_polynomial_form() -> String {
+    "coeff".to_string()
+}
+
+fn default_srs_order() -> u32 {
+    268435456
+}
+
+fn default_max_grpc_message_size() -> usize {
+    100 * 1024 * 1024
+}
+
+fn default_put_timeout_secs() -> u64 {
+    600
+}
+
+fn default_status_poll_interval_millis() -> u64 {
+    1000
+}
+
+fn default_retrieval_timeout_secs() -> u64 {
+    30
+}
+
+impl ProxyConfig {
+    /// Reads the config from a TOML file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::from_toml(&contents)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reads the config from the `EIGENDA_PROXY_*` environment variables. Lists are comma separated.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_env_iter(std::env::vars())
+    }
+
+    /// Reads the config from `EIGENDA_PROXY_*` variables, ignoring any other.
+    pub fn from_env_iter(
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let config: Self = envy::prefixed(ENV_PREFIX).from_iter(vars)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        self.cert_verifier_address()?;
+        self.eth_rpc_url()?;
+        self.payload_form()?;
+        self.signer_private_key()?;
+        self.blob_params()?;
+        if self.relay_keys.is_empty() {
+            return Err(ConfigError::Invalid(
+                "relay_keys must not be empty".to_string(),
+            ));
+        }
+        if self.status_poll_interval_millis == 0 {
+            return Err(ConfigError::Invalid(
+                "status_poll_interval_millis must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn cert_verifier_address(&self) -> Result<CertVerifierAddress, ConfigError> {
+        match (
+            self.cert_verifier_address,
+            self.cert_verifier_router_address,
+        ) {
+            (Some(address), None) => Ok(CertVerifierAddress::Verifier(address)),
+            (None, Some(address)) => Ok(CertVerifierAddress::Router(address)),
+            _ => Err(ConfigError::Invalid(
+                "exactly one of cert_verifier_address and cert_verifier_router_address must be set"
+                    .to_string(),
+            )),
+        }
+    }
+
+    pub fn eth_rpc_url(&self) -> Result<SecretUrl, ConfigError> {
+        // the URL is not included in the error, as it may hold an API key
+        let url = Url::parse(self.eth_rpc_url.expose_secret())
+            .map_err(|e| ConfigError::Invalid(format!("invalid eth_rpc_url: {}", e)))?;
+        Ok(SecretUrl::new(url))
+    }
+
+    pub fn payload_form(&self) -> Result<PayloadForm, ConfigError> {
+        match self.polynomial_form.as_str() {
+            "coeff" => Ok(PayloadForm::Coeff),
+            "eval" => Ok(PayloadForm::Eval),
+            form => Err(ConfigError::Invalid(format!(
+                "invalid polynomial_form {}, expected coeff or eval",
+                form
+            ))),
+        }
+    }
+
+    /// Encoding parameters of `blob_version`, needed to verify retrieved blobs.
+    pub fn blob_params(&self) -> Result<BlobParams, ConfigError> {
+        match (self.blob_num_chunks, self.blob_coding_rate) {
+            (Some(num_chunks), Some(coding_rate)) => Ok(BlobParams {
+                num_chunks,
+                coding_rate,
+            }),
+            (None, None) if self.blob_version == 0 => Ok(BLOB_VERSION_0_PARAMS),
+            _ => Err(ConfigError::Invalid(format!(
+                "blob_num_chunks and blob_coding_rate must both be set for blob version {}",
+                self.blob_version
+            ))),
+        }
+    }
+
+    pub fn signer_private_key(&self) -> Result<SecretKey, ConfigError> {
+        let key = self.signer_private_key.expose_secret();
+        SecretKey::from_str(key.strip_prefix("0x").unwrap_or(key))
+            .map_err(|_| ConfigError::Invalid("invalid signer_private_key".to_string()))
+    }
+
+    pub fn put_timeout(&self) -> Duration {
+        Duration::from_secs(self.put_timeout_secs)
+    }
+
+    pub fn status_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.status_poll_interval_millis)
+    }
+
+    pub fn retrieval_timeout(&self) -> Duration {
+        Duration::from_secs(self.retrieval_timeout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_PRIVATE_KEY: &str =
+        "0x1111111111111111111111111111111111111111111111111111111111111111";
+
+    fn test_vars() -> Vec<(String, String)> {
+        [
+            ("ETH_RPC_URL", "https://ethereum-holesky-rpc.publicnode.com"),
+            (
+                "DISPERSER_RPC",
+                "https://disperser-testnet-holesky.eigenda.xyz",
+            ),
+            (
+                "CERT_VERIFIER_ADDRESS",
+                "0xfe52fe1940858dcb6e12153e2104ad0fdfbe1162",
+            ),
+            (
+                "RELAY_REGISTRY_ADDRESS",
+                "0xac8c6c7ee7572975454e2f0b5c720f9e74989254",
+            ),
+            ("RELAY_KEYS", "0,1,2"),
+            ("SIGNER_PRIVATE_KEY", TEST_PRIVATE_KEY),
+            ("SRS_G1_PATH", "resources/g1.point"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (format!("{}{}", ENV_PREFIX, key), value.to_string()))
+        .chain([("UNRELATED".to_string(), "ignored".to_string())])
+        .collect()
+    }
+
+    #[test]
+    fn test_config_from_env() {
+        let config = ProxyConfig::from_env_iter(test_vars()).unwrap();
+        assert_eq!(config.relay_keys, vec![0, 1, 2]);
+        assert_eq!(config.listen_addr, default_listen_addr());
+        assert_eq!(config.cert_version, EigenDACertVersion::V2);
+        assert!(matches!(
+            config.cert_verifier_address().unwrap(),
+            CertVerifierAddress::Verifier(_)
+        ));
+        assert_eq!(config.blob_params().unwrap(), BLOB_VERSION_0_PARAMS);
+        assert!(config.verify_length_proof);
+        assert_eq!(config.decode_mode, PayloadDecodeMode::Lenient);
+
+        let mut vars = test_vars();
+        vars.push((format!("{}DECODE_MODE", ENV_PREFIX), "strict".to_string()));
+        vars.push((
+            format!("{}VERIFY_LENGTH_PROOF", ENV_PREFIX),
+            "false".to_string(),
+        ));
+        let config = ProxyConfig::from_env_iter(vars).unwrap();
+        assert_eq!(config.decode_mode, PayloadDecodeMode::Strict);
+        assert!(!config.verify_length_proof);
+
+        let mut vars = test_vars();
+        vars.push((
+            format!("{}CERT_VERIFIER_ROUTER_ADDRESS", ENV_PREFIX),
+            "0xfe52fe1940858dcb6e12153e2104ad0fdfbe1162".to_string(),
+        ));
+        assert!(matches!(
+            ProxyConfig::from_env_iter(vars),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_config_from_toml() {
+        let toml = format!(
+            r#"
+            listen_addr = "127.0.0.1:4242"
+            eth_rpc_url = "https://ethereum-holesky-rpc.publicnode.com"
+            disperser_rpc = "https://disperser-testnet-holesky.eigenda.xyz"
+            cert_verifier_router_address = "0xfe52fe1940858dcb6e12153e2104ad0fdfbe1162"
+            relay_registry_address = "0xac8c6c7ee7572975454e2f0b5c720f9e74989254"
+            relay_keys = [0, 1]
+            signer_private_key = "{}"
+            cert_version = "V3"
+            polynomial_form = "eval"
+            srs_g1_path = "resources/g1.point"
+            "#,
+            TEST_PRIVATE_KEY
+        );
+        let config = ProxyConfig::from_toml(&toml).unwrap();
+        assert_eq!(config.listen_addr, "127.0.0.1:4242".parse().unwrap());
+        assert_eq!(config.cert_version, EigenDACertVersion::V3);
+        assert!(matches!(config.payload_form().unwrap(), PayloadForm::Eval));
+        assert!(matches!(
+            config.cert_verifier_address().unwrap(),
+            CertVerifierAddress::Router(_)
+        ));
+
+        assert_eq!(config.blob_params().unwrap(), BLOB_VERSION_0_PARAMS);
+        assert!(config.verify_length_proof);
+
+        let strict = format!(
+            "{}\nverify_length_proof = false\ndecode_mode = \"strict\"\n",
+            toml
+        );
+        let config = ProxyConfig::from_toml(&strict).unwrap();
+        assert!(!config.verify_length_proof);
+        assert_eq!(config.decode_mode, PayloadDecodeMode::Strict);
+
+        // blob versions other than 0 have no default parameters
+        let other_version = format!("{}\nblob_version = 1\n", toml);
+        assert!(ProxyConfig::from_toml(&other_version).is_err());
+        let other_version = format!(
+            "{}\nblob_version = 1\nblob_num_chunks = 4096\nblob_coding_rate = 4\n",
+            toml
+        );
+        assert_eq!(
+            ProxyConfig::from_toml(&other_version)
+                .unwrap()
+                .blob_params()
+                .unwrap(),
+            BlobParams {
+                num_chunks: 4096,
+                coding_rate: 4,
+            }
+        );
+
+        let invalid_form = toml.replace("\"eval\"", "\"monomial\"");
+        assert!(ProxyConfig::from_toml(&invalid_form).is_err());
+        let unknown_field = format!("{}\nunknown = 1\n", toml);
+        assert!(ProxyConfig::from_toml(&unknown_field).is_err());
+    }
+}
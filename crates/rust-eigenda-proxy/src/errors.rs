@@ -0,0 +1,114 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use rust_eigenda_v2_client::errors::{
+    CertVerifierError, EigenClientError, PayloadDisperserError, RelayClientError,
+    RelayPayloadRetrieverError,
+};
+use rust_eigenda_v2_common::EigenDACertError;
+
+/// Errors loading the configuration of the proxy
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid config file: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid config in environment: {0}")]
+    Env(#[from] envy::Error),
+    #[error("Invalid config: {0}")]
+    Invalid(String),
+}
+
+/// Errors of the commitments received by the proxy
+#[derive(Debug, thiserror::Error)]
+pub enum CommitmentError {
+    #[error("Invalid hex commitment: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Empty commitment")]
+    Empty,
+    #[error("Unsupported commitment type {0:#04x}, only generic commitments are supported")]
+    UnsupportedCommitmentType(u8),
+    #[error("Unsupported DA layer {0:#04x}, only EigenDA is supported")]
+    UnsupportedDaLayer(u8),
+    #[error("Unknown commitment mode {0}")]
+    UnknownMode(String),
+    #[error(transparent)]
+    Cert(#[from] EigenDACertError),
+}
+
+/// Errors building the clients the proxy runs on
+#[derive(Debug, thiserror::Error)]
+pub enum BackendError {
+    #[error(transparent)]
+    PayloadDisperser(#[from] PayloadDisperserError),
+    #[error(transparent)]
+    RelayClient(#[from] RelayClientError),
+    #[error(transparent)]
+    RelayPayloadRetriever(#[from] RelayPayloadRetrieverError),
+    #[error(transparent)]
+    CertVerifier(#[from] CertVerifierError),
+    #[error("Failed to load SRS: {0}")]
+    Srs(String),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+}
+
+/// Errors of the requests served by the proxy, each answered with its own status code
+#[derive(Debug, thiserror::Error)]
+pub enum ProxyError {
+    /// The request is malformed, e.g. the commitment cannot be decoded.
+    #[error(transparent)]
+    Commitment(#[from] CommitmentError),
+    #[error("Payload of {size} bytes exceeds the maximum of {max} bytes")]
+    PayloadTooLarge { size: usize, max: usize },
+    /// The cert is not valid and will never be: rollups must drop it.
+    #[error("Invalid certificate: {0}")]
+    InvalidCert(String),
+    /// The dispersal failed. Batchers may fail over to another DA layer.
+    #[error("Dispersal failed: {0}")]
+    Dispersal(#[from] EigenClientError),
+    #[error("Timed out waiting for the blob {0} to be certified")]
+    DispersalTimeout(String),
+    /// The retrieval failed, but may succeed if retried.
+    #[error("Retrieval failed: {0}")]
+    Retrieval(RelayPayloadRetrieverError),
+    #[error("Unsupported request: {0}")]
+    Unsupported(String),
+}
+
+impl From<RelayPayloadRetrieverError> for ProxyError {
+    fn from(err: RelayPayloadRetrieverError) -> Self {
+        match err {
+            RelayPayloadRetrieverError::CertVerificationFailed(failure) => {
+                ProxyError::InvalidCert(failure.to_string())
+            }
+            err => ProxyError::Retrieval(err),
+        }
+    }
+}
+
+impl ProxyError {
+    /// Status code of the error, following the eigenda-proxy: 418 for invalid certs, so that rollup derivation
+    /// drops them, and 503 for failed dispersals, so that batchers fail over.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            // a cert that cannot be decoded is as invalid as one rejected on chain
+            ProxyError::Commitment(CommitmentError::Cert(_)) => StatusCode::IM_A_TEAPOT,
+            ProxyError::Commitment(_) | ProxyError::Unsupported(_) => StatusCode::BAD_REQUEST,
+            ProxyError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            ProxyError::InvalidCert(_) => StatusCode::IM_A_TEAPOT,
+            ProxyError::Dispersal(_) | ProxyError::DispersalTimeout(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            ProxyError::Retrieval(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ProxyError {
+    fn into_response(self) -> Response {
+        (self.status_code(), self.to_string()).into_response()
+    }
+}
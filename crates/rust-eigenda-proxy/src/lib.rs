@@ -0,0 +1,20 @@
+pub mod backend;
+pub mod commitment;
+pub mod config;
+pub mod errors;
+pub mod metrics;
+pub mod server;
+
+#[cfg(test)]
+mod tests {
+    use rust_eigenda_v2_common::{EigenDACert, EigenDACertV2};
+
+    const EIGENDA_CERT_V2_RLP_HEX: &str =
+        include_str!("../../rust-eigenda-v2-common/testdata/eigenda_cert_v2.rlp.hex");
+
+    /// The cert of the test vectors of `rust-eigenda-v2-common`.
+    pub fn get_test_eigenda_cert() -> EigenDACert {
+        let bytes = hex::decode(EIGENDA_CERT_V2_RLP_HEX.trim()).unwrap();
+        EigenDACert::V2(EigenDACertV2::from_rlp_bytes(&bytes).unwrap())
+    }
+}
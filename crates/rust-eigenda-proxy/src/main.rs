@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use rust_eigenda_proxy::{
+    backend::EigenDABackend, config::ProxyConfig, metrics::Metrics, server::router,
+};
+use tracing_subscriber::EnvFilter;
+
+const USAGE: &str = "Usage: eigenda-proxy [--config <config.toml>]
+
+Without --config, the config is read from the EIGENDA_PROXY_* environment variables.
+Logs are filtered with RUST_LOG, info by default.";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = match args.as_slice() {
+        [] => ProxyConfig::from_env()?,
+        [flag, path] if flag == "--config" => ProxyConfig::from_file(path)?,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let backend = EigenDABackend::new(&config).await?;
+    let app = router(Arc::new(backend), Arc::new(Metrics::default()));
+
+    let listener = tokio::net::TcpListener::bind(config.listen_addr).await?;
+    tracing::info!(listen_addr = %config.listen_addr, "eigenda-proxy listening");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+/// Counters of the requests served by the proxy, exposed in the Prometheus text format at `/metrics`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    inner: Mutex<MetricsInner>,
+}
+
+#[derive(Debug, Default)]
+struct MetricsInner {
+    /// Requests by method, route and status code.
+    requests: BTreeMap<(String, String, u16), u64>,
+    /// Sum of the durations of the requests by method and route, in seconds, and their count.
+    durations: BTreeMap<(String, String), (f64, u64)>,
+    /// Payload bytes by route.
+    payload_bytes: BTreeMap<String, u64>,
+}
+
+impl Metrics {
+    pub fn record_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner
+            .requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+        let (sum, count) = inner
+            .durations
+            .entry((method.to_string(), route.to_string()))
+            .or_default();
+        *sum += duration.as_secs_f64();
+        *count += 1;
+    }
+
+    /// Records the size of a payload stored or retrieved through the given route.
+    pub fn record_payload(&self, route: &str, bytes: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner.payload_bytes.entry(route.to_string()).or_default() += bytes as u64;
+    }
+
+    /// Renders the metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let inner = self.inner.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str(
+            "# HELP eigenda_proxy_requests_total Requests served, by method, route and status.\n",
+        );
+        out.push_str("# TYPE eigenda_proxy_requests_total counter\n");
+        for ((method, route, status), count) in &inner.requests {
+            let _ = writeln!(
+                out,
+                "eigenda_proxy_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                method, route, status, count
+            );
+        }
+
+        out.push_str(
+            "# HELP eigenda_proxy_request_duration_seconds Duration of the requests served.\n",
+        );
+        out.push_str("# TYPE eigenda_proxy_request_duration_seconds summary\n");
+        for ((method, route), (sum, count)) in &inner.durations {
+            let _ = writeln!(
+                out,
+                "eigenda_proxy_request_duration_seconds_sum{{method=\"{}\",route=\"{}\"}} {}",
+                method, route, sum
+            );
+            let _ = writeln!(
+                out,
+                "eigenda_proxy_request_duration_seconds_count{{method=\"{}\",route=\"{}\"}} {}",
+                method, route, count
+            );
+        }
+
+        out.push_str("# HELP eigenda_proxy_payload_bytes_total Payload bytes stored or retrieved, by route.\n");
+        out.push_str("# TYPE eigenda_proxy_payload_bytes_total counter\n");
+        for (route, bytes) in &inner.payload_bytes {
+            let _ = writeln!(
+                out,
+                "eigenda_proxy_payload_bytes_total{{route=\"{}\"}} {}",
+                route, bytes
+            );
+        }
+        out
+    }
+}
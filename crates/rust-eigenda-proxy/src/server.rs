@@ -0,0 +1,290 @@
+//! REST API of the eigenda-proxy:
+//!
+//! - `PUT /put?commitment_mode=...`: stores the body as a payload, answering with its commitment.
+//! - `GET /get/0x{commitment}?commitment_mode=...`: answers with the payload of the commitment.
+//! - `GET /health` and `GET /metrics`.
+//!
+//! `POST` is accepted as well as `PUT`, as some clients use it.
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, MatchedPath, Path, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, put},
+    Router,
+};
+use serde::Deserialize;
+
+use crate::{
+    backend::DaBackend,
+    commitment::{decode_hex_commitment, encode_commitment, CommitmentMode},
+    errors::ProxyError,
+    metrics::Metrics,
+};
+
+#[derive(Clone)]
+struct AppState {
+    backend: Arc<dyn DaBackend>,
+    metrics: Arc<Metrics>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitmentQuery {
+    commitment_mode: Option<String>,
+}
+
+impl CommitmentQuery {
+    fn mode(&self) -> Result<CommitmentMode, ProxyError> {
+        match &self.commitment_mode {
+            Some(mode) => Ok(mode.parse()?),
+            None => Ok(CommitmentMode::default()),
+        }
+    }
+}
+
+/// Builds the router of the proxy on top of the given backend.
+pub fn router(backend: Arc<dyn DaBackend>, metrics: Arc<Metrics>) -> Router {
+    let max_payload_size = backend.max_payload_size();
+    let state = AppState { backend, metrics };
+    Router::new()
+        .route("/put", put(put_payload).post(put_payload))
+        .route("/put/", put(put_payload).post(put_payload))
+        .route(
+            "/put/:commitment",
+            put(put_keccak_payload).post(put_keccak_payload),
+        )
+        .route("/get/:commitment", get(get_payload))
+        .route("/health", get(health))
+        .route("/metrics", get(render_metrics))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            record_metrics,
+        ))
+        .layer(DefaultBodyLimit::max(max_payload_size))
+        .with_state(state)
+}
+
+async fn put_payload(
+    State(state): State<AppState>,
+    Query(query): Query<CommitmentQuery>,
+    body: Bytes,
+) -> Result<Response, ProxyError> {
+    let mode = query.mode()?;
+    let max = state.backend.max_payload_size();
+    if body.len() > max {
+        return Err(ProxyError::PayloadTooLarge {
+            size: body.len(),
+            max,
+        });
+    }
+    state.metrics.record_payload("/put", body.len());
+    let cert = state.backend.put(body.to_vec()).await?;
+    Ok(octet_stream(encode_commitment(&cert, mode)))
+}
+
+/// Keccak commitments are computed by the client, which sends them along with the payload. They are not supported,
+/// as EigenDA certs cannot be derived from the payload.
+async fn put_keccak_payload(Path(_commitment): Path<String>) -> Result<Response, ProxyError> {
+    Err(ProxyError::Unsupported(
+        "keccak256 commitments are not supported".to_string(),
+    ))
+}
+
+async fn get_payload(
+    State(state): State<AppState>,
+    Path(commitment): Path<String>,
+    Query(query): Query<CommitmentQuery>,
+) -> Result<Response, ProxyError> {
+    let cert = decode_hex_commitment(&commitment, query.mode()?)?;
+    let payload = state.backend.get(cert).await?;
+    state.metrics.record_payload("/get", payload.len());
+    Ok(octet_stream(payload))
+}
+
+async fn health() -> StatusCode {
+    StatusCode::OK
+}
+
+async fn render_metrics(State(state): State<AppState>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+        .into_response()
+}
+
+async fn record_metrics(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let start = Instant::now();
+    let response = next.run(request).await;
+    state
+        .metrics
+        .record_request(&method, &route, response.status().as_u16(), start.elapsed());
+    response
+}
+
+fn octet_stream(bytes: Vec<u8>) -> Response {
+    ([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use axum::{body::Body, http::Method};
+    use rust_eigenda_v2_common::EigenDACert;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::tests::get_test_eigenda_cert;
+
+    const MAX_PAYLOAD_SIZE: usize = 1024;
+
+    /// Backend that certifies every payload with the same cert, keeping the last one.
+    #[derive(Default)]
+    struct MockBackend {
+        payload: Mutex<Option<Vec<u8>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl DaBackend for MockBackend {
+        async fn put(&self, payload: Vec<u8>) -> Result<EigenDACert, ProxyError> {
+            *self.payload.lock().unwrap() = Some(payload);
+            Ok(get_test_eigenda_cert())
+        }
+
+        async fn get(&self, cert: EigenDACert) -> Result<Vec<u8>, ProxyError> {
+            if cert != get_test_eigenda_cert() {
+                return Err(ProxyError::InvalidCert("unknown cert".to_string()));
+            }
+            Ok(self.payload.lock().unwrap().clone().unwrap_or_default())
+        }
+
+        fn max_payload_size(&self) -> usize {
+            MAX_PAYLOAD_SIZE
+        }
+    }
+
+    fn test_router() -> Router {
+        router(
+            Arc::new(MockBackend::default()),
+            Arc::new(Metrics::default()),
+        )
+    }
+
+    async fn send(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: Vec<u8>,
+    ) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::from(body))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_put_and_get() {
+        let router = test_router();
+        let payload = b"hello eigenda".to_vec();
+        let cert = get_test_eigenda_cert();
+
+        for (query, mode) in [
+            ("", CommitmentMode::OptimismGeneric),
+            (
+                "?commitment_mode=optimism_generic",
+                CommitmentMode::OptimismGeneric,
+            ),
+            ("?commitment_mode=standard", CommitmentMode::Standard),
+        ] {
+            let (status, commitment) = send(
+                &router,
+                Method::PUT,
+                &format!("/put{}", query),
+                payload.clone(),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(commitment, encode_commitment(&cert, mode));
+
+            let uri = format!("/get/0x{}{}", hex::encode(&commitment), query);
+            let (status, retrieved) = send(&router, Method::GET, &uri, vec![]).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(retrieved, payload);
+        }
+
+        let (status, _) = send(&router, Method::POST, "/put/", payload).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_codes() {
+        let router = test_router();
+        let commitment =
+            encode_commitment(&get_test_eigenda_cert(), CommitmentMode::OptimismGeneric);
+
+        // keccak commitments
+        let (status, _) = send(&router, Method::PUT, "/put/0x1234", vec![1]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let mut keccak_commitment = commitment.clone();
+        keccak_commitment[0] = 0x00;
+        let uri = format!("/get/0x{}", hex::encode(&keccak_commitment));
+        assert_eq!(
+            send(&router, Method::GET, &uri, vec![]).await.0,
+            StatusCode::BAD_REQUEST
+        );
+
+        // malformed hex and unknown modes
+        let (status, _) = send(&router, Method::GET, "/get/0xzz", vec![]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&router, Method::PUT, "/put?commitment_mode=keccak", vec![1]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // certs that cannot be decoded are dropped as invalid
+        let uri = format!(
+            "/get/0x{}",
+            hex::encode(&commitment[..commitment.len() - 1])
+        );
+        assert_eq!(
+            send(&router, Method::GET, &uri, vec![]).await.0,
+            StatusCode::IM_A_TEAPOT
+        );
+
+        let (status, _) = send(&router, Method::PUT, "/put", vec![0; MAX_PAYLOAD_SIZE + 1]).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_health_and_metrics() {
+        let router = test_router();
+        assert_eq!(
+            send(&router, Method::GET, "/health", vec![]).await.0,
+            StatusCode::OK
+        );
+        send(&router, Method::PUT, "/put", vec![1, 2, 3]).await;
+
+        let (status, body) = send(&router, Method::GET, "/metrics", vec![]).await;
+        assert_eq!(status, StatusCode::OK);
+        let metrics = String::from_utf8(body).unwrap();
+        assert!(metrics.contains(
+            "eigenda_proxy_requests_total{method=\"PUT\",route=\"/put\",status=\"200\"} 1"
+        ));
+        assert!(metrics.contains("eigenda_proxy_payload_bytes_total{route=\"/put\"} 3"));
+    }
+}
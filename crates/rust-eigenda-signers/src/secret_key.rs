@@ -74,6 +74,20 @@ impl fmt::Debug for SecretKey {
     }
 }
 
+/// Deserializes a hex encoded key, with or without `0x` prefix. Keys are never serialized, so they can only be read
+/// from config files and environment variables.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecretKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        // the error does not include the input, which may be a slightly mistyped key
+        SecretKey::from_str(&s).map_err(|_| serde::de::Error::custom("invalid secret key"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use secp256k1::constants::SECRET_KEY_SIZE;
@@ -160,4 +174,18 @@ mod tests {
         // Then
         assert_eq!(debug_str, expected_debug);
     }
+
+    #[test]
+    fn test_deserialize() {
+        // Given
+        let json = "\"0x0000000000000000000000000000000000000000000000000000000000000001\"";
+
+        // When
+        let sk: SecretKey = serde_json::from_str(json).unwrap();
+        let invalid: Result<SecretKey, _> = serde_json::from_str("\"0x00\"");
+
+        // Then
+        assert_eq!(sk.secret_bytes(), VALID_SK_BYTES);
+        assert!(!invalid.unwrap_err().to_string().contains("0x00"));
+    }
 }
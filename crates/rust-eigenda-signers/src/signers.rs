@@ -2,3 +2,5 @@
 pub mod ethers;
 #[cfg(feature = "private-key-signer")]
 pub mod private_key;
+#[cfg(feature = "web3signer")]
+pub mod web3signer;
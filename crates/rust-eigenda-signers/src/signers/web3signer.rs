@@ -0,0 +1,500 @@
+//! Signer whose key is held by a [Web3Signer](https://github.com/Consensys/web3signer) instance, called through its
+//! Ethereum JSON-RPC: `eth_accounts`, `eth_sign` and `eth_signTransaction`.
+//!
+//! Web3Signer only signs EIP-191 prefixed messages and transactions, never raw digests, so [`Sign::sign_digest`]
+//! always fails: this signer can sign messages and transactions through [`ethers::signers::Signer`], but not the
+//! dispersal, payment and relay requests of EigenDA, which sign raw digests.
+use std::fmt;
+
+use async_trait::async_trait;
+use ethereum_types::{Address, H256, U256};
+use ethers::{
+    providers::{Http, Provider},
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Bytes, Signature,
+    },
+    utils::{hash_message, rlp::Rlp},
+};
+use secp256k1::SECP256K1;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{Message, PublicKey, RecoverableSignature, RecoveryId, Sign};
+
+/// Message signed when connecting, to recover the public key of the account.
+const PUBLIC_KEY_PROBE: &[u8] = b"rust-eigenda-signers web3signer public key";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Web3Signer request {method} failed: {source}")]
+    Request {
+        method: &'static str,
+        #[source]
+        source: ethers::providers::ProviderError,
+    },
+    #[error("Invalid Web3Signer URL: {0}")]
+    Url(String),
+    #[error("Web3Signer does not hold the key of {0:?}")]
+    UnknownAccount(Address),
+    #[error("Invalid Web3Signer response to {method}: {reason}")]
+    InvalidResponse {
+        method: &'static str,
+        reason: String,
+    },
+    #[error("Web3Signer only signs EIP-191 messages and transactions, not raw digests")]
+    DigestSigning,
+    #[error("Web3Signer cannot sign EIP-712 typed data from its digest")]
+    TypedData,
+}
+
+/// Transaction fields of `eth_signTransaction`. The chain id is the one Web3Signer is configured with.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionParams {
+    from: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_price: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_priority_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Bytes>,
+}
+
+impl TransactionParams {
+    fn new(from: Address, tx: &TypedTransaction) -> Self {
+        let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = match tx {
+            TypedTransaction::Eip1559(tx) => {
+                (None, tx.max_fee_per_gas, tx.max_priority_fee_per_gas)
+            }
+            _ => (tx.gas_price(), None, None),
+        };
+        Self {
+            from,
+            to: tx.to_addr().copied(),
+            gas: tx.gas().copied(),
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            nonce: tx.nonce().copied(),
+            value: tx.value().copied(),
+            data: tx.data().cloned(),
+        }
+    }
+}
+
+/// A signer that delegates signing to a Web3Signer instance, see the module docs.
+#[derive(Clone)]
+pub struct Signer {
+    provider: Provider<Http>,
+    address: Address,
+    public_key: PublicKey,
+    chain_id: u64,
+}
+
+// the URL is not printed, as it may hold credentials
+impl fmt::Debug for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("address", &self.address)
+            .field("chain_id", &self.chain_id)
+            .finish()
+    }
+}
+
+impl Signer {
+    /// Connects to Web3Signer, checking that it holds the key of `address` and recovering its public key from the
+    /// signature of a probe message. Transactions are signed for `chain_id`.
+    pub async fn connect(url: &str, address: Address, chain_id: u64) -> Result<Self, Error> {
+        let provider = Provider::<Http>::try_from(url).map_err(|e| Error::Url(e.to_string()))?;
+        let accounts: Vec<Address> = request(&provider, "eth_accounts", ()).await?;
+        if !accounts.contains(&address) {
+            return Err(Error::UnknownAccount(address));
+        }
+
+        let signature = eth_sign(&provider, address, PUBLIC_KEY_PROBE).await?;
+        let public_key = recover_public_key(hash_message(PUBLIC_KEY_PROBE), &signature)
+            .filter(|public_key| public_key.address() == address)
+            .ok_or_else(|| Error::InvalidResponse {
+                method: "eth_sign",
+                reason: "signature does not match the account".to_string(),
+            })?;
+
+        Ok(Self {
+            provider,
+            address,
+            public_key,
+            chain_id,
+        })
+    }
+}
+
+async fn request<T, R>(
+    provider: &Provider<Http>,
+    method: &'static str,
+    params: T,
+) -> Result<R, Error>
+where
+    T: fmt::Debug + Serialize + Send + Sync,
+    R: fmt::Debug + Serialize + serde::de::DeserializeOwned + Send,
+{
+    provider
+        .request(method, params)
+        .await
+        .map_err(|source| Error::Request { method, source })
+}
+
+/// Signs the EIP-191 prefixed message with `eth_sign`, checking the signature against the address.
+async fn eth_sign(
+    provider: &Provider<Http>,
+    address: Address,
+    message: &[u8],
+) -> Result<Signature, Error> {
+    let signature: Bytes = request(
+        provider,
+        "eth_sign",
+        (address, Bytes::from(message.to_vec())),
+    )
+    .await?;
+    let signature =
+        Signature::try_from(signature.as_ref()).map_err(|e| Error::InvalidResponse {
+            method: "eth_sign",
+            reason: e.to_string(),
+        })?;
+    signature
+        .verify(hash_message(message), address)
+        .map_err(|e| Error::InvalidResponse {
+            method: "eth_sign",
+            reason: e.to_string(),
+        })?;
+    Ok(signature)
+}
+
+fn recover_public_key(digest: H256, signature: &Signature) -> Option<PublicKey> {
+    let recovery_id = RecoveryId::from_eth_v(signature.v)?;
+    let recovery_id = secp256k1::ecdsa::RecoveryId::from_i32(recovery_id.to_byte().into()).ok()?;
+    let mut compact = [0u8; 64];
+    signature.r.to_big_endian(&mut compact[..32]);
+    signature.s.to_big_endian(&mut compact[32..]);
+    let signature =
+        secp256k1::ecdsa::RecoverableSignature::from_compact(&compact, recovery_id).ok()?;
+    let message = secp256k1::Message::from_slice(digest.as_bytes()).ok()?;
+    let public_key = SECP256K1.recover_ecdsa(&message, &signature).ok()?;
+    Some(public_key.into())
+}
+
+#[async_trait]
+impl Sign for Signer {
+    type Error = Error;
+
+    /// Always fails, as Web3Signer does not sign raw digests.
+    async fn sign_digest(&self, _message: &Message) -> Result<RecoverableSignature, Self::Error> {
+        Err(Error::DigestSigning)
+    }
+
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+#[async_trait]
+impl ethers::signers::Signer for Signer {
+    type Error = Error;
+
+    /// Signs the hash of the provided message after prefixing it, with `eth_sign`
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        eth_sign(&self.provider, self.address, message.as_ref()).await
+    }
+
+    /// Signs the transaction with `eth_signTransaction`, checking that Web3Signer signed this exact transaction.
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let mut tx_with_chain = tx.clone();
+        let chain_id = tx_with_chain
+            .chain_id()
+            .map(|id| id.as_u64())
+            .unwrap_or(self.chain_id);
+        tx_with_chain.set_chain_id(chain_id);
+
+        let signed: Bytes = request(
+            &self.provider,
+            "eth_signTransaction",
+            [TransactionParams::new(self.address, &tx_with_chain)],
+        )
+        .await?;
+        let invalid = |reason: String| Error::InvalidResponse {
+            method: "eth_signTransaction",
+            reason,
+        };
+        let (signed_tx, signature) = TypedTransaction::decode_signed(&Rlp::new(&signed))
+            .map_err(|e| invalid(e.to_string()))?;
+        let sighash = tx_with_chain.sighash();
+        if signed_tx.sighash() != sighash {
+            return Err(invalid(format!(
+                "signed another transaction, possibly for another chain than {}",
+                chain_id
+            )));
+        }
+        signature
+            .verify(sighash, self.address)
+            .map_err(|e| invalid(e.to_string()))?;
+        Ok(signature)
+    }
+
+    /// Always fails: Web3Signer signs typed data from its JSON form, which [`Eip712`] doesn't give.
+    async fn sign_typed_data<P: Eip712 + Send + Sync>(
+        &self,
+        _payload: &P,
+    ) -> Result<Signature, Self::Error> {
+        Err(Error::TypedData)
+    }
+
+    /// Returns the signer's Ethereum Address
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the signer's chain id
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Sets the signer's chain id
+    fn with_chain_id<C: Into<u64>>(mut self, chain_id: C) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ethers::{
+        core::rand::thread_rng,
+        signers::{LocalWallet, Signer as EthersSignerTrait},
+        types::{Eip1559TransactionRequest, TransactionRequest},
+    };
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// Answers Web3Signer's JSON-RPC requests on a local port, signing with `wallet` for `chain_id`, or with
+    /// `other_wallet` when set to act as a misbehaving signer. Returns the URL of the server.
+    async fn mock_web3signer(
+        wallet: LocalWallet,
+        chain_id: u64,
+        other_wallet: Option<LocalWallet>,
+    ) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new((wallet, chain_id, other_wallet));
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(serve_request(stream, state.clone()));
+            }
+        });
+        url
+    }
+
+    async fn serve_request(
+        mut stream: tokio::net::TcpStream,
+        state: Arc<(LocalWallet, u64, Option<LocalWallet>)>,
+    ) {
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        let body = loop {
+            let read = stream.read(&mut buffer).await.unwrap();
+            if read == 0 {
+                return;
+            }
+            request.extend_from_slice(&buffer[..read]);
+            let Some(headers_end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&request[..headers_end]).to_lowercase();
+            let length: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|length| length.trim().parse().unwrap())
+                .unwrap_or_default();
+            let body_start = headers_end + 4;
+            if request.len() >= body_start + length {
+                break request[body_start..body_start + length].to_vec();
+            }
+        };
+
+        let request: Value = serde_json::from_slice(&body).unwrap();
+        let (wallet, chain_id, other_wallet) = &*state;
+        let signing_wallet = other_wallet.as_ref().unwrap_or(wallet);
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap() {
+            "eth_accounts" => json!([wallet.address()]),
+            "eth_sign" => {
+                let data: Bytes = serde_json::from_value(params[1].clone()).unwrap();
+                let signature = signing_wallet.sign_hash(hash_message(&data)).unwrap();
+                json!(Bytes::from(signature.to_vec()))
+            }
+            "eth_signTransaction" => {
+                let tx: TypedTransaction = if params[0].get("maxFeePerGas").is_some() {
+                    serde_json::from_value::<Eip1559TransactionRequest>(params[0].clone())
+                        .unwrap()
+                        .into()
+                } else {
+                    serde_json::from_value::<TransactionRequest>(params[0].clone())
+                        .unwrap()
+                        .into()
+                };
+                let mut tx = tx;
+                tx.set_chain_id(*chain_id);
+                let signature = signing_wallet.sign_transaction_sync(&tx).unwrap();
+                json!(tx.rlp_signed(&signature))
+            }
+            method => panic!("unexpected method {}", method),
+        };
+        let response =
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+        let reply = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        );
+        stream.write_all(reply.as_bytes()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet.clone(), 1, None).await;
+
+        let signer = Signer::connect(&url, wallet.address(), 1).await.unwrap();
+
+        assert_eq!(signer.public_key().address(), wallet.address());
+        assert_eq!(EthersSignerTrait::address(&signer), wallet.address());
+        assert_eq!(signer.chain_id(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connect_unknown_account() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet, 1, None).await;
+        let address = LocalWallet::new(&mut thread_rng()).address();
+
+        let result = Signer::connect(&url, address, 1).await;
+
+        assert!(matches!(result, Err(Error::UnknownAccount(a)) if a == address));
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejects_signature_of_another_key() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let other_wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet.clone(), 1, Some(other_wallet)).await;
+
+        let result = Signer::connect(&url, wallet.address(), 1).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidResponse {
+                method: "eth_sign",
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet.clone(), 1, None).await;
+        let signer = Signer::connect(&url, wallet.address(), 1).await.unwrap();
+
+        let signature = signer.sign_message("hello").await.unwrap();
+
+        signature.verify("hello", wallet.address()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet.clone(), 17000, None).await;
+        let signer = Signer::connect(&url, wallet.address(), 17000)
+            .await
+            .unwrap();
+        let to = Address::repeat_byte(0x42);
+        let transactions: [TypedTransaction; 2] = [
+            TransactionRequest::new()
+                .to(to)
+                .value(1000)
+                .gas(21000)
+                .gas_price(10)
+                .nonce(3)
+                .into(),
+            Eip1559TransactionRequest::new()
+                .to(to)
+                .data(vec![1, 2, 3])
+                .gas(50000)
+                .max_fee_per_gas(20)
+                .max_priority_fee_per_gas(2)
+                .nonce(4)
+                .into(),
+        ];
+
+        for tx in transactions {
+            let signature = signer.sign_transaction(&tx).await.unwrap();
+
+            let mut tx = tx;
+            tx.set_chain_id(17000);
+            signature.verify(tx.sighash(), wallet.address()).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_for_another_chain() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet.clone(), 1, None).await;
+        let signer = Signer::connect(&url, wallet.address(), 17000)
+            .await
+            .unwrap();
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(Address::repeat_byte(0x42))
+            .gas(21000)
+            .gas_price(10)
+            .nonce(0)
+            .into();
+
+        let result = signer.sign_transaction(&tx).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidResponse {
+                method: "eth_signTransaction",
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_digest_is_unsupported() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let url = mock_web3signer(wallet.clone(), 1, None).await;
+        let signer = Signer::connect(&url, wallet.address(), 1).await.unwrap();
+
+        let result = signer.sign_digest(&Message::new([1u8; 32])).await;
+
+        assert!(matches!(result, Err(Error::DigestSigning)));
+    }
+}
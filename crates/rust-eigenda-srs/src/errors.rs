@@ -0,0 +1,20 @@
+/// Errors returned when loading SRS points
+#[derive(Debug, thiserror::Error)]
+pub enum SrsError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Failed to download SRS file from {0}: {1}")]
+    Download(String, String),
+    #[error("SRS file checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+    #[error("SRS file has {available} points, but {requested} were requested")]
+    NotEnoughPoints { requested: usize, available: usize },
+    #[error("Failed to parse SRS point {0}: {1}")]
+    InvalidPoint(usize, String),
+    #[error("SRS cache directory {0} is not owned by the user or is writable by others")]
+    InsecureCacheDir(std::path::PathBuf),
+    #[error("SRS task failed: {0}")]
+    Task(String),
+    #[error("SRS points lock is poisoned")]
+    Poisoned,
+}
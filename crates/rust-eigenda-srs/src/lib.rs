@@ -0,0 +1,7 @@
+mod errors;
+mod points;
+mod source;
+
+pub use errors::*;
+pub use points::*;
+pub use source::*;
@@ -0,0 +1,152 @@
+use std::sync::{Arc, RwLock};
+
+use ark_bn254::G1Affine;
+use rust_kzg_bn254_primitives::helpers::read_g1_point_from_bytes_be;
+
+use crate::{errors::SrsError, source::SrsBytes};
+
+/// Size of a compressed G1 point, as stored in `g1.point`.
+pub const G1_POINT_SIZE: usize = 32;
+
+/// G1 points of an SRS file (like `g1.point`), decompressed on demand.
+///
+/// Decompressing a point is expensive, so only the first points needed by the largest blob seen so far are kept
+/// in memory. The rest of the file can still be read point by point with [`G1Points::point_at`].
+#[derive(Debug)]
+pub struct G1Points {
+    bytes: SrsBytes,
+    loaded: RwLock<Arc<Vec<G1Affine>>>,
+}
+
+impl G1Points {
+    pub fn new(bytes: SrsBytes) -> Self {
+        Self {
+            bytes,
+            loaded: RwLock::new(Arc::new(Vec::new())),
+        }
+    }
+
+    /// Number of points in the file.
+    pub fn available(&self) -> usize {
+        self.bytes.len() / G1_POINT_SIZE
+    }
+
+    /// Number of points decompressed so far.
+    pub fn loaded(&self) -> Result<usize, SrsError> {
+        Ok(self.loaded.read().map_err(|_| SrsError::Poisoned)?.len())
+    }
+
+    /// Returns at least the first `count` points, decompressing the ones that were not loaded yet.
+    ///
+    /// Points are loaded up to the next power of two of `count`, since blob lengths are powers of two.
+    pub fn points(&self, count: usize) -> Result<Arc<Vec<G1Affine>>, SrsError> {
+        {
+            let loaded = self.loaded.read().map_err(|_| SrsError::Poisoned)?;
+            if loaded.len() >= count {
+                return Ok(loaded.clone());
+            }
+        }
+
+        let available = self.available();
+        if count > available {
+            return Err(SrsError::NotEnoughPoints {
+                requested: count,
+                available,
+            });
+        }
+        let target = count.next_power_of_two().min(available);
+
+        let mut loaded = self.loaded.write().map_err(|_| SrsError::Poisoned)?;
+        // another thread may have loaded them while the lock was released
+        if loaded.len() < target {
+            let mut points = Vec::with_capacity(target);
+            points.extend_from_slice(&loaded);
+            for index in loaded.len()..target {
+                points.push(self.parse(index)?);
+            }
+            *loaded = Arc::new(points);
+        }
+        Ok(loaded.clone())
+    }
+
+    /// Returns the point at `index` (`[s^index]_1`), without loading the previous ones.
+    pub fn point_at(&self, index: usize) -> Result<G1Affine, SrsError> {
+        if let Some(point) = self
+            .loaded
+            .read()
+            .map_err(|_| SrsError::Poisoned)?
+            .get(index)
+        {
+            return Ok(*point);
+        }
+        self.parse(index)
+    }
+
+    fn parse(&self, index: usize) -> Result<G1Affine, SrsError> {
+        let bytes = index
+            .checked_mul(G1_POINT_SIZE)
+            .and_then(|start| Some(start..start.checked_add(G1_POINT_SIZE)?))
+            .and_then(|range| self.bytes.get(range))
+            .ok_or(SrsError::NotEnoughPoints {
+                requested: index.saturating_add(1),
+                available: self.available(),
+            })?;
+        read_g1_point_from_bytes_be(bytes).map_err(|e| SrsError::InvalidPoint(index, e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ec::AffineRepr;
+
+    use super::*;
+
+    // the generator, followed by two commitments of dispersed blobs
+    const POINTS: [&str; 3] = [
+        "8000000000000000000000000000000000000000000000000000000000000001",
+        "8fe9346938e40204330aea61243eb8c4c9b9ea0d41167909e9cae449966229cc",
+        "a7c4441c06c2f25772a92652359a6d8d833b366ddd8a5ebfc8607f071e0338d6",
+    ];
+
+    fn test_points() -> G1Points {
+        let bytes: Vec<u8> = POINTS
+            .iter()
+            .flat_map(|point| hex::decode(point).unwrap())
+            .collect();
+        G1Points::new(SrsBytes::Static(bytes.leak()))
+    }
+
+    #[test]
+    fn test_lazy_points() {
+        let points = test_points();
+        assert_eq!(points.available(), 3);
+        assert_eq!(points.loaded().unwrap(), 0);
+
+        let loaded = points.points(1).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0], G1Affine::generator());
+
+        // loaded up to the next power of two
+        assert_eq!(points.points(3).unwrap().len(), 3);
+        assert_eq!(points.points(2).unwrap().len(), 3);
+        assert_eq!(points.loaded().unwrap(), 3);
+
+        assert!(matches!(
+            points.points(4),
+            Err(SrsError::NotEnoughPoints {
+                requested: 4,
+                available: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_point_at() {
+        let points = test_points();
+        let expected = read_g1_point_from_bytes_be(&hex::decode(POINTS[2]).unwrap()).unwrap();
+        assert_eq!(points.point_at(2).unwrap(), expected);
+        assert_eq!(points.loaded().unwrap(), 0);
+        assert!(points.point_at(3).is_err());
+        assert!(points.point_at(usize::MAX).is_err());
+    }
+}
@@ -0,0 +1,404 @@
+use std::{
+    fs::File,
+    io::Read,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+
+use crate::errors::SrsError;
+
+/// Name of the directory, inside the user's cache directory, where SRS files are cached by default.
+const DEFAULT_CACHE_DIR_NAME: &str = "eigenda-srs";
+
+/// Returns the directory where SRS files are cached when no other directory is configured: `eigenda-srs` inside
+/// `$XDG_CACHE_HOME`, or `$HOME/.cache`. Without either, it is inside the system temporary directory, where it is
+/// only used if it is owned by the user and not writable by others (see [`SrsFile::load`]).
+pub fn default_cache_dir() -> PathBuf {
+    let absolute_var = |name| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+    absolute_var("XDG_CACHE_HOME")
+        .or_else(|| absolute_var("HOME").map(|home| home.join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join(DEFAULT_CACHE_DIR_NAME)
+}
+
+/// Where the bytes of an SRS file are read from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SrsSource {
+    /// A file in the local filesystem.
+    Path(PathBuf),
+    /// A file downloaded over HTTP(S). Downloads are cached on disk, so the file is only fetched once.
+    Url(String),
+    /// Bytes compiled into the binary, e.g. with `include_bytes!`.
+    Embedded(&'static [u8]),
+}
+
+/// An SRS file, optionally pinned to the SHA-256 digest of its contents.
+///
+/// When a digest is pinned, the file is rejected with [`SrsError::ChecksumMismatch`] if its contents differ.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SrsFile {
+    pub source: SrsSource,
+    pub sha256: Option<[u8; 32]>,
+}
+
+impl SrsFile {
+    pub fn new(source: SrsSource) -> Self {
+        Self {
+            source,
+            sha256: None,
+        }
+    }
+
+    /// Pins the SHA-256 digest of the file contents.
+    pub fn with_sha256(mut self, sha256: [u8; 32]) -> Self {
+        self.sha256 = Some(sha256);
+        self
+    }
+
+    /// Loads the contents of the file.
+    ///
+    /// Files in the filesystem (including downloaded ones) are memory-mapped rather than read, so loading is
+    /// cheap regardless of the file size. A pinned digest is checked on the mapped bytes, including for files
+    /// served from the cache. Downloads are cached in `cache_dir`, which is created private to the user and
+    /// rejected with [`SrsError::InsecureCacheDir`] if others can write to it.
+    pub async fn load(&self, cache_dir: &Path) -> Result<SrsBytes, SrsError> {
+        if let SrsSource::Embedded(bytes) = &self.source {
+            self.check_digest(sha256_digest(bytes))?;
+            return Ok(SrsBytes::Static(bytes));
+        }
+
+        let path = self.cached_path(cache_dir).await?;
+        let file = File::open(path)?;
+        // SAFETY: SRS files are never modified once written: downloads are moved into the cache only when complete.
+        // Modifying a mapped file from another process is undefined behaviour, as with any memory-mapped file.
+        let mmap = unsafe { Mmap::map(&file)? };
+        if self.sha256.is_none() {
+            return Ok(SrsBytes::Mapped(mmap));
+        }
+
+        // hashing is blocking, so it is done in a separate thread
+        let (mmap, digest) = tokio::task::spawn_blocking(move || {
+            let digest = sha256_digest(&mmap);
+            (mmap, digest)
+        })
+        .await
+        .map_err(|e| SrsError::Task(e.to_string()))?;
+        self.check_digest(digest)?;
+        Ok(SrsBytes::Mapped(mmap))
+    }
+
+    /// Returns the path of the file in the local filesystem, downloading it (or writing the embedded bytes)
+    /// into `cache_dir` if needed.
+    ///
+    /// Useful for libraries that only load SRS points from a path. A pinned digest is checked each time, but the
+    /// file is read again by the caller, so prefer [`SrsFile::load`] when the bytes themselves are enough.
+    pub async fn local_path(&self, cache_dir: &Path) -> Result<PathBuf, SrsError> {
+        let path = self.cached_path(cache_dir).await?;
+        if self.sha256.is_some() {
+            let digest = file_sha256_digest(path.clone()).await?;
+            self.check_digest(digest)?;
+        }
+        Ok(path)
+    }
+
+    /// Returns the path of the file, downloading it (or writing the embedded bytes) into `cache_dir` if it isn't
+    /// cached yet. Cached files are not hashed again here, that's up to the caller.
+    async fn cached_path(&self, cache_dir: &Path) -> Result<PathBuf, SrsError> {
+        match &self.source {
+            SrsSource::Path(path) => Ok(path.clone()),
+            SrsSource::Url(url) => {
+                create_private_dir(cache_dir).await?;
+                let cached_path = cache_dir.join(self.cache_file_name(url.as_bytes()));
+                if !tokio::fs::try_exists(&cached_path).await? {
+                    self.download(url, cache_dir, &cached_path).await?;
+                }
+                Ok(cached_path)
+            }
+            SrsSource::Embedded(bytes) => {
+                let digest = sha256_digest(bytes);
+                self.check_digest(digest)?;
+                create_private_dir(cache_dir).await?;
+                let cached_path = cache_dir.join(format!("sha256-{}", hex::encode(digest)));
+                if !tokio::fs::try_exists(&cached_path).await? {
+                    let mut file = AsyncTempFile::new_in(cache_dir)?;
+                    file.write_all(bytes).await?;
+                    file.persist(&cached_path).await?;
+                }
+                Ok(cached_path)
+            }
+        }
+    }
+
+    /// Downloads the file into `cache_dir`, checking its digest while it is written.
+    async fn download(
+        &self,
+        url: &str,
+        cache_dir: &Path,
+        cached_path: &Path,
+    ) -> Result<(), SrsError> {
+        let download_error = |e: reqwest::Error| SrsError::Download(url.to_string(), e.to_string());
+        let mut response = reqwest::get(url).await.map_err(download_error)?;
+        if !response.status().is_success() {
+            return Err(SrsError::Download(
+                url.to_string(),
+                format!("unexpected status {}", response.status()),
+            ));
+        }
+
+        let mut file = AsyncTempFile::new_in(cache_dir)?;
+        let mut hasher = Sha256::new();
+        while let Some(chunk) = response.chunk().await.map_err(download_error)? {
+            hasher.update(&chunk);
+            file.write_all(&chunk).await?;
+        }
+        self.check_digest(hasher.finalize().into())?;
+
+        file.persist(cached_path).await
+    }
+
+    /// Name of the cached copy of the file: its digest if pinned, or the digest of `id` otherwise.
+    fn cache_file_name(&self, id: &[u8]) -> String {
+        match self.sha256 {
+            Some(digest) => format!("sha256-{}", hex::encode(digest)),
+            None => format!("id-{}", hex::encode(sha256_digest(id))),
+        }
+    }
+
+    fn check_digest(&self, actual: [u8; 32]) -> Result<(), SrsError> {
+        match self.sha256 {
+            Some(expected) if expected != actual => Err(SrsError::ChecksumMismatch {
+                expected: hex::encode(expected),
+                actual: hex::encode(actual),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Contents of an SRS file.
+#[derive(Debug)]
+pub enum SrsBytes {
+    Mapped(Mmap),
+    Static(&'static [u8]),
+}
+
+impl Deref for SrsBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            SrsBytes::Mapped(mmap) => mmap,
+            SrsBytes::Static(bytes) => bytes,
+        }
+    }
+}
+
+/// Temporary file written asynchronously, and moved to its final path once complete.
+///
+/// The file is created with `O_EXCL` and is only accessible by its owner, so it can't be swapped or read by others
+/// while it is written.
+struct AsyncTempFile {
+    temp_file: NamedTempFile,
+    file: tokio::fs::File,
+}
+
+impl AsyncTempFile {
+    fn new_in(dir: &Path) -> Result<Self, SrsError> {
+        let temp_file = NamedTempFile::new_in(dir)?;
+        let file = tokio::fs::File::from_std(temp_file.reopen()?);
+        Ok(Self { temp_file, file })
+    }
+
+    async fn write_all(&mut self, bytes: &[u8]) -> Result<(), SrsError> {
+        Ok(self.file.write_all(bytes).await?)
+    }
+
+    async fn persist(mut self, path: &Path) -> Result<(), SrsError> {
+        self.file.flush().await?;
+        self.file.sync_all().await?;
+        self.temp_file
+            .persist(path)
+            .map_err(|e| SrsError::Io(e.error))?;
+        Ok(())
+    }
+}
+
+/// Creates `dir` (and its missing parents) only accessible by the user, and checks that an existing one is owned by
+/// the user and not writable by others, so that other users can't plant files in it.
+async fn create_private_dir(dir: &Path) -> Result<(), SrsError> {
+    let mut builder = tokio::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    builder.mode(0o700);
+    builder.create(dir).await?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = tokio::fs::metadata(dir).await?;
+        // SAFETY: geteuid has no preconditions and always succeeds
+        let uid = unsafe { libc::geteuid() };
+        if metadata.uid() != uid || metadata.mode() & 0o022 != 0 {
+            return Err(SrsError::InsecureCacheDir(dir.to_path_buf()));
+        }
+    }
+    Ok(())
+}
+
+fn sha256_digest(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+async fn file_sha256_digest(path: PathBuf) -> Result<[u8; 32], SrsError> {
+    // hashing is blocking, so it is done in a separate thread
+    tokio::task::spawn_blocking(move || {
+        let mut file = File::open(path)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 1 << 20];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Ok(hasher.finalize().into())
+    })
+    .await
+    .map_err(|e| SrsError::Task(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &[u8] = b"eigenda srs points";
+
+    #[tokio::test]
+    async fn test_load_embedded() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let file = SrsFile::new(SrsSource::Embedded(CONTENTS)).with_sha256(sha256_digest(CONTENTS));
+        let bytes = file.load(cache_dir.path()).await.unwrap();
+        assert_eq!(&bytes[..], CONTENTS);
+
+        // embedded bytes are written to the cache when a path is needed
+        let path = file.local_path(cache_dir.path()).await.unwrap();
+        assert!(path.starts_with(cache_dir.path()));
+        assert_eq!(std::fs::read(&path).unwrap(), CONTENTS);
+        assert_eq!(file.local_path(cache_dir.path()).await.unwrap(), path);
+    }
+
+    #[tokio::test]
+    async fn test_load_path() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let path = cache_dir.path().join("g1.point");
+        std::fs::write(&path, CONTENTS).unwrap();
+
+        let file = SrsFile::new(SrsSource::Path(path.clone()));
+        let bytes = file.load(cache_dir.path()).await.unwrap();
+        assert_eq!(&bytes[..], CONTENTS);
+
+        let file = file.with_sha256(sha256_digest(CONTENTS));
+        assert_eq!(file.local_path(cache_dir.path()).await.unwrap(), path);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let path = cache_dir.path().join("g1.point");
+        std::fs::write(&path, CONTENTS).unwrap();
+        let wrong_digest = sha256_digest(b"other points");
+
+        let file = SrsFile::new(SrsSource::Path(path)).with_sha256(wrong_digest);
+        assert!(matches!(
+            file.load(cache_dir.path()).await,
+            Err(SrsError::ChecksumMismatch { .. })
+        ));
+
+        let file = SrsFile::new(SrsSource::Embedded(CONTENTS)).with_sha256(wrong_digest);
+        assert!(matches!(
+            file.load(cache_dir.path()).await,
+            Err(SrsError::ChecksumMismatch { .. })
+        ));
+        assert!(file.local_path(cache_dir.path()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_file_is_checked() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let digest = sha256_digest(CONTENTS);
+        // the URL is never fetched, as a file is already cached under the pinned digest
+        let file = SrsFile::new(SrsSource::Url("http://127.0.0.1:1/g1.point".to_string()))
+            .with_sha256(digest);
+        let cached_path = cache_dir
+            .path()
+            .join(format!("sha256-{}", hex::encode(digest)));
+
+        std::fs::write(&cached_path, CONTENTS).unwrap();
+        assert_eq!(&file.load(cache_dir.path()).await.unwrap()[..], CONTENTS);
+        assert_eq!(
+            file.local_path(cache_dir.path()).await.unwrap(),
+            cached_path
+        );
+
+        std::fs::write(&cached_path, b"planted points").unwrap();
+        assert!(matches!(
+            file.load(cache_dir.path()).await,
+            Err(SrsError::ChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            file.local_path(cache_dir.path()).await,
+            Err(SrsError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cache_dir_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cache").join("eigenda-srs");
+        let file = SrsFile::new(SrsSource::Embedded(CONTENTS));
+        file.local_path(&cache_dir).await.unwrap();
+        let mode = std::fs::metadata(&cache_dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        // a cache directory that others can write to is rejected
+        std::fs::set_permissions(&cache_dir, std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(matches!(
+            file.local_path(&cache_dir).await,
+            Err(SrsError::InsecureCacheDir(_))
+        ));
+        let file = SrsFile::new(SrsSource::Url("http://127.0.0.1:1/g1.point".to_string()));
+        assert!(matches!(
+            file.load(&cache_dir).await,
+            Err(SrsError::InsecureCacheDir(_))
+        ));
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_download() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let file = SrsFile::new(SrsSource::Url(
+            "https://github.com/Layr-Labs/eigenda-proxy/raw/2fd70b99ef5bf137d7bbca3461cf9e1f2c899451/resources/g2.point.powerOf2".to_string(),
+        ));
+        let path = file.local_path(cache_dir.path()).await.unwrap();
+        let bytes = file.load(cache_dir.path()).await.unwrap();
+        assert_eq!(bytes.len(), 28 * 64);
+
+        // the second load is served from the cache
+        assert_eq!(file.local_path(cache_dir.path()).await.unwrap(), path);
+    }
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eigenda_v2_client::core::{Blob, PayloadForm};
+
+// The first 4 bytes are the blob length in symbols (as claimed by a cert), and the rest the blob bytes (as served
+// by a relay).
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 4 {
+        return;
+    }
+    let blob_length_symbols = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    if let Ok(blob) = Blob::deserialize_blob(data[4..].to_vec(), blob_length_symbols) {
+        let _ = blob.to_payload(PayloadForm::Coeff);
+        let _ = blob.to_payload(PayloadForm::Eval);
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eigenda_v2_client::core::BlobKey;
+
+fuzz_target!(|data: &str| {
+    if let Ok(blob_key) = BlobKey::from_hex(data) {
+        assert_eq!(blob_key.to_hex(), data.to_lowercase());
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eigenda_v2_common::EigenDACert;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(cert) = EigenDACert::from_bytes(data) {
+        // anything that deserializes must serialize back
+        cert.to_bytes().unwrap();
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eigenda_v2_client::core::{EncodedPayload, PayloadDecodeMode};
+use rust_kzg_bn254_primitives::helpers::to_fr_array;
+
+fuzz_target!(|data: &[u8]| {
+    let field_elements = to_fr_array(data);
+    if let Ok(encoded_payload) = EncodedPayload::from_field_elements(&field_elements, usize::MAX) {
+        let _ = encoded_payload.decode();
+        let _ = encoded_payload.decode_with_mode(PayloadDecodeMode::Strict);
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_eigenda_v2_client::core::Frame;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Frame::deserialize_bundle(data);
+});
@@ -97,27 +97,27 @@ impl Accountant {
             get_reservation_info_by_nanosecond(timestamp, self.reservation_window);
         let symbol_usage = self.symbols_charged(num_symbols);
 
-        let mut relative_period_record = self.relative_period_record(current_reservation_period);
+        let relative_period_record = self.relative_period_record(current_reservation_period);
         relative_period_record.usage += symbol_usage;
+        let usage = relative_period_record.usage;
 
         // first attempt to use the active reservation
         let bin_limit = self.reservation.symbols_per_second * self.reservation_window;
-        if relative_period_record.usage <= bin_limit {
+        if usage <= bin_limit {
             if !quorum_check(quorums, &self.reservation.quorum_numbers) {
                 return Ok(BigInt::zero());
             }
             return Ok(BigInt::zero());
         }
 
-        let mut overflow_period_record =
-            self.relative_period_record(current_reservation_period + 2);
+        let overflow_period_record = self.relative_period_record(current_reservation_period + 2);
 
         // allow one overflow when the overflow bin is empty, the current usage and new length are both less than the limit
         if overflow_period_record.usage.is_zero()
-            && relative_period_record.usage - symbol_usage < bin_limit
+            && usage - symbol_usage < bin_limit
             && symbol_usage <= bin_limit
         {
-            overflow_period_record.usage += relative_period_record.usage - bin_limit;
+            overflow_period_record.usage += usage - bin_limit;
             if !quorum_check(quorums, &self.reservation.quorum_numbers) {
                 return Ok(BigInt::zero());
             }
@@ -126,7 +126,8 @@ impl Accountant {
 
         // reservation not available, rollback reservation records, attempt on-demand
         //todo: rollback on-demand if disperser respond with some type of rejection?
-        relative_period_record.usage -= symbol_usage;
+        self.relative_period_record(current_reservation_period)
+            .usage -= symbol_usage;
         let increment_required = self.payment_charged(num_symbols);
         self.cumulative_payment += increment_required;
 
@@ -156,7 +157,7 @@ impl Accountant {
         round_up_divide(num_symbols, self.min_num_symbols) * self.min_num_symbols
     }
 
-    fn relative_period_record(&mut self, index: u64) -> PeriodRecord {
+    fn relative_period_record(&mut self, index: u64) -> &mut PeriodRecord {
         let relative_index = index % (self.num_bins as u64);
         if (self.period_records[relative_index as usize].index as u64) != index {
             self.period_records[relative_index as usize] = PeriodRecord {
@@ -165,7 +166,7 @@ impl Accountant {
             };
         }
 
-        self.period_records[relative_index as usize].clone()
+        &mut self.period_records[relative_index as usize]
     }
 
     /// Sets the accountant's state from the disperser's response
@@ -266,3 +267,32 @@ fn quorum_check(quorum_numbers: &[u8], reservation_quorum_numbers: &[u8]) -> boo
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reservation_usage_is_recorded() {
+        let reservation = ReservedPayment {
+            symbols_per_second: 10,
+            quorum_numbers: vec![0, 1],
+            ..Default::default()
+        };
+        let on_demand = OnDemandPayment {
+            cumulative_payment: BigInt::from(1000),
+        };
+        let mut accountant = Accountant::new(Address::zero(), reservation, on_demand, 10, 1, 1, 3);
+        let timestamp = Duration::from_secs(100).as_nanos() as i64;
+
+        // 90 symbols fit the reservation, then 50 more fit its overflow bin
+        let payment = accountant.account_blob(timestamp, 90, &[0, 1]).unwrap();
+        assert_eq!(payment.cumulative_payment, BigInt::zero());
+        let payment = accountant.account_blob(timestamp, 50, &[0, 1]).unwrap();
+        assert_eq!(payment.cumulative_payment, BigInt::zero());
+
+        // the reservation and its overflow bin are used up, so the next blob is paid on demand
+        let payment = accountant.account_blob(timestamp, 50, &[0, 1]).unwrap();
+        assert_eq!(payment.cumulative_payment, BigInt::from(50));
+    }
+}
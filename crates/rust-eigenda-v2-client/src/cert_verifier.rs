@@ -1,27 +1,62 @@
-use ethers::prelude::*;
+use ethers::{abi::AbiDecode, contract::EthError, prelude::*};
 use rust_eigenda_signers::signers::ethers::Signer as EthersSigner;
-use rust_eigenda_v2_common::{EigenDACert, NonSignerStakesAndSignature};
+use rust_eigenda_v2_common::{EigenDACert, EigenDACertV2, NonSignerStakesAndSignature};
 use std::sync::Arc;
+use tracing::{debug, instrument};
 
 use ethereum_types::H160;
 
 use crate::{
     core::eigenda_cert::SignedBatch,
-    errors::{CertVerifierError, ConversionError},
+    errors::{CertVerificationFailure, CertVerifierError, ConversionError},
     generated::{
         disperser::v2::SignedBatch as SignedBatchProto,
         i_cert_verifier::{
             IEigenDACertVerifier,
             NonSignerStakesAndSignature as NonSignerStakesAndSignatureContract,
         },
+        i_cert_verifier_base::{CheckDACertReturn, IEigenDACertVerifierBase},
     },
-    utils::SecretUrl,
+    stateless_execution::{execute_call, CallResult, RpcStateSource, StateSource, TrustedBlock},
+    utils::{contract_error_source, middleware_error_source, SecretUrl},
 };
 
-#[derive(Debug, Clone)]
+/// Client the contracts are called through.
+pub(crate) type SignerClient<S> = SignerMiddleware<Provider<Http>, EthersSigner<S>>;
+
+/// Creates the client the contracts are called through, from the ETH RPC url.
+pub(crate) fn signer_client<S>(
+    rpc_url: SecretUrl,
+    signer: S,
+) -> Result<Arc<SignerClient<S>>, CertVerifierError>
+where
+    EthersSigner<S>: Signer,
+{
+    let url: String = rpc_url.try_into()?;
+
+    let provider = Provider::<Http>::try_from(url).map_err(ConversionError::UrlParse)?;
+    // ethers hard codes 1 when constructing wallets
+    let chain_id = 1;
+    let signer = EthersSigner::new(signer, chain_id);
+    Ok(Arc::new(SignerMiddleware::new(provider, signer)))
+}
+
+#[derive(Debug)]
 /// Provides methods for interacting with the EigenDA CertVerifier contract.
 pub struct CertVerifier<S> {
-    cert_verifier_contract: IEigenDACertVerifier<SignerMiddleware<Provider<Http>, EthersSigner<S>>>,
+    cert_verifier_contract: IEigenDACertVerifier<SignerClient<S>>,
+    /// The same contract, through the `checkDACert` interface of the verifiers of V3 certs.
+    cert_verifier_base_contract: IEigenDACertVerifierBase<SignerClient<S>>,
+}
+
+// the contracts share the client, so the signer does not need to be cloned
+impl<S> Clone for CertVerifier<S> {
+    fn clone(&self) -> Self {
+        Self {
+            cert_verifier_contract: self.cert_verifier_contract.clone(),
+            cert_verifier_base_contract: self.cert_verifier_base_contract.clone(),
+        }
+    }
 }
 
 impl<S> CertVerifier<S> {
@@ -30,18 +65,23 @@ impl<S> CertVerifier<S> {
     where
         EthersSigner<S>: Signer,
     {
-        let url: String = rpc_url.try_into()?;
-
-        let provider = Provider::<Http>::try_from(url).map_err(ConversionError::UrlParse)?;
-        // ethers hard codes 1 when constructing wallets
-        let chain_id = 1;
-        let signer = EthersSigner::new(signer, chain_id);
-        let client = SignerMiddleware::new(provider, signer);
-        let client = Arc::new(client);
-        let cert_verifier_contract = IEigenDACertVerifier::new(address, client);
-        Ok(CertVerifier {
-            cert_verifier_contract,
-        })
+        Ok(Self::with_client(address, signer_client(rpc_url, signer)?))
+    }
+
+    /// Creates a new instance of [`CertVerifier`] calling the contract through an existing client.
+    pub(crate) fn with_client(address: H160, client: Arc<SignerClient<S>>) -> Self
+    where
+        EthersSigner<S>: Signer,
+    {
+        CertVerifier {
+            cert_verifier_base_contract: IEigenDACertVerifierBase::new(address, client.clone()),
+            cert_verifier_contract: IEigenDACertVerifier::new(address, client),
+        }
+    }
+
+    /// Returns the address of the contract.
+    pub fn address(&self) -> H160 {
+        self.cert_verifier_contract.address()
     }
 
     /// Calls the getNonSignerStakesAndSignature view function on the EigenDACertVerifier
@@ -50,6 +90,21 @@ impl<S> CertVerifier<S> {
         &self,
         signed_batch: SignedBatchProto,
     ) -> Result<NonSignerStakesAndSignature, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        self.get_non_signer_stakes_and_signature_at_block(signed_batch, BlockNumber::Latest)
+            .await
+    }
+
+    /// Calls the getNonSignerStakesAndSignature view function on the EigenDACertVerifier contract, at the given
+    /// block.
+    #[instrument(skip_all, fields(contract = ?self.cert_verifier_contract.address()))]
+    pub async fn get_non_signer_stakes_and_signature_at_block(
+        &self,
+        signed_batch: SignedBatchProto,
+        block: impl Into<BlockId>,
+    ) -> Result<NonSignerStakesAndSignature, CertVerifierError>
     where
         EthersSigner<S>: Signer,
     {
@@ -58,10 +113,11 @@ impl<S> CertVerifier<S> {
         let non_signer_stakes_and_signature: NonSignerStakesAndSignatureContract = self
             .cert_verifier_contract
             .get_non_signer_stakes_and_signature(contract_signed_batch)
+            .block(block)
             .call()
             .await
-            .map_err(|_| {
-                CertVerifierError::Contract("non_signer_stakes_and_signature".to_string())
+            .map_err(|e| {
+                contract_call_error("getNonSignerStakesAndSignature", contract_error_source(e))
             })?;
 
         Ok(non_signer_stakes_and_signature.try_into()?)
@@ -70,36 +126,299 @@ impl<S> CertVerifier<S> {
     /// Queries the cert verifier contract for the configured set of quorum numbers that must
     /// be set in the BlobHeader, and verified in VerifyDACertV2 and verifyDACertV2FromSignedBatch
     pub async fn quorum_numbers_required(&self) -> Result<Vec<u8>, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        self.quorum_numbers_required_at_block(BlockNumber::Latest)
+            .await
+    }
+
+    /// Queries the quorum numbers required by the cert verifier contract at the given block.
+    #[instrument(skip_all, fields(contract = ?self.cert_verifier_contract.address()))]
+    pub async fn quorum_numbers_required_at_block(
+        &self,
+        block: impl Into<BlockId>,
+    ) -> Result<Vec<u8>, CertVerifierError>
     where
         EthersSigner<S>: Signer,
     {
         let quorums: Bytes = self
             .cert_verifier_contract
             .quorum_numbers_required()
+            .block(block)
             .call()
             .await
-            .map_err(|_| CertVerifierError::Contract("quorum_numbers_required".to_string()))?;
-        Ok(quorums.to_vec())
+            .map_err(|e| contract_call_error("quorumNumbersRequired", contract_error_source(e)))?;
+        let quorums = quorums.to_vec();
+        debug!(quorums = ?quorums, "read required quorums");
+        Ok(quorums)
     }
 
-    /// Calls the VerifyCertV2 view function on the EigenDACertVerifier contract.
+    /// Returns the number of the latest block of the chain the contract is deployed on.
+    pub async fn block_number(&self) -> Result<u64, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        let block_number = self
+            .cert_verifier_contract
+            .client()
+            .get_block_number()
+            .await
+            .map_err(|e| contract_call_error("eth_blockNumber", middleware_error_source(e)))?;
+        Ok(block_number.as_u64())
+    }
+
+    /// Verifies the cert with the EigenDACertVerifier contract, see [`CertVerifier::verify_cert_v2_at_block`].
     ///
     /// This method returns an empty Result if the cert is successfully verified. Otherwise, it returns a [`CertVerifierError`].
     pub async fn verify_cert_v2(&self, eigenda_cert: &EigenDACert) -> Result<(), CertVerifierError>
     where
         EthersSigner<S>: Signer,
     {
-        self.cert_verifier_contract
-            .verify_da_cert_v2(
-                eigenda_cert.batch_header.clone().into(),
-                eigenda_cert.blob_inclusion_info.clone().into(),
-                eigenda_cert.non_signer_stakes_and_signature.clone().into(),
-                eigenda_cert.signed_quorum_numbers.clone().into(),
-            )
-            .call()
+        self.verify_cert_v2_at_block(eigenda_cert, BlockNumber::Latest)
             .await
-            .map_err(|_| CertVerifierError::Contract("verify_cert_v2".to_string()))?;
-        Ok(())
+    }
+
+    /// Verifies the cert with the EigenDACertVerifier contract at the given block: a number, a hash, or a tag such
+    /// as `safe` or `finalized`. Pinning the block makes the result deterministic, as a cert verified at `latest`
+    /// may stop verifying after a reorg.
+    ///
+    /// The function called depends on the version of the cert: `verifyDACertV2` for V2 certs, and `checkDACert`
+    /// with the ABI encoded cert for V3 certs.
+    ///
+    /// If the contract rejects the cert, [`CertVerifierError::VerificationFailed`] is returned. Any other error
+    /// means that the cert could not be verified (e.g. the RPC is unavailable), so the call may be retried.
+    #[instrument(
+        skip_all,
+        fields(
+            contract = ?self.cert_verifier_contract.address(),
+            reference_block_number = eigenda_cert.reference_block_number()
+        )
+    )]
+    pub async fn verify_cert_v2_at_block(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: impl Into<BlockId>,
+    ) -> Result<(), CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        let contract_error = |e: ContractError<_>| match e.as_revert() {
+            Some(revert_data) => CertVerifierError::VerificationFailed(decode_revert(revert_data)),
+            None => contract_call_error("verify_cert_v2", contract_error_source(e)),
+        };
+        match eigenda_cert {
+            EigenDACert::V2(eigenda_cert) => {
+                self.verify_da_cert_v2_call(eigenda_cert)
+                    .block(block)
+                    .call()
+                    .await
+                    .map_err(contract_error)?;
+                Ok(())
+            }
+            EigenDACert::V3(eigenda_cert) => {
+                let status = self
+                    .cert_verifier_base_contract
+                    .check_da_cert(eigenda_cert.to_abi_bytes().into())
+                    .block(block)
+                    .call()
+                    .await
+                    .map_err(contract_error)?;
+                check_status(status)
+            }
+        }
+    }
+
+    fn verify_da_cert_v2_call(
+        &self,
+        eigenda_cert: &EigenDACertV2,
+    ) -> ContractCall<SignerMiddleware<Provider<Http>, EthersSigner<S>>, ()>
+    where
+        EthersSigner<S>: Signer,
+    {
+        self.cert_verifier_contract.verify_da_cert_v2(
+            eigenda_cert.batch_header.clone().into(),
+            eigenda_cert.blob_inclusion_info.clone().into(),
+            eigenda_cert.non_signer_stakes_and_signature.clone().into(),
+            eigenda_cert.signed_quorum_numbers.clone().into(),
+        )
+    }
+
+    /// Verifies the cert without trusting the RPC: the verification function of the cert version (see
+    /// [`CertVerifier::verify_cert_v2_at_block`]) is executed in an embedded EVM on the state of the trusted block,
+    /// and every account and storage slot it reads is checked against the block state root with `eth_getProof`.
+    ///
+    /// Returns the gas used by the call. If the contract rejects the cert, [`CertVerifierError::VerificationFailed`]
+    /// is returned.
+    pub async fn verify_cert_v2_stateless(
+        &self,
+        eigenda_cert: &EigenDACert,
+        trusted_block: &TrustedBlock,
+    ) -> Result<u64, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        let state_source =
+            RpcStateSource::from_provider(self.cert_verifier_contract.client().inner().clone());
+        self.verify_cert_v2_with_state_source(&state_source, eigenda_cert, trusted_block)
+            .await
+    }
+
+    /// Same as [`CertVerifier::verify_cert_v2_stateless`], reading the state from the given source (e.g. a
+    /// [`RecordedState`](crate::stateless_execution::RecordedState)).
+    #[instrument(
+        skip_all,
+        fields(
+            contract = ?self.cert_verifier_contract.address(),
+            reference_block_number = eigenda_cert.reference_block_number(),
+            block_number = trusted_block.number
+        )
+    )]
+    pub async fn verify_cert_v2_with_state_source(
+        &self,
+        state_source: &dyn StateSource,
+        eigenda_cert: &EigenDACert,
+        trusted_block: &TrustedBlock,
+    ) -> Result<u64, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        let calldata = match eigenda_cert {
+            EigenDACert::V2(eigenda_cert) => self.verify_da_cert_v2_call(eigenda_cert).calldata(),
+            EigenDACert::V3(eigenda_cert) => self
+                .cert_verifier_base_contract
+                .check_da_cert(eigenda_cert.to_abi_bytes().into())
+                .calldata(),
+        }
+        .ok_or_else(|| CertVerifierError::Contract("verify_cert_v2 calldata".to_string()))?;
+
+        let execution = execute_call(
+            state_source,
+            trusted_block,
+            self.cert_verifier_contract.address(),
+            calldata.to_vec(),
+        )
+        .await?;
+        match execution.result {
+            CallResult::Success(output) => match eigenda_cert {
+                EigenDACert::V2(_) => Ok(execution.gas_used),
+                EigenDACert::V3(_) => {
+                    let CheckDACertReturn(status) = CheckDACertReturn::decode(&output)
+                        .map_err(|e| contract_call_error("checkDACert", e))?;
+                    check_status(status)?;
+                    Ok(execution.gas_used)
+                }
+            },
+            CallResult::Revert(revert_data) => Err(CertVerifierError::VerificationFailed(
+                decode_revert(&revert_data),
+            )),
+            CallResult::Halt(reason) => Err(CertVerifierError::VerificationFailed(
+                CertVerificationFailure::Halt(format!(
+                    "{} after using {} gas",
+                    reason, execution.gas_used
+                )),
+            )),
+        }
+    }
+}
+
+/// Wraps the error of a failed contract call, keeping it as the source of the returned error.
+pub(crate) fn contract_call_error(
+    function: &'static str,
+    source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> CertVerifierError {
+    CertVerifierError::ContractCall {
+        function,
+        source: source.into(),
+    }
+}
+
+/// Status returned by `checkDACert` for valid certs.
+const STATUS_SUCCESS: u8 = 1;
+
+/// Maps the status returned by `checkDACert` (`EigenDACertVerificationV3Lib.StatusCode`) to the reason the cert was
+/// rejected.
+fn check_status(status: u8) -> Result<(), CertVerifierError> {
+    let failure = match status {
+        STATUS_SUCCESS => return Ok(()),
+        2 => CertVerificationFailure::InvalidInclusionProof(
+            "checkDACert returned INVALID_INCLUSION_PROOF".to_string(),
+        ),
+        3 => CertVerificationFailure::SecurityAssumptionsNotMet(
+            "checkDACert returned SECURITY_ASSUMPTIONS_NOT_MET".to_string(),
+        ),
+        4 => CertVerificationFailure::BlobQuorumsNotConfirmed,
+        5 => CertVerificationFailure::RequiredQuorumsMissing,
+        status => CertVerificationFailure::Status(status),
+    };
+    Err(CertVerifierError::VerificationFailed(failure))
+}
+
+/// Selector of `Panic(uint256)`, the revert of failed asserts and arithmetic errors.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes the revert data of `verifyDACertV2` into the reason the cert was rejected.
+///
+/// The EigenDACertVerifier contract declares no custom errors, it reverts with `Error(string)` require messages,
+/// which are matched against the known ones.
+pub fn decode_revert(revert_data: &[u8]) -> CertVerificationFailure {
+    if let Some(reason) = String::decode_with_selector(revert_data) {
+        return classify_revert_reason(reason);
+    }
+    if let Some(code) = revert_data
+        .strip_prefix(&PANIC_SELECTOR)
+        .and_then(|data| U256::decode(data).ok())
+    {
+        return CertVerificationFailure::Panic(code);
+    }
+    CertVerificationFailure::UnknownRevert(revert_data.to_vec())
+}
+
+fn classify_revert_reason(reason: String) -> CertVerificationFailure {
+    if reason.contains("inclusion proof is invalid") {
+        CertVerificationFailure::InvalidInclusionProof(reason)
+    } else if reason.contains("signatories do not own at least threshold percentage") {
+        CertVerificationFailure::InsufficientStake(reason)
+    } else if reason.contains("blob quorums are not a subset of the confirmed quorums") {
+        CertVerificationFailure::BlobQuorumsNotConfirmed
+    } else if reason.contains("required quorums are not a subset of the blob quorums") {
+        CertVerificationFailure::RequiredQuorumsMissing
+    } else if reason.contains("security assumptions are not met")
+        || reason.contains("confirmationThreshold")
+    {
+        CertVerificationFailure::SecurityAssumptionsNotMet(reason)
+    } else if reason.starts_with("BLSSignatureChecker") {
+        CertVerificationFailure::InvalidSignature(reason)
+    } else {
+        CertVerificationFailure::Reverted(reason)
+    }
+}
+
+/// Verification of [`EigenDACert`]s against the EigenDACertVerifier contract.
+///
+/// Implemented by [`CertVerifier`]. Allows holding a cert verifier without being generic over its signer.
+#[async_trait::async_trait]
+pub trait CertVerification: Send + Sync + std::fmt::Debug {
+    /// Verifies the cert at the given block. See [`CertVerifier::verify_cert_v2_at_block`].
+    async fn verify_cert(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: BlockId,
+    ) -> Result<(), CertVerifierError>;
+}
+
+#[async_trait::async_trait]
+impl<S> CertVerification for CertVerifier<S>
+where
+    S: Send + Sync + std::fmt::Debug,
+    EthersSigner<S>: Signer,
+{
+    async fn verify_cert(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: BlockId,
+    ) -> Result<(), CertVerifierError> {
+        self.verify_cert_v2_at_block(eigenda_cert, block).await
     }
 }
 
@@ -109,15 +428,28 @@ mod tests {
 
     use ark_bn254::{G1Affine, G2Affine};
     use ark_ff::{BigInt, Fp2};
+    use ethers::{
+        prelude::{Http, Middleware, Provider},
+        types::{BlockId, BlockNumber, U256},
+    };
+    use rust_eigenda_signers::signers::private_key::Signer as PrivateKeySigner;
     use rust_eigenda_v2_common::{
         BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader, BlobInclusionInfo,
-        EigenDACert, NonSignerStakesAndSignature,
+        EigenDACert, EigenDACertV2, NonSignerStakesAndSignature,
     };
     use url::Url;
 
     use crate::{
-        cert_verifier::CertVerifier,
-        tests::{get_test_private_key_signer, CERT_VERIFIER_ADDRESS, HOLESKY_ETH_RPC_URL},
+        cert_verifier::{decode_revert, CertVerifier, PANIC_SELECTOR},
+        errors::{CertVerificationFailure, CertVerifierError},
+        stateless_execution::{
+            tests::{check_slot_code, single_contract_state},
+            RecordingStateSource, RpcStateSource, TrustedBlock,
+        },
+        tests::{
+            get_closed_rpc_url, get_test_private_key_signer, CERT_VERIFIER_ADDRESS,
+            HOLESKY_ETH_RPC_URL,
+        },
         utils::SecretUrl,
     };
 
@@ -256,7 +588,7 @@ mod tests {
             .into(),
         );
 
-        EigenDACert {
+        EigenDACert::V2(EigenDACertV2 {
             blob_inclusion_info: BlobInclusionInfo {
                 blob_certificate: BlobCertificate {
                     blob_header: BlobHeader {
@@ -303,7 +635,7 @@ mod tests {
                 non_signer_stake_indices: vec![vec![], vec![]], // does not break when changed
             },
             signed_quorum_numbers: vec![0, 1], // breaks when changed
-        }
+        })
     }
 
     #[ignore = "depends on external RPC"]
@@ -318,4 +650,247 @@ mod tests {
         let res = cert_verifier.verify_cert_v2(&get_test_eigenda_cert()).await;
         assert!(res.is_ok())
     }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_verify_cert_at_block() {
+        let cert_verifier = CertVerifier::new(
+            CERT_VERIFIER_ADDRESS,
+            SecretUrl::new(Url::from_str(HOLESKY_ETH_RPC_URL).unwrap()),
+            get_test_private_key_signer(),
+        )
+        .unwrap();
+        let res = cert_verifier
+            .verify_cert_v2_at_block(&get_test_eigenda_cert(), BlockNumber::Finalized)
+            .await;
+        assert!(res.is_ok())
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_contract_reads_at_block() {
+        let cert_verifier = CertVerifier::new(
+            CERT_VERIFIER_ADDRESS,
+            SecretUrl::new(Url::from_str(HOLESKY_ETH_RPC_URL).unwrap()),
+            get_test_private_key_signer(),
+        )
+        .unwrap();
+        let provider = Provider::<Http>::try_from(HOLESKY_ETH_RPC_URL).unwrap();
+        let finalized_block = provider
+            .get_block(BlockNumber::Finalized)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // pinned by hash
+        let block = BlockId::Hash(finalized_block.hash.unwrap());
+        cert_verifier
+            .verify_cert_v2_at_block(&get_test_eigenda_cert(), block)
+            .await
+            .unwrap();
+        assert_eq!(
+            cert_verifier
+                .quorum_numbers_required_at_block(block)
+                .await
+                .unwrap(),
+            cert_verifier.quorum_numbers_required().await.unwrap()
+        );
+
+        // the verifier rejects certs whose reference block is not before the verification block
+        let reference_block_number = get_test_eigenda_cert().reference_block_number();
+        let res = cert_verifier
+            .verify_cert_v2_at_block(&get_test_eigenda_cert(), reference_block_number as u64)
+            .await;
+        assert!(matches!(res, Err(CertVerifierError::VerificationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_cert_with_recorded_state() {
+        let cert_verifier = CertVerifier::new(
+            CERT_VERIFIER_ADDRESS,
+            SecretUrl::new(Url::from_str("http://localhost:8545").unwrap()),
+            PrivateKeySigner::random(&mut rand::thread_rng()),
+        )
+        .unwrap();
+
+        // stand-in for the verifier contract, accepting any cert if its storage slot 0 is set
+        let (block, state) =
+            single_contract_state(CERT_VERIFIER_ADDRESS, &check_slot_code(), U256::one());
+        let gas_used = cert_verifier
+            .verify_cert_v2_with_state_source(&state, &get_test_eigenda_cert(), &block)
+            .await
+            .unwrap();
+        assert!(gas_used > 21_000);
+
+        let (block, state) =
+            single_contract_state(CERT_VERIFIER_ADDRESS, &check_slot_code(), U256::zero());
+        let res = cert_verifier
+            .verify_cert_v2_with_state_source(&state, &get_test_eigenda_cert(), &block)
+            .await;
+        assert!(matches!(
+            res,
+            Err(CertVerifierError::VerificationFailed(
+                CertVerificationFailure::UnknownRevert(data)
+            )) if data.is_empty()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_cert_v3_with_recorded_state() {
+        let cert_verifier = CertVerifier::new(
+            CERT_VERIFIER_ADDRESS,
+            SecretUrl::new(Url::from_str("http://localhost:8545").unwrap()),
+            PrivateKeySigner::random(&mut rand::thread_rng()),
+        )
+        .unwrap();
+        let eigenda_cert = match get_test_eigenda_cert() {
+            EigenDACert::V2(eigenda_cert) => EigenDACert::V3(eigenda_cert.into()),
+            eigenda_cert => eigenda_cert,
+        };
+
+        // stand-in for the checkDACert of a verifier, returning the given status for any cert
+        let status_code =
+            |status: u8| hex::decode(format!("60{:02x}60005260206000f3", status)).unwrap();
+        let (block, state) =
+            single_contract_state(CERT_VERIFIER_ADDRESS, &status_code(1), U256::zero());
+        cert_verifier
+            .verify_cert_v2_with_state_source(&state, &eigenda_cert, &block)
+            .await
+            .unwrap();
+
+        let (block, state) =
+            single_contract_state(CERT_VERIFIER_ADDRESS, &status_code(5), U256::zero());
+        let res = cert_verifier
+            .verify_cert_v2_with_state_source(&state, &eigenda_cert, &block)
+            .await;
+        assert!(matches!(
+            res,
+            Err(CertVerifierError::VerificationFailed(
+                CertVerificationFailure::RequiredQuorumsMissing
+            ))
+        ));
+    }
+
+    fn error_string_revert(reason: &str) -> Vec<u8> {
+        let mut revert_data = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert_data.extend(ethabi::encode(&[ethabi::Token::String(reason.to_string())]));
+        revert_data
+    }
+
+    #[test]
+    fn test_decode_revert() {
+        let inclusion_reason =
+            "EigenDACertVerificationUtils._verifyDACertV2ForQuorums: inclusion proof is invalid";
+        assert_eq!(
+            decode_revert(&error_string_revert(inclusion_reason)),
+            CertVerificationFailure::InvalidInclusionProof(inclusion_reason.to_string())
+        );
+        assert_eq!(
+            decode_revert(&error_string_revert(
+                "EigenDACertVerificationUtils._verifyDACertV2ForQuorums: blob quorums are not a subset of the confirmed quorums"
+            )),
+            CertVerificationFailure::BlobQuorumsNotConfirmed
+        );
+        assert_eq!(
+            decode_revert(&error_string_revert(
+                "EigenDACertVerificationUtils._verifyDACertV2ForQuorums: required quorums are not a subset of the blob quorums"
+            )),
+            CertVerificationFailure::RequiredQuorumsMissing
+        );
+        let signature_reason = "BLSSignatureChecker.checkSignatures: signature is invalid";
+        assert_eq!(
+            decode_revert(&error_string_revert(signature_reason)),
+            CertVerificationFailure::InvalidSignature(signature_reason.to_string())
+        );
+        let stake_reason = "EigenDACertVerificationUtils._verifyDACertV2ForQuorums: signatories do not own at least threshold percentage of a quorum";
+        assert_eq!(
+            decode_revert(&error_string_revert(stake_reason)),
+            CertVerificationFailure::InsufficientStake(stake_reason.to_string())
+        );
+        assert_eq!(
+            decode_revert(&error_string_revert("unexpected")),
+            CertVerificationFailure::Reverted("unexpected".to_string())
+        );
+
+        let mut panic_revert = PANIC_SELECTOR.to_vec();
+        panic_revert.extend(ethabi::encode(&[ethabi::Token::Uint(0x11.into())]));
+        assert_eq!(
+            decode_revert(&panic_revert),
+            CertVerificationFailure::Panic(0x11.into())
+        );
+
+        assert_eq!(
+            decode_revert(&[0xde, 0xad]),
+            CertVerificationFailure::UnknownRevert(vec![0xde, 0xad])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_contract_call_error_keeps_source() {
+        let cert_verifier = CertVerifier::new(
+            CERT_VERIFIER_ADDRESS,
+            get_closed_rpc_url(),
+            PrivateKeySigner::random(&mut rand::thread_rng()),
+        )
+        .unwrap();
+
+        let err = cert_verifier.quorum_numbers_required().await.unwrap_err();
+        assert!(matches!(
+            err,
+            CertVerifierError::ContractCall {
+                function: "quorumNumbersRequired",
+                ..
+            }
+        ));
+        assert!(std::error::Error::source(&err).is_some());
+
+        let err = cert_verifier.block_number().await.unwrap_err();
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_verify_cert_stateless() {
+        let cert_verifier = CertVerifier::new(
+            CERT_VERIFIER_ADDRESS,
+            SecretUrl::new(Url::from_str(HOLESKY_ETH_RPC_URL).unwrap()),
+            get_test_private_key_signer(),
+        )
+        .unwrap();
+        let provider = Provider::<Http>::try_from(HOLESKY_ETH_RPC_URL).unwrap();
+        let block = provider
+            .get_block(BlockNumber::Finalized)
+            .await
+            .unwrap()
+            .unwrap();
+        let trusted_block = TrustedBlock::from_block(&block).unwrap();
+
+        // record the state read by the verification, and replay it offline
+        let recording = RecordingStateSource::new(
+            RpcStateSource::from_provider(provider),
+            trusted_block.number,
+        );
+        let gas_used = cert_verifier
+            .verify_cert_v2_with_state_source(&recording, &get_test_eigenda_cert(), &trusted_block)
+            .await
+            .unwrap();
+        let replayed_gas_used = cert_verifier
+            .verify_cert_v2_with_state_source(
+                &recording.recorded(),
+                &get_test_eigenda_cert(),
+                &trusted_block,
+            )
+            .await
+            .unwrap();
+        assert_eq!(gas_used, replayed_gas_used);
+
+        // `EIGENDA_RECORD_FIXTURE=<path>` saves the recording, to be replayed by offline tests
+        if let Ok(path) = std::env::var("EIGENDA_RECORD_FIXTURE") {
+            let fixture = serde_json::json!({
+                "block": trusted_block,
+                "state": recording.recorded(),
+            });
+            std::fs::write(path, serde_json::to_string_pretty(&fixture).unwrap()).unwrap();
+        }
+    }
 }
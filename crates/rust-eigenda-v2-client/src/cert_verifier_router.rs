@@ -0,0 +1,393 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use ethers::prelude::*;
+use rust_eigenda_signers::signers::ethers::Signer as EthersSigner;
+use rust_eigenda_v2_common::EigenDACert;
+use tracing::instrument;
+
+use crate::{
+    cert_verifier::{
+        contract_call_error, signer_client, CertVerification, CertVerifier, SignerClient,
+    },
+    errors::CertVerifierError,
+    generated::i_cert_verifier_router::IEigenDACertVerifierRouter,
+    utils::{contract_error_source, middleware_error_source, SecretUrl},
+};
+
+/// Address of the contract certs are verified with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CertVerifierAddress {
+    /// An EigenDACertVerifier, used to verify every cert.
+    Verifier(H160),
+    /// An EigenDACertVerifierRouter, which maps reference block numbers to the EigenDACertVerifier of the certs with
+    /// that reference block number. Keeps certs verifiable across verifier upgrades.
+    Router(H160),
+}
+
+impl From<H160> for CertVerifierAddress {
+    fn from(address: H160) -> Self {
+        Self::Verifier(address)
+    }
+}
+
+#[derive(Debug)]
+/// Provides methods for interacting with the EigenDA CertVerifierRouter contract.
+pub struct CertVerifierRouter<S> {
+    cert_verifier_router_contract: IEigenDACertVerifierRouter<SignerClient<S>>,
+}
+
+impl<S> Clone for CertVerifierRouter<S> {
+    fn clone(&self) -> Self {
+        Self {
+            cert_verifier_router_contract: self.cert_verifier_router_contract.clone(),
+        }
+    }
+}
+
+impl<S> CertVerifierRouter<S> {
+    /// Creates a new instance of [`CertVerifierRouter`], receiving the address of the contract and the ETH RPC url.
+    pub fn new(address: H160, rpc_url: SecretUrl, signer: S) -> Result<Self, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        Ok(Self::with_client(address, signer_client(rpc_url, signer)?))
+    }
+
+    /// Creates a new instance of [`CertVerifierRouter`] calling the contract through an existing client.
+    pub(crate) fn with_client(address: H160, client: Arc<SignerClient<S>>) -> Self
+    where
+        EthersSigner<S>: Signer,
+    {
+        CertVerifierRouter {
+            cert_verifier_router_contract: IEigenDACertVerifierRouter::new(address, client),
+        }
+    }
+
+    /// Calls the getCertVerifierAt view function on the EigenDACertVerifierRouter contract at the given block,
+    /// returning the address of the verifier of certs with the given reference block number.
+    #[instrument(
+        skip_all,
+        fields(contract = ?self.cert_verifier_router_contract.address(), reference_block_number)
+    )]
+    pub async fn get_cert_verifier_at(
+        &self,
+        reference_block_number: u32,
+        block: impl Into<BlockId>,
+    ) -> Result<H160, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        self.cert_verifier_router_contract
+            .get_cert_verifier_at(reference_block_number)
+            .block(block)
+            .call()
+            .await
+            .map_err(|e| contract_call_error("getCertVerifierAt", contract_error_source(e)))
+    }
+
+    /// Returns the number of the latest block of the chain the contract is deployed on.
+    pub async fn block_number(&self) -> Result<u64, CertVerifierError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        let block_number = self
+            .cert_verifier_router_contract
+            .client()
+            .get_block_number()
+            .await
+            .map_err(|e| contract_call_error("eth_blockNumber", middleware_error_source(e)))?;
+        Ok(block_number.as_u64())
+    }
+}
+
+#[derive(Debug)]
+enum VerifierSource<S> {
+    Fixed(H160),
+    Router(CertVerifierRouter<S>),
+}
+
+impl<S> Clone for VerifierSource<S> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Fixed(address) => Self::Fixed(*address),
+            Self::Router(router) => Self::Router(router.clone()),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Resolves the [`CertVerifier`] of each cert, either a fixed one or the one the router maps the reference block
+/// number of the cert to.
+///
+/// The router and every verifier call their contracts through the same client, which owns the signer: resolving
+/// a new verifier doesn't need another copy of the signer.
+pub struct CertVerifierResolver<S> {
+    source: VerifierSource<S>,
+    client: Arc<SignerClient<S>>,
+    /// Verifiers already resolved, by address.
+    cert_verifiers: Arc<Mutex<HashMap<H160, CertVerifier<S>>>>,
+}
+
+impl<S> Clone for CertVerifierResolver<S> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            client: self.client.clone(),
+            cert_verifiers: self.cert_verifiers.clone(),
+        }
+    }
+}
+
+impl<S> CertVerifierResolver<S>
+where
+    EthersSigner<S>: Signer,
+{
+    /// Creates a new instance of [`CertVerifierResolver`], receiving the address of the verifier or router contract
+    /// and the ETH RPC url.
+    pub fn new(
+        address: CertVerifierAddress,
+        rpc_url: SecretUrl,
+        signer: S,
+    ) -> Result<Self, CertVerifierError> {
+        let client = signer_client(rpc_url, signer)?;
+        let source = match address {
+            CertVerifierAddress::Verifier(address) => VerifierSource::Fixed(address),
+            CertVerifierAddress::Router(address) => {
+                VerifierSource::Router(CertVerifierRouter::with_client(address, client.clone()))
+            }
+        };
+        Ok(Self {
+            source,
+            client,
+            cert_verifiers: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Returns the verifier of certs with the given reference block number, reading the router at the given block.
+    pub async fn cert_verifier_at(
+        &self,
+        reference_block_number: u32,
+        block: impl Into<BlockId>,
+    ) -> Result<CertVerifier<S>, CertVerifierError> {
+        let address = match &self.source {
+            VerifierSource::Fixed(address) => *address,
+            VerifierSource::Router(router) => {
+                router
+                    .get_cert_verifier_at(reference_block_number, block)
+                    .await?
+            }
+        };
+        self.cert_verifier(address)
+    }
+
+    /// Returns the verifier at the given address, creating it the first time it is used.
+    fn cert_verifier(&self, address: H160) -> Result<CertVerifier<S>, CertVerifierError> {
+        // the router returns the zero address for reference blocks before the first verifier
+        if address.is_zero() {
+            return Err(CertVerifierError::InvalidCertVerifierAddress(address));
+        }
+
+        let mut cert_verifiers = self
+            .cert_verifiers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(cert_verifier) = cert_verifiers.get(&address) {
+            return Ok(cert_verifier.clone());
+        }
+        let cert_verifier = CertVerifier::with_client(address, self.client.clone());
+        cert_verifiers.insert(address, cert_verifier.clone());
+        Ok(cert_verifier)
+    }
+
+    /// Returns the verifier of the certs dispersed now, those whose reference block number is the latest block.
+    pub async fn current_cert_verifier(&self) -> Result<CertVerifier<S>, CertVerifierError> {
+        let block_number = self.block_number().await?;
+        let reference_block_number = u32::try_from(block_number).map_err(|_| {
+            CertVerifierError::Contract(format!("block number {} overflows u32", block_number))
+        })?;
+        self.cert_verifier_at(reference_block_number, block_number)
+            .await
+    }
+
+    /// Returns the number of the latest block.
+    pub async fn block_number(&self) -> Result<u64, CertVerifierError> {
+        match &self.source {
+            VerifierSource::Fixed(address) => self.cert_verifier(*address)?.block_number().await,
+            VerifierSource::Router(router) => router.block_number().await,
+        }
+    }
+
+    /// Verifies the cert at the given block, with the verifier of its reference block number.
+    /// See [`CertVerifier::verify_cert_v2_at_block`].
+    pub async fn verify_cert_v2_at_block(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: impl Into<BlockId>,
+    ) -> Result<(), CertVerifierError> {
+        let block = block.into();
+        self.cert_verifier_at(eigenda_cert.reference_block_number(), block)
+            .await?
+            .verify_cert_v2_at_block(eigenda_cert, block)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> CertVerification for CertVerifierResolver<S>
+where
+    S: Send + Sync + std::fmt::Debug,
+    EthersSigner<S>: Signer,
+{
+    async fn verify_cert(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: BlockId,
+    ) -> Result<(), CertVerifierError> {
+        self.verify_cert_v2_at_block(eigenda_cert, block).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use ethers::{abi::Token, types::BlockNumber};
+    use rust_eigenda_signers::signers::private_key::Signer as PrivateKeySigner;
+    use serde_json::json;
+    use url::Url;
+
+    use super::*;
+    use crate::{
+        relay_payload_retriever::tests::get_test_eigenda_cert,
+        tests::{
+            get_test_private_key_signer, mock_eth_rpc, CERT_VERIFIER_ADDRESS, HOLESKY_ETH_RPC_URL,
+        },
+    };
+
+    const ROUTER_ADDRESS: H160 = H160::repeat_byte(0x10);
+    const FIRST_VERIFIER: H160 = H160::repeat_byte(0x01);
+    const SECOND_VERIFIER: H160 = H160::repeat_byte(0x02);
+    /// Reference block number from which the router maps certs to the second verifier.
+    const SECOND_VERIFIER_ACTIVATION: u32 = 100;
+
+    /// Returns a resolver reading a mocked router, which has no verifier for reference block 0, the first verifier
+    /// up to [`SECOND_VERIFIER_ACTIVATION`] and the second one after, along with the number of getCertVerifierAt
+    /// calls it served.
+    async fn get_mocked_router_resolver(
+    ) -> (CertVerifierResolver<PrivateKeySigner>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let served = calls.clone();
+        let eth_rpc_url = mock_eth_rpc(move |method, params| {
+            assert_eq!(method, "eth_call");
+            let call = &params[0];
+            assert_eq!(
+                call["to"].as_str().unwrap(),
+                format!("{:?}", ROUTER_ADDRESS)
+            );
+            // the router is read at the requested block
+            assert_eq!(params[1], json!("0x2a"));
+            let input = call["input"].as_str().or(call["data"].as_str()).unwrap();
+            let input = hex::decode(input.trim_start_matches("0x")).unwrap();
+            let reference_block_number = U256::from_big_endian(&input[4..36]).as_u32();
+            served.fetch_add(1, Ordering::SeqCst);
+
+            let verifier = match reference_block_number {
+                0 => H160::zero(),
+                n if n < SECOND_VERIFIER_ACTIVATION => FIRST_VERIFIER,
+                _ => SECOND_VERIFIER,
+            };
+            let output = ethers::abi::encode(&[Token::Address(verifier)]);
+            Ok(json!(format!("0x{}", hex::encode(output))))
+        })
+        .await;
+        let resolver = CertVerifierResolver::new(
+            CertVerifierAddress::Router(ROUTER_ADDRESS),
+            eth_rpc_url,
+            PrivateKeySigner::random(&mut rand::thread_rng()),
+        )
+        .unwrap();
+        (resolver, calls)
+    }
+
+    #[tokio::test]
+    async fn test_router_resolves_cert_verifier_by_reference_block() {
+        let (resolver, _) = get_mocked_router_resolver().await;
+
+        for (reference_block_number, expected) in [
+            (1, FIRST_VERIFIER),
+            (SECOND_VERIFIER_ACTIVATION - 1, FIRST_VERIFIER),
+            (SECOND_VERIFIER_ACTIVATION, SECOND_VERIFIER),
+            (u32::MAX, SECOND_VERIFIER),
+        ] {
+            let cert_verifier = resolver
+                .cert_verifier_at(reference_block_number, 42u64)
+                .await
+                .unwrap();
+            assert_eq!(cert_verifier.address(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_router_rejects_zero_address() {
+        let (resolver, _) = get_mocked_router_resolver().await;
+
+        let res = resolver.cert_verifier_at(0, 42u64).await;
+        assert!(matches!(
+            res,
+            Err(CertVerifierError::InvalidCertVerifierAddress(address)) if address.is_zero()
+        ));
+        // nothing is cached for the zero address
+        assert!(resolver.cert_verifiers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_router_caches_cert_verifiers() {
+        let (resolver, calls) = get_mocked_router_resolver().await;
+
+        for reference_block_number in [1, 2, SECOND_VERIFIER_ACTIVATION, 3] {
+            resolver
+                .cert_verifier_at(reference_block_number, 42u64)
+                .await
+                .unwrap();
+        }
+        // the router is read for every cert, as it may map a reference block to a new verifier
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        // but a single verifier is created per address, and shared by the clones of the resolver
+        let cached = resolver.clone().cert_verifiers.lock().unwrap().len();
+        assert_eq!(cached, 2);
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_fixed_cert_verifier() {
+        let resolver = CertVerifierResolver::new(
+            CERT_VERIFIER_ADDRESS.into(),
+            SecretUrl::new(Url::from_str(HOLESKY_ETH_RPC_URL).unwrap()),
+            get_test_private_key_signer(),
+        )
+        .unwrap();
+
+        // the fixed verifier is used for every reference block number
+        let eigenda_cert = get_test_eigenda_cert();
+        for reference_block_number in [0, eigenda_cert.reference_block_number()] {
+            let cert_verifier = resolver
+                .cert_verifier_at(reference_block_number, BlockNumber::Latest)
+                .await
+                .unwrap();
+            cert_verifier
+                .verify_cert_v2_at_block(&eigenda_cert, BlockNumber::Finalized)
+                .await
+                .unwrap();
+        }
+        resolver
+            .verify_cert_v2_at_block(&eigenda_cert, BlockNumber::Finalized)
+            .await
+            .unwrap();
+    }
+}
@@ -1,32 +1,34 @@
-use ark_bn254::{G1Affine, G1Projective, G2Affine};
-use ark_ec::{CurveGroup, VariableBaseMSM};
-use ark_ff::{AdditiveGroup, Fp, Fp2, PrimeField, Zero};
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{AdditiveGroup, Field, Fp, Fp2, PrimeField, Zero};
 use ark_serialize::CanonicalSerialize;
 use rust_kzg_bn254_primitives::helpers::{lexicographically_largest, read_g1_point_from_bytes_be};
 
+use rust_eigenda_v2_common::BlobCommitments;
+
 use crate::{
-    errors::{BlobError, Bn254Error, ConversionError},
+    core::Frame,
+    errors::{BlobCommitmentsError, BlobError, Bn254Error, ConversionError},
+    srs::Srs,
     utils::fr_array_from_bytes,
 };
 
 const COMPRESSED_SMALLEST: u8 = 0b10 << 6;
 const COMPRESSED_LARGEST: u8 = 0b11 << 6;
 const COMPRESSED_INFINITY: u8 = 0b01 << 6;
+const G1_COMPRESSED_SIZE: usize = 32;
 const G2_COMPRESSED_SIZE: usize = 64;
 
-fn generate_blob_commitment(
-    g1_srs: Vec<G1Affine>,
-    blob_bytes: &[u8],
-) -> Result<G1Affine, BlobError> {
+fn generate_blob_commitment(g1_srs: &[G1Affine], blob_bytes: &[u8]) -> Result<G1Affine, BlobError> {
     let input_fr = fr_array_from_bytes(blob_bytes);
 
     if g1_srs.len() < input_fr.len() {
         return Err(Bn254Error::InsufficientSrsInMemory(g1_srs.len(), input_fr.len()).into());
     }
 
-    let bases = g1_srs[0..input_fr.len()].to_vec();
-    let commitment = G1Projective::msm(&bases, &input_fr)
-        .map_err(|_| Bn254Error::FailedComputingMSM(bases, input_fr))?
+    let bases = &g1_srs[0..input_fr.len()];
+    let commitment = G1Projective::msm(bases, &input_fr)
+        .map_err(|_| Bn254Error::FailedComputingMSM(bases.to_vec(), input_fr))?
         .into_affine();
     Ok(commitment)
 }
@@ -35,7 +37,7 @@ fn generate_blob_commitment(
 /// commitment. An error is returned if there is a problem generating the commitment. True is returned if the commitment
 /// is successfully generated, and is equal to the claimed commitment, otherwise false.
 pub(crate) fn generate_and_compare_blob_commitment(
-    g1_srs: Vec<G1Affine>,
+    g1_srs: &[G1Affine],
     blob_bytes: Vec<u8>,
     claimed_commitment: G1Affine,
 ) -> Result<bool, BlobError> {
@@ -43,15 +45,185 @@ pub(crate) fn generate_and_compare_blob_commitment(
     Ok(claimed_commitment == computed_commitment)
 }
 
+/// Verifies the KZG multiproof of a [`Frame`] against the blob commitment.
+///
+/// The frame holds the coefficients of the polynomial I interpolating the blob polynomial P over the coset
+/// `coset_leader * <w>`, of size n. Its proof is the commitment to `(P - I) / (X^n - coset_leader^n)`, so the
+/// following pairing equation must hold:
+///
+/// `e([P(s)]_1 - [I(s)]_1, [1]_2) = e(proof, [s^n]_2 - [coset_leader^n]_2)`
+///
+/// `g2_tau_pow_n` must be the G2 SRS point `[s^n]_2`.
+pub(crate) fn verify_frame(
+    g1_srs: &[G1Affine],
+    g2_tau_pow_n: G2Affine,
+    commitment: G1Affine,
+    frame: &Frame,
+    coset_leader: Fr,
+) -> Result<bool, BlobError> {
+    let chunk_length = frame.coeffs.len();
+    if g1_srs.len() < chunk_length {
+        return Err(Bn254Error::InsufficientSrsInMemory(g1_srs.len(), chunk_length).into());
+    }
+
+    let bases = g1_srs[..chunk_length].to_vec();
+    let interpolation_commitment = G1Projective::msm(&bases, &frame.coeffs)
+        .map_err(|_| Bn254Error::FailedComputingMSM(bases, frame.coeffs.clone()))?;
+    let commitment_minus_interpolation =
+        (G1Projective::from(commitment) - interpolation_commitment).into_affine();
+
+    let coset_leader_pow_n = coset_leader.pow([chunk_length as u64]);
+    let tau_pow_n_minus_leader_pow_n = (G2Projective::from(g2_tau_pow_n)
+        - G2Affine::generator() * coset_leader_pow_n)
+        .into_affine();
+
+    let lhs = Bn254::pairing(commitment_minus_interpolation, G2Affine::generator());
+    let rhs = Bn254::pairing(frame.proof, tau_pow_n_minus_leader_pow_n);
+    Ok(lhs == rhs)
+}
+
+/// Computes the KZG opening proof of the polynomial p (given by its coefficients) at `point`.
+///
+/// Returns the evaluation `y = p(point)` and the proof `[q(s)]_1`, being `q(X) = (p(X) - y) / (X - point)` the
+/// quotient polynomial.
+pub(crate) fn compute_opening_proof(
+    g1_srs: &[G1Affine],
+    coeffs: &[Fr],
+    point: Fr,
+) -> Result<(Fr, G1Affine), BlobError> {
+    // synthetic division of p by (X - point): the remainder is p(point)
+    let mut quotient = vec![Fr::zero(); coeffs.len().saturating_sub(1)];
+    let mut remainder = Fr::zero();
+    for (index, coeff) in coeffs.iter().enumerate().rev() {
+        if index < quotient.len() {
+            quotient[index] = remainder;
+        }
+        remainder = *coeff + remainder * point;
+    }
+
+    if g1_srs.len() < quotient.len() {
+        return Err(Bn254Error::InsufficientSrsInMemory(g1_srs.len(), quotient.len()).into());
+    }
+    let bases = &g1_srs[..quotient.len()];
+    let proof = G1Projective::msm(bases, &quotient)
+        .map_err(|_| Bn254Error::FailedComputingMSM(bases.to_vec(), quotient.clone()))?
+        .into_affine();
+    Ok((remainder, proof))
+}
+
+/// Verifies the KZG opening proof that the polynomial committed in `commitment` evaluates to `value` at `point`.
+///
+/// The proof is the commitment to `q(X) = (p(X) - value) / (X - point)`, so the following pairing equation must hold:
+///
+/// `e([p(s)]_1 - [value]_1 + point * proof, [1]_2) = e(proof, [s]_2)`
+///
+/// `g2_tau` must be the G2 SRS point `[s]_2`.
+pub(crate) fn verify_opening_proof(
+    g2_tau: G2Affine,
+    commitment: G1Affine,
+    point: Fr,
+    value: Fr,
+    proof: G1Affine,
+) -> bool {
+    let lhs_point = (G1Projective::from(commitment) - G1Affine::generator() * value
+        + proof * point)
+        .into_affine();
+    Bn254::pairing(lhs_point, G2Affine::generator()) == Bn254::pairing(proof, g2_tau)
+}
+
+/// Parses the G2 SRS points `[s^(2^i)]_2`, stored compressed one after the other (like `g2.point.powerOf2`).
+pub(crate) fn g2_power_of_2_points_from_bytes(
+    bytes: &[u8],
+) -> Result<Vec<G2Affine>, ConversionError> {
+    if !bytes.len().is_multiple_of(G2_COMPRESSED_SIZE) {
+        return Err(ConversionError::SrsFile(format!(
+            "length ({}) is not a multiple of {}",
+            bytes.len(),
+            G2_COMPRESSED_SIZE
+        )));
+    }
+
+    bytes
+        .chunks(G2_COMPRESSED_SIZE)
+        .map(g2_commitment_from_bytes)
+        .collect()
+}
+
+/// Checks that a G1 commitment and a G2 length commitment commit to the same polynomial p:
+///
+/// `e([p(s)]_1, [1]_2) = e([1]_1, [p(s)]_2)`
+pub(crate) fn verify_length_commitment(commitment: G1Affine, length_commitment: G2Affine) -> bool {
+    Bn254::pairing(commitment, G2Affine::generator())
+        == Bn254::pairing(G1Affine::generator(), length_commitment)
+}
+
+/// Verifies the proof that the polynomial p committed in `length_commitment` has less than `length` coefficients.
+///
+/// The length proof is `[p(s) * s^(order - length)]_2`, which can only be computed if the degree of p is lower than
+/// `length`, being `order` the size of the SRS. It is checked with the following pairing equation:
+///
+/// `e([s^(order - length)]_1, [p(s)]_2) = e([1]_1, length_proof)`
+///
+/// `g1_challenge` must be the G1 SRS point `[s^(order - length)]_1`.
+pub(crate) fn verify_length_proof(
+    g1_challenge: G1Affine,
+    length_commitment: G2Affine,
+    length_proof: G2Affine,
+) -> bool {
+    Bn254::pairing(g1_challenge, length_commitment)
+        == Bn254::pairing(G1Affine::generator(), length_proof)
+}
+
+/// Checks that the length commitment of a blob commits to the same polynomial as the blob commitment, and, if
+/// `verify_length_proof` is set, that the length proof is valid for the claimed blob length.
+///
+/// Together with the blob commitment check, this ensures the blob length in the cert can be trusted. The length
+/// proof requires the G1 SRS point `[s^(order - length)]_1`, so the SRS file must hold the first `order` points.
+pub(crate) fn verify_blob_commitments(
+    srs: &Srs,
+    blob_commitments: &BlobCommitments,
+    verify_length_proof: bool,
+) -> Result<(), BlobCommitmentsError> {
+    if !verify_length_commitment(
+        blob_commitments.commitment,
+        blob_commitments.length_commitment,
+    ) {
+        return Err(BlobCommitmentsError::LengthCommitmentMismatch);
+    }
+
+    if !verify_length_proof {
+        return Ok(());
+    }
+
+    let length = blob_commitments.length;
+    if length == 0 || !length.is_power_of_two() || length > srs.order() {
+        return Err(BlobCommitmentsError::InvalidLengthProof(length));
+    }
+    let g1_challenge = srs.g1_point_at((srs.order() - length) as usize)?;
+    if !self::verify_length_proof(
+        g1_challenge,
+        blob_commitments.length_commitment,
+        blob_commitments.length_proof,
+    ) {
+        return Err(BlobCommitmentsError::InvalidLengthProof(length));
+    }
+
+    Ok(())
+}
+
 /// Converts a byte slice to a [`G1Affine`] point.
 /// The points received are in compressed form.
 pub(crate) fn g1_commitment_from_bytes(bytes: &[u8]) -> Result<G1Affine, ConversionError> {
+    if bytes.len() != G1_COMPRESSED_SIZE {
+        return Err(ConversionError::G1Point(
+            "Invalid length for G1 Commitment".to_string(),
+        ));
+    }
     read_g1_point_from_bytes_be(bytes).map_err(|e| ConversionError::G1Point(e.to_string()))
 }
 
 /// Serialize a G1Affine point applying necessary flags.
 /// https://github.com/Consensys/gnark-crypto/blob/5fd6610ac2a1d1b10fae06c5e552550bf43f4d44/ecc/bn254/marshal.go#L790-L801
-#[cfg(test)]
 pub(crate) fn g1_commitment_to_bytes(point: &G1Affine) -> Result<Vec<u8>, ConversionError> {
     let mut bytes = vec![0u8; 32];
 
@@ -316,6 +488,161 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_verify_frame() {
+        use ark_bn254::Fr;
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+
+        use crate::core::{frame::tests::encode_frames, BlobParams};
+
+        // toy trusted setup with a known secret
+        let mut rng = StdRng::seed_from_u64(7);
+        let tau = Fr::rand(&mut rng);
+        let g1_srs: Vec<G1Affine> = (0..16u64)
+            .map(|i| (G1Affine::generator() * tau.pow([i])).into_affine())
+            .collect();
+
+        let params = BlobParams {
+            num_chunks: 4,
+            coding_rate: 2,
+        }
+        .encoding_params(8)
+        .unwrap();
+        let coeffs: Vec<Fr> = (0..8).map(|_| Fr::rand(&mut rng)).collect();
+        let blob_poly = DensePolynomial::from_coefficients_slice(&coeffs);
+        let commitment = (G1Affine::generator() * blob_poly.evaluate(&tau)).into_affine();
+        let g2_tau_pow_n =
+            (G2Affine::generator() * tau.pow([params.chunk_length as u64])).into_affine();
+
+        for (chunk_index, mut frame) in encode_frames(&coeffs, &params) {
+            let coset_leader = params.coset_leader(chunk_index).unwrap();
+            let interpolation_poly = DensePolynomial::from_coefficients_slice(&frame.coeffs);
+            let quotient = (blob_poly.evaluate(&tau) - interpolation_poly.evaluate(&tau))
+                / (tau.pow([params.chunk_length as u64])
+                    - coset_leader.pow([params.chunk_length as u64]));
+            frame.proof = (G1Affine::generator() * quotient).into_affine();
+
+            assert!(verify_frame(&g1_srs, g2_tau_pow_n, commitment, &frame, coset_leader).unwrap());
+
+            // a frame verified against another coset must fail
+            let wrong_leader = params.coset_leader((chunk_index + 1) % 4).unwrap();
+            assert!(
+                !verify_frame(&g1_srs, g2_tau_pow_n, commitment, &frame, wrong_leader).unwrap()
+            );
+
+            // a tampered frame must fail
+            frame.coeffs[0] += Fr::ONE;
+            assert!(
+                !verify_frame(&g1_srs, g2_tau_pow_n, commitment, &frame, coset_leader).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_length_proof() {
+        use ark_bn254::Fr;
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+
+        // toy trusted setup with a known secret
+        let mut rng = StdRng::seed_from_u64(11);
+        let tau = Fr::rand(&mut rng);
+        let order = 16u64;
+        let length = 8u64;
+
+        let coeffs: Vec<Fr> = (0..length).map(|_| Fr::rand(&mut rng)).collect();
+        let p_tau = DensePolynomial::from_coefficients_slice(&coeffs).evaluate(&tau);
+        let commitment = (G1Affine::generator() * p_tau).into_affine();
+        let length_commitment = (G2Affine::generator() * p_tau).into_affine();
+        let length_proof =
+            (G2Affine::generator() * (p_tau * tau.pow([order - length]))).into_affine();
+        let g1_challenge = (G1Affine::generator() * tau.pow([order - length])).into_affine();
+
+        assert!(verify_length_commitment(commitment, length_commitment));
+        assert!(verify_length_proof(
+            g1_challenge,
+            length_commitment,
+            length_proof
+        ));
+
+        // the proof does not hold for a shorter length
+        let shorter_challenge =
+            (G1Affine::generator() * tau.pow([order - length / 2])).into_affine();
+        assert!(!verify_length_proof(
+            shorter_challenge,
+            length_commitment,
+            length_proof
+        ));
+
+        // a length commitment to another polynomial is rejected
+        let other_length_commitment = (G2Affine::generator() * (p_tau + Fr::ONE)).into_affine();
+        assert!(!verify_length_commitment(
+            commitment,
+            other_length_commitment
+        ));
+    }
+
+    #[test]
+    fn test_opening_proof() {
+        use ark_bn254::Fr;
+        use ark_ff::BigInteger;
+        use ark_poly::{univariate::DensePolynomial, DenseUVPolynomial, Polynomial};
+
+        use crate::core::{Payload, PayloadForm};
+
+        // toy trusted setup with a known secret
+        let mut rng = StdRng::seed_from_u64(13);
+        let tau = Fr::rand(&mut rng);
+        let g1_srs: Vec<G1Affine> = (0..8u64)
+            .map(|i| (G1Affine::generator() * tau.pow([i])).into_affine())
+            .collect();
+        let g2_tau = (G2Affine::generator() * tau).into_affine();
+
+        let payload_bytes: Vec<u8> = (0..100).collect();
+        let payload = Payload::new(payload_bytes.clone());
+        let blob = payload.to_blob(PayloadForm::Eval).unwrap();
+        let commitment = (G1Affine::generator()
+            * DensePolynomial::from_coefficients_slice(&blob.coeff_polynomial).evaluate(&tau))
+        .into_affine();
+
+        for offset in [0, 30, 31, 99] {
+            let location = payload.symbol_location(offset).unwrap();
+            assert_eq!(location, blob.symbol_location(offset).unwrap());
+
+            let (value, proof) =
+                compute_opening_proof(&g1_srs, &blob.coeff_polynomial, location.evaluation_point)
+                    .unwrap();
+            // in evaluation form, the opened value is the encoded payload symbol holding the byte
+            assert_eq!(
+                value.into_bigint().to_bytes_be()[location.byte_index],
+                payload_bytes[offset]
+            );
+            assert!(verify_opening_proof(
+                g2_tau,
+                commitment,
+                location.evaluation_point,
+                value,
+                proof
+            ));
+
+            // the proof does not hold for another value
+            assert!(!verify_opening_proof(
+                g2_tau,
+                commitment,
+                location.evaluation_point,
+                value + Fr::ONE,
+                proof
+            ));
+        }
+    }
+
+    #[test]
+    fn test_g2_power_of_2_points_from_bytes() {
+        let bytes = std::fs::read("../../resources/g2.point.powerOf2").unwrap();
+        let points = g2_power_of_2_points_from_bytes(&bytes).unwrap();
+        assert_eq!(points.len(), 28);
+        assert!(points.iter().all(|point| point.is_on_curve()));
+    }
+
     proptest! {
         #[test]
         fn fuzz_g1_point_conversion(g1_point in g1_affine_strategy()) {
@@ -2,17 +2,27 @@ mod blob;
 mod blob_key;
 pub mod eigenda_cert;
 mod encoded_payload;
+pub(crate) mod frame;
+mod opening_proof;
 mod payload;
 mod payment;
 
 pub use blob::Blob;
 pub use blob_key::BlobKey;
 pub use encoded_payload::EncodedPayload;
+pub use frame::{BlobParams, EncodingParams, Frame};
+pub use opening_proof::{OpeningProof, SymbolLocation};
 pub use payload::Payload;
 pub use payment::{OnDemandPayment, PaymentMetadata, PaymentStateRequest, ReservedPayment};
 
 pub(crate) const BYTES_PER_SYMBOL: usize = 32;
 
+/// Maximum size of a blob, in bytes.
+pub const MAX_BLOB_SIZE: usize = 16 * 1024 * 1024; // 16 MB
+
+/// Maximum length of a blob, in symbols. Untrusted blob lengths are checked against it before allocating.
+pub(crate) const MAX_BLOB_LENGTH_SYMBOLS: usize = MAX_BLOB_SIZE / BYTES_PER_SYMBOL;
+
 /// Payload encoding version
 #[derive(Debug, PartialEq)]
 pub enum PayloadEncodingVersion {
@@ -20,10 +30,27 @@ pub enum PayloadEncodingVersion {
 }
 
 /// The form of a payload dictates what conversion, if any, must be performed when creating a blob from the payload.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PayloadForm {
     /// Evaluation form, where the payload is in evaluation form.
     Eval,
     /// Coefficient form, where the payload is in coefficient form.
     Coeff,
 }
+
+/// How strictly blobs and encoded payloads are validated when decoding them into payloads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadDecodeMode {
+    /// Only checks what is needed to extract the payload: the payload length in the header, and that no data
+    /// follows the payload past its last symbol.
+    #[default]
+    Lenient,
+    /// Only accepts the exact encoding produced by [`EncodedPayload::new`]: every blob symbol must be a canonical
+    /// field element, the header must have a known [`PayloadEncodingVersion`] and zeroed reserved bytes, and the
+    /// padding bytes (the first byte of each symbol, and the bytes following the payload) must be zero.
+    ///
+    /// Needed when the same payload must be decoded identically by other implementations, e.g. in fraud proofs.
+    Strict,
+}
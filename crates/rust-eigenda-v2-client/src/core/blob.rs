@@ -1,9 +1,15 @@
 use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
 
+use crate::commitment_utils::compute_opening_proof;
 use crate::errors::{BlobError, EigenClientError};
+use crate::srs::Srs;
 use crate::utils::coeff_to_eval_poly;
 
-use crate::core::{EncodedPayload, Payload, PayloadForm, BYTES_PER_SYMBOL};
+use crate::core::{
+    EncodedPayload, OpeningProof, Payload, PayloadDecodeMode, PayloadForm, SymbolLocation,
+    BYTES_PER_SYMBOL, MAX_BLOB_LENGTH_SYMBOLS,
+};
 
 /// [`Blob`] is data that is dispersed on EigenDA.
 ///
@@ -23,7 +29,34 @@ pub struct Blob {
 
 impl Blob {
     /// Initializes a [`Blob`]` from bytes
+    ///
+    /// `blob_length_symbols` usually comes from an untrusted cert, so it must be a power of two no greater than
+    /// [`MAX_BLOB_LENGTH_SYMBOLS`].
     pub fn deserialize_blob(bytes: Vec<u8>, blob_length_symbols: usize) -> Result<Blob, BlobError> {
+        Self::deserialize_blob_with_mode(bytes, blob_length_symbols, PayloadDecodeMode::Lenient)
+    }
+
+    /// Initializes a [`Blob`] from bytes, validating them as specified by `mode`.
+    ///
+    /// Symbols are reduced modulo the bn254 scalar field, so different bytes can deserialize into the same blob. In
+    /// [`PayloadDecodeMode::Strict`] mode, symbols that are not canonical field elements are rejected instead.
+    pub fn deserialize_blob_with_mode(
+        bytes: Vec<u8>,
+        blob_length_symbols: usize,
+        mode: PayloadDecodeMode,
+    ) -> Result<Blob, BlobError> {
+        if blob_length_symbols == 0 {
+            return Err(BlobError::InvalidBlobLengthZero);
+        }
+        if !blob_length_symbols.is_power_of_two() {
+            return Err(BlobError::InvalidBlobLengthNotPowerOfTwo(
+                blob_length_symbols,
+            ));
+        }
+        if blob_length_symbols > MAX_BLOB_LENGTH_SYMBOLS {
+            return Err(BlobError::InvalidBlobLength(blob_length_symbols));
+        }
+
         // we check that length of bytes is <= blob length, rather than checking for equality, because it's possible
         // that the bytes being deserialized have had trailing 0s truncated.
         if bytes.len() > blob_length_symbols * BYTES_PER_SYMBOL {
@@ -33,6 +66,10 @@ impl Blob {
             ));
         }
 
+        if mode == PayloadDecodeMode::Strict {
+            check_canonical_symbols(&bytes)?;
+        }
+
         let coeff_polynomial = rust_kzg_bn254_primitives::helpers::to_fr_array(&bytes);
 
         Ok(Blob {
@@ -54,17 +91,45 @@ impl Blob {
     /// The payload_form indicates how payloads are interpreted. The way that payloads are interpreted dictates what
     /// conversion, if any, must be performed when creating a payload from the blob.
     pub fn to_payload(&self, payload_form: PayloadForm) -> Result<Payload, EigenClientError> {
+        self.to_payload_with_mode(payload_form, PayloadDecodeMode::Lenient)
+    }
+
+    /// Converts the [`Blob`] into a [`Payload`], validating the encoded payload as specified by `mode`.
+    pub fn to_payload_with_mode(
+        &self,
+        payload_form: PayloadForm,
+        mode: PayloadDecodeMode,
+    ) -> Result<Payload, EigenClientError> {
         let encoded_payload = self.to_encoded_payload(payload_form)?;
         encoded_payload
-            .decode()
+            .decode_with_mode(mode)
             .map_err(EigenClientError::Conversion)
     }
 
+    /// Returns the location in the blob of the payload byte at `payload_offset`.
+    pub fn symbol_location(&self, payload_offset: usize) -> Result<SymbolLocation, BlobError> {
+        SymbolLocation::new(payload_offset, self.blob_length_symbols)
+    }
+
+    /// Computes the KZG opening proof of the blob polynomial at `point`, to be checked against the blob commitment.
+    ///
+    /// For payloads in [`PayloadForm::Eval`], opening at the [`SymbolLocation::evaluation_point`] of a symbol proves
+    /// its value. `srs` must hold at least `blob_length_symbols` G1 points.
+    pub fn opening_proof(&self, srs: &Srs, point: Fr) -> Result<OpeningProof, EigenClientError> {
+        let g1_srs = srs.g1_points(self.coeff_polynomial.len())?;
+        let (value, proof) = compute_opening_proof(&g1_srs, &self.coeff_polynomial, point)?;
+        Ok(OpeningProof {
+            point,
+            value,
+            proof,
+        })
+    }
+
     /// Accepts the length of an array that has been padded with pad_payload
     ///
     /// It returns what the length of the output array would be, if you called remove_internal_padding on it.
     fn get_unpadded_data_length(&self, input_len: usize) -> Result<usize, BlobError> {
-        if input_len % BYTES_PER_SYMBOL != 0 {
+        if !input_len.is_multiple_of(BYTES_PER_SYMBOL) {
             return Err(BlobError::InvalidDataLength(input_len));
         }
         let chunck_count = input_len / BYTES_PER_SYMBOL;
@@ -114,11 +179,31 @@ impl Blob {
     }
 }
 
+/// Checks that every symbol of the serialized blob is lower than the bn254 scalar field modulus.
+///
+/// The last symbol may be truncated, in which case it is padded with zeros, as done when deserializing it.
+fn check_canonical_symbols(bytes: &[u8]) -> Result<(), BlobError> {
+    for (index, symbol) in bytes.chunks(BYTES_PER_SYMBOL).enumerate() {
+        let mut padded_symbol = [0u8; BYTES_PER_SYMBOL];
+        padded_symbol[..symbol.len()].copy_from_slice(symbol);
+        let element = Fr::from_be_bytes_mod_order(&padded_symbol);
+        if element.into_bigint().to_bytes_be() != padded_symbol {
+            return Err(BlobError::NonCanonicalSymbol(index * BYTES_PER_SYMBOL));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
 
-    use crate::core::{blob::Blob, payload::Payload, PayloadForm};
+    use crate::{
+        core::{
+            blob::Blob, payload::Payload, PayloadDecodeMode, PayloadForm, MAX_BLOB_LENGTH_SYMBOLS,
+        },
+        errors::BlobError,
+    };
 
     fn blob_conversion_for_form(payload_bytes: Vec<u8>, payload_form: PayloadForm) {
         let blob: Blob = Payload::new(payload_bytes.clone())
@@ -143,11 +228,61 @@ mod tests {
         blob_conversion_for_form(original_data.to_vec(), PayloadForm::Eval);
     }
 
+    #[test]
+    fn test_deserialize_blob_invalid_length() {
+        assert!(Blob::deserialize_blob(vec![], 0).is_err());
+        assert!(Blob::deserialize_blob(vec![], 3).is_err());
+        assert!(Blob::deserialize_blob(vec![], MAX_BLOB_LENGTH_SYMBOLS * 2).is_err());
+        assert!(Blob::deserialize_blob(vec![], usize::MAX).is_err());
+        assert!(Blob::deserialize_blob(vec![0; 33], 1).is_err());
+    }
+
+    /// Checks that strict deserialization rejects symbols that are not reduced modulo the field
+    #[test]
+    fn test_deserialize_non_canonical_blob() {
+        // an empty payload is encoded into a single zero symbol
+        let blob = Payload::new(vec![]).to_blob(PayloadForm::Coeff).unwrap();
+        let mut bytes = blob.serialize();
+        assert!(Blob::deserialize_blob_with_mode(
+            bytes.clone(),
+            blob.blob_length_symbols,
+            PayloadDecodeMode::Strict
+        )
+        .is_ok());
+
+        // the modulus is reduced to zero, so it deserializes into the same blob
+        bytes[..32].copy_from_slice(
+            &hex::decode("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001")
+                .unwrap(),
+        );
+        let lenient = Blob::deserialize_blob(bytes.clone(), blob.blob_length_symbols).unwrap();
+        assert_eq!(lenient, blob);
+        assert!(matches!(
+            Blob::deserialize_blob_with_mode(
+                bytes,
+                blob.blob_length_symbols,
+                PayloadDecodeMode::Strict
+            ),
+            Err(BlobError::NonCanonicalSymbol(0))
+        ));
+    }
+
     proptest! {
 
         #[test]
         fn fuzz_blob_conversion(original_data in prop::collection::vec(any::<u8>(), 0..1000)) {
             test_blob_conversion(&original_data);
         }
+
+        #[test]
+        fn fuzz_deserialize_arbitrary_blob(
+            bytes in prop::collection::vec(any::<u8>(), 0..1000),
+            blob_length_symbols in 0usize..64,
+        ) {
+            if let Ok(blob) = Blob::deserialize_blob(bytes, blob_length_symbols) {
+                let _ = blob.to_payload(PayloadForm::Coeff);
+                let _ = blob.to_payload(PayloadForm::Eval);
+            }
+        }
     }
 }
@@ -15,7 +15,7 @@ use crate::errors::ConversionError;
 ///
 /// Note that two blobs can have the same content but different headers,
 /// so they are allowed to both exist in the system.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct BlobKey([u8; 32]);
 
 impl BlobKey {
@@ -33,6 +33,11 @@ impl BlobKey {
     ///
     /// Note: The hex string should not include the 0x prefix.
     pub fn from_hex(hex: &str) -> Result<Self, ConversionError> {
+        if hex.len() != 64 {
+            return Err(ConversionError::BlobKey(
+                "Invalid hex string length".to_string(),
+            ));
+        }
         let bytes: [u8; 32] = hex::decode(hex)
             .map_err(|_| ConversionError::BlobKey("Invalid hex string".to_string()))?
             .try_into()
@@ -166,6 +171,8 @@ impl BlobKey {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -175,10 +182,32 @@ mod tests {
         assert_eq!(blob_key.to_hex(), hex);
     }
 
+    #[test]
+    fn test_blob_key_from_invalid_hex() {
+        assert!(BlobKey::from_hex("").is_err());
+        assert!(BlobKey::from_hex("1234").is_err());
+        assert!(BlobKey::from_hex(&"zz".repeat(32)).is_err());
+        // multi-byte characters with a valid byte length
+        assert!(BlobKey::from_hex(&"é".repeat(32)).is_err());
+    }
+
     #[test]
     fn test_blob_key_from_bytes() {
         let bytes = [1; 32];
         let blob_key = BlobKey::from_bytes(bytes);
         assert_eq!(blob_key.to_bytes(), bytes);
     }
+
+    proptest! {
+        #[test]
+        fn fuzz_blob_key_hex_round_trip(bytes in any::<[u8; 32]>()) {
+            let blob_key = BlobKey::from_bytes(bytes);
+            prop_assert_eq!(BlobKey::from_hex(&blob_key.to_hex()).unwrap(), blob_key);
+        }
+
+        #[test]
+        fn fuzz_blob_key_from_hex(hex in ".*") {
+            let _ = BlobKey::from_hex(&hex);
+        }
+    }
 }
@@ -18,7 +18,10 @@ use crate::generated::i_cert_verifier::{
 };
 use crate::generated::i_cert_verifier::{G1Point as G1PointContract, G2Point as G2PointContract};
 
-use crate::commitment_utils::{g1_commitment_from_bytes, g2_commitment_from_bytes};
+use crate::commitment_utils::{
+    g1_commitment_from_bytes, g1_commitment_to_bytes, g2_commitment_from_bytes,
+    g2_commitment_to_bytes,
+};
 
 use crate::generated::{
     common::{
@@ -33,7 +36,7 @@ use crate::generated::{
 
 use rust_eigenda_v2_common::{
     BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader, BlobInclusionInfo, EigenDACert,
-    NonSignerStakesAndSignature,
+    EigenDACertV2, EigenDACertVersion, NonSignerStakesAndSignature,
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -77,6 +80,54 @@ impl From<ProtoPaymentHeader> for PaymentHeader {
     }
 }
 
+impl From<PaymentHeader> for ProtoPaymentHeader {
+    fn from(value: PaymentHeader) -> Self {
+        ProtoPaymentHeader {
+            account_id: value.account_id,
+            timestamp: value.timestamp,
+            cumulative_payment: value.cumulative_payment,
+        }
+    }
+}
+
+impl TryFrom<BlobCommitments> for ProtoBlobCommitment {
+    type Error = ConversionError;
+
+    fn try_from(value: BlobCommitments) -> Result<Self, Self::Error> {
+        Ok(Self {
+            commitment: g1_commitment_to_bytes(&value.commitment)?,
+            length_commitment: g2_commitment_to_bytes(&value.length_commitment)?,
+            length_proof: g2_commitment_to_bytes(&value.length_proof)?,
+            length: value.length,
+        })
+    }
+}
+
+/// Builds the proto [`ProtoBlobHeader`] of a [`BlobHeader`], given the [`PaymentHeader`] it was dispersed with.
+///
+/// Fails if the payment header does not hash to the `payment_header_hash` of the blob header.
+pub fn blob_header_to_proto(
+    blob_header: &BlobHeader,
+    payment_header: &PaymentHeader,
+) -> Result<ProtoBlobHeader, ConversionError> {
+    if payment_header.hash()? != blob_header.payment_header_hash {
+        return Err(ConversionError::BlobHeader(
+            "Payment header does not match the payment header hash".to_string(),
+        ));
+    }
+
+    Ok(ProtoBlobHeader {
+        version: blob_header.version as u32,
+        quorum_numbers: blob_header
+            .quorum_numbers
+            .iter()
+            .map(|&quorum| quorum as u32)
+            .collect(),
+        commitment: Some(blob_header.commitment.clone().try_into()?),
+        payment_header: Some(payment_header.clone().into()),
+    })
+}
+
 impl From<BlobCommitments> for BlobCommitmentContract {
     fn from(value: BlobCommitments) -> Self {
         Self {
@@ -387,9 +438,11 @@ impl TryFrom<ProtoAttestation> for Attestation {
     }
 }
 
+/// Builds a cert of the given version from a BlobStatusReply, and NonSignerStakesAndSignature
 pub(crate) fn build_cert_from_reply(
     blob_status_reply: &BlobStatusReply,
     non_signer_stakes_and_signature: NonSignerStakesAndSignature,
+    cert_version: EigenDACertVersion,
 ) -> Result<EigenDACert, EigenClientError> {
     let binding_inclusion_info = BlobInclusionInfo::try_from(
         blob_status_reply
@@ -420,11 +473,15 @@ pub(crate) fn build_cert_from_reply(
         );
     }
 
-    Ok(EigenDACert {
+    let eigenda_cert = EigenDACertV2 {
         blob_inclusion_info: binding_inclusion_info,
         batch_header: binding_batch_header,
         non_signer_stakes_and_signature,
         signed_quorum_numbers,
+    };
+    Ok(match cert_version {
+        EigenDACertVersion::V2 => EigenDACert::V2(eigenda_cert),
+        EigenDACertVersion::V3 => EigenDACert::V3(eigenda_cert.into()),
     })
 }
 
@@ -515,13 +572,14 @@ mod test {
 
     use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
     use ark_ff::{BigInt, Fp2, PrimeField};
+    use proptest::prelude::*;
 
     use crate::{
         cert_verifier::CertVerifier,
         core::{
             eigenda_cert::{
-                build_cert_from_reply, BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader,
-                BlobInclusionInfo, PaymentHeader,
+                blob_header_to_proto, build_cert_from_reply, BatchHeaderV2, BlobCertificate,
+                BlobCommitments, BlobHeader, BlobInclusionInfo, PaymentHeader,
             },
             BlobKey,
         },
@@ -540,7 +598,13 @@ mod test {
         tests::{get_test_holesky_rpc_url, get_test_private_key_signer, CERT_VERIFIER_ADDRESS},
     };
 
-    use super::{BlobStatusReply, EigenDACert, NonSignerStakesAndSignature};
+    use super::{
+        BatchHeaderV2Contract, BlobInclusionInfoContract, BlobStatusReply, EigenDACert,
+        EigenDACertV2, EigenDACertVersion, NonSignerStakesAndSignature,
+        NonSignerStakesAndSignatureContract, U256,
+    };
+    use ethers::abi::{AbiDecode, AbiEncode};
+    use rust_eigenda_v2_common::{EigenDACertError, EigenDACertV3, LEGACY_CERT_PREFIX};
 
     fn get_test_reply() -> (BlobStatusReply, NonSignerStakesAndSignature) {
         let blob_status_reply = BlobStatusReply {
@@ -873,7 +937,7 @@ mod test {
             .into(),
         );
 
-        EigenDACert {
+        EigenDACert::V2(EigenDACertV2 {
             blob_inclusion_info: BlobInclusionInfo {
                 blob_certificate: BlobCertificate {
                     blob_header: BlobHeader {
@@ -919,6 +983,13 @@ mod test {
                 non_signer_stake_indices: vec![vec![28], vec![]],
             },
             signed_quorum_numbers: vec![0, 1],
+        })
+    }
+
+    fn get_test_eigenda_cert_v2() -> EigenDACertV2 {
+        match get_test_eigenda_cert() {
+            EigenDACert::V2(eigenda_cert) => eigenda_cert,
+            EigenDACert::V3(_) => unreachable!("the test cert is a V2 cert"),
         }
     }
 
@@ -926,8 +997,134 @@ mod test {
     fn test_cert_serialization() {
         let cert = get_test_eigenda_cert();
         let cert_bytes = cert.to_bytes().unwrap();
+        assert_eq!(cert_bytes[0], u8::from(EigenDACertVersion::V2));
         let deserialized = EigenDACert::from_bytes(&cert_bytes).unwrap();
         assert_eq!(cert, deserialized);
+
+        let cert_v3 = EigenDACert::V3(get_test_eigenda_cert_v2().into());
+        let cert_bytes = cert_v3.to_bytes().unwrap();
+        assert_eq!(cert_bytes[0], u8::from(EigenDACertVersion::V3));
+        let deserialized = EigenDACert::from_bytes(&cert_bytes).unwrap();
+        assert_eq!(cert_v3, deserialized);
+        assert_eq!(deserialized.batch_header(), cert.batch_header());
+    }
+
+    #[test]
+    fn test_legacy_cert_deserialization() {
+        // certs serialized before versioning are V2 certs without the version byte
+        let cert = get_test_eigenda_cert_v2();
+        let legacy_bytes = cert.to_bytes().unwrap();
+        assert_eq!(EigenDACertV2::from_bytes(&legacy_bytes).unwrap(), cert);
+        assert_eq!(legacy_bytes[0], LEGACY_CERT_PREFIX);
+        assert_eq!(
+            EigenDACert::from_bytes(&legacy_bytes).unwrap(),
+            EigenDACert::V2(cert.clone())
+        );
+        assert_eq!(
+            &EigenDACert::from(cert).to_bytes().unwrap()[1..],
+            legacy_bytes.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_legacy_cert_fixture_deserialization() {
+        // `get_test_eigenda_cert().to_bytes()` at the commit preceding cert versioning
+        let legacy_bytes =
+            hex::decode(include_str!("../../testdata/legacy_eigenda_cert.bincode.hex").trim())
+                .unwrap();
+        assert_eq!(
+            EigenDACert::from_bytes(&legacy_bytes).unwrap(),
+            get_test_eigenda_cert()
+        );
+    }
+
+    #[test]
+    fn test_cert_deserialization_of_unsupported_version() {
+        let mut cert_bytes = get_test_eigenda_cert().to_bytes().unwrap();
+        for version in [3, u8::MAX] {
+            cert_bytes[0] = version;
+            assert!(matches!(
+                EigenDACert::from_bytes(&cert_bytes),
+                Err(EigenDACertError::UnsupportedVersion(v)) if v == version
+            ));
+        }
+    }
+
+    #[test]
+    fn test_abi_encode_cert_v3() {
+        let cert: EigenDACertV3 = get_test_eigenda_cert_v2().into();
+        let encoded = cert.to_abi_bytes();
+        // a struct with dynamic fields is encoded after its offset
+        assert_eq!(U256::from_big_endian(&encoded[..32]), U256::from(32));
+
+        let ((
+            batch_header,
+            blob_inclusion_info,
+            non_signer_stakes_and_signature,
+            signed_quorum_numbers,
+        ),) = <((
+            BatchHeaderV2Contract,
+            BlobInclusionInfoContract,
+            NonSignerStakesAndSignatureContract,
+            ethers::types::Bytes,
+        ),)>::decode(&encoded)
+        .unwrap();
+        assert_eq!(batch_header, cert.batch_header.clone().into());
+        assert_eq!(blob_inclusion_info, cert.blob_inclusion_info.clone().into());
+        assert_eq!(
+            NonSignerStakesAndSignature::try_from(non_signer_stakes_and_signature).unwrap(),
+            cert.non_signer_stakes_and_signature
+        );
+        assert_eq!(signed_quorum_numbers.to_vec(), cert.signed_quorum_numbers);
+
+        // the encoding matches the one of the contract bindings
+        let contract_encoded = ((
+            BatchHeaderV2Contract::from(cert.batch_header.clone()),
+            BlobInclusionInfoContract::from(cert.blob_inclusion_info.clone()),
+            NonSignerStakesAndSignatureContract::from(cert.non_signer_stakes_and_signature.clone()),
+            ethers::types::Bytes::from(cert.signed_quorum_numbers.clone()),
+        ),)
+            .encode();
+        assert_eq!(encoded, contract_encoded);
+        assert_eq!(
+            EigenDACertV3::from_abi_bytes(&contract_encoded).unwrap(),
+            cert
+        );
+    }
+
+    #[test]
+    fn test_cert_deserialization_of_truncated_bytes() {
+        let cert_bytes = get_test_eigenda_cert().to_bytes().unwrap();
+        for length in 0..cert_bytes.len() {
+            assert!(EigenDACert::from_bytes(&cert_bytes[..length]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_cert_deserialization_of_huge_vector_length() {
+        // the quorum numbers length prefix, right after the cert version and the u16 blob version, is far larger
+        // than the input, so it must fail without allocating it
+        let mut cert_bytes = get_test_eigenda_cert().to_bytes().unwrap();
+        cert_bytes[3..11].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(EigenDACert::from_bytes(&cert_bytes).is_err());
+
+        let oversized = vec![0u8; rust_eigenda_v2_common::MAX_CERT_SIZE + 1];
+        assert!(EigenDACert::from_bytes(&oversized).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn fuzz_cert_deserialization(bytes in prop::collection::vec(any::<u8>(), 0..2048)) {
+            let _ = EigenDACert::from_bytes(&bytes);
+        }
+
+        #[test]
+        fn fuzz_cert_deserialization_of_mutated_bytes(index in any::<prop::sample::Index>(), byte in any::<u8>()) {
+            let mut cert_bytes = get_test_eigenda_cert().to_bytes().unwrap();
+            let index = index.index(cert_bytes.len());
+            cert_bytes[index] = byte;
+            let _ = EigenDACert::from_bytes(&cert_bytes);
+        }
     }
 
     #[test]
@@ -1009,12 +1206,43 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_blob_header_to_proto() {
+        let payment_header = PaymentHeader {
+            account_id: "0x0000000000000000000000000000000000000123".to_string(),
+            timestamp: 5,
+            cumulative_payment: num_bigint::BigInt::from(100).to_signed_bytes_be(),
+        };
+        let mut blob_header = get_test_eigenda_cert()
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header
+            .clone();
+        blob_header.payment_header_hash = payment_header.hash().unwrap();
+
+        let proto_blob_header = blob_header_to_proto(&blob_header, &payment_header).unwrap();
+        assert_eq!(
+            BlobHeader::try_from(proto_blob_header).unwrap(),
+            blob_header
+        );
+
+        let other_payment_header = PaymentHeader {
+            timestamp: 6,
+            ..payment_header
+        };
+        assert!(blob_header_to_proto(&blob_header, &other_payment_header).is_err());
+    }
+
     #[ignore = "depends on external RPC"]
     #[tokio::test]
     async fn test_build_eigenda_cert() {
         let (blob_status_reply, non_signer_stakes_and_signature) = get_test_reply();
-        let eigenda_cert =
-            build_cert_from_reply(&blob_status_reply, non_signer_stakes_and_signature).unwrap();
+        let eigenda_cert = build_cert_from_reply(
+            &blob_status_reply,
+            non_signer_stakes_and_signature,
+            EigenDACertVersion::V2,
+        )
+        .unwrap();
 
         let expected_eigenda_cert = get_test_eigenda_cert();
         assert_eq!(expected_eigenda_cert, eigenda_cert);
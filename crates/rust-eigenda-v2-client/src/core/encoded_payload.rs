@@ -1,10 +1,13 @@
 use crate::{
-    core::{Payload, PayloadEncodingVersion, BYTES_PER_SYMBOL},
+    core::{Payload, PayloadDecodeMode, PayloadEncodingVersion, BYTES_PER_SYMBOL, MAX_BLOB_SIZE},
     errors::ConversionError,
 };
 use ark_bn254::Fr;
 use rust_kzg_bn254_primitives::helpers::{to_byte_array, to_fr_array};
 
+/// Size of the encoded payload header, in bytes.
+const HEADER_SIZE: usize = 32;
+
 /// [`EncodedPayload`] represents a payload that has had an encoding applied to it.
 ///
 /// Encoding Format:
@@ -54,17 +57,13 @@ impl EncodedPayload {
 
     /// Decodes the [`EncodedPayload`] back into a [`Payload`].
     pub fn decode(&self) -> Result<Payload, ConversionError> {
-        let expected_data_length = match self.bytes[2..6].try_into() {
-            Ok(arr) => u32::from_be_bytes(arr),
-            Err(_) => {
-                return Err(ConversionError::Payload(
-                    "Invalid header format: couldn't read data length".to_string(),
-                ))
-            }
-        };
+        let expected_data_length =
+            read_payload_length(&self.bytes).ok_or(ConversionError::Payload(
+                "Invalid header format: couldn't read data length".to_string(),
+            ))?;
         // decode raw data modulo bn254
-        let unpadded_data = remove_internal_padding(&self.bytes[32..])?;
-        let unpadded_data_length = unpadded_data.len() as u32;
+        let unpadded_data = remove_internal_padding(&self.bytes[HEADER_SIZE..])?;
+        let unpadded_data_length = unpadded_data.len();
 
         // data length is checked when constructing an encoded payload. If this error is encountered, that means there
         // must be a flaw in the logic at construction time (or someone was bad and didn't use the proper construction methods)
@@ -74,17 +73,83 @@ impl EncodedPayload {
             ));
         }
 
-        if unpadded_data_length > expected_data_length + 31 {
+        if unpadded_data_length > expected_data_length + (BYTES_PER_SYMBOL - 1) {
             return Err(ConversionError::Payload(
                 "Invalid header format: data length is greater than expected".to_string(),
             ));
         }
 
         Ok(Payload::new(
-            unpadded_data[0..expected_data_length as usize].to_vec(),
+            unpadded_data[0..expected_data_length].to_vec(),
         ))
     }
 
+    /// Decodes the [`EncodedPayload`] back into a [`Payload`], validating it as specified by `mode`.
+    ///
+    /// In [`PayloadDecodeMode::Strict`] mode, any byte that [`EncodedPayload::new`] would not have produced is
+    /// rejected with [`ConversionError::NonCanonicalEncodedPayload`], which holds its offset.
+    pub fn decode_with_mode(&self, mode: PayloadDecodeMode) -> Result<Payload, ConversionError> {
+        let payload = self.decode()?;
+        if mode == PayloadDecodeMode::Strict {
+            self.check_canonical()?;
+        }
+        Ok(payload)
+    }
+
+    /// Checks that the header and the padding bytes are the ones written by [`EncodedPayload::new`].
+    ///
+    /// It must only be called on encoded payloads that were successfully decoded.
+    fn check_canonical(&self) -> Result<(), ConversionError> {
+        let payload_length = read_payload_length(&self.bytes).ok_or(ConversionError::Payload(
+            "Invalid header format: couldn't read data length".to_string(),
+        ))?;
+        let non_canonical = |offset: usize, reason: String| {
+            ConversionError::NonCanonicalEncodedPayload(offset, reason)
+        };
+
+        if self.bytes[0] != 0 {
+            return Err(non_canonical(
+                0,
+                format!("reserved header byte is 0x{:02x}", self.bytes[0]),
+            ));
+        }
+        if self.bytes[1] != PayloadEncodingVersion::Zero as u8 {
+            return Err(non_canonical(
+                1,
+                format!("unknown payload encoding version {}", self.bytes[1]),
+            ));
+        }
+        if let Some(offset) = (6..HEADER_SIZE).find(|&offset| self.bytes[offset] != 0) {
+            return Err(non_canonical(
+                offset,
+                format!("reserved header byte is 0x{:02x}", self.bytes[offset]),
+            ));
+        }
+
+        let bytes_per_chunk = BYTES_PER_SYMBOL - 1;
+        for (index, &byte) in self.bytes.iter().enumerate().skip(HEADER_SIZE) {
+            if byte == 0 {
+                continue;
+            }
+            let position_in_symbol = index % BYTES_PER_SYMBOL;
+            if position_in_symbol == 0 {
+                return Err(non_canonical(
+                    index,
+                    format!("symbol padding byte is 0x{:02x}", byte),
+                ));
+            }
+            let data_index =
+                (index - HEADER_SIZE) / BYTES_PER_SYMBOL * bytes_per_chunk + position_in_symbol - 1;
+            if data_index >= payload_length {
+                return Err(non_canonical(
+                    index,
+                    format!("trailing padding byte is 0x{:02x}", byte),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Converts the encoded payload to an array of field elements.
     pub fn to_field_elements(&self) -> Vec<Fr> {
         to_fr_array(&self.bytes)
@@ -92,31 +157,42 @@ impl EncodedPayload {
 
     /// Creates an `EncodedPayload` from an array of field elements.
     /// `max_payload_length` is the maximum length in bytes that the contained [`Payload`] is permitted to be.
+    ///
+    /// The field elements usually come from an untrusted source, so the payload length in the header is checked
+    /// against `max_payload_length` and [`MAX_BLOB_SIZE`] before allocating the encoded payload.
     pub fn from_field_elements(
         field_elements: &[Fr],
         max_payload_length: usize,
     ) -> Result<EncodedPayload, ConversionError> {
+        if field_elements.len() > MAX_BLOB_SIZE / BYTES_PER_SYMBOL {
+            return Err(ConversionError::EncodedPayload(format!(
+                "invalid field elements: {} field elements exceed the maximum blob size",
+                field_elements.len()
+            )));
+        }
         let serialized_felts = to_byte_array(field_elements, usize::MAX);
         // read payload length from the payload header
-        let payload_length = match serialized_felts[2..6].try_into() {
-            Ok(arr) => u32::from_be_bytes(arr),
-            Err(_) => {
-                return Err(ConversionError::EncodedPayload(
-                    "invalid serialized field elements: couldn't read payload length".to_string(),
-                ))
-            }
-        };
+        let payload_length =
+            read_payload_length(&serialized_felts).ok_or(ConversionError::EncodedPayload(
+                "invalid serialized field elements: couldn't read payload length".to_string(),
+            ))?;
 
-        if payload_length > max_payload_length as u32 {
+        if payload_length > max_payload_length {
             return Err(ConversionError::EncodedPayload(
                 "invalid serialized field elements: payload length is greater than maximum allowed"
                     .to_string(),
             ));
         }
 
-        let padded_length = get_padded_data_length(payload_length as usize);
+        let padded_length = get_padded_data_length(payload_length);
         // add 32 to take into account the payload header
-        let encoded_payload_length = padded_length + 32;
+        let encoded_payload_length = padded_length + HEADER_SIZE;
+        if encoded_payload_length > MAX_BLOB_SIZE {
+            return Err(ConversionError::EncodedPayload(format!(
+                "invalid serialized field elements: payload length {} exceeds the maximum blob size",
+                payload_length
+            )));
+        }
 
         let serialized_felts_length = serialized_felts.len();
         let length_to_copy = encoded_payload_length.min(serialized_felts_length);
@@ -152,12 +228,23 @@ impl EncodedPayload {
     }
 }
 
+/// Reads the payload length from bytes 2..6 of the encoded payload header.
+///
+/// Returns `None` if the bytes are too short to hold the header.
+fn read_payload_length(encoded_payload_bytes: &[u8]) -> Option<usize> {
+    if encoded_payload_bytes.len() < HEADER_SIZE {
+        return None;
+    }
+    let length_bytes: [u8; 4] = encoded_payload_bytes[2..6].try_into().ok()?;
+    Some(u32::from_be_bytes(length_bytes) as usize)
+}
+
 /// Accepts an array of padded data, and removes the internal padding.
 ///
 /// This function assumes that the input aligns to 32 bytes. Since it is removing 1 byte for every 31 bytes kept, the
 /// output from this function is not guaranteed to align to 32 bytes.
 fn remove_internal_padding(padded_data: &[u8]) -> Result<Vec<u8>, ConversionError> {
-    if padded_data.len() % BYTES_PER_SYMBOL != 0 {
+    if !padded_data.len().is_multiple_of(BYTES_PER_SYMBOL) {
         return Err(ConversionError::EncodedPayload(format!(
             "padded data (length {}) must be multiple of BYTES_PER_SYMBOL ({})",
             padded_data.len(),
@@ -190,7 +277,7 @@ fn get_padded_data_length(data_length: usize) -> usize {
     let bytes_per_chunk = BYTES_PER_SYMBOL - 1;
     let mut chunk_count = data_length / bytes_per_chunk;
 
-    if data_length % bytes_per_chunk != 0 {
+    if !data_length.is_multiple_of(bytes_per_chunk) {
         chunk_count += 1;
     }
 
@@ -225,8 +312,14 @@ fn pad_to_bn254(data: &[u8]) -> Vec<u8> {
 
 #[cfg(test)]
 mod tests {
-    use crate::core::{encoded_payload::BYTES_PER_SYMBOL, EncodedPayload, Payload};
+    use crate::core::{
+        encoded_payload::{BYTES_PER_SYMBOL, HEADER_SIZE},
+        EncodedPayload, Payload, PayloadDecodeMode, MAX_BLOB_SIZE,
+    };
+    use crate::errors::ConversionError;
+    use proptest::prelude::*;
     use rand::{thread_rng, Rng};
+    use rust_kzg_bn254_primitives::helpers::to_fr_array;
 
     /// Checks that encoding and decoding a payload works correctly.
     #[test]
@@ -349,4 +442,100 @@ mod tests {
         let result2 = EncodedPayload::from_field_elements(&field_elements2, max_payload_length);
         assert!(result2.is_err());
     }
+
+    /// Checks that strict decoding rejects every byte that the encoding would not have produced, with its offset
+    #[test]
+    fn test_strict_decode() {
+        // 40 bytes of payload: 2 data symbols, the second one holding 9 payload bytes and 22 trailing padding bytes
+        let payload = Payload::new(vec![0xff; 40]);
+        let encoded_payload = EncodedPayload::new(&payload).unwrap();
+        assert_eq!(
+            encoded_payload
+                .decode_with_mode(PayloadDecodeMode::Strict)
+                .unwrap(),
+            payload
+        );
+
+        let last_payload_byte = HEADER_SIZE + BYTES_PER_SYMBOL + 9;
+        for offset in [0, 1, 6, 31, 32, 64, last_payload_byte + 1, 95] {
+            let mut bytes = encoded_payload.bytes.clone();
+            bytes[offset] = 1;
+            let tampered = EncodedPayload { bytes };
+
+            // lenient decoding ignores these bytes
+            assert!(tampered
+                .decode_with_mode(PayloadDecodeMode::Lenient)
+                .is_ok());
+            assert!(matches!(
+                tampered.decode_with_mode(PayloadDecodeMode::Strict),
+                Err(ConversionError::NonCanonicalEncodedPayload(error_offset, _)) if error_offset == offset
+            ));
+        }
+
+        let mut bytes = encoded_payload.bytes.clone();
+        bytes[last_payload_byte] = 1;
+        let tampered = EncodedPayload { bytes };
+        assert!(tampered.decode_with_mode(PayloadDecodeMode::Strict).is_ok());
+    }
+
+    /// Checks that encoded payloads too short to hold a header fail at decode, instead of panicking
+    #[test]
+    fn test_decode_missing_header() {
+        for length in [0, 5, HEADER_SIZE - 1] {
+            let encoded_payload = EncodedPayload {
+                bytes: vec![0; length],
+            };
+            assert!(encoded_payload.decode().is_err());
+        }
+        assert!(EncodedPayload::from_field_elements(&[], usize::MAX).is_err());
+    }
+
+    /// Checks that a header claiming a huge payload length is rejected before allocating it
+    #[test]
+    fn test_huge_payload_length() {
+        let mut header = vec![0u8; HEADER_SIZE];
+        header[2..6].copy_from_slice(&u32::MAX.to_be_bytes());
+        let field_elements = to_fr_array(&header);
+        assert!(EncodedPayload::from_field_elements(&field_elements, usize::MAX).is_err());
+
+        let encoded_payload = EncodedPayload { bytes: header };
+        assert!(encoded_payload.decode().is_err());
+
+        let too_many_elements = vec![ark_bn254::Fr::from(0); MAX_BLOB_SIZE / BYTES_PER_SYMBOL + 1];
+        assert!(EncodedPayload::from_field_elements(&too_many_elements, usize::MAX).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn fuzz_encoding_decoding(payload_bytes in prop::collection::vec(any::<u8>(), 0..2000)) {
+            let payload = Payload::new(payload_bytes);
+            let encoded_payload = EncodedPayload::new(&payload).unwrap();
+            prop_assert_eq!(encoded_payload.decode().unwrap(), payload.clone());
+            prop_assert_eq!(
+                encoded_payload.decode_with_mode(PayloadDecodeMode::Strict).unwrap(),
+                payload.clone()
+            );
+
+            let field_elements = encoded_payload.to_field_elements();
+            let from_field_elements =
+                EncodedPayload::from_field_elements(&field_elements, usize::MAX).unwrap();
+            prop_assert_eq!(from_field_elements.decode().unwrap(), payload);
+        }
+
+        #[test]
+        fn fuzz_decode_arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let _ = EncodedPayload { bytes }.decode();
+        }
+
+        #[test]
+        fn fuzz_from_arbitrary_field_elements(
+            bytes in prop::collection::vec(any::<u8>(), 0..512),
+            max_payload_length in 0usize..1024,
+        ) {
+            let field_elements = to_fr_array(&bytes);
+            if let Ok(encoded_payload) = EncodedPayload::from_field_elements(&field_elements, max_payload_length) {
+                let _ = encoded_payload.decode();
+            }
+        }
+    }
 }
@@ -0,0 +1,544 @@
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::{batch_inversion, FftField, Field, PrimeField, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+
+use crate::{
+    commitment_utils::g1_commitment_from_bytes,
+    core::BYTES_PER_SYMBOL,
+    errors::{BlobError, ConversionError},
+};
+
+const BUNDLE_HEADER_SIZE: usize = 8;
+const GNARK_BUNDLE_ENCODING_FORMAT: u8 = 1;
+
+/// Per blob version parameters that dictate how a blob is erasure coded into chunks.
+///
+/// These values are registered on chain in the EigenDAThresholdRegistry (`versionedBlobParams`).
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize)]
+pub struct BlobParams {
+    /// Number of chunks the extended blob is split into.
+    pub num_chunks: u32,
+    /// Ratio between the size of the erasure coded blob and the size of the original blob.
+    pub coding_rate: u32,
+}
+
+impl BlobParams {
+    /// Returns the [`EncodingParams`] used to encode a blob of `blob_length_symbols` symbols.
+    pub fn encoding_params(&self, blob_length_symbols: usize) -> Result<EncodingParams, BlobError> {
+        if blob_length_symbols == 0 {
+            return Err(BlobError::InvalidBlobLengthZero);
+        }
+        if !blob_length_symbols.is_power_of_two() {
+            return Err(BlobError::InvalidBlobLengthNotPowerOfTwo(
+                blob_length_symbols,
+            ));
+        }
+        if self.num_chunks == 0 || !self.num_chunks.is_power_of_two() {
+            return Err(BlobError::InvalidEncodingParams(format!(
+                "number of chunks must be a non-zero power of two, got {}",
+                self.num_chunks
+            )));
+        }
+        if self.coding_rate == 0 || !self.coding_rate.is_power_of_two() {
+            return Err(BlobError::InvalidEncodingParams(format!(
+                "coding rate must be a non-zero power of two, got {}",
+                self.coding_rate
+            )));
+        }
+
+        let chunk_length =
+            (blob_length_symbols * self.coding_rate as usize / self.num_chunks as usize).max(1);
+
+        Ok(EncodingParams {
+            num_chunks: self.num_chunks as usize,
+            chunk_length,
+        })
+    }
+}
+
+/// Parameters of the Reed-Solomon encoding of a single blob.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EncodingParams {
+    /// Number of chunks the extended blob is split into.
+    pub num_chunks: usize,
+    /// Number of symbols in each chunk.
+    pub chunk_length: usize,
+}
+
+impl EncodingParams {
+    /// Total number of evaluations of the extended blob.
+    pub fn num_evaluations(&self) -> usize {
+        self.num_chunks * self.chunk_length
+    }
+
+    /// Returns the index `e` such that the chunk with index `chunk_index` holds the evaluations of the blob
+    /// polynomial over the coset `w^e * <w^num_chunks>`, being `w` the root of unity of order [`Self::num_evaluations`].
+    pub fn leading_coset_index(&self, chunk_index: u32) -> Result<u32, BlobError> {
+        if chunk_index as usize >= self.num_chunks {
+            return Err(BlobError::InvalidChunkIndex(chunk_index));
+        }
+        let bits = self.num_chunks.trailing_zeros();
+        if bits == 0 {
+            return Ok(0);
+        }
+        Ok(chunk_index.reverse_bits() >> (u32::BITS - bits))
+    }
+
+    /// Returns the chunk index and the position within the chunk of the evaluation of the extended blob
+    /// at `w^evaluation_index`.
+    pub fn evaluation_location(&self, evaluation_index: usize) -> Result<(u32, usize), BlobError> {
+        if evaluation_index >= self.num_evaluations() {
+            return Err(BlobError::InvalidEncodingParams(format!(
+                "evaluation index {} out of range",
+                evaluation_index
+            )));
+        }
+        let coset_index = (evaluation_index % self.num_chunks) as u32;
+        // bit reversal is an involution, so the chunk index of a coset is computed as its leading coset index
+        let chunk_index = self.leading_coset_index(coset_index)?;
+        Ok((chunk_index, evaluation_index / self.num_chunks))
+    }
+
+    /// Returns the first element of the coset covered by the chunk with index `chunk_index`.
+    pub(crate) fn coset_leader(&self, chunk_index: u32) -> Result<Fr, BlobError> {
+        let coset_index = self.leading_coset_index(chunk_index)?;
+        Ok(self.domain()?.element(coset_index as usize))
+    }
+
+    pub(crate) fn domain(&self) -> Result<Radix2EvaluationDomain<Fr>, BlobError> {
+        Radix2EvaluationDomain::<Fr>::new(self.num_evaluations()).ok_or(
+            BlobError::InvalidEncodingParams("Failed to construct evaluation domain".to_string()),
+        )
+    }
+
+    pub(crate) fn chunk_domain(&self) -> Result<Radix2EvaluationDomain<Fr>, BlobError> {
+        Radix2EvaluationDomain::<Fr>::new(self.chunk_length).ok_or(
+            BlobError::InvalidEncodingParams("Failed to construct chunk domain".to_string()),
+        )
+    }
+}
+
+/// A [`Frame`] is a chunk of an erasure coded blob, together with the KZG multiproof
+/// that binds it to the blob commitment.
+///
+/// The coefficients are those of the polynomial that interpolates the chunk's evaluations over its coset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub proof: G1Affine,
+    pub coeffs: Vec<Fr>,
+}
+
+impl Frame {
+    /// Deserializes a [`Frame`] encoded in the gnark format: a 32 bytes compressed G1 proof
+    /// followed by `chunk_length` big endian field elements of 32 bytes each.
+    pub fn deserialize_gnark(bytes: &[u8], chunk_length: usize) -> Result<Frame, ConversionError> {
+        let expected_length = chunk_length
+            .checked_add(1)
+            .and_then(|symbols| symbols.checked_mul(BYTES_PER_SYMBOL));
+        if expected_length != Some(bytes.len()) {
+            return Err(ConversionError::Frame(format!(
+                "expected {} symbols, got {} bytes",
+                chunk_length.saturating_add(1),
+                bytes.len()
+            )));
+        }
+
+        let proof = g1_commitment_from_bytes(&bytes[..BYTES_PER_SYMBOL])?;
+        let coeffs = bytes[BYTES_PER_SYMBOL..]
+            .chunks(BYTES_PER_SYMBOL)
+            .map(fr_from_canonical_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Frame { proof, coeffs })
+    }
+
+    /// Deserializes a bundle of frames, as returned by relays: an 8 bytes big endian header, whose most
+    /// significant byte is the encoding format (1 for gnark) and the remaining bytes the chunk length,
+    /// followed by the frames encoded in the gnark format.
+    pub fn deserialize_bundle(bytes: &[u8]) -> Result<Vec<Frame>, ConversionError> {
+        let header: [u8; BUNDLE_HEADER_SIZE] = bytes
+            .get(..BUNDLE_HEADER_SIZE)
+            .and_then(|header| header.try_into().ok())
+            .ok_or(ConversionError::Frame(
+                "bundle header is missing".to_string(),
+            ))?;
+        let header = u64::from_be_bytes(header);
+        let encoding_format = (header >> 56) as u8;
+        if encoding_format != GNARK_BUNDLE_ENCODING_FORMAT {
+            return Err(ConversionError::Frame(format!(
+                "unsupported bundle encoding format {}",
+                encoding_format
+            )));
+        }
+        let chunk_length = (header & 0x00ff_ffff_ffff_ffff) as usize;
+        let frame_size = chunk_length
+            .checked_add(1)
+            .and_then(|symbols| symbols.checked_mul(BYTES_PER_SYMBOL))
+            .ok_or(ConversionError::Frame(format!(
+                "invalid chunk length {}",
+                chunk_length
+            )))?;
+
+        let frames_bytes = &bytes[BUNDLE_HEADER_SIZE..];
+        if !frames_bytes.len().is_multiple_of(frame_size) {
+            return Err(ConversionError::Frame(format!(
+                "bundle length ({}) is not a multiple of the frame size ({})",
+                frames_bytes.len(),
+                frame_size
+            )));
+        }
+
+        frames_bytes
+            .chunks(frame_size)
+            .map(|frame_bytes| Frame::deserialize_gnark(frame_bytes, chunk_length))
+            .collect()
+    }
+
+    /// Evaluates the frame's interpolation polynomial over the coset `coset_leader * <chunk_domain>`.
+    pub(crate) fn evaluations_on_coset(
+        &self,
+        coset_leader: Fr,
+        chunk_domain: &Radix2EvaluationDomain<Fr>,
+    ) -> Vec<Fr> {
+        // multiply each coefficient by coset_leader^i, so that the FFT over the chunk domain results
+        // in the evaluations over the coset
+        let mut shift = Fr::ONE;
+        let shifted_coeffs: Vec<Fr> = self
+            .coeffs
+            .iter()
+            .map(|coeff| {
+                let shifted = *coeff * shift;
+                shift *= coset_leader;
+                shifted
+            })
+            .collect();
+        chunk_domain.fft(&shifted_coeffs)
+    }
+}
+
+/// Parses a big endian field element, rejecting values that are not reduced modulo the field order.
+fn fr_from_canonical_bytes(bytes: &[u8]) -> Result<Fr, ConversionError> {
+    let element = Fr::from_be_bytes_mod_order(bytes);
+    let mut canonical = vec![0u8; BYTES_PER_SYMBOL];
+    let element_bytes = ark_ff::BigInteger::to_bytes_be(&element.into_bigint());
+    canonical[BYTES_PER_SYMBOL - element_bytes.len()..].copy_from_slice(&element_bytes);
+    if canonical != bytes {
+        return Err(ConversionError::Frame(
+            "field element is not in canonical form".to_string(),
+        ));
+    }
+    Ok(element)
+}
+
+/// Reconstructs the coefficients of a blob from a set of (already verified) frames, indexed by chunk index.
+///
+/// At least `blob_length_symbols / chunk_length` distinct frames are needed. If some chunks are missing, the
+/// missing evaluations are recovered using the vanishing polynomial of the missing cosets.
+pub(crate) fn recover_blob_coefficients(
+    frames: &[(u32, Frame)],
+    params: &EncodingParams,
+    blob_length_symbols: usize,
+) -> Result<Vec<Fr>, BlobError> {
+    let domain = params.domain()?;
+    let chunk_domain = params.chunk_domain()?;
+    let num_evaluations = params.num_evaluations();
+
+    let mut samples: Vec<Option<Fr>> = vec![None; num_evaluations];
+    let mut received_cosets = vec![false; params.num_chunks];
+    for (chunk_index, frame) in frames {
+        if frame.coeffs.len() != params.chunk_length {
+            return Err(BlobError::InvalidChunkLength(
+                frame.coeffs.len(),
+                params.chunk_length,
+            ));
+        }
+        let coset_index = params.leading_coset_index(*chunk_index)? as usize;
+        let evaluations = frame.evaluations_on_coset(domain.element(coset_index), &chunk_domain);
+        for (j, evaluation) in evaluations.into_iter().enumerate() {
+            samples[j * params.num_chunks + coset_index] = Some(evaluation);
+        }
+        received_cosets[coset_index] = true;
+    }
+
+    let received = received_cosets.iter().filter(|received| **received).count();
+    if received * params.chunk_length < blob_length_symbols {
+        return Err(BlobError::NotEnoughChunks(
+            received,
+            blob_length_symbols.div_ceil(params.chunk_length),
+        ));
+    }
+
+    let coefficients = if received == params.num_chunks {
+        let evaluations: Vec<Fr> = samples.into_iter().map(Option::unwrap_or_default).collect();
+        domain.ifft(&evaluations)
+    } else {
+        recover_from_samples(&samples, &received_cosets, params, &domain)?
+    };
+
+    // an honestly encoded blob is a polynomial of degree lower than the blob length
+    if coefficients[blob_length_symbols.min(num_evaluations)..]
+        .iter()
+        .any(|coeff| !coeff.is_zero())
+    {
+        return Err(BlobError::InconsistentChunks);
+    }
+
+    Ok(coefficients[..blob_length_symbols.min(num_evaluations)].to_vec())
+}
+
+/// Recovers the coefficients of the polynomial P when only some of its evaluations are known.
+///
+/// With Z the polynomial vanishing over the missing cosets, E·Z = P·Z holds over the whole domain
+/// (E being the samples with zeros in place of the missing values). P is then obtained by dividing
+/// P·Z by Z over a disjoint coset, where Z has no roots.
+fn recover_from_samples(
+    samples: &[Option<Fr>],
+    received_cosets: &[bool],
+    params: &EncodingParams,
+    domain: &Radix2EvaluationDomain<Fr>,
+) -> Result<Vec<Fr>, BlobError> {
+    let num_evaluations = params.num_evaluations();
+
+    // The vanishing polynomial of the coset w^e * <w^num_chunks> is X^chunk_length - w^(e * chunk_length),
+    // so Z is a polynomial in Y = X^chunk_length.
+    let mut zero_poly_y = vec![Fr::ONE];
+    for (coset_index, _) in received_cosets
+        .iter()
+        .enumerate()
+        .filter(|(_, received)| !**received)
+    {
+        let root = domain
+            .element(coset_index)
+            .pow([params.chunk_length as u64]);
+        let mut next = vec![Fr::zero(); zero_poly_y.len() + 1];
+        for (i, coeff) in zero_poly_y.iter().enumerate() {
+            next[i + 1] += coeff;
+            next[i] -= *coeff * root;
+        }
+        zero_poly_y = next;
+    }
+
+    let mut zero_poly = vec![Fr::zero(); num_evaluations];
+    for (i, coeff) in zero_poly_y.into_iter().enumerate() {
+        zero_poly[i * params.chunk_length] = coeff;
+    }
+
+    let zero_poly_evaluations = domain.fft(&zero_poly);
+    let poly_times_zero_evaluations: Vec<Fr> = samples
+        .iter()
+        .zip(zero_poly_evaluations)
+        .map(|(sample, zero_eval)| sample.unwrap_or_default() * zero_eval)
+        .collect();
+    let poly_times_zero = domain.ifft(&poly_times_zero_evaluations);
+
+    let coset = domain
+        .get_coset(Fr::GENERATOR)
+        .ok_or(BlobError::InvalidEncodingParams(
+            "Failed to construct coset domain".to_string(),
+        ))?;
+    let poly_times_zero_on_coset = coset.fft(&poly_times_zero);
+    let mut zero_poly_on_coset = coset.fft(&zero_poly);
+    batch_inversion(&mut zero_poly_on_coset);
+
+    let poly_on_coset: Vec<Fr> = poly_times_zero_on_coset
+        .into_iter()
+        .zip(zero_poly_on_coset)
+        .map(|(numerator, inverse_denominator)| numerator * inverse_denominator)
+        .collect();
+
+    Ok(coset.ifft(&poly_on_coset))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    /// Splits the coefficients of a blob into frames (without proofs), as an EigenDA encoder would.
+    pub(crate) fn encode_frames(coeffs: &[Fr], params: &EncodingParams) -> Vec<(u32, Frame)> {
+        let domain = params.domain().unwrap();
+        let chunk_domain = params.chunk_domain().unwrap();
+        let evaluations = domain.fft(coeffs);
+
+        (0..params.num_chunks as u32)
+            .map(|chunk_index| {
+                let coset_index = params.leading_coset_index(chunk_index).unwrap() as usize;
+                let coset_evaluations: Vec<Fr> = (0..params.chunk_length)
+                    .map(|j| evaluations[j * params.num_chunks + coset_index])
+                    .collect();
+                let coset_leader_inverse = domain.element(coset_index).inverse().unwrap();
+                let mut shift = Fr::ONE;
+                let coeffs = chunk_domain
+                    .ifft(&coset_evaluations)
+                    .into_iter()
+                    .map(|coeff| {
+                        let unshifted = coeff * shift;
+                        shift *= coset_leader_inverse;
+                        unshifted
+                    })
+                    .collect();
+                (
+                    chunk_index,
+                    Frame {
+                        proof: G1Affine::identity(),
+                        coeffs,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn random_coeffs(length: usize) -> Vec<Fr> {
+        let mut rng = StdRng::seed_from_u64(42);
+        (0..length).map(|_| Fr::rand(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_leading_coset_index() {
+        let params = EncodingParams {
+            num_chunks: 8,
+            chunk_length: 4,
+        };
+        let coset_indices: Vec<u32> = (0..8)
+            .map(|i| params.leading_coset_index(i).unwrap())
+            .collect();
+        assert_eq!(coset_indices, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+        assert!(params.leading_coset_index(8).is_err());
+    }
+
+    #[test]
+    fn test_recover_from_all_chunks() {
+        let params = BlobParams {
+            num_chunks: 8,
+            coding_rate: 4,
+        }
+        .encoding_params(16)
+        .unwrap();
+        let coeffs = random_coeffs(16);
+        let frames = encode_frames(&coeffs, &params);
+
+        let recovered = recover_blob_coefficients(&frames, &params, 16).unwrap();
+        assert_eq!(recovered, coeffs);
+    }
+
+    #[test]
+    fn test_recover_from_minimum_chunks() {
+        let params = BlobParams {
+            num_chunks: 8,
+            coding_rate: 4,
+        }
+        .encoding_params(16)
+        .unwrap();
+        let coeffs = random_coeffs(16);
+        let frames = encode_frames(&coeffs, &params);
+
+        // keep only the last 2 chunks, which hold exactly 16 evaluations
+        let recovered = recover_blob_coefficients(&frames[6..], &params, 16).unwrap();
+        assert_eq!(recovered, coeffs);
+    }
+
+    #[test]
+    fn test_recover_not_enough_chunks() {
+        let params = BlobParams {
+            num_chunks: 8,
+            coding_rate: 4,
+        }
+        .encoding_params(16)
+        .unwrap();
+        let coeffs = random_coeffs(16);
+        let frames = encode_frames(&coeffs, &params);
+
+        let result = recover_blob_coefficients(&frames[..1], &params, 16);
+        assert!(matches!(result, Err(BlobError::NotEnoughChunks(1, 2))));
+    }
+
+    #[test]
+    fn test_recover_inconsistent_chunks() {
+        let params = BlobParams {
+            num_chunks: 8,
+            coding_rate: 4,
+        }
+        .encoding_params(16)
+        .unwrap();
+        let coeffs = random_coeffs(16);
+        let mut frames = encode_frames(&coeffs, &params);
+        frames[0].1.coeffs[0] += Fr::ONE;
+
+        let result = recover_blob_coefficients(&frames, &params, 16);
+        assert!(matches!(result, Err(BlobError::InconsistentChunks)));
+    }
+
+    #[test]
+    fn test_evaluation_location() {
+        let params = BlobParams {
+            num_chunks: 8,
+            coding_rate: 4,
+        }
+        .encoding_params(16)
+        .unwrap();
+        let coeffs = random_coeffs(16);
+        let evaluations = params.domain().unwrap().fft(&coeffs);
+        let frames = encode_frames(&coeffs, &params);
+        let chunk_domain = params.chunk_domain().unwrap();
+
+        for (evaluation_index, evaluation) in evaluations.iter().enumerate() {
+            let (chunk_index, position) = params.evaluation_location(evaluation_index).unwrap();
+            let coset_leader = params.coset_leader(chunk_index).unwrap();
+            let frame = &frames[chunk_index as usize].1;
+            assert_eq!(
+                frame.evaluations_on_coset(coset_leader, &chunk_domain)[position],
+                *evaluation
+            );
+        }
+        assert!(params.evaluation_location(64).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_bundle() {
+        let mut frame_bytes =
+            hex::decode("8fe9346938e40204330aea61243eb8c4c9b9ea0d41167909e9cae449966229cc")
+                .unwrap();
+        frame_bytes.extend_from_slice(&[0u8; 31]);
+        frame_bytes.push(7);
+
+        let mut bundle = ((1u64 << 56) | 1).to_be_bytes().to_vec();
+        bundle.extend_from_slice(&frame_bytes);
+        bundle.extend_from_slice(&frame_bytes);
+
+        let frames = Frame::deserialize_bundle(&bundle).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].coeffs, vec![Fr::from(7)]);
+
+        // truncated bundle
+        assert!(Frame::deserialize_bundle(&bundle[..bundle.len() - 1]).is_err());
+        // unknown encoding format
+        bundle[0] = 2;
+        assert!(Frame::deserialize_bundle(&bundle).is_err());
+        // missing header
+        assert!(Frame::deserialize_bundle(&[1, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_gnark_frame() {
+        let mut bytes =
+            hex::decode("8fe9346938e40204330aea61243eb8c4c9b9ea0d41167909e9cae449966229cc")
+                .unwrap();
+        bytes.extend_from_slice(&[0u8; 31]);
+        bytes.push(7);
+
+        let frame = Frame::deserialize_gnark(&bytes, 1).unwrap();
+        assert_eq!(frame.coeffs, vec![Fr::from(7)]);
+
+        // modulus of the scalar field is not a canonical element
+        let mut non_canonical = bytes[..32].to_vec();
+        non_canonical.extend_from_slice(
+            &hex::decode("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001")
+                .unwrap(),
+        );
+        assert!(Frame::deserialize_gnark(&non_canonical, 1).is_err());
+
+        assert!(Frame::deserialize_gnark(&bytes, 2).is_err());
+    }
+}
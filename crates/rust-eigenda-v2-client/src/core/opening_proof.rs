@@ -0,0 +1,145 @@
+use ark_bn254::{Fr, G1Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ethabi::Token;
+use ethereum_types::U256;
+
+use crate::{
+    commitment_utils::verify_opening_proof,
+    core::BYTES_PER_SYMBOL,
+    errors::{BlobError, ConversionError, EigenClientError},
+    srs::Srs,
+};
+
+/// Location of a payload byte within a blob.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymbolLocation {
+    /// Index of the encoded payload symbol holding the byte. Symbol 0 is the encoded payload header.
+    pub symbol_index: usize,
+    /// Position of the byte within the symbol. The first byte of each symbol is padding, so it is never 0.
+    pub byte_index: usize,
+    /// Point at which the blob polynomial evaluates to the symbol, `v^symbol_index`, being `v` the root of unity of
+    /// order `blob_length_symbols`.
+    ///
+    /// Only payloads in [`PayloadForm::Eval`](crate::core::PayloadForm::Eval) are evaluations of the blob
+    /// polynomial: in coefficient form, the symbol is a coefficient and can't be opened at a single point.
+    pub evaluation_point: Fr,
+}
+
+impl SymbolLocation {
+    /// Returns the location of the payload byte at `payload_offset`, in a blob of `blob_length_symbols` symbols.
+    pub fn new(payload_offset: usize, blob_length_symbols: usize) -> Result<Self, BlobError> {
+        // the first symbol is the encoded payload header, and each of the following holds 31 bytes of the payload
+        let symbol_index = payload_offset / (BYTES_PER_SYMBOL - 1) + 1;
+        if symbol_index >= blob_length_symbols {
+            return Err(BlobError::InvalidPayloadOffset(payload_offset));
+        }
+        let domain = GeneralEvaluationDomain::<Fr>::new(blob_length_symbols).ok_or(
+            BlobError::InvalidBlobLengthNotPowerOfTwo(blob_length_symbols),
+        )?;
+
+        Ok(Self {
+            symbol_index,
+            byte_index: payload_offset % (BYTES_PER_SYMBOL - 1) + 1,
+            evaluation_point: domain.element(symbol_index),
+        })
+    }
+}
+
+/// KZG opening proof that a blob polynomial `p` evaluates to `value` at `point`.
+///
+/// The proof is `[q(s)]_1`, being `q(X) = (p(X) - value) / (X - point)`, and is checked against the blob commitment
+/// (the G1 commitment of the cert).
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpeningProof {
+    pub point: Fr,
+    pub value: Fr,
+    pub proof: G1Affine,
+}
+
+impl OpeningProof {
+    /// Verifies the proof against the blob commitment. Needs the G2 point `[s]_2`, the first of the
+    /// `g2_power_of_2` points of the SRS.
+    pub fn verify(&self, srs: &Srs, commitment: G1Affine) -> Result<bool, EigenClientError> {
+        let g2_tau = srs.g2_tau_pow(1).ok_or(ConversionError::SrsFile(
+            "g2_power_of_2 points are required to verify opening proofs".to_string(),
+        ))?;
+        Ok(verify_opening_proof(
+            g2_tau, commitment, self.point, self.value, self.proof,
+        ))
+    }
+
+    /// Returns the ABI tokens of `(BN254.G1Point commitment, uint256 z, uint256 y, BN254.G1Point proof)`, the
+    /// arguments of a point evaluation check against `commitment`.
+    pub fn to_tokens(&self, commitment: &G1Affine) -> Vec<Token> {
+        vec![
+            g1_point_token(commitment),
+            Token::Uint(fr_to_u256(&self.point)),
+            Token::Uint(fr_to_u256(&self.value)),
+            g1_point_token(&self.proof),
+        ]
+    }
+
+    /// ABI-encodes the proof along with the commitment it opens, as described in [`OpeningProof::to_tokens`].
+    pub fn abi_encode(&self, commitment: &G1Affine) -> Vec<u8> {
+        ethabi::encode(&self.to_tokens(commitment))
+    }
+}
+
+fn fr_to_u256(element: &Fr) -> U256 {
+    U256::from_big_endian(&element.into_bigint().to_bytes_be())
+}
+
+fn g1_point_token(point: &G1Affine) -> Token {
+    Token::Tuple(vec![
+        Token::Uint(U256::from_big_endian(&point.x.into_bigint().to_bytes_be())),
+        Token::Uint(U256::from_big_endian(&point.y.into_bigint().to_bytes_be())),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ec::AffineRepr;
+    use ark_ff::Field;
+
+    use super::*;
+
+    #[test]
+    fn test_symbol_location() {
+        let location = SymbolLocation::new(0, 8).unwrap();
+        assert_eq!(location.symbol_index, 1);
+        assert_eq!(location.byte_index, 1);
+
+        let location = SymbolLocation::new(61, 8).unwrap();
+        assert_eq!(location.symbol_index, 2);
+        assert_eq!(location.byte_index, 31);
+
+        let location = SymbolLocation::new(62, 8).unwrap();
+        assert_eq!(location.symbol_index, 3);
+        assert_eq!(location.byte_index, 1);
+        // the evaluation point is a root of unity of order 8
+        assert_eq!(location.evaluation_point.pow([8]), Fr::ONE);
+        assert_ne!(location.evaluation_point.pow([4]), Fr::ONE);
+
+        assert!(SymbolLocation::new(7 * 31 - 1, 8).is_ok());
+        assert!(SymbolLocation::new(7 * 31, 8).is_err());
+        assert!(SymbolLocation::new(usize::MAX, 8).is_err());
+        assert!(SymbolLocation::new(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_abi_encode() {
+        let proof = OpeningProof {
+            point: Fr::from(2),
+            value: Fr::from(3),
+            proof: G1Affine::generator(),
+        };
+        let encoded = proof.abi_encode(&G1Affine::generator());
+        // static tuples are encoded in place: 6 words
+        assert_eq!(encoded.len(), 6 * 32);
+        assert_eq!(encoded[31], 1); // commitment X
+        assert_eq!(encoded[63], 2); // commitment Y
+        assert_eq!(encoded[95], 2); // z
+        assert_eq!(encoded[127], 3); // y
+    }
+}
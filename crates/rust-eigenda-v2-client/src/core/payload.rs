@@ -1,9 +1,9 @@
-use crate::core::{Blob, EncodedPayload, PayloadForm};
-use crate::errors::ConversionError;
+use crate::core::{Blob, EncodedPayload, PayloadForm, SymbolLocation, BYTES_PER_SYMBOL};
+use crate::errors::{BlobError, ConversionError};
 use crate::utils::eval_to_coeff_poly;
 
 /// Payload represents arbitrary user data, without any processing.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Payload {
     bytes: Vec<u8>,
 }
@@ -43,6 +43,17 @@ impl Payload {
         })
     }
 
+    /// Returns the location of the byte at `offset` in the blob the payload is converted into by
+    /// [`Payload::to_blob`].
+    pub fn symbol_location(&self, offset: usize) -> Result<SymbolLocation, BlobError> {
+        if offset >= self.bytes.len() {
+            return Err(BlobError::InvalidPayloadOffset(offset));
+        }
+        // the encoded payload header, followed by 31 bytes of the payload per symbol
+        let symbol_count = 1 + self.bytes.len().div_ceil(BYTES_PER_SYMBOL - 1);
+        SymbolLocation::new(offset, symbol_count.next_power_of_two())
+    }
+
     /// Returns the bytes that underlie the payload, i.e. the unprocessed user data.
     pub fn serialize(&self) -> Vec<u8> {
         self.bytes.clone()
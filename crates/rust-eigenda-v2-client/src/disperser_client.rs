@@ -8,6 +8,7 @@ use rust_eigenda_signers::{Message, Sign};
 use rust_eigenda_v2_common::{BlobCommitments, BlobHeader};
 use tokio::sync::Mutex;
 use tonic::transport::{Channel, ClientTlsConfig};
+use tracing::{debug, debug_span, field, info, instrument, Instrument};
 
 use crate::accountant::Accountant;
 use crate::core::eigenda_cert::PaymentHeader;
@@ -65,6 +66,7 @@ pub struct DisperserClient<S = PrivateKeySigner> {
 
 impl<S> DisperserClient<S> {
     /// Creates a new disperser client from a configuration.
+    #[instrument(skip_all, fields(disperser_rpc = %config.disperser_rpc))]
     pub async fn new(config: DisperserClientConfig<S>) -> Result<Self, DisperseError>
     where
         S: Sign,
@@ -97,6 +99,16 @@ impl<S> DisperserClient<S> {
     }
 
     /// Disperse a sequence of bytes to the disperser.
+    #[instrument(
+        skip_all,
+        fields(
+            blob_version,
+            quorums = ?quorums,
+            size = data.len(),
+            symbol_count = field::Empty,
+            blob_key = field::Empty
+        )
+    )]
     pub async fn disperse_blob(
         &self,
         data: &[u8],
@@ -111,16 +123,20 @@ impl<S> DisperserClient<S> {
         }
 
         let symbol_length = data.len().div_ceil(BYTES_PER_SYMBOL).next_power_of_two();
-        let payment = self
-            .accountant
-            .lock()
-            .await
-            .account_blob(
-                SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as i64,
-                symbol_length as u64,
-                quorums,
-            )
-            .map_err(DisperseError::Accountant)?;
+        tracing::Span::current().record("symbol_count", symbol_length);
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as i64;
+        let payment = {
+            let mut accountant = self.accountant.lock().await;
+            debug_span!("account_blob").in_scope(|| {
+                accountant
+                    .account_blob(timestamp, symbol_length as u64, quorums)
+                    .map_err(DisperseError::Accountant)
+            })?
+        };
+        debug!(
+            cumulative_payment = %payment.cumulative_payment,
+            "accounted blob"
+        );
 
         let blob_commitment_reply = self.blob_commitment(data).await?;
         let Some(blob_commitments) = blob_commitment_reply.blob_commitment else {
@@ -153,9 +169,11 @@ impl<S> DisperserClient<S> {
         };
 
         let blob_key = BlobKey::compute_blob_key(&blob_header)?;
+        tracing::Span::current().record("blob_key", field::display(blob_key.to_hex()));
         let signature = self
             .signer
             .sign_digest(&Message::new(blob_key.to_bytes()))
+            .instrument(debug_span!("sign_blob_key"))
             .await
             .map_err(|e| DisperseError::Signer(Box::new(e)))?
             .to_bytes()
@@ -176,26 +194,29 @@ impl<S> DisperserClient<S> {
             signature,
         };
 
-        let reply = self
-            .rpc_client
-            .lock()
-            .await
-            .disperse_blob(disperse_request)
-            .await
-            .map(|response| response.into_inner())
-            .map_err(DisperseError::FailedRPC)?;
+        let reply = async {
+            self.rpc_client
+                .lock()
+                .await
+                .disperse_blob(disperse_request)
+                .await
+                .map(|response| response.into_inner())
+                .map_err(DisperseError::FailedRPC)
+        }
+        .instrument(debug_span!("disperse_blob_rpc"))
+        .await?;
 
         if BlobKey::compute_blob_key(&blob_header)?.to_bytes().to_vec() != reply.blob_key {
             return Err(DisperseError::BlobKeyMismatch);
         }
 
-        Ok((
-            BlobStatus::try_from(reply.result)?,
-            BlobKey::compute_blob_key(&blob_header)?,
-        ))
+        let blob_status = BlobStatus::try_from(reply.result)?;
+        info!(status = ?blob_status, "dispersed blob");
+        Ok((blob_status, BlobKey::compute_blob_key(&blob_header)?))
     }
 
     /// Populates the accountant with the payment state from the disperser.
+    #[instrument(skip_all)]
     async fn populate_accountant(&mut self) -> Result<(), DisperseError>
     where
         S: Sign,
@@ -206,10 +227,16 @@ impl<S> DisperserClient<S> {
             .await
             .set_payment_state(&payment_state)
             .map_err(DisperseError::Accountant)?;
+        debug!(
+            reservation = payment_state.reservation.is_some(),
+            period_records = payment_state.period_records.len(),
+            "synced payment state"
+        );
         Ok(())
     }
 
     /// Returns the status of a blob with the given blob key.
+    #[instrument(skip_all, fields(blob_key = %blob_key.to_hex()))]
     pub async fn blob_status(&self, blob_key: &BlobKey) -> Result<BlobStatusReply, DisperseError> {
         let request = BlobStatusRequest {
             blob_key: blob_key.to_bytes().to_vec(),
@@ -224,8 +251,10 @@ impl<S> DisperserClient<S> {
             .map_err(DisperseError::FailedRPC)
     }
 
-    /// Returns the payment state of the disperser client
-    pub(crate) async fn payment_state(&mut self) -> Result<GetPaymentStateReply, DisperseError>
+    /// Returns the payment state of the account of the signer: its reservation, its on-demand payments and the
+    /// global payment parameters of the disperser.
+    #[instrument(skip_all, fields(account = ?self.signer.public_key().address()))]
+    pub async fn payment_state(&self) -> Result<GetPaymentStateReply, DisperseError>
     where
         S: Sign,
     {
@@ -256,6 +285,7 @@ impl<S> DisperserClient<S> {
             .map_err(DisperseError::FailedRPC)
     }
 
+    #[instrument(skip_all, fields(size = data.len()))]
     pub async fn blob_commitment(&self, data: &[u8]) -> Result<BlobCommitmentReply, DisperseError> {
         let request = BlobCommitmentRequest {
             blob: data.to_vec(),
@@ -1,5 +1,5 @@
 use ark_bn254::{Fr, G1Affine};
-use ethereum_types::H160;
+use ethereum_types::{H160, U256};
 use ethers::signers::WalletError;
 use rust_kzg_bn254_primitives::errors::KzgError;
 
@@ -24,6 +24,8 @@ pub enum ConversionError {
     Payload(String),
     #[error("Failed to parse encoded payload: {0}")]
     EncodedPayload(String),
+    #[error("Non-canonical encoded payload at byte {0}: {1}")]
+    NonCanonicalEncodedPayload(usize, String),
     #[error("Failed to convert polynomial: {0}")]
     Poly(String),
     #[error("Failed to parse G1 point: {0}")]
@@ -54,6 +56,12 @@ pub enum ConversionError {
     EigenDACert(#[from] rust_eigenda_v2_common::ConversionError),
     #[error("Failed to convert U256: {0}")]
     U256Conversion(String),
+    #[error("Failed to parse frame: {0}")]
+    Frame(String),
+    #[error("Failed to load SRS points: {0}")]
+    SrsFile(String),
+    #[error(transparent)]
+    Srs(#[from] rust_eigenda_srs::SrsError),
 }
 
 /// Errors specific to the [`RelayPayloadRetriever`].
@@ -73,6 +81,120 @@ pub enum RelayPayloadRetrieverError {
     InvalidCertificate(String),
     #[error("Retrieval request to relay timed out")]
     RetrievalTimeout,
+    #[error("Validator fallback retrieval failed: {0}")]
+    ValidatorRetrieval(#[from] ValidatorPayloadRetrieverError),
+    #[error("No blob params configured for blob version {0}")]
+    UnknownBlobVersion(u16),
+    #[error("G2 SRS point for chunk length {0} is not loaded")]
+    MissingG2PowerOf2Point(usize),
+    #[error("Invalid payload range: {0}")]
+    InvalidRange(String),
+    #[error(transparent)]
+    BlobCommitments(#[from] BlobCommitmentsError),
+    /// The cert was rejected by the EigenDACertVerifier contract, or its inclusion proof is not valid. Retrying will
+    /// not help, the cert must be dropped.
+    #[error("Invalid certificate: {0}")]
+    CertVerificationFailed(CertVerificationFailure),
+    /// The cert could not be verified on chain (e.g. the RPC is unavailable). The retrieval may be retried.
+    #[error("Unable to verify certificate on chain: {0}")]
+    CertVerifier(CertVerifierError),
+    #[error("No cert verifier set to verify the certificate on chain")]
+    MissingCertVerifier,
+}
+
+/// Errors specific to the [`RetrieverPayloadRetriever`].
+#[derive(Debug, thiserror::Error)]
+pub enum RetrieverPayloadRetrieverError {
+    #[error("Max grpc message size must be greater than 0")]
+    InvalidMaxGrpcMessageSize,
+    #[error("Invalid retriever URI: {0}")]
+    InvalidURI(String),
+    #[error("Failed RPC call: {0}")]
+    FailedRPC(#[from] tonic::Status),
+    #[error("Failed connection call")]
+    FailedConnection(#[from] tonic::transport::Error),
+    #[error(transparent)]
+    Blob(#[from] BlobError),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error(transparent)]
+    Kzg(#[from] KzgError),
+    #[error("Unable to retrieve payload")]
+    UnableToRetrievePayload,
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[error("Retrieval request to retriever timed out")]
+    RetrievalTimeout,
+    #[error(transparent)]
+    BlobCommitments(#[from] BlobCommitmentsError),
+}
+
+/// Errors of the checks of the blob commitments of a cert, which make its blob length trustworthy.
+#[derive(Debug, thiserror::Error)]
+pub enum BlobCommitmentsError {
+    #[error("Length commitment does not match the blob commitment")]
+    LengthCommitmentMismatch,
+    #[error("Length proof is not valid for blob length {0}")]
+    InvalidLengthProof(u32),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+}
+
+/// Errors specific to the [`PayloadCache`] implementations.
+#[derive(Debug, thiserror::Error)]
+pub enum RetrievalCacheError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Cache lock is poisoned")]
+    Poisoned,
+}
+
+/// Errors specific to the [`ValidatorPayloadRetriever`].
+#[derive(Debug, thiserror::Error)]
+pub enum ValidatorPayloadRetrieverError {
+    #[error(transparent)]
+    ValidatorClient(#[from] ValidatorClientError),
+    #[error(transparent)]
+    Blob(#[from] BlobError),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error(transparent)]
+    Kzg(#[from] KzgError),
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[error("Retrieval request to validator timed out")]
+    RetrievalTimeout,
+    #[error("Validator returned {0} chunks, expected {1}")]
+    UnexpectedChunkCount(usize, usize),
+    #[error("No blob params configured for blob version {0}")]
+    UnknownBlobVersion(u16),
+    #[error("G2 SRS point for chunk length {0} is not loaded")]
+    MissingG2PowerOf2Point(usize),
+    #[error("Not enough valid chunks retrieved from validators: got {0}, need {1}")]
+    NotEnoughValidChunks(usize, usize),
+    #[error("Reconstructed blob does not match the commitment")]
+    InvalidReconstructedBlob,
+    #[error(transparent)]
+    BlobCommitments(#[from] BlobCommitmentsError),
+    #[error(transparent)]
+    EigenClient(#[from] EigenClientError),
+}
+
+/// Errors specific to the [`ValidatorClient`].
+#[derive(Debug, thiserror::Error)]
+pub enum ValidatorClientError {
+    #[error("Max grpc message size must be greater than 0")]
+    InvalidMaxGrpcMessageSize,
+    #[error("Failed RPC call: {0}")]
+    FailedRPC(#[from] tonic::Status),
+    #[error("Failed connection call")]
+    FailedConnection(#[from] tonic::transport::Error),
+    #[error("Invalid validator URI: {0}")]
+    InvalidURI(String),
+    #[error("Unsupported chunk encoding format: {0}")]
+    UnsupportedChunkEncoding(i32),
+    #[error("Failed to fetch validator assignments: {0}")]
+    Assignments(String),
 }
 
 /// Errors specific to the Blob type
@@ -88,10 +210,24 @@ pub enum BlobError {
     CommitmentAndBlobLengthMismatch(usize, usize),
     #[error("Invalid data length: {0}")]
     InvalidDataLength(usize),
+    #[error("Blob symbol at byte {0} is not a canonical field element")]
+    NonCanonicalSymbol(usize),
+    #[error("Payload offset {0} is out of bounds")]
+    InvalidPayloadOffset(usize),
     #[error("Invalid quorum number: {0}")]
     InvalidQuorumNumber(u32),
     #[error("Missing field: {0}")]
     MissingField(String),
+    #[error("Invalid encoding params: {0}")]
+    InvalidEncodingParams(String),
+    #[error("Invalid chunk index: {0}")]
+    InvalidChunkIndex(u32),
+    #[error("Invalid chunk length: got {0}, expected {1}")]
+    InvalidChunkLength(usize, usize),
+    #[error("Not enough chunks to reconstruct blob: got {0}, need {1}")]
+    NotEnoughChunks(usize, usize),
+    #[error("Chunks are not consistent with a blob of the claimed length")]
+    InconsistentChunks,
     #[error(transparent)]
     Bn254(#[from] Bn254Error),
 }
@@ -126,8 +262,44 @@ pub enum RelayClientError {
     EthClient(#[from] EthClientError),
     #[error(transparent)]
     Conversion(#[from] ConversionError),
-    #[error("Failed to parse relay key to URL: {0}")]
-    RelayKeyToUrl(u32),
+    #[error("Failed to parse relay key {relay_key} to URL: {source}")]
+    RelayKeyToUrl {
+        relay_key: u32,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Unexpected relay reply: {0}")]
+    UnexpectedReply(String),
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+}
+
+/// Errors of the checks of the network the clients are connected to
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkError {
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error("Failed to fetch the chain id: {0}")]
+    ChainId(String),
+    #[error("ETH RPC is connected to chain {actual}, expected chain {expected}")]
+    ChainIdMismatch { expected: u64, actual: U256 },
+}
+
+/// Errors of the client configs, raised when they are built or loaded
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Missing config field {0}")]
+    MissingField(&'static str),
+    #[error("Invalid config field {field}: {reason}")]
+    InvalidField { field: &'static str, reason: String },
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid TOML config: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid JSON config: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid config environment variables: {0}")]
+    Env(#[from] envy::Error),
 }
 
 /// Errors for the EthClient
@@ -200,6 +372,21 @@ pub enum PayloadDisperserError {
     Decode(#[from] DecodeError),
     #[error(transparent)]
     CertVerifier(#[from] CertVerifierError),
+    #[error(transparent)]
+    InclusionProof(#[from] InclusionProofError),
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+}
+
+/// Errors of the local verification of the inclusion of a blob in its batch
+#[derive(Debug, thiserror::Error)]
+pub enum InclusionProofError {
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error("Invalid inclusion proof length: {0} is not a multiple of 32")]
+    InvalidProofLength(usize),
+    #[error("Blob is not included in the batch: batch root is {expected}, but the proof leads to {actual}")]
+    RootMismatch { expected: String, actual: String },
 }
 
 /// Errors specific to the CertVerifier
@@ -209,8 +396,72 @@ pub enum CertVerifierError {
     Conversion(#[from] ConversionError),
     #[error("Invalid cert verifier contract address: {0}")]
     InvalidCertVerifierAddress(H160),
+    /// The contract could not be called (e.g. the RPC is unavailable). Unlike [`CertVerifierError::VerificationFailed`]
+    /// this says nothing about the cert, the call may be retried.
+    #[error("Error while calling contract function {function}: {source}")]
+    ContractCall {
+        function: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// The contract call could not be built, or returned an unexpected output.
     #[error("Error while calling contract function: {0}")]
     Contract(String),
     #[error("Error while signing: {0}")]
     Signing(String),
+    /// The cert is invalid, it must be dropped.
+    #[error("Cert verification failed: {0}")]
+    VerificationFailed(#[from] CertVerificationFailure),
+    #[error("Failed to read the quorum state: {0}")]
+    QuorumState(String),
+    #[error(transparent)]
+    StatelessExecution(#[from] StatelessExecutionError),
+}
+
+/// Reasons a cert is rejected, decoded from the revert of the EigenDACertVerifier contract or found by its local
+/// verification.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CertVerificationFailure {
+    #[error("Invalid inclusion proof: {0}")]
+    InvalidInclusionProof(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Signatories do not own at least the threshold percentage of a quorum: {0}")]
+    InsufficientStake(String),
+    #[error("Blob quorums are not a subset of the confirmed quorums")]
+    BlobQuorumsNotConfirmed,
+    #[error("Required quorums are not a subset of the blob quorums")]
+    RequiredQuorumsMissing,
+    #[error("Security assumptions are not met: {0}")]
+    SecurityAssumptionsNotMet(String),
+    /// Reverted with a reason not matching any of the known ones.
+    #[error("Reverted: {0}")]
+    Reverted(String),
+    /// `checkDACert` returned a status that is neither success nor a known failure.
+    #[error("Unknown checkDACert status {0}")]
+    Status(u8),
+    #[error("Panicked with code {0:#x}")]
+    Panic(U256),
+    #[error("Reverted with undecodable data 0x{}", hex::encode(.0))]
+    UnknownRevert(Vec<u8>),
+    /// The execution halted without reverting (e.g. it ran out of gas).
+    #[error("Halted: {0}")]
+    Halt(String),
+}
+
+/// Errors of the execution of calls against the proven state of a trusted block
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessExecutionError {
+    #[error("Failed to fetch the state: {0}")]
+    Rpc(String),
+    #[error("State is missing from the recorded state: {0}")]
+    MissingState(String),
+    #[error("Invalid state proof: {0}")]
+    InvalidProof(String),
+    #[error("Code of account {0:?} does not match its code hash")]
+    CodeHashMismatch(H160),
+    #[error("EVM error: {0}")]
+    Evm(String),
+    #[error("Call still reads unfetched state after {0} executions")]
+    TooManyFetchRounds(usize),
 }
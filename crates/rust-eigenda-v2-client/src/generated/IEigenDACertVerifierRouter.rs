@@ -0,0 +1,271 @@
+pub use i_eigen_da_cert_verifier_router::*;
+/// This module was auto-generated with ethers-rs Abigen.
+/// More information at: <https://github.com/gakonst/ethers-rs>
+#[allow(
+    clippy::enum_variant_names,
+    clippy::too_many_arguments,
+    clippy::upper_case_acronyms,
+    clippy::type_complexity,
+    dead_code,
+    non_camel_case_types,
+)]
+pub mod i_eigen_da_cert_verifier_router {
+    const _: () = {
+        ::core::include_bytes!(
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/generated/abi/IEigenDACertVerifierRouter.json",
+            ),
+        );
+    };
+    #[allow(deprecated)]
+    fn __abi() -> ::ethers::core::abi::Abi {
+        ::ethers::core::abi::ethabi::Contract {
+            constructor: ::core::option::Option::None,
+            functions: ::core::convert::From::from([
+                (
+                    ::std::borrow::ToOwned::to_owned("checkDACert"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("checkDACert"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("abiEncodedCert"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Bytes,
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("bytes"),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Uint(8usize),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("uint8"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
+                (
+                    ::std::borrow::ToOwned::to_owned("getCertVerifierAt"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("getCertVerifierAt"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned(
+                                        "referenceBlockNumber",
+                                    ),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Uint(32usize),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("uint32"),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Address,
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("address"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
+            ]),
+            events: ::std::collections::BTreeMap::new(),
+            errors: ::std::collections::BTreeMap::new(),
+            receive: false,
+            fallback: false,
+        }
+    }
+    ///The parsed JSON ABI of the contract.
+    pub static IEIGENDACERTVERIFIERROUTER_ABI: ::ethers::contract::Lazy<
+        ::ethers::core::abi::Abi,
+    > = ::ethers::contract::Lazy::new(__abi);
+    pub struct IEigenDACertVerifierRouter<M>(::ethers::contract::Contract<M>);
+    impl<M> ::core::clone::Clone for IEigenDACertVerifierRouter<M> {
+        fn clone(&self) -> Self {
+            Self(::core::clone::Clone::clone(&self.0))
+        }
+    }
+    impl<M> ::core::ops::Deref for IEigenDACertVerifierRouter<M> {
+        type Target = ::ethers::contract::Contract<M>;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+    impl<M> ::core::ops::DerefMut for IEigenDACertVerifierRouter<M> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
+        }
+    }
+    impl<M> ::core::fmt::Debug for IEigenDACertVerifierRouter<M> {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_tuple(::core::stringify!(IEigenDACertVerifierRouter))
+                .field(&self.address())
+                .finish()
+        }
+    }
+    impl<M: ::ethers::providers::Middleware> IEigenDACertVerifierRouter<M> {
+        /// Creates a new contract instance with the specified `ethers` client at
+        /// `address`. The contract derefs to a `ethers::Contract` object.
+        pub fn new<T: Into<::ethers::core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            Self(
+                ::ethers::contract::Contract::new(
+                    address.into(),
+                    IEIGENDACERTVERIFIERROUTER_ABI.clone(),
+                    client,
+                ),
+            )
+        }
+        ///Calls the contract's `checkDACert` (0x9077193b) function
+        pub fn check_da_cert(
+            &self,
+            abi_encoded_cert: ::ethers::core::types::Bytes,
+        ) -> ::ethers::contract::builders::ContractCall<M, u8> {
+            self.0
+                .method_hash([144, 119, 25, 59], abi_encoded_cert)
+                .expect("method not found (this should never happen)")
+        }
+        ///Calls the contract's `getCertVerifierAt` (0x4a4ae0e2) function
+        pub fn get_cert_verifier_at(
+            &self,
+            reference_block_number: u32,
+        ) -> ::ethers::contract::builders::ContractCall<
+            M,
+            ::ethers::core::types::Address,
+        > {
+            self.0
+                .method_hash([74, 74, 224, 226], reference_block_number)
+                .expect("method not found (this should never happen)")
+        }
+    }
+    impl<M: ::ethers::providers::Middleware> From<::ethers::contract::Contract<M>>
+    for IEigenDACertVerifierRouter<M> {
+        fn from(contract: ::ethers::contract::Contract<M>) -> Self {
+            Self::new(contract.address(), contract.client())
+        }
+    }
+    ///Container type for all input parameters for the `checkDACert` function with signature `checkDACert(bytes)` and selector `0x9077193b`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "checkDACert", abi = "checkDACert(bytes)")]
+    pub struct CheckDACertCall {
+        pub abi_encoded_cert: ::ethers::core::types::Bytes,
+    }
+    ///Container type for all input parameters for the `getCertVerifierAt` function with signature `getCertVerifierAt(uint32)` and selector `0x4a4ae0e2`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "getCertVerifierAt", abi = "getCertVerifierAt(uint32)")]
+    pub struct GetCertVerifierAtCall {
+        pub reference_block_number: u32,
+    }
+    ///Container type for all of the contract's call
+    #[derive(Clone, ::ethers::contract::EthAbiType, Debug, PartialEq, Eq, Hash)]
+    pub enum IEigenDACertVerifierRouterCalls {
+        CheckDACert(CheckDACertCall),
+        GetCertVerifierAt(GetCertVerifierAtCall),
+    }
+    impl ::ethers::core::abi::AbiDecode for IEigenDACertVerifierRouterCalls {
+        fn decode(
+            data: impl AsRef<[u8]>,
+        ) -> ::core::result::Result<Self, ::ethers::core::abi::AbiError> {
+            let data = data.as_ref();
+            if let Ok(decoded) = <CheckDACertCall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::CheckDACert(decoded));
+            }
+            if let Ok(decoded) = <GetCertVerifierAtCall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::GetCertVerifierAt(decoded));
+            }
+            Err(::ethers::core::abi::Error::InvalidData.into())
+        }
+    }
+    impl ::ethers::core::abi::AbiEncode for IEigenDACertVerifierRouterCalls {
+        fn encode(self) -> Vec<u8> {
+            match self {
+                Self::CheckDACert(element) => {
+                    ::ethers::core::abi::AbiEncode::encode(element)
+                }
+                Self::GetCertVerifierAt(element) => {
+                    ::ethers::core::abi::AbiEncode::encode(element)
+                }
+            }
+        }
+    }
+    impl ::core::fmt::Display for IEigenDACertVerifierRouterCalls {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            match self {
+                Self::CheckDACert(element) => ::core::fmt::Display::fmt(element, f),
+                Self::GetCertVerifierAt(element) => ::core::fmt::Display::fmt(element, f),
+            }
+        }
+    }
+    impl ::core::convert::From<CheckDACertCall> for IEigenDACertVerifierRouterCalls {
+        fn from(value: CheckDACertCall) -> Self {
+            Self::CheckDACert(value)
+        }
+    }
+    impl ::core::convert::From<GetCertVerifierAtCall>
+    for IEigenDACertVerifierRouterCalls {
+        fn from(value: GetCertVerifierAtCall) -> Self {
+            Self::GetCertVerifierAt(value)
+        }
+    }
+    ///Container type for all return fields from the `checkDACert` function with signature `checkDACert(bytes)` and selector `0x9077193b`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct CheckDACertReturn(pub u8);
+    ///Container type for all return fields from the `getCertVerifierAt` function with signature `getCertVerifierAt(uint32)` and selector `0x4a4ae0e2`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct GetCertVerifierAtReturn(pub ::ethers::core::types::Address);
+}
@@ -0,0 +1,158 @@
+use ethabi::Token;
+use ethers::utils::keccak256;
+use rust_eigenda_v2_common::{BatchHeaderV2, BlobCertificate, BlobInclusionInfo, EigenDACert};
+
+use crate::{
+    core::BlobKey,
+    errors::{ConversionError, InclusionProofError},
+};
+
+/// Size of each node of the inclusion proof, a keccak256 hash.
+const NODE_SIZE: usize = 32;
+
+/// Verifies that the blob certificate of the cert is included in its batch.
+///
+/// This is the inclusion check done by the EigenDACertVerifier contract, so it can be used to reject invalid certs
+/// before calling the contract. It does not check the batch signatures.
+pub fn verify_cert_inclusion(eigenda_cert: &EigenDACert) -> Result<(), InclusionProofError> {
+    verify_blob_inclusion(
+        eigenda_cert.blob_inclusion_info(),
+        eigenda_cert.batch_header(),
+    )
+}
+
+/// Verifies the Merkle proof of the inclusion of a blob certificate in the batch with the given header.
+///
+/// The leaf is `keccak256(hash(blob_certificate))`, and the proof is walked up to the root as in
+/// `Merkle.processInclusionProofKeccak`.
+pub fn verify_blob_inclusion(
+    blob_inclusion_info: &BlobInclusionInfo,
+    batch_header: &BatchHeaderV2,
+) -> Result<(), InclusionProofError> {
+    let blob_certificate_hash = hash_blob_certificate(&blob_inclusion_info.blob_certificate)?;
+    let leaf = keccak256(blob_certificate_hash);
+
+    let root = process_inclusion_proof(
+        &blob_inclusion_info.inclusion_proof,
+        leaf,
+        blob_inclusion_info.blob_index,
+    )?;
+    if root != batch_header.batch_root {
+        return Err(InclusionProofError::RootMismatch {
+            expected: hex::encode(batch_header.batch_root),
+            actual: hex::encode(root),
+        });
+    }
+    Ok(())
+}
+
+/// Hashes a blob certificate as the contracts do: `keccak256(abi.encode(blobKey, signature, relayKeys))`, being
+/// the blob key the hash of the blob header.
+pub fn hash_blob_certificate(
+    blob_certificate: &BlobCertificate,
+) -> Result<[u8; 32], ConversionError> {
+    let blob_key = BlobKey::compute_blob_key(&blob_certificate.blob_header)?;
+    let encoded = ethabi::encode(&[
+        Token::FixedBytes(blob_key.to_bytes().to_vec()),
+        Token::Bytes(blob_certificate.signature.clone()),
+        Token::Array(
+            blob_certificate
+                .relay_keys
+                .iter()
+                .map(|relay_key| Token::Uint((*relay_key).into()))
+                .collect(),
+        ),
+    ]);
+    Ok(keccak256(&encoded))
+}
+
+/// Computes the Merkle root given a leaf, its index and the sibling nodes on the path to the root.
+fn process_inclusion_proof(
+    proof: &[u8],
+    leaf: [u8; 32],
+    index: u32,
+) -> Result<[u8; 32], InclusionProofError> {
+    if !proof.len().is_multiple_of(NODE_SIZE) {
+        return Err(InclusionProofError::InvalidProofLength(proof.len()));
+    }
+
+    let mut index = index;
+    let mut computed_hash = leaf;
+    for sibling in proof.chunks(NODE_SIZE) {
+        let mut buffer = [0u8; 2 * NODE_SIZE];
+        if index.is_multiple_of(2) {
+            buffer[..NODE_SIZE].copy_from_slice(&computed_hash);
+            buffer[NODE_SIZE..].copy_from_slice(sibling);
+        } else {
+            buffer[..NODE_SIZE].copy_from_slice(sibling);
+            buffer[NODE_SIZE..].copy_from_slice(&computed_hash);
+        }
+        computed_hash = keccak256(buffer);
+        index /= 2;
+    }
+    Ok(computed_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::relay_payload_retriever::tests::get_test_eigenda_cert;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_cert_inclusion() {
+        let eigenda_cert = get_test_eigenda_cert();
+        let blob_key = BlobKey::compute_blob_key(
+            &eigenda_cert
+                .blob_inclusion_info()
+                .blob_certificate
+                .blob_header,
+        )
+        .unwrap();
+        assert_eq!(
+            blob_key.to_hex(),
+            "03f05eeacde94b44a7e48576aabc374e6652490ce3ee1eeeb23c127959da854d"
+        );
+        verify_cert_inclusion(&eigenda_cert).unwrap();
+    }
+
+    #[test]
+    fn test_verify_tampered_cert_inclusion() {
+        let eigenda_cert = get_test_eigenda_cert();
+
+        let mut wrong_index = eigenda_cert.clone();
+        wrong_index.blob_inclusion_info_mut().blob_index = 0;
+        assert!(matches!(
+            verify_cert_inclusion(&wrong_index),
+            Err(InclusionProofError::RootMismatch { .. })
+        ));
+
+        let mut wrong_relay_keys = eigenda_cert.clone();
+        wrong_relay_keys
+            .blob_inclusion_info_mut()
+            .blob_certificate
+            .relay_keys
+            .push(3);
+        assert!(matches!(
+            verify_cert_inclusion(&wrong_relay_keys),
+            Err(InclusionProofError::RootMismatch { .. })
+        ));
+
+        let mut wrong_root = eigenda_cert.clone();
+        wrong_root.batch_header_mut().batch_root[0] ^= 1;
+        assert!(matches!(
+            verify_cert_inclusion(&wrong_root),
+            Err(InclusionProofError::RootMismatch { .. })
+        ));
+
+        let mut truncated_proof = eigenda_cert;
+        truncated_proof
+            .blob_inclusion_info_mut()
+            .inclusion_proof
+            .pop();
+        assert!(matches!(
+            verify_cert_inclusion(&truncated_proof),
+            Err(InclusionProofError::InvalidProofLength(31))
+        ));
+    }
+}
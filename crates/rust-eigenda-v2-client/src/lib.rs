@@ -1,18 +1,30 @@
 pub mod accountant;
 pub mod cert_verifier;
+pub mod cert_verifier_router;
 pub mod commitment_utils;
+pub mod config;
 pub mod core;
 pub mod disperser_client;
 pub mod errors;
+pub mod inclusion_proof;
+pub mod local_cert_verifier;
+pub mod network;
 pub mod payload_disperser;
+pub mod preflight;
 pub mod relay_client;
 pub mod relay_payload_retriever;
 pub mod relay_registry;
+pub mod retrieval_cache;
+pub mod retriever_payload_retriever;
+pub mod srs;
+pub mod stateless_execution;
 pub mod utils;
+pub mod validator_client;
+pub mod validator_payload_retriever;
 // So users can use the client without having to depend on the signers crate as well.
 pub use rust_eigenda_signers;
 
-#[allow(clippy::all)]
+#[allow(clippy::all, dead_code)]
 pub(crate) mod generated {
     pub mod common {
         include!("generated/common.rs");
@@ -52,6 +64,14 @@ pub(crate) mod generated {
         include!("generated/IEigenDACertVerifier.rs");
     }
 
+    pub mod i_cert_verifier_base {
+        include!("generated/IEigenDACertVerifierBase.rs");
+    }
+
+    pub mod i_cert_verifier_router {
+        include!("generated/IEigenDACertVerifierRouter.rs");
+    }
+
     pub mod i_relay_registry {
         include!("generated/IRelayRegistry.rs");
     }
@@ -60,16 +80,18 @@ pub(crate) mod generated {
 #[cfg(test)]
 mod tests {
     use dotenv::dotenv;
-    use ethereum_types::H160;
+    use rust_eigenda_srs::{SrsFile, SrsSource};
     use rust_eigenda_v2_common::EigenDACert;
-    use std::{env, str::FromStr, time::Duration};
+    use std::{collections::HashMap, env, str::FromStr, sync::Arc, time::Duration};
     use url::Url;
 
     use crate::{
-        core::{BlobKey, Payload, PayloadForm},
+        core::{BlobKey, BlobParams, Payload, PayloadDecodeMode, PayloadForm},
+        network::Network,
         payload_disperser::{PayloadDisperser, PayloadDisperserConfig},
         relay_client::RelayClient,
-        relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig, SRSConfig},
+        relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig},
+        srs::{SRSConfig, Srs},
         utils::SecretUrl,
     };
 
@@ -78,15 +100,10 @@ mod tests {
     const TEST_BLOB_FINALIZATION_TIMEOUT: u64 = 180;
     const TEST_PAYLOAD_DATA: &[u8] = &[1, 2, 3, 4, 5];
     pub const HOLESKY_ETH_RPC_URL: &str = "https://ethereum-holesky-rpc.publicnode.com";
-    pub const HOLESKY_DISPERSER_RPC_URL: &str = "https://disperser-testnet-holesky.eigenda.xyz";
-    pub const HOLESKY_RELAY_REGISTRY_ADDRESS: H160 = H160([
-        0xac, 0x8c, 0x6c, 0x7e, 0xe7, 0x57, 0x29, 0x75, 0x45, 0x4e, 0x2f, 0x0b, 0x5c, 0x72, 0x0f,
-        0x9e, 0x74, 0x98, 0x92, 0x54,
-    ]);
-    pub const CERT_VERIFIER_ADDRESS: H160 = H160([
-        0xfe, 0x52, 0xfe, 0x19, 0x40, 0x85, 0x8d, 0xcb, 0x6e, 0x12, 0x15, 0x3e, 0x21, 0x04, 0xad,
-        0x0f, 0xdf, 0xbe, 0x11, 0x62,
-    ]);
+    pub use crate::network::{
+        HOLESKY_CERT_VERIFIER_ADDRESS as CERT_VERIFIER_ADDRESS, HOLESKY_DISPERSER_RPC_URL,
+        HOLESKY_RELAY_REGISTRY_ADDRESS,
+    };
 
     pub fn get_test_private_key_signer() -> PrivateKeySigner {
         dotenv().ok();
@@ -99,12 +116,8 @@ mod tests {
 
     fn get_test_payload_disperser_config() -> PayloadDisperserConfig {
         PayloadDisperserConfig {
-            polynomial_form: PayloadForm::Coeff,
-            blob_version: 0,
-            cert_verifier_address: CERT_VERIFIER_ADDRESS,
-            eth_rpc_url: get_test_holesky_rpc_url(),
-            disperser_rpc: HOLESKY_DISPERSER_RPC_URL.to_string(),
             use_secure_grpc_flag: false,
+            ..Network::Holesky.payload_disperser_config(get_test_holesky_rpc_url())
         }
     }
 
@@ -112,26 +125,160 @@ mod tests {
         RelayPayloadRetrieverConfig {
             payload_form: PayloadForm::Coeff,
             retrieval_timeout_secs: Duration::from_secs(10),
+            blob_params: HashMap::from([(
+                0,
+                BlobParams {
+                    num_chunks: 8192,
+                    coding_rate: 8,
+                },
+            )]),
+            // the test G1 file holds only the first points of the SRS
+            verify_length_proof: false,
+            decode_mode: PayloadDecodeMode::Lenient,
         }
     }
 
     pub fn get_srs_test_config() -> SRSConfig {
         SRSConfig {
-            source_path: "../../resources/g1.point".to_string(),
+            g1: SrsFile::new(SrsSource::Path("../../resources/g1.point".into())),
             order: 9999999,
-            points_to_load: 9999999,
+            g2_power_of_2: Some(SrsFile::new(SrsSource::Path(
+                "../../resources/g2.point.powerOf2".into(),
+            ))),
+            cache_dir: None,
         }
     }
 
+    pub async fn get_test_srs() -> Arc<Srs> {
+        Arc::new(Srs::load(&get_srs_test_config()).await.unwrap())
+    }
+
+    /// Secret `s` of the SRS returned by [`get_insecure_test_srs`].
+    pub const INSECURE_TEST_SRS_SECRET: u64 = 1337;
+    /// Order of the SRS returned by [`get_insecure_test_srs`].
+    pub const INSECURE_TEST_SRS_ORDER: u32 = 1024;
+
+    /// Returns an SRS generated from a known secret, so that tests can run without the SRS files and forge
+    /// commitments and proofs.
+    pub async fn get_insecure_test_srs() -> Arc<Srs> {
+        use ark_bn254::{Fr, G1Affine, G2Affine};
+        use ark_ec::{AffineRepr, CurveGroup};
+        use ark_ff::Field;
+
+        use crate::commitment_utils::{g1_commitment_to_bytes, g2_commitment_to_bytes};
+
+        // the points are generated once, as it takes seconds in debug builds
+        static POINTS: std::sync::OnceLock<(&[u8], &[u8])> = std::sync::OnceLock::new();
+        let (g1, g2_power_of_2) = *POINTS.get_or_init(|| {
+            let secret = Fr::from(INSECURE_TEST_SRS_SECRET);
+            let mut g1 = Vec::new();
+            let mut power = Fr::ONE;
+            for _ in 0..INSECURE_TEST_SRS_ORDER {
+                let point = (G1Affine::generator() * power).into_affine();
+                g1.extend(g1_commitment_to_bytes(&point).unwrap());
+                power *= secret;
+            }
+            let mut g2_power_of_2 = Vec::new();
+            let mut power = secret;
+            for _ in 0..=INSECURE_TEST_SRS_ORDER.trailing_zeros() {
+                let point = (G2Affine::generator() * power).into_affine();
+                g2_power_of_2.extend(g2_commitment_to_bytes(&point).unwrap());
+                power.square_in_place();
+            }
+            (g1.leak(), g2_power_of_2.leak())
+        });
+
+        let config = SRSConfig {
+            g1: SrsFile::new(SrsSource::Embedded(g1)),
+            order: INSECURE_TEST_SRS_ORDER,
+            g2_power_of_2: Some(SrsFile::new(SrsSource::Embedded(g2_power_of_2))),
+            cache_dir: None,
+        };
+        Arc::new(Srs::load(&config).await.unwrap())
+    }
+
     pub fn get_relay_client_test_config() -> crate::relay_client::RelayClientConfig {
         crate::relay_client::RelayClientConfig {
             max_grpc_message_size: 9999999,
-            relay_clients_keys: vec![0, 1, 2],
-            relay_registry_address: HOLESKY_RELAY_REGISTRY_ADDRESS,
-            eth_rpc_url: get_test_holesky_rpc_url(),
+            ..Network::Holesky.relay_client_config(get_test_holesky_rpc_url(), vec![0, 1, 2])
         }
     }
 
+    /// Serves JSON-RPC requests on a local port, answering each with the result, or the error, returned by `respond`
+    /// for its method and params. Returns the URL of the server.
+    pub async fn mock_eth_rpc<F>(respond: F) -> SecretUrl
+    where
+        F: Fn(&str, &serde_json::Value) -> Result<serde_json::Value, serde_json::Value>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let respond = Arc::new(respond);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(serve_eth_rpc_request(stream, respond.clone()));
+            }
+        });
+        SecretUrl::new(Url::from_str(&url).unwrap())
+    }
+
+    async fn serve_eth_rpc_request<F>(mut stream: tokio::net::TcpStream, respond: Arc<F>)
+    where
+        F: Fn(&str, &serde_json::Value) -> Result<serde_json::Value, serde_json::Value>,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        let body = loop {
+            let read = stream.read(&mut buffer).await.unwrap();
+            if read == 0 {
+                return;
+            }
+            request.extend_from_slice(&buffer[..read]);
+            let Some(headers_end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&request[..headers_end]).to_lowercase();
+            let length: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|length| length.trim().parse().unwrap())
+                .unwrap_or_default();
+            let body_start = headers_end + 4;
+            if request.len() >= body_start + length {
+                break request[body_start..body_start + length].to_vec();
+            }
+        };
+
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let mut response = serde_json::json!({ "jsonrpc": "2.0", "id": request["id"] });
+        match respond(request["method"].as_str().unwrap(), &request["params"]) {
+            Ok(result) => response["result"] = result,
+            Err(error) => response["error"] = error,
+        }
+        let response = response.to_string();
+        let reply = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        );
+        stream.write_all(reply.as_bytes()).await.unwrap();
+    }
+
+    /// Returns the URL of a local port nothing listens on, so that every request to it fails to connect.
+    pub fn get_closed_rpc_url() -> SecretUrl {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        SecretUrl::new(Url::from_str(&format!("http://127.0.0.1:{}", port)).unwrap())
+    }
+
     pub fn get_test_holesky_rpc_url() -> SecretUrl {
         SecretUrl::new(Url::from_str(HOLESKY_ETH_RPC_URL).unwrap())
     }
@@ -191,10 +338,9 @@ mod tests {
 
         // Finally we retrieve the blob using a Relay Payload Retriever
         let relay_config = get_relay_payload_retriever_test_config();
-        let srs_config = get_srs_test_config();
+        let srs = get_test_srs().await;
         let relay_client = get_test_relay_client().await;
-        let mut client =
-            RelayPayloadRetriever::new(relay_config, srs_config, relay_client).unwrap();
+        let mut client = RelayPayloadRetriever::new(relay_config, srs, relay_client).unwrap();
 
         let result = client.get_payload(eigenda_cert).await;
         let retrieved_payload = result.unwrap().serialize();
@@ -0,0 +1,969 @@
+use std::collections::HashMap;
+
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ethabi::{ParamType, Token};
+use ethereum_types::{H160, U256};
+use ethers::{
+    prelude::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, BlockId, BlockNumber, TransactionRequest},
+    utils::keccak256,
+};
+use rust_eigenda_v2_common::{BatchHeaderV2, EigenDACert};
+
+use crate::{
+    cert_verifier::{contract_call_error, CertVerification},
+    errors::{CertVerificationFailure, CertVerifierError, ConversionError},
+    inclusion_proof::verify_cert_inclusion,
+    utils::SecretUrl,
+};
+
+/// Denominator of the confirmation threshold, which is a percentage.
+const THRESHOLD_DENOMINATOR: u128 = 100;
+
+/// Operator state of the EigenDA quorums at a reference block number, as registered in the EigenLayer middleware
+/// contracts (`BLSApkRegistry`, `StakeRegistry` and `RegistryCoordinator`).
+///
+/// The `index` arguments are the indices into the contract histories given by the cert
+/// ([`NonSignerStakesAndSignature`](rust_eigenda_v2_common::NonSignerStakesAndSignature)). Sources that don't read
+/// the contract histories, like a snapshot, can ignore them. The `block` arguments are the block of the chain the
+/// histories are read at: the state is always looked up at the reference block number, but the histories can still
+/// change with a reorg.
+#[async_trait::async_trait]
+pub trait QuorumStateSource: Sync + Send + std::fmt::Debug {
+    /// Returns the first 24 bytes of the hash of the aggregate public key of the quorum.
+    async fn quorum_apk_hash(
+        &self,
+        quorum: u8,
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<[u8; 24], CertVerifierError>;
+
+    /// Returns the total stake of the quorum.
+    async fn total_stake(
+        &self,
+        quorum: u8,
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<u128, CertVerifierError>;
+
+    /// Returns the stake of an operator in the quorum.
+    async fn operator_stake(
+        &self,
+        quorum: u8,
+        operator_id: [u8; 32],
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<u128, CertVerifierError>;
+
+    /// Returns the bitmap of the quorums the operator is registered in.
+    async fn operator_quorum_bitmap(
+        &self,
+        operator_id: [u8; 32],
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<U256, CertVerifierError>;
+}
+
+/// [`QuorumStateSource`] backed by a snapshot of the operator state at a single reference block number. The snapshot
+/// is fixed, so the block it is read at is ignored.
+///
+/// Operators are identified by their id, the hash of their G1 public key (see [`operator_id`]).
+#[derive(Clone, Debug, Default)]
+pub struct QuorumStateSnapshot {
+    pub reference_block_number: u32,
+    pub quorum_apks: HashMap<u8, G1Affine>,
+    pub total_stakes: HashMap<u8, u128>,
+    pub operator_stakes: HashMap<(u8, [u8; 32]), u128>,
+    pub operator_quorum_bitmaps: HashMap<[u8; 32], U256>,
+}
+
+impl QuorumStateSnapshot {
+    fn check_block(&self, reference_block_number: u32) -> Result<(), CertVerifierError> {
+        if reference_block_number != self.reference_block_number {
+            return Err(CertVerifierError::QuorumState(format!(
+                "snapshot is for block {}, not {}",
+                self.reference_block_number, reference_block_number
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl QuorumStateSource for QuorumStateSnapshot {
+    async fn quorum_apk_hash(
+        &self,
+        quorum: u8,
+        reference_block_number: u32,
+        _index: u32,
+        _block: BlockId,
+    ) -> Result<[u8; 24], CertVerifierError> {
+        self.check_block(reference_block_number)?;
+        let apk = self.quorum_apks.get(&quorum).ok_or_else(|| {
+            CertVerifierError::QuorumState(format!("missing apk of quorum {}", quorum))
+        })?;
+        Ok(apk_hash(apk))
+    }
+
+    async fn total_stake(
+        &self,
+        quorum: u8,
+        reference_block_number: u32,
+        _index: u32,
+        _block: BlockId,
+    ) -> Result<u128, CertVerifierError> {
+        self.check_block(reference_block_number)?;
+        self.total_stakes.get(&quorum).copied().ok_or_else(|| {
+            CertVerifierError::QuorumState(format!("missing total stake of quorum {}", quorum))
+        })
+    }
+
+    async fn operator_stake(
+        &self,
+        quorum: u8,
+        operator_id: [u8; 32],
+        reference_block_number: u32,
+        _index: u32,
+        _block: BlockId,
+    ) -> Result<u128, CertVerifierError> {
+        self.check_block(reference_block_number)?;
+        Ok(self
+            .operator_stakes
+            .get(&(quorum, operator_id))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn operator_quorum_bitmap(
+        &self,
+        operator_id: [u8; 32],
+        reference_block_number: u32,
+        _index: u32,
+        _block: BlockId,
+    ) -> Result<U256, CertVerifierError> {
+        self.check_block(reference_block_number)?;
+        Ok(self
+            .operator_quorum_bitmaps
+            .get(&operator_id)
+            .copied()
+            .unwrap_or_default())
+    }
+}
+
+/// [`QuorumStateSource`] that reads the operator state from the EigenLayer middleware contracts.
+#[derive(Clone, Debug)]
+pub struct ChainQuorumState {
+    provider: Provider<Http>,
+    registry_coordinator: H160,
+    bls_apk_registry: H160,
+    stake_registry: H160,
+}
+
+impl ChainQuorumState {
+    pub fn new(
+        rpc_url: SecretUrl,
+        registry_coordinator: H160,
+        bls_apk_registry: H160,
+        stake_registry: H160,
+    ) -> Result<Self, ConversionError> {
+        let url: String = rpc_url.try_into()?;
+        let provider = Provider::<Http>::try_from(url).map_err(ConversionError::UrlParse)?;
+        Ok(Self {
+            provider,
+            registry_coordinator,
+            bls_apk_registry,
+            stake_registry,
+        })
+    }
+
+    /// Calls a view function at the given block, returning its single output.
+    async fn call(
+        &self,
+        to: H160,
+        function: &'static str,
+        params: &[ParamType],
+        args: &[Token],
+        output: ParamType,
+        block: BlockId,
+    ) -> Result<Token, CertVerifierError> {
+        let mut data = ethabi::short_signature(function, params).to_vec();
+        data.extend(ethabi::encode(args));
+        let tx: TypedTransaction = TransactionRequest::new().to(to).data(data).into();
+
+        let result = self
+            .provider
+            .call(&tx, Some(block))
+            .await
+            .map_err(|e| contract_call_error(function, e))?;
+        ethabi::decode(&[output], &result)
+            .map_err(|e| contract_call_error(function, e))?
+            .pop()
+            .ok_or_else(|| CertVerifierError::Contract(format!("{}: empty output", function)))
+    }
+}
+
+#[async_trait::async_trait]
+impl QuorumStateSource for ChainQuorumState {
+    async fn quorum_apk_hash(
+        &self,
+        quorum: u8,
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<[u8; 24], CertVerifierError> {
+        let hash = self
+            .call(
+                self.bls_apk_registry,
+                "getApkHashAtBlockNumberAndIndex",
+                &[
+                    ParamType::Uint(8),
+                    ParamType::Uint(32),
+                    ParamType::Uint(256),
+                ],
+                &[
+                    Token::Uint(quorum.into()),
+                    Token::Uint(reference_block_number.into()),
+                    Token::Uint(index.into()),
+                ],
+                ParamType::FixedBytes(24),
+                block,
+            )
+            .await?;
+        hash.into_fixed_bytes()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| CertVerifierError::Contract("getApkHashAtBlockNumberAndIndex".into()))
+    }
+
+    async fn total_stake(
+        &self,
+        quorum: u8,
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<u128, CertVerifierError> {
+        let stake = self
+            .call(
+                self.stake_registry,
+                "getTotalStakeAtBlockNumberFromIndex",
+                &[
+                    ParamType::Uint(8),
+                    ParamType::Uint(32),
+                    ParamType::Uint(256),
+                ],
+                &[
+                    Token::Uint(quorum.into()),
+                    Token::Uint(reference_block_number.into()),
+                    Token::Uint(index.into()),
+                ],
+                ParamType::Uint(96),
+                block,
+            )
+            .await?;
+        stake
+            .into_uint()
+            .map(|stake| stake.low_u128())
+            .ok_or_else(|| {
+                CertVerifierError::Contract("getTotalStakeAtBlockNumberFromIndex".into())
+            })
+    }
+
+    async fn operator_stake(
+        &self,
+        quorum: u8,
+        operator_id: [u8; 32],
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<u128, CertVerifierError> {
+        let stake = self
+            .call(
+                self.stake_registry,
+                "getStakeAtBlockNumberAndIndex",
+                &[
+                    ParamType::Uint(8),
+                    ParamType::Uint(32),
+                    ParamType::FixedBytes(32),
+                    ParamType::Uint(256),
+                ],
+                &[
+                    Token::Uint(quorum.into()),
+                    Token::Uint(reference_block_number.into()),
+                    Token::FixedBytes(operator_id.to_vec()),
+                    Token::Uint(index.into()),
+                ],
+                ParamType::Uint(96),
+                block,
+            )
+            .await?;
+        stake
+            .into_uint()
+            .map(|stake| stake.low_u128())
+            .ok_or_else(|| CertVerifierError::Contract("getStakeAtBlockNumberAndIndex".into()))
+    }
+
+    async fn operator_quorum_bitmap(
+        &self,
+        operator_id: [u8; 32],
+        reference_block_number: u32,
+        index: u32,
+        block: BlockId,
+    ) -> Result<U256, CertVerifierError> {
+        let bitmap = self
+            .call(
+                self.registry_coordinator,
+                "getQuorumBitmapAtBlockNumberByIndex",
+                &[
+                    ParamType::FixedBytes(32),
+                    ParamType::Uint(32),
+                    ParamType::Uint(256),
+                ],
+                &[
+                    Token::FixedBytes(operator_id.to_vec()),
+                    Token::Uint(reference_block_number.into()),
+                    Token::Uint(index.into()),
+                ],
+                ParamType::Uint(192),
+                block,
+            )
+            .await?;
+        bitmap.into_uint().ok_or_else(|| {
+            CertVerifierError::Contract("getQuorumBitmapAtBlockNumberByIndex".into())
+        })
+    }
+}
+
+/// Signed and total stake of each quorum that signed a batch, in the order of the signed quorum numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuorumStakeTotals {
+    pub signed_stake: Vec<u128>,
+    pub total_stake: Vec<u128>,
+}
+
+/// Verifies [`EigenDACert`]s locally, doing the same checks as the EigenDACertVerifier contract without an `eth_call`.
+///
+/// The operator state the signatures are checked against is read from a [`QuorumStateSource`], so certs can be
+/// verified against a snapshot, without trusting an RPC. The security parameters of the blob version are not
+/// checked.
+#[derive(Debug)]
+pub struct LocalCertVerifier {
+    quorum_state: Box<dyn QuorumStateSource>,
+    /// Percentage of the stake of a quorum that must sign a batch for the quorum to be confirmed.
+    confirmation_threshold: u8,
+    /// Quorums that every blob must be dispersed to.
+    required_quorums: Vec<u8>,
+}
+
+impl LocalCertVerifier {
+    pub fn new(
+        quorum_state: Box<dyn QuorumStateSource>,
+        confirmation_threshold: u8,
+        required_quorums: Vec<u8>,
+    ) -> Self {
+        Self {
+            quorum_state,
+            confirmation_threshold,
+            required_quorums,
+        }
+    }
+
+    /// Verifies the cert with the operator state read at the latest block, see
+    /// [`LocalCertVerifier::verify_cert_v2_at_block`].
+    pub async fn verify_cert_v2(
+        &self,
+        eigenda_cert: &EigenDACert,
+    ) -> Result<(), CertVerifierError> {
+        self.verify_cert_v2_at_block(eigenda_cert, BlockNumber::Latest)
+            .await
+    }
+
+    /// Verifies the cert: the inclusion of the blob in the batch, the aggregate signature of the batch, and that
+    /// the quorums of the blob (and the required quorums) were signed by enough stake.
+    ///
+    /// The operator state is read at the given block, so that the result matches the one of the
+    /// EigenDACertVerifier contract at that block. The confirmation threshold and the required quorums are the
+    /// configured ones.
+    ///
+    /// If the cert is not valid, [`CertVerifierError::VerificationFailed`] is returned.
+    pub async fn verify_cert_v2_at_block(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: impl Into<BlockId>,
+    ) -> Result<(), CertVerifierError> {
+        verify_cert_inclusion(eigenda_cert)
+            .map_err(|e| CertVerificationFailure::InvalidInclusionProof(e.to_string()))?;
+
+        let stake_totals = self.check_signatures_at_block(eigenda_cert, block).await?;
+
+        let mut confirmed_quorums = U256::zero();
+        for (i, &quorum) in eigenda_cert.signed_quorum_numbers().iter().enumerate() {
+            let signed =
+                U256::from(stake_totals.signed_stake[i]) * U256::from(THRESHOLD_DENOMINATOR);
+            let required =
+                U256::from(stake_totals.total_stake[i]) * U256::from(self.confirmation_threshold);
+            if signed >= required {
+                confirmed_quorums |= U256::one() << (quorum as usize);
+            }
+        }
+
+        let blob_quorums = &eigenda_cert
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header
+            .quorum_numbers;
+        if blob_quorums
+            .iter()
+            .any(|&quorum| !confirmed_quorums.bit(quorum as usize))
+        {
+            return Err(CertVerificationFailure::BlobQuorumsNotConfirmed.into());
+        }
+        if self
+            .required_quorums
+            .iter()
+            .any(|quorum| !blob_quorums.contains(quorum))
+        {
+            return Err(CertVerificationFailure::RequiredQuorumsMissing.into());
+        }
+        Ok(())
+    }
+
+    /// Checks the aggregate signature of the batch of the cert with the operator state read at the latest block, see
+    /// [`LocalCertVerifier::check_signatures_at_block`].
+    pub async fn check_signatures(
+        &self,
+        eigenda_cert: &EigenDACert,
+    ) -> Result<QuorumStakeTotals, CertVerifierError> {
+        self.check_signatures_at_block(eigenda_cert, BlockNumber::Latest)
+            .await
+    }
+
+    /// Checks the aggregate signature of the batch of the cert, returning the signed stake of each signed quorum.
+    ///
+    /// This is `BLSSignatureChecker.checkSignatures`: the aggregate public key of the signers is the sum of the
+    /// quorum public keys minus the public keys of the non-signers (once per signed quorum they are registered in).
+    pub async fn check_signatures_at_block(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: impl Into<BlockId>,
+    ) -> Result<QuorumStakeTotals, CertVerifierError> {
+        let block = block.into();
+        let invalid = |reason: String| {
+            CertVerifierError::VerificationFailed(CertVerificationFailure::InvalidSignature(reason))
+        };
+        let params = eigenda_cert.non_signer_stakes_and_signature();
+        let quorums = eigenda_cert.signed_quorum_numbers();
+        let reference_block_number = eigenda_cert.reference_block_number();
+
+        if quorums.is_empty() {
+            return Err(invalid("no signed quorums".to_string()));
+        }
+        if params.quorum_apks.len() != quorums.len()
+            || params.quorum_apk_indices.len() != quorums.len()
+            || params.total_stake_indices.len() != quorums.len()
+            || params.non_signer_stake_indices.len() != quorums.len()
+        {
+            return Err(invalid(
+                "quorum params length does not match the signed quorums".to_string(),
+            ));
+        }
+        if params.non_signer_quorum_bitmap_indices.len() != params.non_signer_pubkeys.len() {
+            return Err(invalid(
+                "non signer params length does not match the non signer pubkeys".to_string(),
+            ));
+        }
+        let mut signing_quorums = U256::zero();
+        for &quorum in quorums {
+            signing_quorums |= U256::one() << (quorum as usize);
+        }
+
+        // non signers must be sorted by operator id, to reject duplicates
+        let mut non_signers = Vec::with_capacity(params.non_signer_pubkeys.len());
+        let mut apk = G1Projective::default();
+        for (pubkey, &bitmap_index) in params
+            .non_signer_pubkeys
+            .iter()
+            .zip(&params.non_signer_quorum_bitmap_indices)
+        {
+            let operator_id = operator_id(pubkey);
+            if let Some((previous_id, _)) = non_signers.last() {
+                if operator_id <= *previous_id {
+                    return Err(invalid("non signer pubkeys are not sorted".to_string()));
+                }
+            }
+            let bitmap = self
+                .quorum_state
+                .operator_quorum_bitmap(operator_id, reference_block_number, bitmap_index, block)
+                .await?;
+            let signing_quorum_count: u32 = (bitmap & signing_quorums)
+                .0
+                .iter()
+                .map(|limb| limb.count_ones())
+                .sum();
+            apk -= *pubkey * Fr::from(signing_quorum_count);
+            non_signers.push((operator_id, bitmap));
+        }
+
+        let mut stake_totals = QuorumStakeTotals {
+            signed_stake: Vec::with_capacity(quorums.len()),
+            total_stake: Vec::with_capacity(quorums.len()),
+        };
+        for (i, &quorum) in quorums.iter().enumerate() {
+            let quorum_apk = params.quorum_apks[i];
+            let apk_hash_at_block = self
+                .quorum_state
+                .quorum_apk_hash(
+                    quorum,
+                    reference_block_number,
+                    params.quorum_apk_indices[i],
+                    block,
+                )
+                .await?;
+            if apk_hash(&quorum_apk) != apk_hash_at_block {
+                return Err(invalid(format!(
+                    "apk of quorum {} does not match the registered one",
+                    quorum
+                )));
+            }
+            apk += quorum_apk;
+
+            let total_stake = self
+                .quorum_state
+                .total_stake(
+                    quorum,
+                    reference_block_number,
+                    params.total_stake_indices[i],
+                    block,
+                )
+                .await?;
+            let mut signed_stake = total_stake;
+            let mut stake_indices = params.non_signer_stake_indices[i].iter();
+            for (operator_id, bitmap) in &non_signers {
+                if !bitmap.bit(quorum as usize) {
+                    continue;
+                }
+                let stake_index = stake_indices.next().ok_or_else(|| {
+                    invalid(format!(
+                        "missing non signer stake index of quorum {}",
+                        quorum
+                    ))
+                })?;
+                let stake = self
+                    .quorum_state
+                    .operator_stake(
+                        quorum,
+                        *operator_id,
+                        reference_block_number,
+                        *stake_index,
+                        block,
+                    )
+                    .await?;
+                signed_stake = signed_stake.checked_sub(stake).ok_or_else(|| {
+                    invalid(format!(
+                        "non signer stake exceeds the stake of quorum {}",
+                        quorum
+                    ))
+                })?;
+            }
+            stake_totals.signed_stake.push(signed_stake);
+            stake_totals.total_stake.push(total_stake);
+        }
+
+        let message_hash = hash_batch_header(eigenda_cert.batch_header());
+        if !verify_aggregate_signature(message_hash, apk.into_affine(), params.apk_g2, params.sigma)
+        {
+            return Err(invalid("invalid aggregate signature".to_string()));
+        }
+        Ok(stake_totals)
+    }
+}
+
+#[async_trait::async_trait]
+impl CertVerification for LocalCertVerifier {
+    async fn verify_cert(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: BlockId,
+    ) -> Result<(), CertVerifierError> {
+        self.verify_cert_v2_at_block(eigenda_cert, block).await
+    }
+}
+
+/// Returns the id of an operator: the hash of its G1 public key.
+pub fn operator_id(pubkey: &G1Affine) -> [u8; 32] {
+    hash_g1_point(pubkey)
+}
+
+/// Hash of the batch header signed by the operators: `keccak256(abi.encode(batchHeader))`.
+pub fn hash_batch_header(batch_header: &BatchHeaderV2) -> [u8; 32] {
+    keccak256(ethabi::encode(&[
+        Token::FixedBytes(batch_header.batch_root.to_vec()),
+        Token::Uint(batch_header.reference_block_number.into()),
+    ]))
+}
+
+/// Checks the BLS aggregate signature `sigma` of `message_hash` by the signers with public keys `apk` (in G1)
+/// and `apk_g2` (in G2), as `BLSSignatureChecker.trySignatureAndApkVerification`.
+///
+/// Both public keys are checked to hold the same secret with a random linear combination, being `gamma` derived
+/// from all the inputs:
+///
+/// `e(sigma + gamma * apk, [1]_2) = e(H(m) + gamma * [1]_1, apk_g2)`
+fn verify_aggregate_signature(
+    message_hash: [u8; 32],
+    apk: G1Affine,
+    apk_g2: G2Affine,
+    sigma: G1Affine,
+) -> bool {
+    let mut gamma_input = message_hash.to_vec();
+    for coordinate in [
+        apk.x,
+        apk.y,
+        apk_g2.x.c1,
+        apk_g2.x.c0,
+        apk_g2.y.c1,
+        apk_g2.y.c0,
+        sigma.x,
+        sigma.y,
+    ] {
+        gamma_input.extend(coordinate.into_bigint().to_bytes_be());
+    }
+    let gamma = Fr::from_be_bytes_mod_order(&keccak256(&gamma_input));
+
+    let lhs = (apk * gamma + sigma).into_affine();
+    let rhs = (G1Affine::generator() * gamma + hash_to_g1(message_hash)).into_affine();
+    Bn254::pairing(lhs, G2Affine::generator()) == Bn254::pairing(rhs, apk_g2)
+}
+
+/// Maps a message hash to a G1 point by try-and-increment, as `BN254.hashToG1`.
+pub(crate) fn hash_to_g1(message_hash: [u8; 32]) -> G1Affine {
+    // y = beta^((p + 1) / 4) is a square root of beta, if it has one, since p = 3 mod 4
+    let mut exponent = Fq::MODULUS;
+    exponent.add_with_carry(&1u64.into());
+    exponent.div2();
+    exponent.div2();
+
+    let mut x = Fq::from_be_bytes_mod_order(&message_hash);
+    loop {
+        let beta = x.square() * x + Fq::from(3u64);
+        let y = beta.pow(exponent);
+        if y.square() == beta {
+            return G1Affine::new_unchecked(x, y);
+        }
+        x += Fq::ONE;
+    }
+}
+
+/// `BN254.hashG1Point`: `keccak256(abi.encodePacked(point.X, point.Y))`.
+pub(crate) fn hash_g1_point(point: &G1Affine) -> [u8; 32] {
+    let mut bytes = point.x.into_bigint().to_bytes_be();
+    bytes.extend(point.y.into_bigint().to_bytes_be());
+    keccak256(&bytes)
+}
+
+/// First 24 bytes of the hash of an aggregate public key, as stored in the `BLSApkRegistry`.
+fn apk_hash(apk: &G1Affine) -> [u8; 24] {
+    let mut hash = [0u8; 24];
+    hash.copy_from_slice(&hash_g1_point(apk)[..24]);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::relay_payload_retriever::tests::get_test_eigenda_cert;
+
+    use super::*;
+
+    const STAKE: u128 = 10;
+
+    /// Quorums of each test operator.
+    const OPERATOR_QUORUMS: [&[u8]; 4] = [&[0, 1], &[0], &[1], &[0, 1]];
+
+    /// Returns the test cert, signed by every test operator but `non_signers`, and a snapshot of the operator state.
+    fn signed_cert(non_signers: &[usize]) -> (EigenDACert, QuorumStateSnapshot) {
+        let mut rng = StdRng::seed_from_u64(17);
+        let mut eigenda_cert = get_test_eigenda_cert();
+        let reference_block_number = eigenda_cert.reference_block_number();
+        let mut snapshot = QuorumStateSnapshot {
+            reference_block_number,
+            ..Default::default()
+        };
+
+        let mut signer_secret = Fr::from(0u64);
+        let mut non_signer_pubkeys = Vec::new();
+        for (index, quorums) in OPERATOR_QUORUMS.iter().enumerate() {
+            let secret = Fr::rand(&mut rng);
+            let pubkey = (G1Affine::generator() * secret).into_affine();
+            let id = operator_id(&pubkey);
+            let mut bitmap = U256::zero();
+            for &quorum in quorums.iter() {
+                bitmap |= U256::one() << (quorum as usize);
+                let apk = snapshot
+                    .quorum_apks
+                    .entry(quorum)
+                    .or_insert(G1Affine::zero());
+                *apk = (*apk + pubkey).into_affine();
+                *snapshot.total_stakes.entry(quorum).or_default() += STAKE;
+                snapshot.operator_stakes.insert((quorum, id), STAKE);
+            }
+            snapshot.operator_quorum_bitmaps.insert(id, bitmap);
+
+            if non_signers.contains(&index) {
+                non_signer_pubkeys.push(pubkey);
+            } else {
+                // signers sign once per quorum they are in
+                signer_secret += secret * Fr::from(quorums.len() as u64);
+            }
+        }
+        non_signer_pubkeys.sort_by_key(operator_id);
+
+        let quorums = vec![0, 1];
+        let message_hash = hash_batch_header(eigenda_cert.batch_header());
+        let params = eigenda_cert.non_signer_stakes_and_signature_mut();
+        params.non_signer_quorum_bitmap_indices = vec![0; non_signer_pubkeys.len()];
+        params.non_signer_stake_indices = quorums
+            .iter()
+            .map(|&quorum| {
+                non_signers
+                    .iter()
+                    .filter(|&&index| OPERATOR_QUORUMS[index].contains(&quorum))
+                    .map(|_| 0)
+                    .collect()
+            })
+            .collect();
+        params.non_signer_pubkeys = non_signer_pubkeys;
+        params.quorum_apks = quorums
+            .iter()
+            .map(|quorum| snapshot.quorum_apks[quorum])
+            .collect();
+        params.quorum_apk_indices = vec![0; quorums.len()];
+        params.total_stake_indices = vec![0; quorums.len()];
+        params.apk_g2 = (G2Affine::generator() * signer_secret).into_affine();
+        params.sigma = (hash_to_g1(message_hash) * signer_secret).into_affine();
+        *eigenda_cert.signed_quorum_numbers_mut() = quorums;
+
+        (eigenda_cert, snapshot)
+    }
+
+    fn verifier(
+        snapshot: QuorumStateSnapshot,
+        confirmation_threshold: u8,
+        required_quorums: Vec<u8>,
+    ) -> LocalCertVerifier {
+        LocalCertVerifier::new(Box::new(snapshot), confirmation_threshold, required_quorums)
+    }
+
+    #[tokio::test]
+    async fn test_verify_cert_signed_by_all() {
+        let (eigenda_cert, snapshot) = signed_cert(&[]);
+        let verifier = verifier(snapshot, 100, vec![0, 1]);
+
+        let stake_totals = verifier.check_signatures(&eigenda_cert).await.unwrap();
+        assert_eq!(stake_totals.signed_stake, vec![3 * STAKE, 3 * STAKE]);
+        assert_eq!(stake_totals.total_stake, vec![3 * STAKE, 3 * STAKE]);
+        verifier.verify_cert_v2(&eigenda_cert).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_cert_with_non_signers() {
+        let (eigenda_cert, snapshot) = signed_cert(&[1, 3]);
+
+        let stake_totals = verifier(snapshot.clone(), 30, vec![])
+            .check_signatures(&eigenda_cert)
+            .await
+            .unwrap();
+        assert_eq!(stake_totals.signed_stake, vec![STAKE, 2 * STAKE]);
+
+        // quorum 0 is signed by a third of its stake
+        verifier(snapshot.clone(), 33, vec![])
+            .verify_cert_v2(&eigenda_cert)
+            .await
+            .unwrap();
+        assert!(matches!(
+            verifier(snapshot, 34, vec![])
+                .verify_cert_v2(&eigenda_cert)
+                .await,
+            Err(CertVerifierError::VerificationFailed(
+                CertVerificationFailure::BlobQuorumsNotConfirmed
+            ))
+        ));
+    }
+
+    /// Snapshot recording the blocks it is read at.
+    #[derive(Debug)]
+    struct BlockRecordingSnapshot {
+        snapshot: QuorumStateSnapshot,
+        blocks: Arc<Mutex<Vec<BlockId>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl QuorumStateSource for BlockRecordingSnapshot {
+        async fn quorum_apk_hash(
+            &self,
+            quorum: u8,
+            reference_block_number: u32,
+            index: u32,
+            block: BlockId,
+        ) -> Result<[u8; 24], CertVerifierError> {
+            self.blocks.lock().unwrap().push(block);
+            self.snapshot
+                .quorum_apk_hash(quorum, reference_block_number, index, block)
+                .await
+        }
+
+        async fn total_stake(
+            &self,
+            quorum: u8,
+            reference_block_number: u32,
+            index: u32,
+            block: BlockId,
+        ) -> Result<u128, CertVerifierError> {
+            self.blocks.lock().unwrap().push(block);
+            self.snapshot
+                .total_stake(quorum, reference_block_number, index, block)
+                .await
+        }
+
+        async fn operator_stake(
+            &self,
+            quorum: u8,
+            operator_id: [u8; 32],
+            reference_block_number: u32,
+            index: u32,
+            block: BlockId,
+        ) -> Result<u128, CertVerifierError> {
+            self.blocks.lock().unwrap().push(block);
+            self.snapshot
+                .operator_stake(quorum, operator_id, reference_block_number, index, block)
+                .await
+        }
+
+        async fn operator_quorum_bitmap(
+            &self,
+            operator_id: [u8; 32],
+            reference_block_number: u32,
+            index: u32,
+            block: BlockId,
+        ) -> Result<U256, CertVerifierError> {
+            self.blocks.lock().unwrap().push(block);
+            self.snapshot
+                .operator_quorum_bitmap(operator_id, reference_block_number, index, block)
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_cert_reads_state_at_block() {
+        let (eigenda_cert, snapshot) = signed_cert(&[1]);
+        let blocks = Arc::new(Mutex::new(Vec::new()));
+        let verifier = LocalCertVerifier::new(
+            Box::new(BlockRecordingSnapshot {
+                snapshot,
+                blocks: blocks.clone(),
+            }),
+            0,
+            vec![],
+        );
+
+        let block = BlockId::from(BlockNumber::Finalized);
+        CertVerification::verify_cert(&verifier, &eigenda_cert, block)
+            .await
+            .unwrap();
+        let blocks = blocks.lock().unwrap();
+        assert!(!blocks.is_empty());
+        assert!(blocks.iter().all(|read_block| *read_block == block));
+    }
+
+    #[tokio::test]
+    async fn test_verify_invalid_cert() {
+        let (eigenda_cert, snapshot) = signed_cert(&[2]);
+
+        // wrong signature
+        let mut wrong_sigma = eigenda_cert.clone();
+        let sigma = &mut wrong_sigma.non_signer_stakes_and_signature_mut().sigma;
+        *sigma = (*sigma + G1Affine::generator()).into_affine();
+        assert!(matches!(
+            verifier(snapshot.clone(), 0, vec![])
+                .verify_cert_v2(&wrong_sigma)
+                .await,
+            Err(CertVerifierError::VerificationFailed(
+                CertVerificationFailure::InvalidSignature(_)
+            ))
+        ));
+
+        // non signer omitted from the cert
+        let mut missing_non_signer = eigenda_cert.clone();
+        let params = missing_non_signer.non_signer_stakes_and_signature_mut();
+        params.non_signer_pubkeys.clear();
+        params.non_signer_quorum_bitmap_indices.clear();
+        params.non_signer_stake_indices = vec![vec![], vec![]];
+        assert!(matches!(
+            verifier(snapshot.clone(), 0, vec![])
+                .verify_cert_v2(&missing_non_signer)
+                .await,
+            Err(CertVerifierError::VerificationFailed(_))
+        ));
+
+        // quorum apk not matching the registered one
+        let mut other_snapshot = snapshot.clone();
+        other_snapshot.quorum_apks.insert(1, G1Affine::generator());
+        assert!(matches!(
+            verifier(other_snapshot, 0, vec![])
+                .verify_cert_v2(&eigenda_cert)
+                .await,
+            Err(CertVerifierError::VerificationFailed(_))
+        ));
+
+        // required quorum not in the blob
+        assert!(matches!(
+            verifier(snapshot.clone(), 0, vec![0, 2])
+                .verify_cert_v2(&eigenda_cert)
+                .await,
+            Err(CertVerifierError::VerificationFailed(
+                CertVerificationFailure::RequiredQuorumsMissing
+            ))
+        ));
+
+        // snapshot of another block
+        let mut other_snapshot = snapshot;
+        other_snapshot.reference_block_number += 1;
+        assert!(matches!(
+            verifier(other_snapshot, 0, vec![])
+                .verify_cert_v2(&eigenda_cert)
+                .await,
+            Err(CertVerifierError::QuorumState(_))
+        ));
+    }
+
+    /// Checks the aggregate signature of a cert of a known, dispersed blob in holesky chain.
+    #[tokio::test]
+    async fn test_check_holesky_cert_signatures() {
+        let eigenda_cert = get_test_eigenda_cert();
+        let params = eigenda_cert.non_signer_stakes_and_signature();
+
+        // the only non signer is registered in both signed quorums
+        let non_signer_id = operator_id(&params.non_signer_pubkeys[0]);
+        let snapshot = QuorumStateSnapshot {
+            reference_block_number: eigenda_cert.reference_block_number(),
+            quorum_apks: HashMap::from([(0, params.quorum_apks[0]), (1, params.quorum_apks[1])]),
+            total_stakes: HashMap::from([(0, 100), (1, 100)]),
+            operator_stakes: HashMap::from([((0, non_signer_id), 1), ((1, non_signer_id), 1)]),
+            operator_quorum_bitmaps: HashMap::from([(non_signer_id, U256::from(0b11))]),
+        };
+
+        let stake_totals = verifier(snapshot, 0, vec![])
+            .check_signatures(&eigenda_cert)
+            .await
+            .unwrap();
+        assert_eq!(stake_totals.signed_stake, vec![99, 99]);
+    }
+}
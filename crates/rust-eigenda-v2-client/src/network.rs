@@ -0,0 +1,203 @@
+use ethers::prelude::*;
+use hex_literal::hex;
+use rust_eigenda_v2_common::EigenDACertVersion;
+use tracing::instrument;
+
+use crate::{
+    cert_verifier_router::CertVerifierAddress,
+    core::PayloadForm,
+    errors::{ConversionError, NetworkError},
+    payload_disperser::{PayloadDisperserConfig, DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH},
+    relay_client::{RelayClientConfig, RelayKey},
+    utils::SecretUrl,
+};
+
+/// Max size of the GRPC messages of the relay clients built from a [`Network`].
+pub const DEFAULT_MAX_GRPC_MESSAGE_SIZE: usize = 100 * 1024 * 1024;
+
+pub use rust_eigenda_v2_common::network::{
+    HOLESKY_CHAIN_ID, HOLESKY_V2_DISPERSER_RPC_URL as HOLESKY_DISPERSER_RPC_URL, MAINNET_CHAIN_ID,
+    SEPOLIA_CHAIN_ID,
+};
+
+// The Holesky addresses are the deployment the integration tests of this crate run against. The v2 contracts of the
+// other networks are not preset until their addresses are checked against the published deployment list, use
+// `Network::Custom` for them.
+
+pub const HOLESKY_CERT_VERIFIER_ADDRESS: H160 =
+    H160(hex!("fe52fe1940858dcb6e12153e2104ad0fdfbe1162"));
+pub const HOLESKY_RELAY_REGISTRY_ADDRESS: H160 =
+    H160(hex!("ac8c6c7ee7572975454e2f0b5c720f9e74989254"));
+
+/// EigenDA deployment the clients connect to, with the canonical disperser and contract addresses of Holesky.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Network {
+    Holesky,
+    /// Any other deployment, such as a local devnet.
+    Custom(NetworkConfig),
+}
+
+/// Chain and EigenDA deployment of a [`Network`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkConfig {
+    /// Chain id the ETH RPC must be connected to.
+    pub chain_id: u64,
+    pub disperser_rpc: String,
+    pub cert_verifier_address: CertVerifierAddress,
+    /// Version of the certs verified by the cert verifier.
+    pub cert_version: EigenDACertVersion,
+    pub relay_registry_address: H160,
+}
+
+impl Network {
+    /// Returns the chain and EigenDA deployment of the network.
+    pub fn config(&self) -> NetworkConfig {
+        match self {
+            Network::Holesky => NetworkConfig {
+                chain_id: HOLESKY_CHAIN_ID,
+                disperser_rpc: HOLESKY_DISPERSER_RPC_URL.to_string(),
+                cert_verifier_address: CertVerifierAddress::Verifier(HOLESKY_CERT_VERIFIER_ADDRESS),
+                cert_version: EigenDACertVersion::V2,
+                relay_registry_address: HOLESKY_RELAY_REGISTRY_ADDRESS,
+            },
+            Network::Custom(config) => config.clone(),
+        }
+    }
+
+    /// Returns the chain id the ETH RPC must be connected to.
+    pub fn chain_id(&self) -> u64 {
+        self.config().chain_id
+    }
+
+    /// Returns the config of a [`crate::payload_disperser::PayloadDisperser`] dispersing payloads in coefficient form
+    /// with blob version 0 over TLS, verifying certs at the latest block.
+    pub fn payload_disperser_config(&self, eth_rpc_url: SecretUrl) -> PayloadDisperserConfig {
+        let config = self.config();
+        PayloadDisperserConfig {
+            polynomial_form: PayloadForm::Coeff,
+            blob_version: 0,
+            cert_verifier_address: config.cert_verifier_address,
+            eth_rpc_url,
+            disperser_rpc: config.disperser_rpc,
+            use_secure_grpc_flag: true,
+            settlement_layer_confirmation_depth: DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH,
+            cert_version: config.cert_version,
+            chain_id: Some(config.chain_id),
+        }
+    }
+
+    /// Returns the config of a [`crate::relay_client::RelayClient`] connecting to the relays with the given keys.
+    pub fn relay_client_config(
+        &self,
+        eth_rpc_url: SecretUrl,
+        relay_clients_keys: Vec<RelayKey>,
+    ) -> RelayClientConfig {
+        let config = self.config();
+        RelayClientConfig {
+            max_grpc_message_size: DEFAULT_MAX_GRPC_MESSAGE_SIZE,
+            relay_clients_keys,
+            relay_registry_address: config.relay_registry_address,
+            eth_rpc_url,
+            chain_id: Some(config.chain_id),
+        }
+    }
+}
+
+/// Checks that the ETH RPC is connected to the chain with the given id, so that a client configured for one network
+/// does not silently talk to the contracts of another.
+#[instrument(skip(eth_rpc_url))]
+pub(crate) async fn check_chain_id(
+    eth_rpc_url: &SecretUrl,
+    expected: u64,
+) -> Result<(), NetworkError> {
+    let url: String = eth_rpc_url.clone().try_into()?;
+    let provider = Provider::<Http>::try_from(url).map_err(ConversionError::UrlParse)?;
+    let actual = provider
+        .get_chainid()
+        .await
+        .map_err(|e| NetworkError::ChainId(e.to_string()))?;
+    if actual != U256::from(expected) {
+        return Err(NetworkError::ChainIdMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use url::Url;
+
+    use super::*;
+    use crate::tests::{get_test_holesky_rpc_url, HOLESKY_ETH_RPC_URL};
+
+    /// Checks that the RPC is connected to the chain of the network, and that its contracts are deployed.
+    async fn check_deployment(network: Network, eth_rpc_url: &str) {
+        let eth_rpc_url = SecretUrl::new(Url::from_str(eth_rpc_url).unwrap());
+        check_chain_id(&eth_rpc_url, network.chain_id())
+            .await
+            .unwrap();
+
+        let config = network.config();
+        let cert_verifier_address = match config.cert_verifier_address {
+            CertVerifierAddress::Verifier(address) | CertVerifierAddress::Router(address) => {
+                address
+            }
+        };
+        let url: String = eth_rpc_url.try_into().unwrap();
+        let provider = Provider::<Http>::try_from(url).unwrap();
+        for address in [cert_verifier_address, config.relay_registry_address] {
+            let code = provider.get_code(address, None).await.unwrap();
+            assert!(!code.is_empty(), "no contract deployed at {:?}", address);
+        }
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_holesky_deployment() {
+        check_deployment(Network::Holesky, HOLESKY_ETH_RPC_URL).await;
+    }
+
+    #[test]
+    fn test_network_configs() {
+        assert_eq!(Network::Holesky.chain_id(), 17000);
+
+        let custom = NetworkConfig {
+            chain_id: 31337,
+            disperser_rpc: "http://localhost:32003".to_string(),
+            cert_verifier_address: CertVerifierAddress::Router(H160::repeat_byte(1)),
+            cert_version: EigenDACertVersion::V3,
+            relay_registry_address: H160::repeat_byte(2),
+        };
+        let network = Network::Custom(custom.clone());
+        assert_eq!(network.config(), custom);
+
+        let payload_disperser_config = network.payload_disperser_config(get_test_holesky_rpc_url());
+        assert_eq!(payload_disperser_config.chain_id, Some(31337));
+        assert_eq!(payload_disperser_config.disperser_rpc, custom.disperser_rpc);
+        assert_eq!(
+            payload_disperser_config.cert_verifier_address,
+            custom.cert_verifier_address
+        );
+
+        let relay_client_config = network.relay_client_config(get_test_holesky_rpc_url(), vec![0]);
+        assert_eq!(relay_client_config.chain_id, Some(31337));
+        assert_eq!(
+            relay_client_config.relay_registry_address,
+            custom.relay_registry_address
+        );
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_check_chain_id() {
+        let eth_rpc_url = get_test_holesky_rpc_url();
+        check_chain_id(&eth_rpc_url, Network::Holesky.chain_id())
+            .await
+            .unwrap();
+        assert!(matches!(
+            check_chain_id(&eth_rpc_url, MAINNET_CHAIN_ID).await,
+            Err(NetworkError::ChainIdMismatch { expected: 1, .. })
+        ));
+    }
+}
@@ -1,24 +1,235 @@
-use ethereum_types::H160;
-use rust_eigenda_v2_common::EigenDACert;
+use ethers::types::{BlockId, BlockNumber, H160};
+use rust_eigenda_v2_common::{EigenDACert, EigenDACertVersion};
+use serde::Deserialize;
+use tracing::{debug, field, info, instrument};
 
 use crate::{
-    cert_verifier::CertVerifier,
-    core::{eigenda_cert::build_cert_from_reply, BlobKey, Payload, PayloadForm},
+    cert_verifier_router::{CertVerifierAddress, CertVerifierResolver},
+    config::{check_address, check_chain_id, check_secret_url, check_url, invalid, LoadableConfig},
+    core::{eigenda_cert::build_cert_from_reply, BlobKey, Payload, PayloadForm, MAX_BLOB_SIZE},
     disperser_client::{DisperserClient, DisperserClientConfig},
-    errors::{ConversionError, EigenClientError, PayloadDisperserError},
+    errors::{
+        CertVerifierError, ConfigError, ConversionError, EigenClientError, PayloadDisperserError,
+    },
     generated::disperser::v2::{BlobStatus, BlobStatusReply},
+    inclusion_proof::verify_cert_inclusion,
+    network::{self, Network},
     rust_eigenda_signers::{signers::private_key::Signer as PrivateKeySigner, Sign},
     utils::SecretUrl,
 };
 
-#[derive(Clone, Debug)]
+/// Default of [`PayloadDisperserConfig::settlement_layer_confirmation_depth`]: certs are verified at the latest block.
+pub const DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH: u32 = 0;
+
+/// Configuration of a [`PayloadDisperser`], built with [`PayloadDisperserConfig::builder`] or read with
+/// [`LoadableConfig`].
+///
+/// When read, the cert verifier is set by either `cert_verifier_address` or `cert_verifier_router_address`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "PayloadDisperserConfigFields")]
 pub struct PayloadDisperserConfig {
     pub polynomial_form: PayloadForm,
     pub blob_version: u16,
-    pub cert_verifier_address: H160,
+    /// Address of the cert verifier, or of the router that resolves the verifier of each cert from its reference
+    /// block number.
+    pub cert_verifier_address: CertVerifierAddress,
     pub eth_rpc_url: SecretUrl,
     pub disperser_rpc: String,
     pub use_secure_grpc_flag: bool,
+    /// Number of blocks the reference block of a cert must be behind the head of the chain before the cert is
+    /// verified, at the block at that depth. A value of 0 verifies certs at the latest block. Defaults to
+    /// [`DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH`] when built or read.
+    pub settlement_layer_confirmation_depth: u32,
+    /// Version of the certs built for the dispersed blobs. Must be the version verified by the cert verifier.
+    pub cert_version: EigenDACertVersion,
+    /// Chain id the ETH RPC must be connected to, checked when the [`PayloadDisperser`] is created. `None` skips the
+    /// check.
+    pub chain_id: Option<u64>,
+}
+
+impl PayloadDisperserConfig {
+    pub fn builder() -> PayloadDisperserConfigBuilder {
+        PayloadDisperserConfigBuilder::default()
+    }
+}
+
+impl LoadableConfig for PayloadDisperserConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_url("disperser_rpc", &self.disperser_rpc)?;
+        check_secret_url("eth_rpc_url", &self.eth_rpc_url)?;
+        let cert_verifier_address = match self.cert_verifier_address {
+            CertVerifierAddress::Verifier(address) | CertVerifierAddress::Router(address) => {
+                address
+            }
+        };
+        check_address("cert_verifier_address", cert_verifier_address)?;
+        check_chain_id(self.chain_id)
+    }
+}
+
+/// Builds a [`PayloadDisperserConfig`]. Payloads are dispersed in coefficient form with blob version 0 over TLS, and
+/// V2 certs are verified at the latest block, unless set otherwise.
+#[derive(Clone, Debug)]
+pub struct PayloadDisperserConfigBuilder {
+    polynomial_form: PayloadForm,
+    blob_version: u16,
+    cert_verifier_address: Option<CertVerifierAddress>,
+    eth_rpc_url: Option<SecretUrl>,
+    disperser_rpc: Option<String>,
+    use_secure_grpc_flag: bool,
+    settlement_layer_confirmation_depth: u32,
+    cert_version: EigenDACertVersion,
+    chain_id: Option<u64>,
+}
+
+impl Default for PayloadDisperserConfigBuilder {
+    fn default() -> Self {
+        Self {
+            polynomial_form: PayloadForm::Coeff,
+            blob_version: 0,
+            cert_verifier_address: None,
+            eth_rpc_url: None,
+            disperser_rpc: None,
+            use_secure_grpc_flag: true,
+            settlement_layer_confirmation_depth: DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH,
+            cert_version: EigenDACertVersion::V2,
+            chain_id: None,
+        }
+    }
+}
+
+impl PayloadDisperserConfigBuilder {
+    /// Sets the disperser, cert verifier, cert version and chain id of a network.
+    pub fn with_network(mut self, network: &Network) -> Self {
+        let network = network.config();
+        self.disperser_rpc = Some(network.disperser_rpc);
+        self.cert_verifier_address = Some(network.cert_verifier_address);
+        self.cert_version = network.cert_version;
+        self.chain_id = Some(network.chain_id);
+        self
+    }
+
+    pub fn with_polynomial_form(mut self, polynomial_form: PayloadForm) -> Self {
+        self.polynomial_form = polynomial_form;
+        self
+    }
+
+    pub fn with_blob_version(mut self, blob_version: u16) -> Self {
+        self.blob_version = blob_version;
+        self
+    }
+
+    pub fn with_cert_verifier_address(mut self, address: CertVerifierAddress) -> Self {
+        self.cert_verifier_address = Some(address);
+        self
+    }
+
+    pub fn with_eth_rpc_url(mut self, eth_rpc_url: SecretUrl) -> Self {
+        self.eth_rpc_url = Some(eth_rpc_url);
+        self
+    }
+
+    pub fn with_disperser_rpc(mut self, disperser_rpc: impl Into<String>) -> Self {
+        self.disperser_rpc = Some(disperser_rpc.into());
+        self
+    }
+
+    pub fn with_secure_grpc(mut self, use_secure_grpc_flag: bool) -> Self {
+        self.use_secure_grpc_flag = use_secure_grpc_flag;
+        self
+    }
+
+    pub fn with_settlement_layer_confirmation_depth(mut self, depth: u32) -> Self {
+        self.settlement_layer_confirmation_depth = depth;
+        self
+    }
+
+    pub fn with_cert_version(mut self, cert_version: EigenDACertVersion) -> Self {
+        self.cert_version = cert_version;
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn build(self) -> Result<PayloadDisperserConfig, ConfigError> {
+        let config = PayloadDisperserConfig {
+            polynomial_form: self.polynomial_form,
+            blob_version: self.blob_version,
+            cert_verifier_address: self
+                .cert_verifier_address
+                .ok_or(ConfigError::MissingField("cert_verifier_address"))?,
+            eth_rpc_url: self
+                .eth_rpc_url
+                .ok_or(ConfigError::MissingField("eth_rpc_url"))?,
+            disperser_rpc: self
+                .disperser_rpc
+                .ok_or(ConfigError::MissingField("disperser_rpc"))?,
+            use_secure_grpc_flag: self.use_secure_grpc_flag,
+            settlement_layer_confirmation_depth: self.settlement_layer_confirmation_depth,
+            cert_version: self.cert_version,
+            chain_id: self.chain_id,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Fields of a [`PayloadDisperserConfig`] as read from files and environment variables.
+#[derive(Deserialize)]
+struct PayloadDisperserConfigFields {
+    polynomial_form: Option<PayloadForm>,
+    blob_version: Option<u16>,
+    cert_verifier_address: Option<H160>,
+    cert_verifier_router_address: Option<H160>,
+    eth_rpc_url: Option<SecretUrl>,
+    disperser_rpc: Option<String>,
+    use_secure_grpc_flag: Option<bool>,
+    settlement_layer_confirmation_depth: Option<u32>,
+    cert_version: Option<EigenDACertVersion>,
+    chain_id: Option<u64>,
+}
+
+impl TryFrom<PayloadDisperserConfigFields> for PayloadDisperserConfig {
+    type Error = ConfigError;
+
+    fn try_from(fields: PayloadDisperserConfigFields) -> Result<Self, Self::Error> {
+        let mut builder = PayloadDisperserConfig::builder();
+        builder.cert_verifier_address =
+            match (
+                fields.cert_verifier_address,
+                fields.cert_verifier_router_address,
+            ) {
+                (Some(_), Some(_)) => return Err(invalid(
+                    "cert_verifier_address",
+                    "only one of cert_verifier_address and cert_verifier_router_address may be set",
+                )),
+                (Some(address), None) => Some(CertVerifierAddress::Verifier(address)),
+                (None, Some(address)) => Some(CertVerifierAddress::Router(address)),
+                (None, None) => None,
+            };
+        builder.eth_rpc_url = fields.eth_rpc_url;
+        builder.disperser_rpc = fields.disperser_rpc;
+        builder.chain_id = fields.chain_id;
+        if let Some(polynomial_form) = fields.polynomial_form {
+            builder.polynomial_form = polynomial_form;
+        }
+        if let Some(blob_version) = fields.blob_version {
+            builder.blob_version = blob_version;
+        }
+        if let Some(use_secure_grpc_flag) = fields.use_secure_grpc_flag {
+            builder.use_secure_grpc_flag = use_secure_grpc_flag;
+        }
+        if let Some(depth) = fields.settlement_layer_confirmation_depth {
+            builder.settlement_layer_confirmation_depth = depth;
+        }
+        if let Some(cert_version) = fields.cert_version {
+            builder.cert_version = cert_version;
+        }
+        builder.build()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -26,13 +237,15 @@ pub struct PayloadDisperserConfig {
 pub struct PayloadDisperser<S = PrivateKeySigner> {
     config: PayloadDisperserConfig,
     disperser_client: DisperserClient<S>,
-    cert_verifier: CertVerifier<S>,
-    required_quorums: Vec<u8>,
+    cert_verifier: CertVerifierResolver<S>,
+    /// Quorums required by the fixed cert verifier. Not set with a router, as they may change with the verifier.
+    required_quorums: Option<Vec<u8>>,
 }
 
 impl<S> PayloadDisperser<S> {
-    const BLOB_SIZE_LIMIT: usize = 1024 * 1024 * 16; // 16 MB
+    const BLOB_SIZE_LIMIT: usize = MAX_BLOB_SIZE;
     /// Creates a [`PayloadDisperser`] from the specified configuration.
+    #[instrument(skip_all, fields(disperser_rpc = %payload_config.disperser_rpc))]
     pub async fn new(
         payload_config: PayloadDisperserConfig,
         signer: S,
@@ -40,18 +253,30 @@ impl<S> PayloadDisperser<S> {
     where
         S: Sign + Clone,
     {
+        if let Some(chain_id) = payload_config.chain_id {
+            network::check_chain_id(&payload_config.eth_rpc_url, chain_id).await?;
+        }
         let disperser_config = DisperserClientConfig {
             disperser_rpc: payload_config.disperser_rpc.clone(),
             signer: signer.clone(),
             use_secure_grpc_flag: payload_config.use_secure_grpc_flag,
         };
         let disperser_client = DisperserClient::new(disperser_config).await?;
-        let cert_verifier = CertVerifier::new(
+        let cert_verifier = CertVerifierResolver::new(
             payload_config.cert_verifier_address,
             payload_config.eth_rpc_url.clone(),
             signer,
         )?;
-        let required_quorums = cert_verifier.quorum_numbers_required().await?;
+        let required_quorums = match payload_config.cert_verifier_address {
+            CertVerifierAddress::Verifier(_) => Some(
+                cert_verifier
+                    .current_cert_verifier()
+                    .await?
+                    .quorum_numbers_required()
+                    .await?,
+            ),
+            CertVerifierAddress::Router(_) => None,
+        };
         Ok(PayloadDisperser {
             disperser_client,
             config: payload_config.clone(),
@@ -61,20 +286,32 @@ impl<S> PayloadDisperser<S> {
     }
 
     /// Executes the dispersal of a payload, returning the associated blob key
+    #[instrument(skip_all, fields(blob_key = field::Empty))]
     pub async fn send_payload(&self, payload: Payload) -> Result<BlobKey, PayloadDisperserError>
     where
         S: Sign,
     {
         let blob = payload.to_blob(self.config.polynomial_form)?;
+        let required_quorums = match &self.required_quorums {
+            Some(required_quorums) => required_quorums.clone(),
+            None => {
+                self.cert_verifier
+                    .current_cert_verifier()
+                    .await?
+                    .quorum_numbers_required()
+                    .await?
+            }
+        };
 
         let (blob_status, blob_key) = self
             .disperser_client
             .disperse_blob(
                 &blob.serialize(),
                 self.config.blob_version,
-                &self.required_quorums,
+                &required_quorums,
             )
             .await?;
+        tracing::Span::current().record("blob_key", field::display(blob_key.to_hex()));
 
         match blob_status {
             BlobStatus::Unknown | BlobStatus::Failed => {
@@ -89,7 +326,9 @@ impl<S> PayloadDisperser<S> {
     }
 
     /// Retrieves the inclusion data for a given blob key
-    /// If the requested blob is still not complete, returns None
+    /// If the requested blob is still not complete, or its reference block is not yet
+    /// `settlement_layer_confirmation_depth` blocks deep, returns None
+    #[instrument(skip_all, fields(blob_key = %blob_key.to_hex(), status = field::Empty))]
     pub async fn get_inclusion_data(
         &self,
         blob_key: &BlobKey,
@@ -105,23 +344,65 @@ impl<S> PayloadDisperser<S> {
 
         let blob_status = BlobStatus::try_from(status.status)
             .map_err(|e| EigenClientError::PayloadDisperser(PayloadDisperserError::Decode(e)))?;
+        tracing::Span::current().record("status", field::debug(blob_status));
+        debug!("polled blob status");
         match blob_status {
             BlobStatus::Unknown | BlobStatus::Failed => Err(PayloadDisperserError::BlobStatus)?,
             BlobStatus::Encoded | BlobStatus::GatheringSignatures | BlobStatus::Queued => Ok(None),
             BlobStatus::Complete => {
                 let eigenda_cert = self.build_eigenda_cert(&status).await?;
+                verify_cert_inclusion(&eigenda_cert).map_err(|e| {
+                    EigenClientError::PayloadDisperser(PayloadDisperserError::InclusionProof(e))
+                })?;
+                let cert_verifier_error =
+                    |e| EigenClientError::PayloadDisperser(PayloadDisperserError::CertVerifier(e));
+                let block: BlockId = match self
+                    .confirmed_block_number()
+                    .await
+                    .map_err(cert_verifier_error)?
+                {
+                    Some(block_number) => {
+                        // the contract only verifies certs whose reference block is before the current one
+                        let reference_block_number =
+                            u64::from(eigenda_cert.reference_block_number());
+                        if reference_block_number >= block_number {
+                            debug!(
+                                reference_block_number,
+                                confirmed_block_number = block_number,
+                                "cert is not yet confirmed"
+                            );
+                            return Ok(None);
+                        }
+                        block_number.into()
+                    }
+                    None => BlockNumber::Latest.into(),
+                };
                 self.cert_verifier
-                    .verify_cert_v2(&eigenda_cert)
+                    .verify_cert_v2_at_block(&eigenda_cert, block)
                     .await
-                    .map_err(|e| {
-                        EigenClientError::PayloadDisperser(PayloadDisperserError::CertVerifier(e))
-                    })?;
+                    .map_err(cert_verifier_error)?;
+                info!("blob certified");
                 Ok(Some(eigenda_cert))
             }
         }
     }
 
-    /// Creates a new EigenDACert from a BlobStatusReply, and NonSignerStakesAndSignature
+    /// Returns the number of the block `settlement_layer_confirmation_depth` blocks deep, at which certs are
+    /// verified, or None if certs are verified at the latest block.
+    async fn confirmed_block_number(&self) -> Result<Option<u64>, CertVerifierError>
+    where
+        S: Sign,
+    {
+        let depth = self.config.settlement_layer_confirmation_depth;
+        if depth == 0 {
+            return Ok(None);
+        }
+        let latest_block_number = self.cert_verifier.block_number().await?;
+        Ok(confirmed_block_number(latest_block_number, depth))
+    }
+
+    /// Creates a new EigenDACert of the configured version from a BlobStatusReply, and NonSignerStakesAndSignature
+    #[instrument(skip_all)]
     pub async fn build_eigenda_cert(
         &self,
         status: &BlobStatusReply,
@@ -139,15 +420,34 @@ impl<S> PayloadDisperser<S> {
                 ))
             }
         };
+        let reference_block_number = signed_batch
+            .header
+            .as_ref()
+            .map(|header| header.reference_block_number)
+            .and_then(|reference_block_number| u32::try_from(reference_block_number).ok())
+            .ok_or_else(|| {
+                EigenClientError::PayloadDisperser(PayloadDisperserError::Conversion(
+                    ConversionError::SignedBatch(
+                        "Missing or invalid reference block number".to_string(),
+                    ),
+                ))
+            })?;
+        let cert_verifier_error =
+            |e| EigenClientError::PayloadDisperser(PayloadDisperserError::CertVerifier(e));
         let non_signer_stakes_and_signature = self
             .cert_verifier
+            .cert_verifier_at(reference_block_number, BlockNumber::Latest)
+            .await
+            .map_err(cert_verifier_error)?
             .get_non_signer_stakes_and_signature(signed_batch)
             .await
-            .map_err(|e| {
-                EigenClientError::PayloadDisperser(PayloadDisperserError::CertVerifier(e))
-            })?;
+            .map_err(cert_verifier_error)?;
 
-        let cert = build_cert_from_reply(status, non_signer_stakes_and_signature)?;
+        let cert = build_cert_from_reply(
+            status,
+            non_signer_stakes_and_signature,
+            self.config.cert_version,
+        )?;
 
         Ok(cert)
     }
@@ -158,17 +458,72 @@ impl<S> PayloadDisperser<S> {
     }
 }
 
+/// Returns the number of the block `depth` blocks deep from the latest one, or None for a depth of 0. As in v1, a
+/// depth of 1 is the latest block. Near genesis, the depth saturates at block 0.
+fn confirmed_block_number(latest_block_number: u64, depth: u32) -> Option<u64> {
+    let depth = u64::from(depth).checked_sub(1)?;
+    Some(latest_block_number.saturating_sub(depth))
+}
+
 #[cfg(test)]
 mod tests {
+    use rust_eigenda_v2_common::EigenDACertVersion;
+
     use crate::{
+        config::LoadableConfig,
         core::{Payload, PayloadForm},
-        payload_disperser::{PayloadDisperser, PayloadDisperserConfig},
+        network::HOLESKY_CHAIN_ID,
+        payload_disperser::{
+            confirmed_block_number, PayloadDisperser, PayloadDisperserConfig,
+            DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH,
+        },
         tests::{
             get_test_holesky_rpc_url, get_test_private_key_signer, CERT_VERIFIER_ADDRESS,
             HOLESKY_DISPERSER_RPC_URL,
         },
     };
 
+    #[test]
+    fn test_confirmed_block_number() {
+        // a depth of 0 verifies at the latest block, whatever its number
+        assert_eq!(confirmed_block_number(100, 0), None);
+        assert_eq!(confirmed_block_number(0, 0), None);
+
+        assert_eq!(confirmed_block_number(100, 1), Some(100));
+        assert_eq!(confirmed_block_number(100, 5), Some(96));
+        assert_eq!(confirmed_block_number(100, 101), Some(0));
+        assert_eq!(confirmed_block_number(u64::MAX, 2), Some(u64::MAX - 1));
+
+        // near genesis the depth saturates at block 0
+        assert_eq!(confirmed_block_number(0, 1), Some(0));
+        assert_eq!(confirmed_block_number(3, 10), Some(0));
+        assert_eq!(confirmed_block_number(100, u32::MAX), Some(0));
+    }
+
+    #[test]
+    fn test_default_confirmation_depth() {
+        let built = PayloadDisperserConfig::builder()
+            .with_cert_verifier_address(CERT_VERIFIER_ADDRESS.into())
+            .with_eth_rpc_url(get_test_holesky_rpc_url())
+            .with_disperser_rpc(HOLESKY_DISPERSER_RPC_URL)
+            .build()
+            .unwrap();
+        assert_eq!(
+            built.settlement_layer_confirmation_depth,
+            DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH
+        );
+
+        let read = PayloadDisperserConfig::from_toml(&format!(
+            "cert_verifier_address = \"{:?}\"\neth_rpc_url = \"https://example.com\"\ndisperser_rpc = \"{}\"\n",
+            CERT_VERIFIER_ADDRESS, HOLESKY_DISPERSER_RPC_URL
+        ))
+        .unwrap();
+        assert_eq!(
+            read.settlement_layer_confirmation_depth,
+            DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH
+        );
+    }
+
     #[ignore = "depends on external RPC"]
     #[tokio::test]
     async fn test_disperse_payload() {
@@ -177,10 +532,13 @@ mod tests {
         let payload_config = PayloadDisperserConfig {
             polynomial_form: PayloadForm::Coeff,
             blob_version: 0,
-            cert_verifier_address: CERT_VERIFIER_ADDRESS,
+            cert_verifier_address: CERT_VERIFIER_ADDRESS.into(),
             eth_rpc_url: get_test_holesky_rpc_url(),
             disperser_rpc: HOLESKY_DISPERSER_RPC_URL.to_string(),
             use_secure_grpc_flag: false,
+            settlement_layer_confirmation_depth: DEFAULT_SETTLEMENT_LAYER_CONFIRMATION_DEPTH,
+            cert_version: EigenDACertVersion::V2,
+            chain_id: Some(HOLESKY_CHAIN_ID),
         };
 
         let payload_disperser =
@@ -0,0 +1,544 @@
+//! Startup self-check of the configuration of the v2 clients.
+//!
+//! A misconfigured client otherwise fails late: a wrong cert verifier address on the first
+//! `get_inclusion_data`, a wrong relay registry inside [`RelayClient::new`] and bad SRS files on the first
+//! retrieval. [`preflight`] checks every part of the configuration up front and reports each result on its own,
+//! so that every problem is found in a single run.
+//!
+//! [`RelayClient::new`]: crate::relay_client::RelayClient::new
+use std::{
+    fmt,
+    future::Future,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ethers::prelude::*;
+use num_bigint::BigUint;
+use rust_eigenda_signers::Sign;
+use tonic::transport::Channel;
+
+use crate::{
+    cert_verifier::CertVerifier,
+    cert_verifier_router::{CertVerifierAddress, CertVerifierRouter},
+    core::{BYTES_PER_SYMBOL, MAX_BLOB_SIZE},
+    disperser_client::{DisperserClient, DisperserClientConfig},
+    errors::ConversionError,
+    generated::disperser::v2::GetPaymentStateReply,
+    network,
+    payload_disperser::PayloadDisperserConfig,
+    relay_client::RelayClientConfig,
+    relay_registry::RelayRegistry,
+    srs::{SRSConfig, Srs},
+    utils::SecretUrl,
+};
+
+/// Time each check of [`preflight`] is given before it fails.
+pub const DEFAULT_PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What [`preflight`] checks. The relays and the SRS are only checked if their configs are set.
+#[derive(Clone, Debug)]
+pub struct PreflightConfig {
+    pub payload_disperser: PayloadDisperserConfig,
+    pub relay_client: Option<RelayClientConfig>,
+    pub srs: Option<SRSConfig>,
+    /// Size in bytes of the largest blob the SRS must be able to commit to.
+    pub max_blob_size: usize,
+    /// Time each check is given before it fails.
+    pub timeout: Duration,
+}
+
+impl PreflightConfig {
+    /// Creates a config checking the disperser and cert verifier, and an SRS for blobs of up to [`MAX_BLOB_SIZE`]
+    /// bytes if one is set.
+    pub fn new(payload_disperser: PayloadDisperserConfig) -> Self {
+        Self {
+            payload_disperser,
+            relay_client: None,
+            srs: None,
+            max_blob_size: MAX_BLOB_SIZE,
+            timeout: DEFAULT_PREFLIGHT_TIMEOUT,
+        }
+    }
+
+    pub fn with_relay_client(mut self, relay_client: RelayClientConfig) -> Self {
+        self.relay_client = Some(relay_client);
+        self
+    }
+
+    pub fn with_srs(mut self, srs: SRSConfig) -> Self {
+        self.srs = Some(srs);
+        self
+    }
+
+    pub fn with_max_blob_size(mut self, max_blob_size: usize) -> Self {
+        self.max_blob_size = max_blob_size;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Result of a single check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    /// Not run, as it depends on a check that failed or on a setting that is not configured.
+    Skipped,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Passed => write!(f, "PASS"),
+            CheckStatus::Failed => write!(f, "FAIL"),
+            CheckStatus::Skipped => write!(f, "SKIP"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreflightCheck {
+    /// What was checked, e.g. `relay 0`.
+    pub name: String,
+    pub status: CheckStatus,
+    /// What was found, or why the check failed or was skipped.
+    pub detail: String,
+}
+
+/// Results of the checks of [`preflight`], in the order they were run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreflightReport {
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    /// Returns whether no check failed.
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &PreflightCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Failed)
+    }
+
+    fn push(&mut self, name: impl Into<String>, result: Result<String, String>) -> bool {
+        let (status, detail) = match result {
+            Ok(detail) => (CheckStatus::Passed, detail),
+            Err(detail) => (CheckStatus::Failed, detail),
+        };
+        self.checks.push(PreflightCheck {
+            name: name.into(),
+            status,
+            detail,
+        });
+        status == CheckStatus::Passed
+    }
+
+    fn skip(&mut self, name: impl Into<String>, reason: impl Into<String>) {
+        self.checks.push(PreflightCheck {
+            name: name.into(),
+            status: CheckStatus::Skipped,
+            detail: reason.into(),
+        });
+    }
+}
+
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{}] {}: {}", check.status, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the configuration of the clients against the network, returning the result of every check:
+///
+/// - the ETH RPC is connected to the configured chain,
+/// - the disperser is reachable and the signer can pay for dispersals, with an active reservation or an on-demand
+///   deposit left,
+/// - the cert verifier (and router) addresses hold contracts with the expected interface, and the verifier
+///   requires a non-empty set of quorums,
+/// - the relay registry address holds a contract, and every relay key resolves to a reachable relay,
+/// - the SRS holds enough G1 points to commit to blobs of the max blob size.
+///
+/// Checks that depend on a failed one are skipped. Errors never include the ETH RPC URL, which may hold an API key.
+pub async fn preflight<S>(config: &PreflightConfig, signer: S) -> PreflightReport
+where
+    S: Sign + Clone,
+{
+    let mut report = PreflightReport::default();
+    check_disperser(config, signer.clone(), &mut report).await;
+    check_cert_verifier(config, signer.clone(), &mut report).await;
+    match &config.relay_client {
+        Some(relay_client) => check_relays(relay_client, config.timeout, signer, &mut report).await,
+        None => report.skip("relays", "no relay client config"),
+    }
+    match &config.srs {
+        Some(srs) => check_srs(srs, config.max_blob_size, &mut report).await,
+        None => report.skip("srs", "no SRS config"),
+    }
+    report
+}
+
+async fn check_disperser<S>(config: &PreflightConfig, signer: S, report: &mut PreflightReport)
+where
+    S: Sign,
+{
+    let disperser_config = &config.payload_disperser;
+    match disperser_config.chain_id {
+        Some(chain_id) => {
+            let result = with_timeout(config.timeout, async {
+                network::check_chain_id(&disperser_config.eth_rpc_url, chain_id)
+                    .await
+                    .map(|_| format!("connected to chain {}", chain_id))
+                    .map_err(|e| e.to_string())
+            })
+            .await;
+            report.push("chain id", redact(result, &disperser_config.eth_rpc_url));
+        }
+        None => report.skip("chain id", "no chain id configured"),
+    }
+
+    let client_config = DisperserClientConfig {
+        disperser_rpc: disperser_config.disperser_rpc.clone(),
+        signer,
+        use_secure_grpc_flag: disperser_config.use_secure_grpc_flag,
+    };
+    let client = with_timeout(config.timeout, async {
+        DisperserClient::new(client_config)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await;
+    let client = match client {
+        Ok(client) => {
+            report.push(
+                "disperser",
+                Ok(format!("connected to {}", disperser_config.disperser_rpc)),
+            );
+            client
+        }
+        Err(e) => {
+            report.push("disperser", Err(e));
+            report.skip("payment state", "the disperser is not reachable");
+            return;
+        }
+    };
+
+    let result = with_timeout(config.timeout, async {
+        let state = client.payment_state().await.map_err(|e| e.to_string())?;
+        check_payment_state(&state)
+    })
+    .await;
+    report.push("payment state", result);
+}
+
+/// Checks that the account has an active reservation or an on-demand deposit that is not used up.
+fn check_payment_state(state: &GetPaymentStateReply) -> Result<String, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    if let Some(reservation) = &state.reservation {
+        let start = u64::from(reservation.start_timestamp);
+        let end = u64::from(reservation.end_timestamp);
+        if start <= now && now < end {
+            return Ok(format!(
+                "reservation of {} symbols/s for quorums {:?}",
+                reservation.symbols_per_second, reservation.quorum_numbers
+            ));
+        }
+    }
+    let deposit = BigUint::from_bytes_be(&state.onchain_cumulative_payment);
+    let used = BigUint::from_bytes_be(&state.cumulative_payment);
+    if deposit > used {
+        return Ok(format!(
+            "on-demand deposit of {} wei, {} wei used",
+            deposit, used
+        ));
+    }
+    Err("no active reservation and no on-demand deposit left".to_string())
+}
+
+async fn check_cert_verifier<S>(config: &PreflightConfig, signer: S, report: &mut PreflightReport)
+where
+    S: Sign + Clone,
+{
+    let eth_rpc_url = &config.payload_disperser.eth_rpc_url;
+    let verifier_address = match config.payload_disperser.cert_verifier_address {
+        CertVerifierAddress::Verifier(address) => address,
+        CertVerifierAddress::Router(router_address) => {
+            let result = check_code(eth_rpc_url, router_address, config.timeout).await;
+            if !report.push("cert verifier router contract", result) {
+                report.skip("cert verifier contract", "the router has no code");
+                return;
+            }
+            // the verifier of the certs dispersed now is the one of the latest reference block number
+            let result = with_timeout(config.timeout, async {
+                let router =
+                    CertVerifierRouter::new(router_address, eth_rpc_url.clone(), signer.clone())
+                        .map_err(|e| e.to_string())?;
+                let block_number = router.block_number().await.map_err(|e| e.to_string())?;
+                let reference_block_number = u32::try_from(block_number)
+                    .map_err(|_| format!("block number {} overflows u32", block_number))?;
+                router
+                    .get_cert_verifier_at(reference_block_number, block_number)
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .await;
+            match redact(result, eth_rpc_url) {
+                Ok(address) if !address.is_zero() => address,
+                Ok(_) => {
+                    report.push(
+                        "cert verifier router interface",
+                        Err("no cert verifier for the latest block".to_string()),
+                    );
+                    return;
+                }
+                Err(e) => {
+                    report.push("cert verifier router interface", Err(e));
+                    return;
+                }
+            }
+        }
+    };
+
+    let result = check_code(eth_rpc_url, verifier_address, config.timeout).await;
+    if !report.push("cert verifier contract", result) {
+        report.skip("required quorums", "the cert verifier has no code");
+        return;
+    }
+    let result = with_timeout(config.timeout, async {
+        let cert_verifier = CertVerifier::new(verifier_address, eth_rpc_url.clone(), signer)
+            .map_err(|e| e.to_string())?;
+        let quorums = cert_verifier
+            .quorum_numbers_required()
+            .await
+            .map_err(|e| e.to_string())?;
+        if quorums.is_empty() {
+            return Err("quorumNumbersRequired is empty".to_string());
+        }
+        Ok(format!("quorums {:?}", quorums))
+    })
+    .await;
+    report.push("required quorums", redact(result, eth_rpc_url));
+}
+
+async fn check_relays<S>(
+    config: &RelayClientConfig,
+    timeout: Duration,
+    signer: S,
+    report: &mut PreflightReport,
+) where
+    S: Sign,
+{
+    let eth_rpc_url = &config.eth_rpc_url;
+    let result = check_code(eth_rpc_url, config.relay_registry_address, timeout).await;
+    if !report.push("relay registry contract", result) {
+        report.skip("relays", "the relay registry has no code");
+        return;
+    }
+    let relay_registry =
+        match RelayRegistry::new(config.relay_registry_address, eth_rpc_url.clone(), signer) {
+            Ok(relay_registry) => relay_registry,
+            Err(e) => {
+                report.push("relays", redact(Err(e.to_string()), eth_rpc_url));
+                return;
+            }
+        };
+
+    for relay_key in &config.relay_clients_keys {
+        let result = with_timeout(timeout, async {
+            let url = relay_registry
+                .get_url_from_relay_key(*relay_key)
+                .await
+                .map_err(|e| e.to_string())?;
+            // the registry returns an empty URL for unregistered keys
+            if url == "https://" {
+                return Err("not registered".to_string());
+            }
+            let endpoint =
+                Channel::from_shared(url.clone()).map_err(|_| format!("invalid URL {}", url))?;
+            endpoint
+                .connect()
+                .await
+                .map_err(|e| format!("{} is not reachable: {}", url, e))?;
+            Ok(format!("reachable at {}", url))
+        })
+        .await;
+        report.push(format!("relay {}", relay_key), redact(result, eth_rpc_url));
+    }
+}
+
+async fn check_srs(config: &SRSConfig, max_blob_size: usize, report: &mut PreflightReport) {
+    let required = max_blob_size.div_ceil(BYTES_PER_SYMBOL);
+    let result = async {
+        let srs = Srs::load(config).await.map_err(|e| e.to_string())?;
+        let available = srs.g1_points_available();
+        if available < required {
+            return Err(format!(
+                "{} G1 points, blobs of {} bytes need {}",
+                available, max_blob_size, required
+            ));
+        }
+        // a corrupted file is only noticed once its points are decompressed
+        let _ = srs
+            .g1_point_at(required.saturating_sub(1))
+            .map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} G1 points, enough for blobs of {} bytes",
+            available, max_blob_size
+        ))
+    }
+    .await;
+    report.push("srs", result);
+}
+
+/// Checks that there is a contract deployed at the address.
+async fn check_code(
+    eth_rpc_url: &SecretUrl,
+    address: H160,
+    timeout: Duration,
+) -> Result<String, String> {
+    let result = with_timeout(timeout, async {
+        let url: String = eth_rpc_url
+            .clone()
+            .try_into()
+            .map_err(|e: ConversionError| e.to_string())?;
+        let provider = Provider::<Http>::try_from(url).map_err(|e| e.to_string())?;
+        let code = provider
+            .get_code(address, None)
+            .await
+            .map_err(|e| e.to_string())?;
+        if code.is_empty() {
+            return Err(format!("no contract at {:?}", address));
+        }
+        Ok(format!("contract at {:?}", address))
+    })
+    .await;
+    redact(result, eth_rpc_url)
+}
+
+async fn with_timeout<T>(
+    timeout: Duration,
+    check: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    tokio::time::timeout(timeout, check)
+        .await
+        .map_err(|_| format!("timed out after {}s", timeout.as_secs_f32()))?
+}
+
+/// Removes the ETH RPC URL from the error of a check, as RPC errors may include it.
+fn redact<T>(result: Result<T, String>, eth_rpc_url: &SecretUrl) -> Result<T, String> {
+    result.map_err(|e| e.replace(eth_rpc_url.expose_secret(), "[REDACTED]"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use url::Url;
+
+    use super::*;
+    use crate::{
+        generated::disperser::v2::Reservation,
+        network::Network,
+        tests::{get_test_holesky_rpc_url, get_test_private_key_signer},
+    };
+
+    #[test]
+    fn test_check_payment_state() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as u32;
+        let mut state = GetPaymentStateReply {
+            reservation: Some(Reservation {
+                symbols_per_second: 100,
+                start_timestamp: now - 10,
+                end_timestamp: now + 10,
+                quorum_numbers: vec![0, 1],
+                quorum_splits: vec![50, 50],
+            }),
+            ..Default::default()
+        };
+        assert!(check_payment_state(&state).is_ok());
+
+        // an expired reservation falls back to the on-demand deposit
+        state.reservation.as_mut().unwrap().end_timestamp = now - 1;
+        assert!(check_payment_state(&state).is_err());
+        state.onchain_cumulative_payment = vec![1, 0];
+        state.cumulative_payment = vec![255];
+        assert_eq!(
+            check_payment_state(&state).unwrap(),
+            "on-demand deposit of 256 wei, 255 wei used"
+        );
+        state.cumulative_payment = vec![1, 0];
+        assert!(check_payment_state(&state).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let eth_rpc_url = SecretUrl::new(Url::from_str("https://rpc.example/api-key").unwrap());
+        let mut report = PreflightReport::default();
+        assert!(report.push("disperser", Ok("connected".to_string())));
+        report.skip("srs", "no SRS config");
+        assert!(report.passed());
+
+        let error = Err("error sending request for url (https://rpc.example/api-key)".to_string());
+        assert!(!report.push("relay 0", redact::<String>(error, &eth_rpc_url)));
+        assert!(!report.passed());
+        assert_eq!(report.failures().count(), 1);
+        assert_eq!(
+            report.to_string(),
+            "[PASS] disperser: connected\n\
+             [SKIP] srs: no SRS config\n\
+             [FAIL] relay 0: error sending request for url ([REDACTED])\n"
+        );
+    }
+
+    #[ignore = "depends on external RPC"]
+    #[tokio::test]
+    async fn test_preflight() {
+        let network = Network::Holesky;
+        let config =
+            PreflightConfig::new(network.payload_disperser_config(get_test_holesky_rpc_url()))
+                .with_relay_client(
+                    network.relay_client_config(get_test_holesky_rpc_url(), vec![0, 1, 2]),
+                );
+        let report = preflight(&config, get_test_private_key_signer()).await;
+        let check = |name: &str| {
+            report
+                .checks
+                .iter()
+                .find(|check| check.name == name)
+                .unwrap()
+                .status
+        };
+        assert_eq!(check("chain id"), CheckStatus::Passed);
+        assert_eq!(check("disperser"), CheckStatus::Passed);
+        assert_eq!(check("cert verifier contract"), CheckStatus::Passed);
+        assert_eq!(check("required quorums"), CheckStatus::Passed);
+        assert_eq!(check("relay registry contract"), CheckStatus::Passed);
+        assert_eq!(check("relay 0"), CheckStatus::Passed);
+        assert_eq!(check("srs"), CheckStatus::Skipped);
+
+        // a contract without the cert verifier interface
+        let mut config = config;
+        config.payload_disperser.cert_verifier_address =
+            CertVerifierAddress::Verifier(network.config().relay_registry_address);
+        let report = preflight(&config, get_test_private_key_signer()).await;
+        assert!(!report.passed());
+        assert!(report
+            .failures()
+            .any(|check| check.name == "required quorums"));
+    }
+}
@@ -1,16 +1,29 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
+use ark_bn254::{Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
 use ethabi::Address;
-use ethers::signers::Signer;
+use ethers::{signers::Signer, utils::keccak256};
+use serde::Deserialize;
 use tonic::transport::Channel;
+use tracing::{debug, instrument};
 
 use crate::{
+    commitment_utils::g1_commitment_to_bytes,
+    config::{check_address, check_chain_id, check_secret_url, invalid, LoadableConfig},
     core::BlobKey,
-    errors::RelayClientError,
+    errors::{ConfigError, RelayClientError},
     generated::relay::{
+        chunk_request::Request as ProtoChunkRequestKind,
         relay_client::{self, RelayClient as RpcRelayClient},
-        GetBlobRequest,
+        ChunkRequest as ProtoChunkRequest, ChunkRequestByIndex, ChunkRequestByRange,
+        GetBlobRequest, GetChunksRequest,
     },
+    local_cert_verifier::{hash_g1_point, hash_to_g1},
+    network::{self, Network, DEFAULT_MAX_GRPC_MESSAGE_SIZE},
     relay_registry::RelayRegistry,
     utils::SecretUrl,
 };
@@ -18,11 +31,281 @@ use rust_eigenda_signers::signers::ethers::Signer as EthersSigner;
 
 pub type RelayKey = u32;
 
+/// Chunks of a blob to request to a relay.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChunkRequest {
+    /// Chunks with the given indices.
+    ByIndex {
+        blob_key: BlobKey,
+        chunk_indices: Vec<u32>,
+    },
+    /// Chunks with indices in `start_index..end_index`.
+    ByRange {
+        blob_key: BlobKey,
+        start_index: u32,
+        end_index: u32,
+    },
+}
+
+impl From<&ChunkRequest> for ProtoChunkRequest {
+    fn from(value: &ChunkRequest) -> Self {
+        let request = match value {
+            ChunkRequest::ByIndex {
+                blob_key,
+                chunk_indices,
+            } => ProtoChunkRequestKind::ByIndex(ChunkRequestByIndex {
+                blob_key: blob_key.to_bytes().to_vec(),
+                chunk_indices: chunk_indices.clone(),
+            }),
+            ChunkRequest::ByRange {
+                blob_key,
+                start_index,
+                end_index,
+            } => ProtoChunkRequestKind::ByRange(ChunkRequestByRange {
+                blob_key: blob_key.to_bytes().to_vec(),
+                start_index: *start_index,
+                end_index: *end_index,
+            }),
+        };
+        ProtoChunkRequest {
+            request: Some(request),
+        }
+    }
+}
+
+/// BLS key of an operator, used to authenticate chunk requests to relays that only serve chunks to validators.
+#[derive(Clone)]
+pub struct OperatorKey {
+    secret: Fr,
+    operator_id: [u8; 32],
+}
+
+impl OperatorKey {
+    /// Creates the key of the operator with the given BLS secret key. The operator id is derived from it, as the
+    /// hash of the G1 public key.
+    pub fn new(secret: Fr) -> Self {
+        let public_key = (G1Affine::generator() * secret).into_affine();
+        Self {
+            secret,
+            operator_id: hash_g1_point(&public_key),
+        }
+    }
+
+    pub fn operator_id(&self) -> [u8; 32] {
+        self.operator_id
+    }
+
+    /// Returns the G2 public key, which relays check the signatures against.
+    pub fn public_key_g2(&self) -> G2Affine {
+        (G2Affine::generator() * self.secret).into_affine()
+    }
+
+    /// Signs a message hash, as `KeyPair.SignMessage` in the Go implementation: `H(m) * sk`, with `H` mapping the
+    /// hash to G1 by try-and-increment.
+    fn sign(&self, message_hash: [u8; 32]) -> G1Affine {
+        (hash_to_g1(message_hash) * self.secret).into_affine()
+    }
+}
+
+impl std::fmt::Debug for OperatorKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OperatorKey")
+            .field("operator_id", &hex::encode(self.operator_id))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hash of a chunks request signed by the operator, as `HashGetChunksRequest` in the Go implementation of the relay.
+///
+/// Integers are encoded as 4 byte big endian values. The hash covers the operator id (prefixed with its length), the
+/// number of chunk requests, each chunk request, and the timestamp. A request by index is hashed as the byte `i`, the
+/// blob key (prefixed with its length) and each chunk index; a request by range as the byte `r`, the blob key
+/// (prefixed with its length), the start index and the end index.
+pub(crate) fn hash_get_chunks_request(request: &GetChunksRequest) -> [u8; 32] {
+    fn push_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+        data.extend((bytes.len() as u32).to_be_bytes());
+        data.extend(bytes);
+    }
+
+    let mut data = Vec::new();
+    push_bytes(&mut data, &request.operator_id);
+    data.extend((request.chunk_requests.len() as u32).to_be_bytes());
+    for chunk_request in &request.chunk_requests {
+        match &chunk_request.request {
+            Some(ProtoChunkRequestKind::ByIndex(by_index)) => {
+                data.push(b'i');
+                push_bytes(&mut data, &by_index.blob_key);
+                for chunk_index in &by_index.chunk_indices {
+                    data.extend(chunk_index.to_be_bytes());
+                }
+            }
+            Some(ProtoChunkRequestKind::ByRange(by_range)) => {
+                data.push(b'r');
+                push_bytes(&mut data, &by_range.blob_key);
+                data.extend(by_range.start_index.to_be_bytes());
+                data.extend(by_range.end_index.to_be_bytes());
+            }
+            None => {}
+        }
+    }
+    data.extend(request.timestamp.to_be_bytes());
+    keccak256(data)
+}
+
+/// Builds a chunks request, signed by the operator if a key is given.
+fn build_get_chunks_request(
+    operator_key: Option<&OperatorKey>,
+    requests: &[ChunkRequest],
+    timestamp: u32,
+) -> Result<GetChunksRequest, RelayClientError> {
+    let mut request = GetChunksRequest {
+        chunk_requests: requests.iter().map(ProtoChunkRequest::from).collect(),
+        operator_id: vec![],
+        timestamp,
+        operator_signature: vec![],
+    };
+    if let Some(operator_key) = operator_key {
+        request.operator_id = operator_key.operator_id.to_vec();
+        let signature = operator_key.sign(hash_get_chunks_request(&request));
+        request.operator_signature = g1_commitment_to_bytes(&signature)?;
+    }
+    Ok(request)
+}
+
+/// Configuration of a [`RelayClient`], built with [`RelayClientConfig::builder`] or read with [`LoadableConfig`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "RelayClientConfigFields")]
 pub struct RelayClientConfig {
     pub max_grpc_message_size: usize,
     pub relay_clients_keys: Vec<u32>,
     pub relay_registry_address: Address,
     pub eth_rpc_url: SecretUrl,
+    /// Chain id the ETH RPC must be connected to, checked when the [`RelayClient`] is created. `None` skips the check.
+    pub chain_id: Option<u64>,
+}
+
+impl RelayClientConfig {
+    pub fn builder() -> RelayClientConfigBuilder {
+        RelayClientConfigBuilder::default()
+    }
+}
+
+impl LoadableConfig for RelayClientConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_grpc_message_size == 0 {
+            return Err(invalid("max_grpc_message_size", "must be positive"));
+        }
+        if self.relay_clients_keys.is_empty() {
+            return Err(invalid("relay_clients_keys", "must not be empty"));
+        }
+        let unique_keys: HashSet<_> = self.relay_clients_keys.iter().collect();
+        if unique_keys.len() != self.relay_clients_keys.len() {
+            return Err(invalid("relay_clients_keys", "must not repeat keys"));
+        }
+        check_address("relay_registry_address", self.relay_registry_address)?;
+        check_secret_url("eth_rpc_url", &self.eth_rpc_url)?;
+        check_chain_id(self.chain_id)
+    }
+}
+
+/// Builds a [`RelayClientConfig`], with a max GRPC message size of [`DEFAULT_MAX_GRPC_MESSAGE_SIZE`] unless set
+/// otherwise.
+#[derive(Clone, Debug)]
+pub struct RelayClientConfigBuilder {
+    max_grpc_message_size: usize,
+    relay_clients_keys: Vec<RelayKey>,
+    relay_registry_address: Option<Address>,
+    eth_rpc_url: Option<SecretUrl>,
+    chain_id: Option<u64>,
+}
+
+impl Default for RelayClientConfigBuilder {
+    fn default() -> Self {
+        Self {
+            max_grpc_message_size: DEFAULT_MAX_GRPC_MESSAGE_SIZE,
+            relay_clients_keys: vec![],
+            relay_registry_address: None,
+            eth_rpc_url: None,
+            chain_id: None,
+        }
+    }
+}
+
+impl RelayClientConfigBuilder {
+    /// Sets the relay registry and chain id of a network.
+    pub fn with_network(mut self, network: &Network) -> Self {
+        let network = network.config();
+        self.relay_registry_address = Some(network.relay_registry_address);
+        self.chain_id = Some(network.chain_id);
+        self
+    }
+
+    pub fn with_max_grpc_message_size(mut self, max_grpc_message_size: usize) -> Self {
+        self.max_grpc_message_size = max_grpc_message_size;
+        self
+    }
+
+    pub fn with_relay_keys(mut self, relay_keys: Vec<RelayKey>) -> Self {
+        self.relay_clients_keys = relay_keys;
+        self
+    }
+
+    pub fn with_relay_registry_address(mut self, address: Address) -> Self {
+        self.relay_registry_address = Some(address);
+        self
+    }
+
+    pub fn with_eth_rpc_url(mut self, eth_rpc_url: SecretUrl) -> Self {
+        self.eth_rpc_url = Some(eth_rpc_url);
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn build(self) -> Result<RelayClientConfig, ConfigError> {
+        let config = RelayClientConfig {
+            max_grpc_message_size: self.max_grpc_message_size,
+            relay_clients_keys: self.relay_clients_keys,
+            relay_registry_address: self
+                .relay_registry_address
+                .ok_or(ConfigError::MissingField("relay_registry_address"))?,
+            eth_rpc_url: self
+                .eth_rpc_url
+                .ok_or(ConfigError::MissingField("eth_rpc_url"))?,
+            chain_id: self.chain_id,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Fields of a [`RelayClientConfig`] as read from files and environment variables.
+#[derive(Deserialize)]
+struct RelayClientConfigFields {
+    max_grpc_message_size: Option<usize>,
+    #[serde(default)]
+    relay_clients_keys: Vec<RelayKey>,
+    relay_registry_address: Option<Address>,
+    eth_rpc_url: Option<SecretUrl>,
+    chain_id: Option<u64>,
+}
+
+impl TryFrom<RelayClientConfigFields> for RelayClientConfig {
+    type Error = ConfigError;
+
+    fn try_from(fields: RelayClientConfigFields) -> Result<Self, Self::Error> {
+        let mut builder = RelayClientConfig::builder().with_relay_keys(fields.relay_clients_keys);
+        if let Some(max_grpc_message_size) = fields.max_grpc_message_size {
+            builder = builder.with_max_grpc_message_size(max_grpc_message_size);
+        }
+        builder.relay_registry_address = fields.relay_registry_address;
+        builder.eth_rpc_url = fields.eth_rpc_url;
+        builder.chain_id = fields.chain_id;
+        builder.build()
+    }
 }
 
 /// [`RelayClient`] is a client for the entire relay subsystem.
@@ -30,11 +313,16 @@ pub struct RelayClientConfig {
 /// It is a wrapper around a collection of GRPC clients, which are used to interact with individual relays.
 /// This struct is a low level implementation and should not be used directly,
 /// use a high level abstraction to interact with it ([`RelayPayloadRetriever`]).
+///
+/// Clones share the underlying connections.
+#[derive(Clone)]
 pub struct RelayClient {
     rpc_clients: HashMap<RelayKey, RpcRelayClient<tonic::transport::Channel>>,
+    operator_key: Option<OperatorKey>,
 }
 
 impl RelayClient {
+    #[instrument(skip_all, fields(relay_keys = ?config.relay_clients_keys))]
     pub async fn new<S>(config: RelayClientConfig, signer: S) -> Result<Self, RelayClientError>
     where
         EthersSigner<S>: Signer,
@@ -42,6 +330,9 @@ impl RelayClient {
         if config.max_grpc_message_size == 0 {
             return Err(RelayClientError::InvalidMaxGrpcMessageSize);
         }
+        if let Some(chain_id) = config.chain_id {
+            network::check_chain_id(&config.eth_rpc_url, chain_id).await?;
+        }
 
         let relay_registry = RelayRegistry::new(
             config.relay_registry_address,
@@ -52,17 +343,28 @@ impl RelayClient {
         let mut rpc_clients = HashMap::new();
         for relay_key in config.relay_clients_keys.iter() {
             let url = relay_registry.get_url_from_relay_key(*relay_key).await?;
-            let endpoint =
-                Channel::from_shared(url.clone()).map_err(|_| RelayClientError::InvalidURI(url))?;
+            let endpoint = Channel::from_shared(url.clone())
+                .map_err(|_| RelayClientError::InvalidURI(url.clone()))?;
             let channel = endpoint.connect().await?;
+            debug!(relay_key, url = %url, "connected to relay");
             let rpc_client = relay_client::RelayClient::new(channel);
             rpc_clients.insert(*relay_key, rpc_client);
         }
 
-        Ok(Self { rpc_clients })
+        Ok(Self {
+            rpc_clients,
+            operator_key: None,
+        })
+    }
+
+    /// Sets the BLS key of the operator that signs chunk requests, for relays that only serve chunks to validators.
+    pub fn with_operator_key(mut self, operator_key: OperatorKey) -> Self {
+        self.operator_key = Some(operator_key);
+        self
     }
 
     /// Retrieves a blob from a relay.
+    #[instrument(skip_all, fields(relay_key, blob_key = %blob_key.to_hex()))]
     pub async fn get_blob(
         &mut self,
         relay_key: RelayKey,
@@ -81,12 +383,51 @@ impl RelayClient {
 
         Ok(res.blob)
     }
+
+    /// Retrieves chunks of blobs from a relay.
+    ///
+    /// Returns one bundle of frames per request, in the same order as the requests (see [`Frame::deserialize_bundle`]).
+    /// The request is signed with the operator key, if one is set (see [`Self::with_operator_key`]). Otherwise it is
+    /// not authenticated, and relays that only serve chunks to validators will reject it.
+    ///
+    /// [`Frame::deserialize_bundle`]: crate::core::Frame::deserialize_bundle
+    #[instrument(skip_all, fields(relay_key, requests = requests.len()))]
+    pub async fn get_chunks(
+        &mut self,
+        relay_key: RelayKey,
+        requests: &[ChunkRequest],
+    ) -> Result<Vec<Vec<u8>>, RelayClientError> {
+        if requests.is_empty() {
+            return Err(RelayClientError::EmptyRequest);
+        }
+        let relay_client = self
+            .rpc_clients
+            .get_mut(&relay_key)
+            .ok_or(RelayClientError::InvalidRelayKey(relay_key))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| RelayClientError::FailedToFetchCurrentTimestamp)?
+            .as_secs() as u32;
+        let request = build_get_chunks_request(self.operator_key.as_ref(), requests, timestamp)?;
+        let res = relay_client.get_chunks(request).await?.into_inner();
+
+        if res.data.len() != requests.len() {
+            return Err(RelayClientError::UnexpectedReply(format!(
+                "expected {} bundles, got {}",
+                requests.len(),
+                res.data.len()
+            )));
+        }
+
+        Ok(res.data)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        network::HOLESKY_CHAIN_ID,
         relay_client::RelayClient,
         tests::{
             get_test_holesky_rpc_url, get_test_private_key_signer, HOLESKY_RELAY_REGISTRY_ADDRESS,
@@ -99,9 +440,81 @@ mod tests {
             relay_clients_keys: vec![0, 1, 2],
             relay_registry_address: HOLESKY_RELAY_REGISTRY_ADDRESS,
             eth_rpc_url: get_test_holesky_rpc_url(),
+            chain_id: Some(HOLESKY_CHAIN_ID),
         }
     }
 
+    #[test]
+    fn test_signed_chunks_request() {
+        use ark_bn254::Bn254;
+        use ark_ec::pairing::Pairing;
+
+        use crate::commitment_utils::g1_commitment_from_bytes;
+
+        let blob_key = BlobKey::from_bytes([7; 32]);
+        let requests = [
+            ChunkRequest::ByIndex {
+                blob_key,
+                chunk_indices: vec![3, 1, 4],
+            },
+            ChunkRequest::ByRange {
+                blob_key,
+                start_index: 10,
+                end_index: 20,
+            },
+        ];
+
+        let request = build_get_chunks_request(None, &requests, 1_700_000_000).unwrap();
+        assert!(request.operator_id.is_empty());
+        assert!(request.operator_signature.is_empty());
+
+        let operator_key = OperatorKey::new(Fr::from(42u64));
+        let request =
+            build_get_chunks_request(Some(&operator_key), &requests, 1_700_000_000).unwrap();
+        assert_eq!(request.operator_id, operator_key.operator_id().to_vec());
+        let signature = g1_commitment_from_bytes(&request.operator_signature).unwrap();
+        let message = hash_to_g1(hash_get_chunks_request(&request));
+        assert_eq!(
+            Bn254::pairing(signature, G2Affine::generator()),
+            Bn254::pairing(message, operator_key.public_key_g2())
+        );
+
+        // the signature covers every field of the request
+        let hash = hash_get_chunks_request(&request);
+        let mut later = request.clone();
+        later.timestamp += 1;
+        assert_ne!(hash_get_chunks_request(&later), hash);
+        let mut other_operator = request.clone();
+        other_operator.operator_id = vec![0; 32];
+        assert_ne!(hash_get_chunks_request(&other_operator), hash);
+        let other_chunks = build_get_chunks_request(
+            Some(&operator_key),
+            &[ChunkRequest::ByIndex {
+                blob_key,
+                chunk_indices: vec![10, 20],
+            }],
+            1_700_000_000,
+        )
+        .unwrap();
+        let by_range = build_get_chunks_request(
+            Some(&operator_key),
+            &[ChunkRequest::ByRange {
+                blob_key,
+                start_index: 10,
+                end_index: 20,
+            }],
+            1_700_000_000,
+        )
+        .unwrap();
+        assert_ne!(
+            hash_get_chunks_request(&other_chunks),
+            hash_get_chunks_request(&by_range)
+        );
+
+        // the secret key is not printed
+        assert!(!format!("{:?}", operator_key).contains("secret"));
+    }
+
     #[ignore = "depends on external RPC"]
     #[tokio::test]
     async fn test_retrieve_single_blob() {
@@ -1,21 +1,44 @@
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    ops::Range,
+    sync::Arc,
+    time::Duration,
+};
 
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use ethers::types::{BlockId, BlockNumber};
 use rand::seq::SliceRandom;
 use rust_eigenda_v2_common::EigenDACert;
-use rust_kzg_bn254_prover::srs::SRS;
+use serde::Deserialize;
 use tokio::time::timeout;
+use tracing::{debug, debug_span, field, instrument, warn, Instrument};
 
 use crate::{
-    commitment_utils::generate_and_compare_blob_commitment,
-    core::{Blob, BlobKey, Payload, PayloadForm},
-    errors::{ConversionError, RelayPayloadRetrieverError},
-    relay_client::{RelayClient, RelayKey},
+    cert_verifier::CertVerification,
+    commitment_utils::{
+        generate_and_compare_blob_commitment, verify_blob_commitments, verify_frame,
+    },
+    config::{check_timeout, invalid, LoadableConfig},
+    core::{
+        Blob, BlobKey, BlobParams, EncodingParams, Frame, Payload, PayloadDecodeMode, PayloadForm,
+        BYTES_PER_SYMBOL,
+    },
+    errors::{
+        CertVerificationFailure, CertVerifierError, ConfigError, ConversionError,
+        RelayPayloadRetrieverError,
+    },
+    inclusion_proof::verify_cert_inclusion,
+    relay_client::{ChunkRequest, RelayClient, RelayKey},
+    retrieval_cache::{CacheKey, CacheStats, PayloadCache},
+    srs::Srs,
+    validator_payload_retriever::ValidatorPayloadRetriever,
 };
 
 /// Computes the blob_key of the blob that belongs to the EigenDACert
-fn compute_blob_key(eigenda_cert: &EigenDACert) -> Result<BlobKey, ConversionError> {
+pub(crate) fn compute_blob_key(eigenda_cert: &EigenDACert) -> Result<BlobKey, ConversionError> {
     let blob_header = eigenda_cert
-        .blob_inclusion_info
+        .blob_inclusion_info()
         .blob_certificate
         .blob_header
         .clone();
@@ -23,61 +46,335 @@ fn compute_blob_key(eigenda_cert: &EigenDACert) -> Result<BlobKey, ConversionErr
     BlobKey::compute_blob_key(&blob_header)
 }
 
-pub struct SRSConfig {
-    pub source_path: String,
-    pub order: u32,
-    pub points_to_load: u32,
-}
+/// Number of payload bytes held by each symbol of an encoded payload (the first byte is padding).
+const PAYLOAD_BYTES_PER_SYMBOL: usize = BYTES_PER_SYMBOL - 1;
 
+/// Configuration of a [`RelayPayloadRetriever`], built with [`RelayPayloadRetrieverConfig::builder`] or read with
+/// [`LoadableConfig`].
+///
+/// When read, the blob params are a list of tables with a `blob_version` each, and the timeout is a number of
+/// seconds.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "RelayPayloadRetrieverConfigFields")]
 pub struct RelayPayloadRetrieverConfig {
     pub payload_form: PayloadForm,
     pub retrieval_timeout_secs: Duration,
+    /// Encoding parameters of each blob version, as registered in the EigenDAThresholdRegistry.
+    /// Only needed for range reads in evaluation form. Blob version 0 defaults to the [`BLOB_VERSION_0_PARAMS`] of
+    /// the public networks when built or read, so only other versions need to be set.
+    pub blob_params: HashMap<u16, BlobParams>,
+    /// Whether to verify the length proof of the blob commitments, on every retrieval path. Enabled by default:
+    /// without it, the blob length claimed by the cert is not proven, and a blob may be read truncated.
+    ///
+    /// It requires the G1 SRS point `[s^(order - length)]_1`, so the SRS file must hold all the `order` points
+    /// (although only the ones needed are loaded in memory): 8 GiB for the 2^28 points of the public networks, while
+    /// the `g1.point` files usually distributed only hold the first 2^20. Retrievers fail to be created otherwise, so
+    /// it must be disabled to retrieve with a truncated SRS file.
+    pub verify_length_proof: bool,
+    /// How strictly retrieved blobs are validated when decoding them into payloads.
+    pub decode_mode: PayloadDecodeMode,
+}
+
+/// Default time a relay has to serve a blob before the next relay is tried.
+pub const DEFAULT_RETRIEVAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Encoding parameters of blob version 0 on the public networks.
+pub const BLOB_VERSION_0_PARAMS: BlobParams = BlobParams {
+    num_chunks: 8192,
+    coding_rate: 8,
+};
+
+impl RelayPayloadRetrieverConfig {
+    pub fn builder() -> RelayPayloadRetrieverConfigBuilder {
+        RelayPayloadRetrieverConfigBuilder::default()
+    }
+}
+
+impl LoadableConfig for RelayPayloadRetrieverConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_timeout("retrieval_timeout_secs", self.retrieval_timeout_secs)?;
+        for params in self.blob_params.values() {
+            if !params.num_chunks.is_power_of_two() || !params.coding_rate.is_power_of_two() {
+                return Err(invalid(
+                    "blob_params",
+                    "the number of chunks and the coding rate must be non-zero powers of two",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds a [`RelayPayloadRetrieverConfig`]. Payloads are in coefficient form and decoded leniently, length proofs
+/// are verified, relays have [`DEFAULT_RETRIEVAL_TIMEOUT`] to serve a blob, and blob version 0 has the
+/// [`BLOB_VERSION_0_PARAMS`], unless set otherwise.
+#[derive(Clone, Debug)]
+pub struct RelayPayloadRetrieverConfigBuilder {
+    config: RelayPayloadRetrieverConfig,
+}
+
+impl Default for RelayPayloadRetrieverConfigBuilder {
+    fn default() -> Self {
+        Self {
+            config: RelayPayloadRetrieverConfig {
+                payload_form: PayloadForm::Coeff,
+                retrieval_timeout_secs: DEFAULT_RETRIEVAL_TIMEOUT,
+                blob_params: HashMap::from([(0, BLOB_VERSION_0_PARAMS)]),
+                verify_length_proof: true,
+                decode_mode: PayloadDecodeMode::Lenient,
+            },
+        }
+    }
+}
+
+impl RelayPayloadRetrieverConfigBuilder {
+    pub fn with_payload_form(mut self, payload_form: PayloadForm) -> Self {
+        self.config.payload_form = payload_form;
+        self
+    }
+
+    pub fn with_retrieval_timeout(mut self, retrieval_timeout: Duration) -> Self {
+        self.config.retrieval_timeout_secs = retrieval_timeout;
+        self
+    }
+
+    /// Sets the encoding parameters of a blob version.
+    pub fn with_blob_params(mut self, blob_version: u16, params: BlobParams) -> Self {
+        self.config.blob_params.insert(blob_version, params);
+        self
+    }
+
+    pub fn with_verify_length_proof(mut self, verify_length_proof: bool) -> Self {
+        self.config.verify_length_proof = verify_length_proof;
+        self
+    }
+
+    pub fn with_decode_mode(mut self, decode_mode: PayloadDecodeMode) -> Self {
+        self.config.decode_mode = decode_mode;
+        self
+    }
+
+    pub fn build(self) -> Result<RelayPayloadRetrieverConfig, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Encoding parameters of a blob version, as read from files.
+#[derive(Deserialize)]
+struct VersionedBlobParams {
+    blob_version: u16,
+    num_chunks: u32,
+    coding_rate: u32,
+}
+
+/// Fields of a [`RelayPayloadRetrieverConfig`] as read from files and environment variables.
+#[derive(Deserialize)]
+struct RelayPayloadRetrieverConfigFields {
+    payload_form: Option<PayloadForm>,
+    retrieval_timeout_secs: Option<u64>,
+    #[serde(default)]
+    blob_params: Vec<VersionedBlobParams>,
+    verify_length_proof: Option<bool>,
+    decode_mode: Option<PayloadDecodeMode>,
+}
+
+impl TryFrom<RelayPayloadRetrieverConfigFields> for RelayPayloadRetrieverConfig {
+    type Error = ConfigError;
+
+    fn try_from(fields: RelayPayloadRetrieverConfigFields) -> Result<Self, Self::Error> {
+        let mut builder = RelayPayloadRetrieverConfig::builder();
+        if let Some(payload_form) = fields.payload_form {
+            builder = builder.with_payload_form(payload_form);
+        }
+        if let Some(secs) = fields.retrieval_timeout_secs {
+            builder = builder.with_retrieval_timeout(Duration::from_secs(secs));
+        }
+        for params in fields.blob_params {
+            builder = builder.with_blob_params(
+                params.blob_version,
+                BlobParams {
+                    num_chunks: params.num_chunks,
+                    coding_rate: params.coding_rate,
+                },
+            );
+        }
+        if let Some(verify_length_proof) = fields.verify_length_proof {
+            builder = builder.with_verify_length_proof(verify_length_proof);
+        }
+        if let Some(decode_mode) = fields.decode_mode {
+            builder = builder.with_decode_mode(decode_mode);
+        }
+        builder.build()
+    }
+}
+
+/// Bytes of a payload read through [`RelayPayloadRetriever::get_payload_range`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PayloadRange {
+    /// The requested payload bytes.
+    pub bytes: Vec<u8>,
+    /// The verified chunks the bytes were read from, by chunk index. Each chunk carries the KZG multiproof
+    /// that binds its evaluations to the blob commitment. Empty for payloads in coefficient form, which are read
+    /// from the whole blob.
+    pub chunks: Vec<(u32, Frame)>,
 }
 
 /// Provides the ability to get payloads from the relay subsystem.
 pub struct RelayPayloadRetriever {
-    srs: SRS,
+    srs: Arc<Srs>,
     config: RelayPayloadRetrieverConfig,
     relay_client: RelayClient,
+    validator_fallback: Option<ValidatorPayloadRetriever>,
+    cache: Option<Box<dyn PayloadCache>>,
+    cert_verifier: Option<Box<dyn CertVerification>>,
 }
 
 impl RelayPayloadRetriever {
-    /// Assembles a RelayPayloadRetriever from specified configs and a
+    /// Assembles a RelayPayloadRetriever from specified config, SRS and a
     /// relay client that have already been constructed.
+    ///
+    /// Fails if length proofs are verified but the SRS doesn't hold all its G1 points, see
+    /// [`Srs::check_length_proof_points`].
     pub fn new(
         config: RelayPayloadRetrieverConfig,
-        srs_config: SRSConfig,
+        srs: Arc<Srs>,
         relay_client: RelayClient,
     ) -> Result<Self, RelayPayloadRetrieverError> {
-        let srs = SRS::new(
-            &srs_config.source_path,
-            srs_config.order,
-            srs_config.points_to_load,
-        )?;
-
+        if config.verify_length_proof {
+            srs.check_length_proof_points()?;
+        }
         Ok(RelayPayloadRetriever {
             srs,
             config,
             relay_client,
+            validator_fallback: None,
+            cache: None,
+            cert_verifier: None,
         })
     }
 
+    /// Sets a [`ValidatorPayloadRetriever`] to fall back to when the blob cannot be retrieved from any relay.
+    pub fn with_validator_fallback(
+        mut self,
+        validator_retriever: ValidatorPayloadRetriever,
+    ) -> Self {
+        self.validator_fallback = Some(validator_retriever);
+        self
+    }
+
+    /// Sets a [`PayloadCache`] where verified payloads are stored, so that reading them again skips both
+    /// the retrieval and the verification.
+    pub fn with_cache(mut self, cache: Box<dyn PayloadCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets a [`CertVerification`] used to verify certs on chain before retrieving their payloads.
+    ///
+    /// A [`CertVerifierResolver`](crate::cert_verifier_router::CertVerifierResolver) with a router verifies each cert
+    /// with the verifier of its reference block number, so historical certs are still verified after an upgrade.
+    ///
+    /// Once set, [`Self::get_payload`] verifies certs at the latest block. Use [`Self::get_payload_at_block`] to
+    /// verify them at a specific block.
+    pub fn with_cert_verifier(mut self, cert_verifier: Box<dyn CertVerification>) -> Self {
+        self.cert_verifier = Some(cert_verifier);
+        self
+    }
+
+    /// Returns the hits and misses of the cache, if one is set.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
     // Iteratively attempts to fetch a given blob with key blobKey from relays that have it, as claimed by the
     // blob certificate. The relays are attempted in random order.
     //
+    // Before contacting any relay or reading the cache, the blob commitments of the certificate are checked to be
+    // consistent, and the length proof to be valid if enabled, returning an error if they are not.
+    //
     // If the blob is successfully retrieved, then the blob is verified against the certificate. If the verification
     // succeeds, the blob is decoded to yield the payload (the original user data, with no padding or any modification),
     // and the payload is returned.
     //
-    // This method does NOT verify the [`EigenDACert`] on chain: it is assumed that the input [`EigenDACert`] has already been
-    // verified prior to calling this method.
+    // If no relay serves a valid blob and a validator fallback is set, the blob is reconstructed from chunks
+    // retrieved directly from the validators.
+    //
+    // This method verifies the [`EigenDACert`] on chain (at the latest block) only if a cert verifier is set (see
+    // [`Self::with_cert_verifier`]). Otherwise, it is assumed that the input [`EigenDACert`] has already been verified
+    // prior to calling this method.
+    #[instrument(skip_all, fields(blob_key = field::Empty))]
     pub async fn get_payload(
         &mut self,
         eigenda_cert: EigenDACert,
+    ) -> Result<Payload, RelayPayloadRetrieverError> {
+        if self.cert_verifier.is_some() {
+            self.verify_cert(&eigenda_cert, BlockNumber::Latest.into())
+                .await?;
+        }
+        self.retrieve_payload(eigenda_cert).await
+    }
+
+    // Verifies the [`EigenDACert`] on chain at the given block (a number, a hash, or a tag such as `finalized`), and
+    // then retrieves its payload as [`Self::get_payload`] does. Requires a cert verifier (see
+    // [`Self::with_cert_verifier`]).
+    //
+    // If the cert is rejected on chain, [`RelayPayloadRetrieverError::CertVerificationFailed`] is returned, and the
+    // cert should be dropped. Any other error may be transient, so the call may be retried.
+    #[instrument(skip_all, fields(blob_key = field::Empty))]
+    pub async fn get_payload_at_block(
+        &mut self,
+        eigenda_cert: EigenDACert,
+        block: impl Into<BlockId>,
+    ) -> Result<Payload, RelayPayloadRetrieverError> {
+        self.verify_cert(&eigenda_cert, block.into()).await?;
+        self.retrieve_payload(eigenda_cert).await
+    }
+
+    /// Verifies the cert on chain at the given block, separating rejected certs from failed verifications.
+    ///
+    /// The inclusion of the blob in its batch is checked locally first, so certs with an invalid inclusion proof are
+    /// rejected without calling the contract.
+    #[instrument(
+        skip_all,
+        fields(reference_block_number = eigenda_cert.reference_block_number(), block = ?block)
+    )]
+    async fn verify_cert(
+        &self,
+        eigenda_cert: &EigenDACert,
+        block: BlockId,
+    ) -> Result<(), RelayPayloadRetrieverError> {
+        let cert_verifier = self
+            .cert_verifier
+            .as_ref()
+            .ok_or(RelayPayloadRetrieverError::MissingCertVerifier)?;
+        verify_cert_inclusion(eigenda_cert).map_err(|err| {
+            RelayPayloadRetrieverError::CertVerificationFailed(
+                CertVerificationFailure::InvalidInclusionProof(err.to_string()),
+            )
+        })?;
+        cert_verifier
+            .verify_cert(eigenda_cert, block)
+            .await
+            .map_err(|err| match err {
+                CertVerifierError::VerificationFailed(failure) => {
+                    RelayPayloadRetrieverError::CertVerificationFailed(failure)
+                }
+                err => RelayPayloadRetrieverError::CertVerifier(err),
+            })
+    }
+
+    async fn retrieve_payload(
+        &mut self,
+        eigenda_cert: EigenDACert,
     ) -> Result<Payload, RelayPayloadRetrieverError> {
         let blob_key = compute_blob_key(&eigenda_cert)?;
+        tracing::Span::current().record("blob_key", field::display(blob_key.to_hex()));
 
-        let relay_keys = eigenda_cert.blob_inclusion_info.blob_certificate.relay_keys;
+        let relay_keys = &eigenda_cert
+            .blob_inclusion_info()
+            .blob_certificate
+            .relay_keys;
         if relay_keys.is_empty() {
             return Err(RelayPayloadRetrieverError::InvalidCertificate(
                 "relay key count is zero".to_string(),
@@ -85,12 +382,36 @@ impl RelayPayloadRetriever {
         }
 
         let blob_commitments = eigenda_cert
-            .blob_inclusion_info
+            .blob_inclusion_info()
             .blob_certificate
             .blob_header
             .commitment
             .clone();
 
+        // checked before the cache, which may have been filled by a retriever that did not verify length proofs
+        debug_span!(
+            "verify_blob_commitments",
+            symbol_count = blob_commitments.length
+        )
+        .in_scope(|| {
+            verify_blob_commitments(
+                &self.srs,
+                &blob_commitments,
+                self.config.verify_length_proof,
+            )
+        })?;
+
+        let cache_key = CacheKey::new(
+            blob_key,
+            &blob_commitments,
+            self.config.payload_form,
+            self.config.decode_mode,
+        )?;
+        if let Some(payload) = self.cache.as_ref().and_then(|cache| cache.get(&cache_key)) {
+            debug!("payload served from the cache");
+            return Ok(payload);
+        }
+
         // create a randomized array of indices, so that it isn't always the first relay in the list which gets hit
         let mut indices: Vec<usize> = (0..relay_keys.len()).collect();
         indices.shuffle(&mut rand::thread_rng()); // TODO: use other rng
@@ -103,54 +424,255 @@ impl RelayPayloadRetriever {
             let relay_key = relay_keys[idx];
 
             let blob_length_symbols = eigenda_cert
-                .blob_inclusion_info
+                .blob_inclusion_info()
                 .blob_certificate
                 .blob_header
                 .commitment
                 .length;
 
+            let span = debug_span!(
+                "relay_attempt",
+                relay_key,
+                symbol_count = blob_length_symbols
+            );
+
             // if get_blob returned and error, try calling a different relay
             let blob = match self
                 .retrieve_blob_with_timeout(relay_key, &blob_key, blob_length_symbols)
+                .instrument(span.clone())
                 .await
             {
                 Ok(blob) => blob,
                 Err(err) => {
-                    println!("Error retrieving blob from relay {}: {}", relay_key, err);
+                    warn!(parent: &span, error = %err, "failed to retrieve blob from relay");
                     continue;
                 }
             };
 
-            let g1_srs = self.srs.g1.clone();
-            let valid = generate_and_compare_blob_commitment(
-                g1_srs,
-                blob.serialize(),
-                blob_commitments.commitment,
-            )
-            .unwrap_or(false);
+            let g1_srs = self.srs.g1_points(blob_length_symbols as usize)?;
+            let valid = debug_span!(parent: &span, "verify_blob_commitment").in_scope(|| {
+                generate_and_compare_blob_commitment(
+                    &g1_srs,
+                    blob.serialize(),
+                    blob_commitments.commitment,
+                )
+                .unwrap_or(false)
+            });
             if !valid {
-                println!("Retrieved blob from relay {} is not valid", relay_key);
+                warn!(parent: &span, "blob retrieved from relay does not match its commitment");
                 continue;
             }
 
-            let payload = match blob.to_payload(self.config.payload_form) {
+            let payload = match blob
+                .to_payload_with_mode(self.config.payload_form, self.config.decode_mode)
+            {
                 Ok(payload) => payload,
                 Err(err) => {
-                    println!(
-                        "Error converting blob retrieved from relay {} to payload: {}",
-                        relay_key, err
+                    warn!(
+                        parent: &span,
+                        error = %err,
+                        "failed to decode the payload of the blob retrieved from relay"
                     );
                     continue;
                 }
             };
 
+            debug!(parent: &span, "retrieved payload from relay");
+            self.cache_payload(cache_key, &payload);
             return Ok(payload);
         }
 
         // If we reach this point, we've tried all relays and failed to retrieve the blob
+        match self.validator_fallback.as_mut() {
+            Some(validator_retriever) => {
+                warn!("unable to retrieve blob from relays, falling back to validators");
+                let payload = validator_retriever.get_payload(eigenda_cert).await?;
+                self.cache_payload(cache_key, &payload);
+                Ok(payload)
+            }
+            None => Err(RelayPayloadRetrieverError::UnableToRetrievePayload),
+        }
+    }
+
+    /// Stores a verified payload in the cache, if one is set. Failing to cache a payload does not fail the retrieval.
+    fn cache_payload(&self, cache_key: CacheKey, payload: &Payload) {
+        if let Some(cache) = self.cache.as_ref() {
+            if let Err(err) = cache.insert(cache_key, payload) {
+                warn!(error = %err, "failed to cache payload");
+            }
+        }
+    }
+
+    // Reads the bytes in `range` of the payload, fetching from the relays only the chunks that hold them.
+    //
+    // For payloads in evaluation form, each symbol of the encoded payload is an evaluation of the blob polynomial,
+    // and thus part of exactly one chunk. The chunk holding the encoded payload header is always fetched, to check
+    // the range against the payload length. Every chunk is verified against the blob commitment, once the blob
+    // commitments are checked as [`Self::get_payload`] does.
+    //
+    // For payloads in coefficient form, every evaluation depends on every symbol, so no range can be read without
+    // the whole blob: the payload is retrieved and verified as [`Self::get_payload`] does (and served from the cache,
+    // if set), and the returned range holds no chunks.
+    //
+    // This method does NOT verify the [`EigenDACert`] on chain: it is assumed that the input [`EigenDACert`] has already been
+    // verified prior to calling this method.
+    #[instrument(skip_all, fields(blob_key = field::Empty, range = ?range))]
+    pub async fn get_payload_range(
+        &mut self,
+        eigenda_cert: &EigenDACert,
+        range: Range<usize>,
+    ) -> Result<PayloadRange, RelayPayloadRetrieverError> {
+        if range.start > range.end {
+            return Err(RelayPayloadRetrieverError::InvalidRange(format!(
+                "range start {} is greater than range end {}",
+                range.start, range.end
+            )));
+        }
+        if matches!(self.config.payload_form, PayloadForm::Coeff) {
+            let payload = self
+                .retrieve_payload(eigenda_cert.clone())
+                .await?
+                .serialize();
+            if range.end > payload.len() {
+                return Err(RelayPayloadRetrieverError::InvalidRange(format!(
+                    "range end {} exceeds the payload length {}",
+                    range.end,
+                    payload.len()
+                )));
+            }
+            return Ok(PayloadRange {
+                bytes: payload[range].to_vec(),
+                chunks: vec![],
+            });
+        }
+
+        let blob_key = compute_blob_key(eigenda_cert)?;
+        tracing::Span::current().record("blob_key", field::display(blob_key.to_hex()));
+        let blob_certificate = &eigenda_cert.blob_inclusion_info().blob_certificate;
+        if blob_certificate.relay_keys.is_empty() {
+            return Err(RelayPayloadRetrieverError::InvalidCertificate(
+                "relay key count is zero".to_string(),
+            ));
+        }
+        let blob_header = &blob_certificate.blob_header;
+        verify_blob_commitments(
+            &self.srs,
+            &blob_header.commitment,
+            self.config.verify_length_proof,
+        )?;
+        let blob_length_symbols = blob_header.commitment.length as usize;
+        let params = self
+            .config
+            .blob_params
+            .get(&blob_header.version)
+            .ok_or(RelayPayloadRetrieverError::UnknownBlobVersion(
+                blob_header.version,
+            ))?
+            .encoding_params(blob_length_symbols)?;
+        let g2_tau_pow_n = self.srs.g2_tau_pow(params.chunk_length).ok_or(
+            RelayPayloadRetrieverError::MissingG2PowerOf2Point(params.chunk_length),
+        )?;
+        let g1_srs = self.srs.g1_points(params.chunk_length)?;
+
+        let symbol_indices = payload_range_symbols(&range, blob_length_symbols)?;
+        let locations = symbol_indices
+            .iter()
+            .map(|symbol_index| {
+                let location =
+                    symbol_evaluation_location(&params, blob_length_symbols, *symbol_index)?;
+                Ok((*symbol_index, location))
+            })
+            .collect::<Result<BTreeMap<usize, (u32, usize)>, RelayPayloadRetrieverError>>()?;
+        let chunk_indices: Vec<u32> = locations
+            .values()
+            .map(|(chunk_index, _)| *chunk_index)
+            .collect::<BTreeSet<u32>>()
+            .into_iter()
+            .collect();
+
+        let mut relay_keys = blob_certificate.relay_keys.clone();
+        relay_keys.shuffle(&mut rand::thread_rng());
+
+        for relay_key in relay_keys {
+            let span = debug_span!("relay_attempt", relay_key, chunks = chunk_indices.len());
+            let frames = match self
+                .retrieve_frames_with_timeout(relay_key, &blob_key, &chunk_indices)
+                .instrument(span.clone())
+                .await
+            {
+                Ok(frames) => frames,
+                Err(err) => {
+                    warn!(parent: &span, error = %err, "failed to retrieve chunks from relay");
+                    continue;
+                }
+            };
+
+            let chunks: Vec<(u32, Frame)> = chunk_indices.iter().copied().zip(frames).collect();
+            let all_valid = debug_span!(parent: &span, "verify_chunks").in_scope(|| {
+                chunks.iter().all(|(chunk_index, frame)| {
+                    frame.coeffs.len() == params.chunk_length
+                        && params
+                            .coset_leader(*chunk_index)
+                            .and_then(|coset_leader| {
+                                verify_frame(
+                                    &g1_srs,
+                                    g2_tau_pow_n,
+                                    blob_header.commitment.commitment,
+                                    frame,
+                                    coset_leader,
+                                )
+                            })
+                            .unwrap_or(false)
+                })
+            });
+            if !all_valid {
+                warn!(parent: &span, "chunks retrieved from relay do not match the blob commitment");
+                continue;
+            }
+
+            let bytes = read_payload_range(&chunks, &locations, &params, &range)?;
+            return Ok(PayloadRange { bytes, chunks });
+        }
+
+        // If we reach this point, we've tried all relays and failed to retrieve the chunks
         Err(RelayPayloadRetrieverError::UnableToRetrievePayload)
     }
 
+    /// Attempts to retrieve the frames with the given chunk indices from a given [`RelayKey`].
+    ///
+    /// Times out based on config's `retrieval_timeout_secs`.
+    async fn retrieve_frames_with_timeout(
+        &mut self,
+        relay_key: RelayKey,
+        blob_key: &BlobKey,
+        chunk_indices: &[u32],
+    ) -> Result<Vec<Frame>, RelayPayloadRetrieverError> {
+        let request = ChunkRequest::ByIndex {
+            blob_key: *blob_key,
+            chunk_indices: chunk_indices.to_vec(),
+        };
+        let bundles = timeout(
+            self.config.retrieval_timeout_secs,
+            self.relay_client.get_chunks(relay_key, &[request]),
+        )
+        .await
+        .map_err(|_| {
+            warn!(timeout = ?self.config.retrieval_timeout_secs, "relay request timed out");
+            RelayPayloadRetrieverError::RetrievalTimeout
+        })??;
+
+        // get_chunks returns exactly one bundle per request
+        let frames = Frame::deserialize_bundle(&bundles[0])?;
+        if frames.len() != chunk_indices.len() {
+            return Err(RelayPayloadRetrieverError::InvalidRange(format!(
+                "relay returned {} chunks, expected {}",
+                frames.len(),
+                chunk_indices.len()
+            )));
+        }
+        Ok(frames)
+    }
+
     /// Attempts to retrieve a [`Blob`] from a given [`RelayKey`].
     ///
     /// Times out based on config's `retrieval_timeout_secs`.
@@ -167,31 +689,149 @@ impl RelayPayloadRetriever {
             self.relay_client.get_blob(relay_key, blob_key),
         )
         .await
-        .map_err(|_| RelayPayloadRetrieverError::RetrievalTimeout)??;
+        .map_err(|_| {
+            warn!(timeout = ?self.config.retrieval_timeout_secs, "relay request timed out");
+            RelayPayloadRetrieverError::RetrievalTimeout
+        })??;
 
-        let blob = Blob::deserialize_blob(blob_bytes, blob_length_symbols as usize)?;
+        let blob = Blob::deserialize_blob_with_mode(
+            blob_bytes,
+            blob_length_symbols as usize,
+            self.config.decode_mode,
+        )?;
         Ok(blob)
     }
 }
 
+/// Returns the indices of the encoded payload symbols that hold the payload bytes in `range`, always including
+/// the header symbol.
+fn payload_range_symbols(
+    range: &Range<usize>,
+    blob_length_symbols: usize,
+) -> Result<Vec<usize>, RelayPayloadRetrieverError> {
+    // the first symbol is the encoded payload header, and each of the following holds 31 bytes of the payload
+    let mut symbol_indices = vec![0];
+    if !range.is_empty() {
+        let first_symbol = 1 + range.start / PAYLOAD_BYTES_PER_SYMBOL;
+        let last_symbol = 1 + (range.end - 1) / PAYLOAD_BYTES_PER_SYMBOL;
+        if last_symbol >= blob_length_symbols {
+            return Err(RelayPayloadRetrieverError::InvalidRange(format!(
+                "range end {} exceeds the blob length",
+                range.end
+            )));
+        }
+        symbol_indices.extend(first_symbol..=last_symbol);
+    }
+    Ok(symbol_indices)
+}
+
+/// Returns the chunk index and the position within the chunk of an encoded payload symbol.
+///
+/// In evaluation form, symbol `i` is the evaluation of the blob polynomial at `v^i`, being `v` the root of unity of
+/// order `blob_length_symbols`. Since `v = w^(n / blob_length_symbols)`, being `w` the root of unity of order `n`
+/// (the number of evaluations of the extended blob), it is the evaluation at `w^(i * n / blob_length_symbols)`.
+fn symbol_evaluation_location(
+    params: &EncodingParams,
+    blob_length_symbols: usize,
+    symbol_index: usize,
+) -> Result<(u32, usize), RelayPayloadRetrieverError> {
+    let stride = params.num_evaluations() / blob_length_symbols;
+    Ok(params.evaluation_location(symbol_index * stride)?)
+}
+
+/// Reads the payload bytes in `range` from verified chunks, given the location of each needed symbol.
+fn read_payload_range(
+    chunks: &[(u32, Frame)],
+    locations: &BTreeMap<usize, (u32, usize)>,
+    params: &EncodingParams,
+    range: &Range<usize>,
+) -> Result<Vec<u8>, RelayPayloadRetrieverError> {
+    let chunk_domain = params.chunk_domain()?;
+    let evaluations: HashMap<u32, Vec<Fr>> = chunks
+        .iter()
+        .map(|(chunk_index, frame)| {
+            let coset_leader = params.coset_leader(*chunk_index)?;
+            Ok((
+                *chunk_index,
+                frame.evaluations_on_coset(coset_leader, &chunk_domain),
+            ))
+        })
+        .collect::<Result<_, RelayPayloadRetrieverError>>()?;
+
+    let symbol_bytes =
+        |symbol_index: usize| -> Result<[u8; BYTES_PER_SYMBOL], RelayPayloadRetrieverError> {
+            let (chunk_index, position) =
+                locations
+                    .get(&symbol_index)
+                    .ok_or(RelayPayloadRetrieverError::InvalidRange(format!(
+                        "symbol {} was not fetched",
+                        symbol_index
+                    )))?;
+            let evaluation = evaluations
+                .get(chunk_index)
+                .and_then(|chunk_evaluations| chunk_evaluations.get(*position))
+                .ok_or(RelayPayloadRetrieverError::InvalidRange(format!(
+                    "chunk {} was not fetched",
+                    chunk_index
+                )))?;
+            let mut bytes = [0u8; BYTES_PER_SYMBOL];
+            let evaluation_bytes = evaluation.into_bigint().to_bytes_be();
+            bytes[BYTES_PER_SYMBOL - evaluation_bytes.len()..].copy_from_slice(&evaluation_bytes);
+            Ok(bytes)
+        };
+
+    // bytes 2..6 of the encoded payload header hold the payload length
+    let header = symbol_bytes(0)?;
+    let payload_length = u32::from_be_bytes([header[2], header[3], header[4], header[5]]) as usize;
+    if range.end > payload_length {
+        return Err(RelayPayloadRetrieverError::InvalidRange(format!(
+            "range end {} exceeds the payload length {}",
+            range.end, payload_length
+        )));
+    }
+
+    range
+        .clone()
+        .map(|byte_index| {
+            let symbol = symbol_bytes(1 + byte_index / PAYLOAD_BYTES_PER_SYMBOL)?;
+            // the first byte of each symbol is padding
+            Ok(symbol[1 + byte_index % PAYLOAD_BYTES_PER_SYMBOL])
+        })
+        .collect()
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use rust_eigenda_v2_common::{
         BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader, BlobInclusionInfo,
-        NonSignerStakesAndSignature,
+        EigenDACertV2, NonSignerStakesAndSignature,
     };
 
+    use ethabi::Token;
+    use rust_eigenda_signers::signers::private_key::Signer as PrivateKeySigner;
+    use serde_json::json;
+
     use crate::{
-        commitment_utils::{g1_commitment_from_bytes, g2_commitment_from_bytes},
+        cert_verifier::CertVerifier,
+        commitment_utils::{
+            g1_commitment_from_bytes, g2_commitment_from_bytes, verify_length_commitment,
+        },
+        errors::BlobCommitmentsError,
+        relay_client::RelayClientConfig,
+        retrieval_cache::{InMemoryPayloadCache, PayloadCacheConfig},
         tests::{
-            get_relay_payload_retriever_test_config, get_srs_test_config, get_test_relay_client,
+            get_closed_rpc_url, get_insecure_test_srs, get_relay_client_test_config,
+            get_relay_payload_retriever_test_config, get_test_relay_client, get_test_srs,
+            mock_eth_rpc, CERT_VERIFIER_ADDRESS, INSECURE_TEST_SRS_ORDER, INSECURE_TEST_SRS_SECRET,
         },
+        utils::SecretUrl,
+        validator_payload_retriever::tests::get_test_validator_retriever,
     };
 
     use super::*;
 
     // Certificate of a known, dispersed blob in holesky chain.
-    fn get_test_eigenda_cert() -> EigenDACert {
+    pub(crate) fn get_test_eigenda_cert() -> EigenDACert {
         let commitment_bytes =
             hex::decode("a7c4441c06c2f25772a92652359a6d8d833b366ddd8a5ebfc8607f071e0338d6")
                 .unwrap();
@@ -211,7 +851,7 @@ mod tests {
             hex::decode("d8bcbb37fb19641347b7165298d481368adde8b332e565bb0768f564bdbbae06")
                 .unwrap();
 
-        EigenDACert {
+        EigenDACert::V2(EigenDACertV2 {
             blob_inclusion_info: BlobInclusionInfo {
                 blob_certificate: BlobCertificate {
                     blob_header: BlobHeader {
@@ -267,17 +907,481 @@ mod tests {
                 non_signer_stake_indices: vec![vec![12], vec![5]],
             },
             signed_quorum_numbers: vec![0, 1],
+        })
+    }
+
+    #[test]
+    fn test_cert_length_commitment() {
+        let mut blob_commitments = get_test_eigenda_cert()
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header
+            .commitment
+            .clone();
+        assert!(verify_length_commitment(
+            blob_commitments.commitment,
+            blob_commitments.length_commitment
+        ));
+
+        blob_commitments.length_commitment = blob_commitments.length_proof;
+        assert!(!verify_length_commitment(
+            blob_commitments.commitment,
+            blob_commitments.length_commitment
+        ));
+    }
+
+    #[test]
+    fn test_read_payload_range() {
+        use crate::core::frame::tests::encode_frames;
+
+        let payload_bytes: Vec<u8> = (0..100).collect();
+        let blob = Payload::new(payload_bytes.clone())
+            .to_blob(PayloadForm::Eval)
+            .unwrap();
+        let params = BlobParams {
+            num_chunks: 8,
+            coding_rate: 4,
+        }
+        .encoding_params(blob.blob_length_symbols)
+        .unwrap();
+        let mut coeffs = blob.coeff_polynomial.clone();
+        coeffs.resize(blob.blob_length_symbols, Fr::from(0));
+        let all_chunks = encode_frames(&coeffs, &params);
+
+        for range in [0..100, 0..0, 30..33, 61..62, 99..100] {
+            let symbol_indices = payload_range_symbols(&range, blob.blob_length_symbols).unwrap();
+            let locations: BTreeMap<usize, (u32, usize)> = symbol_indices
+                .into_iter()
+                .map(|symbol_index| {
+                    let location =
+                        symbol_evaluation_location(&params, blob.blob_length_symbols, symbol_index)
+                            .unwrap();
+                    (symbol_index, location)
+                })
+                .collect();
+            // keep only the chunks that hold the needed symbols
+            let chunks: Vec<(u32, Frame)> = all_chunks
+                .iter()
+                .filter(|(chunk_index, _)| {
+                    locations.values().any(|(needed, _)| needed == chunk_index)
+                })
+                .cloned()
+                .collect();
+
+            let bytes = read_payload_range(&chunks, &locations, &params, &range).unwrap();
+            assert_eq!(bytes, payload_bytes[range].to_vec());
+        }
+
+        // out of the payload, but inside the blob
+        let range = 100..101;
+        let symbol_indices = payload_range_symbols(&range, blob.blob_length_symbols).unwrap();
+        let locations = symbol_indices
+            .into_iter()
+            .map(|symbol_index| {
+                let location =
+                    symbol_evaluation_location(&params, blob.blob_length_symbols, symbol_index)
+                        .unwrap();
+                (symbol_index, location)
+            })
+            .collect();
+        assert!(read_payload_range(&all_chunks, &locations, &params, &range).is_err());
+
+        // out of the blob
+        assert!(payload_range_symbols(&(0..1000), blob.blob_length_symbols).is_err());
+    }
+
+    /// Returns a relay client without relays.
+    async fn get_relay_client_without_relays() -> RelayClient {
+        RelayClient::new(
+            RelayClientConfig {
+                relay_clients_keys: vec![],
+                eth_rpc_url: get_closed_rpc_url(),
+                chain_id: None,
+                ..get_relay_client_test_config()
+            },
+            PrivateKeySigner::random(&mut rand::thread_rng()),
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Returns a retriever with the given config and the insecure test SRS, without relays.
+    pub(crate) async fn get_retriever_without_relays(
+        config: RelayPayloadRetrieverConfig,
+    ) -> RelayPayloadRetriever {
+        let relay_client = get_relay_client_without_relays().await;
+        RelayPayloadRetriever::new(config, get_insecure_test_srs().await, relay_client).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_length_proof_needs_all_g1_points() {
+        use ark_bn254::G1Affine;
+        use ark_ec::AffineRepr;
+        use rust_eigenda_srs::{SrsFile, SrsSource};
+
+        use crate::{commitment_utils::g1_commitment_to_bytes, srs::SRSConfig};
+
+        // only the first point of the SRS, as in the truncated g1.point files
+        let g1 = g1_commitment_to_bytes(&G1Affine::generator()).unwrap();
+        let srs = Srs::load(&SRSConfig {
+            g1: SrsFile::new(SrsSource::Embedded(g1.leak())),
+            order: INSECURE_TEST_SRS_ORDER,
+            g2_power_of_2: None,
+            cache_dir: None,
+        })
+        .await
+        .unwrap();
+        let srs = Arc::new(srs);
+
+        let config = RelayPayloadRetrieverConfig::builder().build().unwrap();
+        assert!(matches!(
+            RelayPayloadRetriever::new(
+                config.clone(),
+                srs.clone(),
+                get_relay_client_without_relays().await
+            ),
+            Err(RelayPayloadRetrieverError::Conversion(
+                ConversionError::SrsFile(_)
+            ))
+        ));
+
+        let config = RelayPayloadRetrieverConfig {
+            verify_length_proof: false,
+            ..config
+        };
+        assert!(
+            RelayPayloadRetriever::new(config, srs, get_relay_client_without_relays().await)
+                .is_ok()
+        );
+    }
+
+    /// Returns a retriever without relays, verifying certs with the cert verifier behind the given ETH RPC.
+    async fn get_verifying_retriever_without_relays(
+        eth_rpc_url: SecretUrl,
+    ) -> RelayPayloadRetriever {
+        let signer = PrivateKeySigner::random(&mut rand::thread_rng());
+        let cert_verifier = CertVerifier::new(CERT_VERIFIER_ADDRESS, eth_rpc_url, signer).unwrap();
+        get_retriever_without_relays(get_relay_payload_retriever_test_config())
+            .await
+            .with_cert_verifier(Box::new(cert_verifier))
+    }
+
+    /// Returns the test cert, with the commitments of the blob of `payload` in the given form, computed with the
+    /// secret of the insecure test SRS.
+    pub(crate) fn get_test_eigenda_cert_for_payload(
+        payload: &Payload,
+        payload_form: PayloadForm,
+    ) -> EigenDACert {
+        use ark_bn254::{G1Affine, G2Affine};
+        use ark_ec::{AffineRepr, CurveGroup};
+        use ark_ff::Field;
+
+        let blob = payload.to_blob(payload_form).unwrap();
+        let secret = Fr::from(INSECURE_TEST_SRS_SECRET);
+        // p(s), from the coefficients of the blob polynomial
+        let evaluation = blob
+            .coeff_polynomial
+            .iter()
+            .rev()
+            .fold(Fr::from(0u64), |acc, coeff| acc * secret + coeff);
+        let length = blob.blob_length_symbols as u32;
+        let shift = secret.pow([u64::from(INSECURE_TEST_SRS_ORDER - length)]);
+
+        let mut eigenda_cert = get_test_eigenda_cert();
+        let commitments = &mut eigenda_cert
+            .blob_inclusion_info_mut()
+            .blob_certificate
+            .blob_header
+            .commitment;
+        commitments.commitment = (G1Affine::generator() * evaluation).into_affine();
+        commitments.length_commitment = (G2Affine::generator() * evaluation).into_affine();
+        commitments.length_proof = (G2Affine::generator() * (evaluation * shift)).into_affine();
+        commitments.length = length;
+        eigenda_cert
+    }
+
+    /// Returns the key the payload of a cert is cached under.
+    pub(crate) fn get_cache_key(
+        eigenda_cert: &EigenDACert,
+        payload_form: PayloadForm,
+        decode_mode: PayloadDecodeMode,
+    ) -> CacheKey {
+        let blob_certificate = &eigenda_cert.blob_inclusion_info().blob_certificate;
+        CacheKey::new(
+            compute_blob_key(eigenda_cert).unwrap(),
+            &blob_certificate.blob_header.commitment,
+            payload_form,
+            decode_mode,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_default_blob_params() {
+        let config = RelayPayloadRetrieverConfig::from_toml("").unwrap();
+        assert_eq!(
+            config.blob_params,
+            HashMap::from([(0, BLOB_VERSION_0_PARAMS)])
+        );
+
+        let config = RelayPayloadRetrieverConfig::from_toml(
+            "[[blob_params]]\nblob_version = 1\nnum_chunks = 4096\ncoding_rate = 4\n",
+        )
+        .unwrap();
+        assert_eq!(config.blob_params.len(), 2);
+        assert_eq!(config.blob_params[&1].num_chunks, 4096);
+    }
+
+    /// Returns a cert of `payload` whose blob claims the given length, which its length proof does not prove.
+    pub(crate) fn get_test_eigenda_cert_with_false_length(
+        payload: &Payload,
+        payload_form: PayloadForm,
+        false_length: u32,
+    ) -> EigenDACert {
+        let mut eigenda_cert = get_test_eigenda_cert_for_payload(payload, payload_form);
+        let commitments = &mut eigenda_cert
+            .blob_inclusion_info_mut()
+            .blob_certificate
+            .blob_header
+            .commitment;
+        assert_ne!(commitments.length, false_length);
+        commitments.length = false_length;
+        eigenda_cert
+    }
+
+    #[tokio::test]
+    async fn test_false_length_is_rejected() {
+        let payload = Payload::new((0..100).collect());
+        for payload_form in [PayloadForm::Coeff, PayloadForm::Eval] {
+            let length = get_test_eigenda_cert_for_payload(&payload, payload_form)
+                .blob_inclusion_info()
+                .blob_certificate
+                .blob_header
+                .commitment
+                .length;
+            for false_length in [length / 2, length * 2] {
+                let eigenda_cert =
+                    get_test_eigenda_cert_with_false_length(&payload, payload_form, false_length);
+                // a cache filled without verifying length proofs must not bypass the check
+                let cache = InMemoryPayloadCache::new(PayloadCacheConfig::new(1 << 20));
+                cache
+                    .insert(
+                        get_cache_key(&eigenda_cert, payload_form, PayloadDecodeMode::Lenient),
+                        &payload,
+                    )
+                    .unwrap();
+                let mut retriever = get_retriever_without_relays(
+                    RelayPayloadRetrieverConfig::builder()
+                        .with_payload_form(payload_form)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .with_cache(Box::new(cache))
+                .with_validator_fallback(get_test_validator_retriever(payload_form).await);
+
+                // relays, the cache and the validator fallback
+                let res = retriever.get_payload(eigenda_cert.clone()).await;
+                assert!(matches!(
+                    res,
+                    Err(RelayPayloadRetrieverError::BlobCommitments(
+                        BlobCommitmentsError::InvalidLengthProof(length)
+                    )) if length == false_length
+                ));
+
+                // range reads
+                let res = retriever.get_payload_range(&eigenda_cert, 0..10).await;
+                assert!(matches!(
+                    res,
+                    Err(RelayPayloadRetrieverError::BlobCommitments(
+                        BlobCommitmentsError::InvalidLengthProof(length)
+                    )) if length == false_length
+                ));
+            }
+
+            // the true length is accepted, and the payload read from the cache
+            let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, payload_form);
+            let cache = InMemoryPayloadCache::new(PayloadCacheConfig::new(1 << 20));
+            cache
+                .insert(
+                    get_cache_key(&eigenda_cert, payload_form, PayloadDecodeMode::Lenient),
+                    &payload,
+                )
+                .unwrap();
+            let mut retriever = get_retriever_without_relays(
+                RelayPayloadRetrieverConfig::builder()
+                    .with_payload_form(payload_form)
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .with_cache(Box::new(cache));
+            assert_eq!(retriever.get_payload(eigenda_cert).await.unwrap(), payload);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_by_decode_mode() {
+        let payload = Payload::new((0..100).collect());
+        let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Coeff);
+        let lenient_key = get_cache_key(
+            &eigenda_cert,
+            PayloadForm::Coeff,
+            PayloadDecodeMode::Lenient,
+        );
+        assert_ne!(
+            lenient_key,
+            get_cache_key(&eigenda_cert, PayloadForm::Coeff, PayloadDecodeMode::Strict)
+        );
+        assert_ne!(
+            lenient_key,
+            get_cache_key(&eigenda_cert, PayloadForm::Eval, PayloadDecodeMode::Lenient)
+        );
+
+        for (decode_mode, hit) in [
+            (PayloadDecodeMode::Lenient, true),
+            (PayloadDecodeMode::Strict, false),
+        ] {
+            let cache = InMemoryPayloadCache::new(PayloadCacheConfig::new(1 << 20));
+            cache.insert(lenient_key, &payload).unwrap();
+            let mut retriever = get_retriever_without_relays(
+                RelayPayloadRetrieverConfig::builder()
+                    .with_payload_form(PayloadForm::Coeff)
+                    .with_decode_mode(decode_mode)
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .with_cache(Box::new(cache));
+
+            // no relay serves the blob, so only a cache hit returns the payload
+            let res = retriever.get_payload(eigenda_cert.clone()).await;
+            if hit {
+                assert_eq!(res.unwrap(), payload);
+            } else {
+                assert!(matches!(
+                    res,
+                    Err(RelayPayloadRetrieverError::UnableToRetrievePayload)
+                ));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_payload_range_in_coeff_form() {
+        let payload = Payload::new((0..100).collect());
+        let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Coeff);
+        // no relay serves the blob, so the payload is read from the cache
+        let cache = InMemoryPayloadCache::new(PayloadCacheConfig::new(1 << 20));
+        cache
+            .insert(
+                get_cache_key(
+                    &eigenda_cert,
+                    PayloadForm::Coeff,
+                    PayloadDecodeMode::Lenient,
+                ),
+                &payload,
+            )
+            .unwrap();
+        let mut retriever = get_retriever_without_relays(RelayPayloadRetrieverConfig {
+            payload_form: PayloadForm::Coeff,
+            ..get_relay_payload_retriever_test_config()
+        })
+        .await
+        .with_cache(Box::new(cache));
+        let commitments = &eigenda_cert
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header
+            .commitment;
+        verify_blob_commitments(&retriever.srs, commitments, true).unwrap();
+
+        for range in [0..100, 0..0, 30..33, 99..100] {
+            let payload_range = retriever
+                .get_payload_range(&eigenda_cert, range.clone())
+                .await
+                .unwrap();
+            assert_eq!(payload_range.bytes, payload.serialize()[range].to_vec());
+            assert!(payload_range.chunks.is_empty());
         }
+
+        let res = retriever.get_payload_range(&eigenda_cert, 90..101).await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::InvalidRange(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cert_verification_error_classes() {
+        let block = BlockNumber::Finalized;
+
+        // the contract rejects the cert: the cert must be dropped
+        let reason = "EigenDACertVerificationUtils._verifyDACertV2ForQuorums: signatories do not own at least threshold percentage of a quorum";
+        let mut revert_data = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert_data.extend(ethabi::encode(&[Token::String(reason.to_string())]));
+        let revert_data = format!("0x{}", hex::encode(revert_data));
+        let eth_rpc_url = mock_eth_rpc(move |_, _| {
+            Err(json!({ "code": 3, "message": "execution reverted", "data": revert_data }))
+        })
+        .await;
+        let mut retriever = get_verifying_retriever_without_relays(eth_rpc_url).await;
+        let res = retriever
+            .get_payload_at_block(get_test_eigenda_cert(), block)
+            .await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::CertVerificationFailed(
+                CertVerificationFailure::InsufficientStake(_)
+            ))
+        ));
+
+        // the RPC fails without executing the call: the verification may be retried
+        let eth_rpc_url =
+            mock_eth_rpc(|_, _| Err(json!({ "code": -32000, "message": "header not found" })))
+                .await;
+        let mut retriever = get_verifying_retriever_without_relays(eth_rpc_url).await;
+        let res = retriever
+            .get_payload_at_block(get_test_eigenda_cert(), block)
+            .await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::CertVerifier(
+                CertVerifierError::ContractCall { .. }
+            ))
+        ));
+
+        // the RPC is unreachable
+        let mut retriever = get_verifying_retriever_without_relays(get_closed_rpc_url()).await;
+        let res = retriever
+            .get_payload_at_block(get_test_eigenda_cert(), block)
+            .await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::CertVerifier(
+                CertVerifierError::ContractCall { .. }
+            ))
+        ));
+
+        // the inclusion proof is checked locally, before calling the unreachable RPC
+        let mut eigenda_cert = get_test_eigenda_cert();
+        eigenda_cert.blob_inclusion_info_mut().inclusion_proof[0] ^= 1;
+        let res = retriever.get_payload_at_block(eigenda_cert, block).await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::CertVerificationFailed(
+                CertVerificationFailure::InvalidInclusionProof(_)
+            ))
+        ));
     }
 
     #[ignore = "depends on external RPC"]
     #[tokio::test]
     async fn get_payload_from_relay() {
         let relay_config = get_relay_payload_retriever_test_config();
-        let srs_config = get_srs_test_config();
+        let srs = get_test_srs().await;
         let relay_client = get_test_relay_client().await;
-        let mut client =
-            RelayPayloadRetriever::new(relay_config, srs_config, relay_client).unwrap();
+        let mut client = RelayPayloadRetriever::new(relay_config, srs, relay_client).unwrap();
 
         let eigenda_cert = get_test_eigenda_cert();
         let res = client.get_payload(eigenda_cert).await;
@@ -1,12 +1,13 @@
 use ethers::prelude::*;
 use rust_eigenda_signers::signers::ethers::Signer as EthersSigner;
 use std::sync::Arc;
+use tracing::instrument;
 
 use crate::{
     errors::{ConversionError, RelayClientError},
     generated::i_relay_registry::IRelayRegistry,
     relay_client::RelayKey,
-    utils::SecretUrl,
+    utils::{contract_error_source, SecretUrl},
 };
 
 /// Provides methods for interacting with the EigenDA RelayRegistry contract.
@@ -39,6 +40,21 @@ impl<S> RelayRegistry<S> {
         &self,
         relay_key: RelayKey,
     ) -> Result<String, RelayClientError>
+    where
+        EthersSigner<S>: Signer,
+    {
+        self.get_url_from_relay_key_at_block(relay_key, BlockNumber::Latest)
+            .await
+    }
+
+    /// Calls the relayKeyToUrl view function on the EigenDARelayRegistry contract at the given block: a number, a
+    /// hash, or a tag such as `safe` or `finalized`.
+    #[instrument(skip_all, fields(contract = ?self.relay_registry_contract.address(), relay_key))]
+    pub async fn get_url_from_relay_key_at_block(
+        &self,
+        relay_key: RelayKey,
+        block: impl Into<BlockId>,
+    ) -> Result<String, RelayClientError>
     where
         EthersSigner<S>: Signer,
     {
@@ -46,9 +62,13 @@ impl<S> RelayRegistry<S> {
             "https://{}",
             self.relay_registry_contract
                 .relay_key_to_url(relay_key)
+                .block(block)
                 .call()
                 .await
-                .map_err(|_| RelayClientError::RelayKeyToUrl(relay_key))?
+                .map_err(|e| RelayClientError::RelayKeyToUrl {
+                    relay_key,
+                    source: contract_error_source(e),
+                })?
         ); // TODO: forcing https schema on local stack will fail
         Ok(url)
     }
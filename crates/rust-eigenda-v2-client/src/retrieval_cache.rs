@@ -0,0 +1,569 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rust_eigenda_v2_common::BlobCommitments;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::{
+    commitment_utils::{g1_commitment_to_bytes, g2_commitment_to_bytes},
+    core::{BlobKey, Payload, PayloadDecodeMode, PayloadForm},
+    errors::{ConversionError, RetrievalCacheError},
+};
+
+/// Period during which EigenDA guarantees the availability of a blob (14 days).
+///
+/// Payloads older than this can no longer be retrieved from the network, so they are not
+/// expected to be read again either. Caches measure it from the time an entry is inserted, not from
+/// the dispersal of the blob, so an entry may outlive the availability of its blob by up to this period.
+pub const BLOB_RETENTION_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// Key of a cached [`Payload`]: the [`BlobKey`] together with a digest of the blob commitments
+/// it was verified against and of the form and mode it was decoded with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub blob_key: BlobKey,
+    pub commitment_digest: [u8; 32],
+}
+
+impl CacheKey {
+    pub fn new(
+        blob_key: BlobKey,
+        commitments: &BlobCommitments,
+        payload_form: PayloadForm,
+        decode_mode: PayloadDecodeMode,
+    ) -> Result<Self, ConversionError> {
+        let mut hasher = Keccak::v256();
+        hasher.update(&g1_commitment_to_bytes(&commitments.commitment)?);
+        hasher.update(&g2_commitment_to_bytes(&commitments.length_commitment)?);
+        hasher.update(&g2_commitment_to_bytes(&commitments.length_proof)?);
+        hasher.update(&commitments.length.to_be_bytes());
+        hasher.update(&[match payload_form {
+            PayloadForm::Eval => 0,
+            PayloadForm::Coeff => 1,
+        }]);
+        hasher.update(&[match decode_mode {
+            PayloadDecodeMode::Lenient => 0,
+            PayloadDecodeMode::Strict => 1,
+        }]);
+        let mut commitment_digest = [0u8; 32];
+        hasher.finalize(&mut commitment_digest);
+
+        Ok(Self {
+            blob_key,
+            commitment_digest,
+        })
+    }
+
+    /// Hex representation of the key, used as file name by [`DiskPayloadCache`].
+    fn to_hex(self) -> String {
+        format!(
+            "{}-{}",
+            hex::encode(self.blob_key.to_bytes()),
+            hex::encode(self.commitment_digest)
+        )
+    }
+
+    fn from_hex(hex: &str) -> Option<Self> {
+        let (blob_key, commitment_digest) = hex.split_once('-')?;
+        Some(Self {
+            blob_key: BlobKey::from_hex(blob_key).ok()?,
+            commitment_digest: hex::decode(commitment_digest).ok()?.try_into().ok()?,
+        })
+    }
+}
+
+/// Number of hits and misses of a [`PayloadCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheCounters {
+    fn record<T>(&self, entry: Option<T>) -> Option<T> {
+        match entry {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        entry
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Cache of verified payloads, used by [`RelayPayloadRetriever`] to skip retrieval and verification
+/// of blobs it has already read.
+///
+/// Only payloads that were verified against the commitments in the [`CacheKey`] must be inserted.
+/// The key also covers the payload form and decode mode, so a cache can be shared by retrievers configured differently.
+pub trait PayloadCache: Send + Sync {
+    /// Returns the payload stored under `key`, if present and not expired.
+    fn get(&self, key: &CacheKey) -> Option<Payload>;
+
+    /// Stores a verified payload under `key`, evicting other entries if needed to stay within bounds.
+    fn insert(&self, key: CacheKey, payload: &Payload) -> Result<(), RetrievalCacheError>;
+
+    /// Returns the number of hits and misses since the cache was created.
+    fn stats(&self) -> CacheStats;
+}
+
+/// Bounds of a [`PayloadCache`].
+#[derive(Clone, Copy, Debug)]
+pub struct PayloadCacheConfig {
+    /// Maximum total size of the cached payloads, in bytes.
+    pub max_size_bytes: u64,
+    /// Time after which an entry is evicted, counted from its insertion in the cache (the cache key does
+    /// not carry the dispersal time of the blob). Defaults to [`BLOB_RETENTION_PERIOD`].
+    pub retention: Duration,
+}
+
+impl PayloadCacheConfig {
+    pub fn new(max_size_bytes: u64) -> Self {
+        Self {
+            max_size_bytes,
+            retention: BLOB_RETENTION_PERIOD,
+        }
+    }
+}
+
+/// Bookkeeping of the entries of a cache, in least recently used order.
+#[derive(Debug, Default)]
+struct LruIndex {
+    entries: HashMap<CacheKey, LruEntry>,
+    /// keys by last access tick, the first one being the least recently used
+    order: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    size_bytes: u64,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    size_bytes: u64,
+    /// seconds since the unix epoch at which the entry was inserted
+    inserted_at: u64,
+    last_access: u64,
+}
+
+impl LruIndex {
+    fn touch(&mut self, key: &CacheKey) {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.order.remove(&entry.last_access);
+            entry.last_access = self.tick;
+            self.order.insert(self.tick, *key);
+        }
+    }
+
+    fn insert(&mut self, key: CacheKey, size_bytes: u64, inserted_at: u64) {
+        self.remove(&key);
+        self.tick += 1;
+        self.entries.insert(
+            key,
+            LruEntry {
+                size_bytes,
+                inserted_at,
+                last_access: self.tick,
+            },
+        );
+        self.order.insert(self.tick, key);
+        self.size_bytes += size_bytes;
+    }
+
+    fn remove(&mut self, key: &CacheKey) -> bool {
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.order.remove(&entry.last_access);
+                self.size_bytes -= entry.size_bytes;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_expired(&self, key: &CacheKey, retention: Duration, now: u64) -> bool {
+        self.entries
+            .get(key)
+            .is_some_and(|entry| now.saturating_sub(entry.inserted_at) >= retention.as_secs())
+    }
+
+    /// Returns the keys to evict so that an entry of `incoming_bytes` fits: first the expired entries,
+    /// then the least recently used ones.
+    fn keys_to_evict(
+        &self,
+        config: &PayloadCacheConfig,
+        incoming_bytes: u64,
+        now: u64,
+    ) -> Vec<CacheKey> {
+        let mut evicted: Vec<CacheKey> = self
+            .entries
+            .keys()
+            .filter(|key| self.is_expired(key, config.retention, now))
+            .copied()
+            .collect();
+        let mut size_bytes: u64 = self.size_bytes
+            - evicted
+                .iter()
+                .map(|key| self.entries[key].size_bytes)
+                .sum::<u64>();
+
+        for key in self.order.values() {
+            if size_bytes + incoming_bytes <= config.max_size_bytes {
+                break;
+            }
+            if evicted.contains(key) {
+                continue;
+            }
+            size_bytes -= self.entries[key].size_bytes;
+            evicted.push(*key);
+        }
+        evicted
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// In-memory [`PayloadCache`] with least recently used eviction.
+pub struct InMemoryPayloadCache {
+    config: PayloadCacheConfig,
+    state: Mutex<(LruIndex, HashMap<CacheKey, Payload>)>,
+    counters: CacheCounters,
+}
+
+impl InMemoryPayloadCache {
+    pub fn new(config: PayloadCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new((LruIndex::default(), HashMap::new())),
+            counters: CacheCounters::default(),
+        }
+    }
+}
+
+impl PayloadCache for InMemoryPayloadCache {
+    fn get(&self, key: &CacheKey) -> Option<Payload> {
+        let Ok(mut state) = self.state.lock() else {
+            return self.counters.record(None);
+        };
+        let (index, payloads) = &mut *state;
+        if index.is_expired(key, self.config.retention, unix_now()) {
+            index.remove(key);
+            payloads.remove(key);
+        }
+        index.touch(key);
+        self.counters.record(payloads.get(key).cloned())
+    }
+
+    fn insert(&self, key: CacheKey, payload: &Payload) -> Result<(), RetrievalCacheError> {
+        let size_bytes = payload.serialize().len() as u64;
+        if size_bytes > self.config.max_size_bytes {
+            return Ok(());
+        }
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| RetrievalCacheError::Poisoned)?;
+        let (index, payloads) = &mut *state;
+        let now = unix_now();
+        index.remove(&key);
+        for evicted in index.keys_to_evict(&self.config, size_bytes, now) {
+            index.remove(&evicted);
+            payloads.remove(&evicted);
+        }
+        index.insert(key, size_bytes, now);
+        payloads.insert(key, payload.clone());
+        Ok(())
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.stats()
+    }
+}
+
+/// On-disk [`PayloadCache`], storing one file per payload in a directory.
+///
+/// Each file holds the insertion time (8 bytes, big endian seconds since the unix epoch) followed by the payload.
+/// Entries already present in the directory are picked up on creation, so the cache survives restarts.
+/// Temporary files left over by an insertion interrupted by a crash are removed at the same time.
+pub struct DiskPayloadCache {
+    config: PayloadCacheConfig,
+    dir: PathBuf,
+    index: Mutex<LruIndex>,
+    counters: CacheCounters,
+}
+
+const DISK_ENTRY_HEADER_SIZE: usize = 8;
+const TMP_FILE_SUFFIX: &str = ".tmp";
+
+impl DiskPayloadCache {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        config: PayloadCacheConfig,
+    ) -> Result<Self, RetrievalCacheError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let mut index = LruIndex::default();
+        let mut existing: Vec<(u64, CacheKey, u64)> = vec![];
+        for dir_entry in fs::read_dir(&dir)? {
+            let path = dir_entry?.path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if let Some(tmp_key) = file_name.strip_suffix(TMP_FILE_SUFFIX) {
+                if CacheKey::from_hex(tmp_key).is_some() {
+                    remove_if_exists(&path)?;
+                }
+                continue;
+            }
+            let Some(key) = CacheKey::from_hex(file_name) else {
+                continue;
+            };
+            let file_size = fs::metadata(&path)?.len();
+            let mut header = [0u8; DISK_ENTRY_HEADER_SIZE];
+            if file_size < DISK_ENTRY_HEADER_SIZE as u64
+                || fs::File::open(&path)?.read_exact(&mut header).is_err()
+            {
+                remove_if_exists(&path)?;
+                continue;
+            }
+            existing.push((
+                u64::from_be_bytes(header),
+                key,
+                file_size - DISK_ENTRY_HEADER_SIZE as u64,
+            ));
+        }
+        // older entries are considered less recently used
+        existing.sort_by_key(|(inserted_at, _, _)| *inserted_at);
+        for (inserted_at, key, size_bytes) in existing {
+            index.insert(key, size_bytes, inserted_at);
+        }
+
+        let cache = Self {
+            config,
+            dir,
+            index: Mutex::new(index),
+            counters: CacheCounters::default(),
+        };
+        cache.evict(0, unix_now())?;
+        Ok(cache)
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(key.to_hex())
+    }
+
+    /// Evicts entries so that a new entry of `incoming_bytes` fits in the cache.
+    fn evict(&self, incoming_bytes: u64, now: u64) -> Result<(), RetrievalCacheError> {
+        let mut index = self
+            .index
+            .lock()
+            .map_err(|_| RetrievalCacheError::Poisoned)?;
+        for evicted in index.keys_to_evict(&self.config, incoming_bytes, now) {
+            index.remove(&evicted);
+            remove_if_exists(&self.path(&evicted))?;
+        }
+        Ok(())
+    }
+
+    fn read(&self, key: &CacheKey) -> Option<Payload> {
+        let mut index = self.index.lock().ok()?;
+        if index.is_expired(key, self.config.retention, unix_now()) {
+            index.remove(key);
+            let _ = remove_if_exists(&self.path(key));
+            return None;
+        }
+        if !index.entries.contains_key(key) {
+            return None;
+        }
+        index.touch(key);
+
+        match fs::read(self.path(key)) {
+            Ok(bytes) if bytes.len() >= DISK_ENTRY_HEADER_SIZE => {
+                Some(Payload::new(bytes[DISK_ENTRY_HEADER_SIZE..].to_vec()))
+            }
+            _ => {
+                // the file was removed or corrupted externally
+                index.remove(key);
+                None
+            }
+        }
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), RetrievalCacheError> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+impl PayloadCache for DiskPayloadCache {
+    fn get(&self, key: &CacheKey) -> Option<Payload> {
+        self.counters.record(self.read(key))
+    }
+
+    fn insert(&self, key: CacheKey, payload: &Payload) -> Result<(), RetrievalCacheError> {
+        let payload_bytes = payload.serialize();
+        let size_bytes = payload_bytes.len() as u64;
+        if size_bytes > self.config.max_size_bytes {
+            return Ok(());
+        }
+        let now = unix_now();
+        {
+            let mut index = self
+                .index
+                .lock()
+                .map_err(|_| RetrievalCacheError::Poisoned)?;
+            index.remove(&key);
+        }
+        self.evict(size_bytes, now)?;
+
+        let mut bytes = now.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&payload_bytes);
+        // write to a temporary file first, so that readers never see a partially written entry
+        let tmp_path = self
+            .dir
+            .join(format!("{}{}", key.to_hex(), TMP_FILE_SUFFIX));
+        fs::write(&tmp_path, &bytes)?;
+        fs::rename(&tmp_path, self.path(&key))?;
+
+        let mut index = self
+            .index
+            .lock()
+            .map_err(|_| RetrievalCacheError::Poisoned)?;
+        index.insert(key, size_bytes, now);
+        Ok(())
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.counters.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> CacheKey {
+        CacheKey {
+            blob_key: BlobKey::from_bytes([byte; 32]),
+            commitment_digest: [byte; 32],
+        }
+    }
+
+    fn payload(byte: u8, length: usize) -> Payload {
+        Payload::new(vec![byte; length])
+    }
+
+    fn test_lru_eviction(cache: &dyn PayloadCache) {
+        cache.insert(key(1), &payload(1, 40)).unwrap();
+        cache.insert(key(2), &payload(2, 40)).unwrap();
+        // key 1 becomes the most recently used
+        assert_eq!(cache.get(&key(1)), Some(payload(1, 40)));
+
+        // inserting key 3 exceeds the 100 bytes bound, so key 2 is evicted
+        cache.insert(key(3), &payload(3, 40)).unwrap();
+        assert_eq!(cache.get(&key(2)), None);
+        assert_eq!(cache.get(&key(1)), Some(payload(1, 40)));
+        assert_eq!(cache.get(&key(3)), Some(payload(3, 40)));
+
+        // payloads bigger than the cache are not stored
+        cache.insert(key(4), &payload(4, 101)).unwrap();
+        assert_eq!(cache.get(&key(4)), None);
+
+        assert_eq!(cache.stats(), CacheStats { hits: 3, misses: 2 });
+    }
+
+    #[test]
+    fn test_in_memory_cache() {
+        let cache = InMemoryPayloadCache::new(PayloadCacheConfig::new(100));
+        test_lru_eviction(&cache);
+    }
+
+    #[test]
+    fn test_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskPayloadCache::new(dir.path(), PayloadCacheConfig::new(100)).unwrap();
+        test_lru_eviction(&cache);
+
+        // entries survive a restart
+        let cache = DiskPayloadCache::new(dir.path(), PayloadCacheConfig::new(100)).unwrap();
+        assert_eq!(cache.get(&key(3)), Some(payload(3, 40)));
+        assert_eq!(cache.get(&key(2)), None);
+    }
+
+    #[test]
+    fn test_retention() {
+        let config = PayloadCacheConfig {
+            max_size_bytes: 100,
+            retention: Duration::ZERO,
+        };
+        let cache = InMemoryPayloadCache::new(config);
+        cache.insert(key(1), &payload(1, 10)).unwrap();
+        assert_eq!(cache.get(&key(1)), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskPayloadCache::new(dir.path(), config).unwrap();
+        cache.insert(key(1), &payload(1, 10)).unwrap();
+        assert_eq!(cache.get(&key(1)), None);
+    }
+
+    #[test]
+    fn test_disk_cache_startup_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskPayloadCache::new(dir.path(), PayloadCacheConfig::new(100)).unwrap();
+        cache.insert(key(1), &payload(1, 10)).unwrap();
+        drop(cache);
+
+        // leftovers of an interrupted insertion and a truncated entry
+        let tmp_path = dir
+            .path()
+            .join(format!("{}{}", key(2).to_hex(), TMP_FILE_SUFFIX));
+        fs::write(&tmp_path, [0u8; 20]).unwrap();
+        let truncated_path = dir.path().join(key(3).to_hex());
+        fs::write(&truncated_path, [0u8; 4]).unwrap();
+        // unrelated files are left alone
+        let unrelated_path = dir.path().join("unrelated.tmp");
+        fs::write(&unrelated_path, [0u8; 4]).unwrap();
+
+        let cache = DiskPayloadCache::new(dir.path(), PayloadCacheConfig::new(100)).unwrap();
+        assert!(!tmp_path.exists());
+        assert!(!truncated_path.exists());
+        assert!(unrelated_path.exists());
+        assert_eq!(cache.get(&key(1)), Some(payload(1, 10)));
+        assert_eq!(cache.get(&key(2)), None);
+        assert_eq!(cache.get(&key(3)), None);
+    }
+
+    #[test]
+    fn test_cache_key_hex() {
+        let key = key(7);
+        assert_eq!(CacheKey::from_hex(&key.to_hex()), Some(key));
+        assert_eq!(CacheKey::from_hex("invalid"), None);
+    }
+}
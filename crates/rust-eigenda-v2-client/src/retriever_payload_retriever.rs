@@ -0,0 +1,274 @@
+use std::{sync::Arc, time::Duration};
+
+use rand::seq::SliceRandom;
+use rust_eigenda_v2_common::EigenDACert;
+use tokio::time::timeout;
+use tonic::transport::{Channel, ClientTlsConfig};
+use tracing::{debug, debug_span, instrument, warn, Instrument};
+
+use crate::{
+    commitment_utils::{generate_and_compare_blob_commitment, verify_blob_commitments},
+    core::{
+        eigenda_cert::{blob_header_to_proto, PaymentHeader},
+        Blob, Payload, PayloadDecodeMode, PayloadForm,
+    },
+    errors::RetrieverPayloadRetrieverError,
+    generated::{
+        common::v2::BlobHeader as ProtoBlobHeader,
+        retriever::v2::{retriever_client::RetrieverClient as RpcRetrieverClient, BlobRequest},
+    },
+    srs::Srs,
+};
+
+pub struct RetrieverPayloadRetrieverConfig {
+    pub payload_form: PayloadForm,
+    pub retrieval_timeout_secs: Duration,
+    pub retriever_rpc: String,
+    pub use_secure_grpc_flag: bool,
+    pub max_grpc_message_size: usize,
+    /// Whether to verify the length proof of the blob commitments. See
+    /// [`RelayPayloadRetrieverConfig::verify_length_proof`](crate::relay_payload_retriever::RelayPayloadRetrieverConfig::verify_length_proof).
+    pub verify_length_proof: bool,
+    /// How strictly retrieved blobs are validated when decoding them into payloads.
+    pub decode_mode: PayloadDecodeMode,
+}
+
+/// Provides the ability to get payloads from an EigenDA retriever service.
+///
+/// The retriever fetches the chunks of the blob from the validators and reconstructs it, so this is an
+/// alternative to [`RelayPayloadRetriever`] that does not depend on relays.
+pub struct RetrieverPayloadRetriever {
+    srs: Arc<Srs>,
+    config: RetrieverPayloadRetrieverConfig,
+    rpc_client: RpcRetrieverClient<Channel>,
+}
+
+impl RetrieverPayloadRetriever {
+    /// Connects to the retriever service and assembles a RetrieverPayloadRetriever from the specified config and SRS.
+    pub async fn new(
+        config: RetrieverPayloadRetrieverConfig,
+        srs: Arc<Srs>,
+    ) -> Result<Self, RetrieverPayloadRetrieverError> {
+        if config.max_grpc_message_size == 0 {
+            return Err(RetrieverPayloadRetrieverError::InvalidMaxGrpcMessageSize);
+        }
+        if config.verify_length_proof {
+            srs.check_length_proof_points()?;
+        }
+
+        let mut endpoint = Channel::from_shared(config.retriever_rpc.clone()).map_err(|_| {
+            RetrieverPayloadRetrieverError::InvalidURI(config.retriever_rpc.clone())
+        })?;
+        if config.use_secure_grpc_flag {
+            endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+        }
+        let channel = endpoint.connect().await?;
+        let rpc_client = RpcRetrieverClient::new(channel)
+            .max_decoding_message_size(config.max_grpc_message_size);
+
+        Ok(RetrieverPayloadRetriever {
+            srs,
+            config,
+            rpc_client,
+        })
+    }
+
+    // Requests the blob referenced by the certificate to the retriever, once per quorum of the blob (in random order),
+    // until it is retrieved.
+    //
+    // Before contacting the retriever, the blob commitments of the certificate are checked to be consistent, and the
+    // length proof to be valid if enabled. If the blob is successfully retrieved, then the blob is verified against the
+    // certificate. If the verification succeeds, the blob is decoded to yield the payload (the original user data, with
+    // no padding or any modification), and the payload is returned.
+    //
+    // The retriever identifies blobs by their full header, so the [`PaymentHeader`] the blob was dispersed with is needed.
+    // It must match the payment header hash in the certificate.
+    //
+    // This method does NOT verify the [`EigenDACert`] on chain: it is assumed that the input [`EigenDACert`] has already been
+    // verified prior to calling this method.
+    #[instrument(skip_all, fields(reference_block_number = eigenda_cert.reference_block_number()))]
+    pub async fn get_payload(
+        &mut self,
+        eigenda_cert: EigenDACert,
+        payment_header: &PaymentHeader,
+    ) -> Result<Payload, RetrieverPayloadRetrieverError> {
+        let blob_header = &eigenda_cert
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header;
+        if blob_header.quorum_numbers.is_empty() {
+            return Err(RetrieverPayloadRetrieverError::InvalidCertificate(
+                "quorum count is zero".to_string(),
+            ));
+        }
+        verify_blob_commitments(
+            &self.srs,
+            &blob_header.commitment,
+            self.config.verify_length_proof,
+        )?;
+        let proto_blob_header = blob_header_to_proto(blob_header, payment_header)?;
+        let reference_block_number = eigenda_cert.reference_block_number();
+        let blob_length_symbols = blob_header.commitment.length;
+
+        let mut quorum_ids = blob_header.quorum_numbers.clone();
+        quorum_ids.shuffle(&mut rand::thread_rng());
+
+        for quorum_id in quorum_ids {
+            let span = debug_span!(
+                "retriever_attempt",
+                quorum = quorum_id,
+                symbol_count = blob_length_symbols
+            );
+            let blob = match self
+                .retrieve_blob_with_timeout(
+                    proto_blob_header.clone(),
+                    reference_block_number,
+                    quorum_id,
+                    blob_length_symbols,
+                )
+                .instrument(span.clone())
+                .await
+            {
+                Ok(blob) => blob,
+                Err(err) => {
+                    warn!(parent: &span, error = %err, "failed to retrieve blob from retriever");
+                    continue;
+                }
+            };
+
+            let g1_srs = self.srs.g1_points(blob_length_symbols as usize)?;
+            let valid = debug_span!(parent: &span, "verify_blob_commitment").in_scope(|| {
+                generate_and_compare_blob_commitment(
+                    &g1_srs,
+                    blob.serialize(),
+                    blob_header.commitment.commitment,
+                )
+                .unwrap_or(false)
+            });
+            if !valid {
+                warn!(parent: &span, "blob retrieved from retriever does not match its commitment");
+                continue;
+            }
+
+            let payload = match blob
+                .to_payload_with_mode(self.config.payload_form, self.config.decode_mode)
+            {
+                Ok(payload) => payload,
+                Err(err) => {
+                    warn!(
+                        parent: &span,
+                        error = %err,
+                        "failed to decode the payload of the blob retrieved from retriever"
+                    );
+                    continue;
+                }
+            };
+
+            debug!(parent: &span, "retrieved payload from retriever");
+            return Ok(payload);
+        }
+
+        // If we reach this point, we've tried all quorums and failed to retrieve the blob
+        Err(RetrieverPayloadRetrieverError::UnableToRetrievePayload)
+    }
+
+    /// Attempts to retrieve a [`Blob`] from the retriever, reconstructed from the chunks of the given quorum.
+    ///
+    /// Times out based on config's `retrieval_timeout_secs`.
+    ///
+    /// Returns [`RetrieverPayloadRetrieverError::RetrievalTimeout`] if the timeout is exceeded.
+    async fn retrieve_blob_with_timeout(
+        &mut self,
+        blob_header: ProtoBlobHeader,
+        reference_block_number: u32,
+        quorum_id: u8,
+        blob_length_symbols: u32,
+    ) -> Result<Blob, RetrieverPayloadRetrieverError> {
+        let reply = timeout(
+            self.config.retrieval_timeout_secs,
+            self.rpc_client.retrieve_blob(BlobRequest {
+                blob_header: Some(blob_header),
+                reference_block_number,
+                quorum_id: quorum_id as u32,
+            }),
+        )
+        .await
+        .map_err(|_| {
+            warn!(timeout = ?self.config.retrieval_timeout_secs, "retriever request timed out");
+            RetrieverPayloadRetrieverError::RetrievalTimeout
+        })??
+        .into_inner();
+
+        let blob = Blob::deserialize_blob_with_mode(
+            reply.data,
+            blob_length_symbols as usize,
+            self.config.decode_mode,
+        )?;
+        Ok(blob)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::eigenda_cert::PaymentHeader,
+        errors::BlobCommitmentsError,
+        relay_payload_retriever::tests::{
+            get_test_eigenda_cert_for_payload, get_test_eigenda_cert_with_false_length,
+        },
+        tests::get_insecure_test_srs,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_false_length_is_rejected() {
+        // a retriever that accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let retriever_rpc = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        let mut retriever = RetrieverPayloadRetriever::new(
+            RetrieverPayloadRetrieverConfig {
+                payload_form: PayloadForm::Coeff,
+                retrieval_timeout_secs: Duration::from_secs(1),
+                retriever_rpc,
+                use_secure_grpc_flag: false,
+                max_grpc_message_size: 1 << 20,
+                verify_length_proof: true,
+                decode_mode: PayloadDecodeMode::Strict,
+            },
+            get_insecure_test_srs().await,
+        )
+        .await
+        .unwrap();
+        let payment_header = PaymentHeader {
+            account_id: String::new(),
+            timestamp: 0,
+            cumulative_payment: vec![],
+        };
+
+        let payload = Payload::new((0..100).collect());
+        let length = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Coeff)
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header
+            .commitment
+            .length;
+        for false_length in [length / 2, length * 2] {
+            let eigenda_cert =
+                get_test_eigenda_cert_with_false_length(&payload, PayloadForm::Coeff, false_length);
+            let res = retriever.get_payload(eigenda_cert, &payment_header).await;
+            assert!(matches!(
+                res,
+                Err(RetrieverPayloadRetrieverError::BlobCommitments(
+                    BlobCommitmentsError::InvalidLengthProof(length)
+                )) if length == false_length
+            ));
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::{path::PathBuf, sync::Arc};
+
+use ark_bn254::{G1Affine, G2Affine};
+use rust_eigenda_srs::{default_cache_dir, G1Points, SrsFile, SrsSource};
+use serde::Deserialize;
+
+use crate::{
+    commitment_utils::g2_power_of_2_points_from_bytes,
+    config::{check_url, invalid, LoadableConfig},
+    errors::{ConfigError, ConversionError},
+};
+
+/// Configuration of the [`Srs`], built with [`SRSConfig::builder`] or read with [`LoadableConfig`].
+///
+/// When read, each file is set by either a `_path` or a `_url` field (e.g. `g1_path` or `g1_url`), and may be pinned
+/// to the hex encoded SHA-256 digest of its contents with a `_sha256` field.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "SRSConfigFields")]
+pub struct SRSConfig {
+    /// The G1 points (`g1.point`).
+    pub g1: SrsFile,
+    /// Number of points of the SRS the G1 points belong to.
+    pub order: u32,
+    /// The `[s^(2^i)]_2` points (`g2.point.powerOf2`), needed only to verify chunks (range reads and retrieval
+    /// from validators).
+    pub g2_power_of_2: Option<SrsFile>,
+    /// Directory where downloaded SRS files are cached. Defaults to [`default_cache_dir`].
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl SRSConfig {
+    pub fn builder() -> SRSConfigBuilder {
+        SRSConfigBuilder::default()
+    }
+}
+
+fn check_srs_file(field: &'static str, file: &SrsFile) -> Result<(), ConfigError> {
+    match &file.source {
+        SrsSource::Path(path) if path.as_os_str().is_empty() => Err(invalid(field, "empty path")),
+        SrsSource::Url(url) => check_url(field, url),
+        _ => Ok(()),
+    }
+}
+
+impl LoadableConfig for SRSConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.order == 0 {
+            return Err(invalid("order", "must be positive"));
+        }
+        check_srs_file("g1", &self.g1)?;
+        if let Some(g2_power_of_2) = &self.g2_power_of_2 {
+            check_srs_file("g2_power_of_2", g2_power_of_2)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds an [`SRSConfig`], with the G1 points and the SRS order as the only required fields.
+#[derive(Clone, Debug, Default)]
+pub struct SRSConfigBuilder {
+    g1: Option<SrsFile>,
+    order: Option<u32>,
+    g2_power_of_2: Option<SrsFile>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl SRSConfigBuilder {
+    pub fn with_g1(mut self, g1: SrsFile) -> Self {
+        self.g1 = Some(g1);
+        self
+    }
+
+    pub fn with_order(mut self, order: u32) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn with_g2_power_of_2(mut self, g2_power_of_2: SrsFile) -> Self {
+        self.g2_power_of_2 = Some(g2_power_of_2);
+        self
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    pub fn build(self) -> Result<SRSConfig, ConfigError> {
+        let config = SRSConfig {
+            g1: self.g1.ok_or(ConfigError::MissingField("g1"))?,
+            order: self.order.ok_or(ConfigError::MissingField("order"))?,
+            g2_power_of_2: self.g2_power_of_2,
+            cache_dir: self.cache_dir,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Fields of an [`SRSConfig`] as read from files and environment variables.
+#[derive(Deserialize)]
+struct SRSConfigFields {
+    g1_path: Option<PathBuf>,
+    g1_url: Option<String>,
+    g1_sha256: Option<String>,
+    order: Option<u32>,
+    g2_power_of_2_path: Option<PathBuf>,
+    g2_power_of_2_url: Option<String>,
+    g2_power_of_2_sha256: Option<String>,
+    cache_dir: Option<PathBuf>,
+}
+
+/// Returns the file set by the `_path`, `_url` and `_sha256` fields of a file, if any.
+fn srs_file(
+    field: &'static str,
+    path: Option<PathBuf>,
+    url: Option<String>,
+    sha256: Option<String>,
+) -> Result<Option<SrsFile>, ConfigError> {
+    let source = match (path, url) {
+        (Some(_), Some(_)) => {
+            return Err(invalid(field, "only one of a path and a URL may be set"))
+        }
+        (Some(path), None) => SrsSource::Path(path),
+        (None, Some(url)) => SrsSource::Url(url),
+        (None, None) if sha256.is_some() => {
+            return Err(invalid(field, "digest set without a file"))
+        }
+        (None, None) => return Ok(None),
+    };
+    let mut file = SrsFile::new(source);
+    if let Some(sha256) = sha256 {
+        let mut digest = [0u8; 32];
+        hex::decode_to_slice(sha256.strip_prefix("0x").unwrap_or(&sha256), &mut digest)
+            .map_err(|e| invalid(field, format!("invalid SHA-256 digest: {}", e)))?;
+        file = file.with_sha256(digest);
+    }
+    Ok(Some(file))
+}
+
+impl TryFrom<SRSConfigFields> for SRSConfig {
+    type Error = ConfigError;
+
+    fn try_from(fields: SRSConfigFields) -> Result<Self, Self::Error> {
+        let mut builder = SRSConfig::builder();
+        builder.g1 = srs_file("g1", fields.g1_path, fields.g1_url, fields.g1_sha256)?;
+        builder.order = fields.order;
+        builder.g2_power_of_2 = srs_file(
+            "g2_power_of_2",
+            fields.g2_power_of_2_path,
+            fields.g2_power_of_2_url,
+            fields.g2_power_of_2_sha256,
+        )?;
+        builder.cache_dir = fields.cache_dir;
+        builder.build()
+    }
+}
+
+/// SRS points needed to verify blobs and chunks.
+///
+/// The SRS files are memory-mapped, and G1 points are decompressed the first time a blob long enough to need them
+/// is verified. Loading it once and sharing it (in an [`Arc`]) between payload retrievers avoids keeping several
+/// copies of the points in memory.
+pub struct Srs {
+    order: u32,
+    g1: G1Points,
+    g2_power_of_2: Option<Vec<G2Affine>>,
+}
+
+impl Srs {
+    /// Loads the SRS files of the config, downloading them if needed.
+    pub async fn load(config: &SRSConfig) -> Result<Self, ConversionError> {
+        let cache_dir = config.cache_dir.clone().unwrap_or_else(default_cache_dir);
+        let g1 = G1Points::new(config.g1.load(&cache_dir).await?);
+        let g2_power_of_2 = match config.g2_power_of_2.as_ref() {
+            Some(file) => Some(g2_power_of_2_points_from_bytes(
+                &file.load(&cache_dir).await?,
+            )?),
+            None => None,
+        };
+
+        Ok(Self {
+            order: config.order,
+            g1,
+            g2_power_of_2,
+        })
+    }
+
+    pub fn order(&self) -> u32 {
+        self.order
+    }
+
+    /// Number of G1 points in the G1 file, which bounds the length of the blobs that can be committed to.
+    pub fn g1_points_available(&self) -> usize {
+        self.g1.available()
+    }
+
+    /// Checks that the G1 file holds all the `order` points of the SRS, as verifying length proofs needs the point
+    /// `[s^(order - length)]_1` of any blob length. The truncated `g1.point` files distributed for committing to blobs
+    /// don't.
+    pub fn check_length_proof_points(&self) -> Result<(), ConversionError> {
+        let available = self.g1_points_available();
+        if available < self.order as usize {
+            return Err(ConversionError::SrsFile(format!(
+                "verifying length proofs needs all the {} G1 points of the SRS, the G1 file holds {}",
+                self.order, available
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns at least the first `count` G1 points.
+    pub fn g1_points(&self, count: usize) -> Result<Arc<Vec<G1Affine>>, ConversionError> {
+        Ok(self.g1.points(count)?)
+    }
+
+    /// Returns the G1 point `[s^index]_1`.
+    pub fn g1_point_at(&self, index: usize) -> Result<G1Affine, ConversionError> {
+        Ok(self.g1.point_at(index)?)
+    }
+
+    /// Returns the `[s^(2^i)]_2` points, if they were loaded.
+    pub fn g2_power_of_2(&self) -> Option<&[G2Affine]> {
+        self.g2_power_of_2.as_deref()
+    }
+
+    /// Returns the G2 point `[s^n]_2` for a chunk length `n`, which must be a power of two.
+    pub(crate) fn g2_tau_pow(&self, chunk_length: usize) -> Option<G2Affine> {
+        self.g2_power_of_2()?
+            .get(chunk_length.trailing_zeros() as usize)
+            .copied()
+    }
+}
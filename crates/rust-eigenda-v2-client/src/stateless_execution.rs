@@ -0,0 +1,1006 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+};
+
+use ethereum_types::{H160, H256, U256};
+use ethers::{
+    prelude::{Http, Middleware, Provider},
+    types::{Block, Bytes, EIP1186ProofResponse, StorageProof},
+    utils::{keccak256, rlp::Rlp},
+};
+use revm::{
+    primitives::{
+        AccountInfo, Address, Bytecode, ExecutionResult, Output, SpecId, TxKind, B256,
+        KECCAK_EMPTY, U256 as EvmU256,
+    },
+    Database, Evm,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{ConversionError, StatelessExecutionError},
+    utils::SecretUrl,
+};
+
+/// Max number of times the call is re-executed to fetch the state it touches.
+///
+/// Each execution fetches all the accounts and storage slots it read but were not proven yet, so this bounds the
+/// depth of the chain of reads that depend on previously read values.
+const MAX_FETCH_ROUNDS: usize = 64;
+
+/// Gas limit of the executed calls.
+const CALL_GAS_LIMIT: u64 = 30_000_000;
+
+/// Root of an empty Merkle Patricia trie, `keccak256(rlp(""))`.
+const EMPTY_TRIE_ROOT: [u8; 32] = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+/// A block whose state root is trusted, e.g. because it was checked by a light client or is finalized.
+///
+/// Calls are executed against the state of this block: every account and storage slot they read is proven against
+/// `state_root`, so the RPC serving the proofs does not need to be trusted.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrustedBlock {
+    pub number: u64,
+    pub timestamp: u64,
+    pub state_root: H256,
+}
+
+impl TrustedBlock {
+    /// Takes the block number, timestamp and state root of a block header.
+    ///
+    /// The header must come from a trusted source: it is not checked against any block hash.
+    pub fn from_block<T>(block: &Block<T>) -> Result<Self, StatelessExecutionError> {
+        let number = block
+            .number
+            .ok_or_else(|| StatelessExecutionError::Rpc("block is pending".to_string()))?;
+        Ok(Self {
+            number: number.as_u64(),
+            timestamp: block.timestamp.as_u64(),
+            state_root: block.state_root,
+        })
+    }
+}
+
+/// Source of the (untrusted) state of a block, as served by `eth_getProof` and `eth_getCode`.
+#[async_trait::async_trait]
+pub trait StateSource: Sync + Send + std::fmt::Debug {
+    /// Returns the account proof of `address`, along with the proofs of the given storage slots.
+    async fn get_proof(
+        &self,
+        address: H160,
+        slots: Vec<H256>,
+        block_number: u64,
+    ) -> Result<EIP1186ProofResponse, StatelessExecutionError>;
+
+    /// Returns the code of the contract at `address`.
+    async fn get_code(
+        &self,
+        address: H160,
+        block_number: u64,
+    ) -> Result<Bytes, StatelessExecutionError>;
+}
+
+/// [`StateSource`] querying an Ethereum RPC.
+#[derive(Clone, Debug)]
+pub struct RpcStateSource {
+    provider: Provider<Http>,
+}
+
+impl RpcStateSource {
+    pub fn new(rpc_url: SecretUrl) -> Result<Self, ConversionError> {
+        let url: String = rpc_url.try_into()?;
+        let provider = Provider::<Http>::try_from(url).map_err(ConversionError::UrlParse)?;
+        Ok(Self::from_provider(provider))
+    }
+
+    pub fn from_provider(provider: Provider<Http>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait::async_trait]
+impl StateSource for RpcStateSource {
+    async fn get_proof(
+        &self,
+        address: H160,
+        slots: Vec<H256>,
+        block_number: u64,
+    ) -> Result<EIP1186ProofResponse, StatelessExecutionError> {
+        self.provider
+            .get_proof(address, slots, Some(block_number.into()))
+            .await
+            .map_err(|e| StatelessExecutionError::Rpc(format!("eth_getProof: {}", e)))
+    }
+
+    async fn get_code(
+        &self,
+        address: H160,
+        block_number: u64,
+    ) -> Result<Bytes, StatelessExecutionError> {
+        self.provider
+            .get_code(address, Some(block_number.into()))
+            .await
+            .map_err(|e| StatelessExecutionError::Rpc(format!("eth_getCode: {}", e)))
+    }
+}
+
+/// State recorded from a [`StateSource`], to replay calls offline (e.g. as test fixtures).
+///
+/// Serializes to JSON with the same format as the `eth_getProof` responses.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedState {
+    pub block_number: u64,
+    /// Account proofs, each with the proofs of all the recorded slots of the account.
+    pub proofs: Vec<EIP1186ProofResponse>,
+    pub codes: HashMap<H160, Bytes>,
+}
+
+impl RecordedState {
+    fn check_block(&self, block_number: u64) -> Result<(), StatelessExecutionError> {
+        if block_number != self.block_number {
+            return Err(StatelessExecutionError::MissingState(format!(
+                "state of block {} was recorded, but block {} was requested",
+                self.block_number, block_number
+            )));
+        }
+        Ok(())
+    }
+
+    fn record_proof(&mut self, response: EIP1186ProofResponse) {
+        match self
+            .proofs
+            .iter_mut()
+            .find(|proof| proof.address == response.address)
+        {
+            Some(proof) => {
+                for storage_proof in response.storage_proof {
+                    if !proof
+                        .storage_proof
+                        .iter()
+                        .any(|recorded| recorded.key == storage_proof.key)
+                    {
+                        proof.storage_proof.push(storage_proof);
+                    }
+                }
+            }
+            None => self.proofs.push(response),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StateSource for RecordedState {
+    async fn get_proof(
+        &self,
+        address: H160,
+        slots: Vec<H256>,
+        block_number: u64,
+    ) -> Result<EIP1186ProofResponse, StatelessExecutionError> {
+        self.check_block(block_number)?;
+        let recorded = self
+            .proofs
+            .iter()
+            .find(|proof| proof.address == address)
+            .ok_or_else(|| {
+                StatelessExecutionError::MissingState(format!("account {:?}", address))
+            })?;
+        let storage_proof = slots
+            .iter()
+            .map(|slot| {
+                recorded
+                    .storage_proof
+                    .iter()
+                    .find(|proof| storage_key(proof.key) == *slot)
+                    .cloned()
+                    .ok_or_else(|| {
+                        StatelessExecutionError::MissingState(format!(
+                            "slot {:?} of account {:?}",
+                            slot, address
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(EIP1186ProofResponse {
+            storage_proof,
+            ..recorded.clone()
+        })
+    }
+
+    async fn get_code(
+        &self,
+        address: H160,
+        block_number: u64,
+    ) -> Result<Bytes, StatelessExecutionError> {
+        self.check_block(block_number)?;
+        self.codes.get(&address).cloned().ok_or_else(|| {
+            StatelessExecutionError::MissingState(format!("code of account {:?}", address))
+        })
+    }
+}
+
+/// [`StateSource`] recording the state served by another source, to be saved as a [`RecordedState`].
+#[derive(Debug)]
+pub struct RecordingStateSource<S> {
+    source: S,
+    recorded: Mutex<RecordedState>,
+}
+
+impl<S> RecordingStateSource<S> {
+    pub fn new(source: S, block_number: u64) -> Self {
+        Self {
+            source,
+            recorded: Mutex::new(RecordedState {
+                block_number,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns the state recorded so far.
+    pub fn recorded(&self) -> RecordedState {
+        self.recorded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: StateSource> StateSource for RecordingStateSource<S> {
+    async fn get_proof(
+        &self,
+        address: H160,
+        slots: Vec<H256>,
+        block_number: u64,
+    ) -> Result<EIP1186ProofResponse, StatelessExecutionError> {
+        let response = self.source.get_proof(address, slots, block_number).await?;
+        self.recorded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .record_proof(response.clone());
+        Ok(response)
+    }
+
+    async fn get_code(
+        &self,
+        address: H160,
+        block_number: u64,
+    ) -> Result<Bytes, StatelessExecutionError> {
+        let code = self.source.get_code(address, block_number).await?;
+        self.recorded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .codes
+            .insert(address, code.clone());
+        Ok(code)
+    }
+}
+
+/// Outcome of a call executed by [`execute_call`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum CallResult {
+    /// The call succeeded, returning the given data.
+    Success(Vec<u8>),
+    /// The call reverted, with the given revert data.
+    Revert(Vec<u8>),
+    /// The call halted, e.g. because it ran out of gas.
+    Halt(String),
+}
+
+/// A call executed by [`execute_call`], and the gas it used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallExecution {
+    pub result: CallResult,
+    pub gas_used: u64,
+}
+
+/// Executes a call to `to` in an embedded EVM, on the state of the trusted block.
+///
+/// The accounts, code and storage slots read by the call are fetched from `source` and checked against the state
+/// root of the block, so the result is as trustworthy as the block. As reads are only known while executing, the
+/// call is executed again each time it reads state that was not fetched yet.
+pub async fn execute_call(
+    source: &dyn StateSource,
+    block: &TrustedBlock,
+    to: H160,
+    calldata: Vec<u8>,
+) -> Result<CallExecution, StatelessExecutionError> {
+    let mut state = ProvenState::default();
+    for _ in 0..MAX_FETCH_ROUNDS {
+        let mut db = ProvenDb {
+            state: &state,
+            missing: HashMap::new(),
+        };
+        let execution = execute_on(&mut db, block, to, calldata.clone())?;
+        let missing = db.missing;
+        if missing.is_empty() {
+            return Ok(execution);
+        }
+        state.fetch(source, block, missing).await?;
+    }
+    Err(StatelessExecutionError::TooManyFetchRounds(
+        MAX_FETCH_ROUNDS,
+    ))
+}
+
+fn execute_on(
+    db: &mut ProvenDb,
+    block: &TrustedBlock,
+    to: H160,
+    calldata: Vec<u8>,
+) -> Result<CallExecution, StatelessExecutionError> {
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .with_spec_id(SpecId::CANCUN)
+        .modify_block_env(|block_env| {
+            block_env.number = EvmU256::from(block.number);
+            block_env.timestamp = EvmU256::from(block.timestamp);
+            block_env.gas_limit = EvmU256::from(CALL_GAS_LIMIT);
+            block_env.basefee = EvmU256::ZERO;
+            block_env.prevrandao = Some(B256::ZERO);
+        })
+        .modify_tx_env(|tx| {
+            tx.caller = Address::ZERO;
+            tx.transact_to = TxKind::Call(Address::from(to.0));
+            tx.data = calldata.into();
+            tx.gas_limit = CALL_GAS_LIMIT;
+            tx.gas_price = EvmU256::ZERO;
+            tx.chain_id = None;
+            tx.nonce = None;
+        })
+        .build();
+
+    let result = evm.transact().map_err(|e| match e {
+        revm::primitives::EVMError::Database(e) => e,
+        e => StatelessExecutionError::Evm(e.to_string()),
+    })?;
+    Ok(match result.result {
+        ExecutionResult::Success {
+            gas_used, output, ..
+        } => {
+            let output = match output {
+                Output::Call(data) => data.to_vec(),
+                Output::Create(data, _) => data.to_vec(),
+            };
+            CallExecution {
+                result: CallResult::Success(output),
+                gas_used,
+            }
+        }
+        ExecutionResult::Revert { gas_used, output } => CallExecution {
+            result: CallResult::Revert(output.to_vec()),
+            gas_used,
+        },
+        ExecutionResult::Halt { reason, gas_used } => CallExecution {
+            result: CallResult::Halt(format!("{:?}", reason)),
+            gas_used,
+        },
+    })
+}
+
+/// An account whose fields were proven against the state root of the trusted block.
+#[derive(Debug)]
+struct ProvenAccount {
+    nonce: u64,
+    balance: U256,
+    storage_root: H256,
+    code_hash: H256,
+    code: Bytecode,
+    storage: HashMap<H256, U256>,
+}
+
+/// State proven so far. Accounts are `None` if they were proven not to exist.
+#[derive(Debug, Default)]
+struct ProvenState {
+    accounts: HashMap<H160, Option<ProvenAccount>>,
+}
+
+impl ProvenState {
+    /// Fetches and proves the missing accounts and slots.
+    async fn fetch(
+        &mut self,
+        source: &dyn StateSource,
+        block: &TrustedBlock,
+        missing: HashMap<H160, BTreeSet<H256>>,
+    ) -> Result<(), StatelessExecutionError> {
+        for (address, slots) in missing {
+            let slots: Vec<H256> = slots.into_iter().collect();
+            let response = source
+                .get_proof(address, slots.clone(), block.number)
+                .await?;
+            let account = verify_account_proof(block.state_root, address, &response)?;
+
+            let mut account = match self.accounts.remove(&address) {
+                Some(proven) => proven,
+                None => match account {
+                    Some((nonce, balance, storage_root, code_hash)) => {
+                        let code = if code_hash.0 == KECCAK_EMPTY.0 {
+                            Bytecode::default()
+                        } else {
+                            let code = source.get_code(address, block.number).await?;
+                            if H256(keccak256(&code)) != code_hash {
+                                return Err(StatelessExecutionError::CodeHashMismatch(address));
+                            }
+                            Bytecode::new_raw(code.to_vec().into())
+                        };
+                        Some(ProvenAccount {
+                            nonce,
+                            balance,
+                            storage_root,
+                            code_hash,
+                            code,
+                            storage: HashMap::new(),
+                        })
+                    }
+                    None => None,
+                },
+            };
+
+            if let Some(account) = &mut account {
+                for slot in slots {
+                    let storage_proof = response
+                        .storage_proof
+                        .iter()
+                        .find(|proof| storage_key(proof.key) == slot)
+                        .ok_or_else(|| {
+                            StatelessExecutionError::InvalidProof(format!(
+                                "missing proof of slot {:?} of account {:?}",
+                                slot, address
+                            ))
+                        })?;
+                    let value = verify_storage_proof(account.storage_root, storage_proof)?;
+                    account.storage.insert(slot, value);
+                }
+            }
+            self.accounts.insert(address, account);
+        }
+        Ok(())
+    }
+}
+
+/// EVM database serving the proven state, and recording the reads of state not proven yet.
+///
+/// Reads of missing state return empty values, so that the execution goes on and finds as many missing reads as
+/// possible before fetching them.
+struct ProvenDb<'a> {
+    state: &'a ProvenState,
+    missing: HashMap<H160, BTreeSet<H256>>,
+}
+
+impl Database for ProvenDb<'_> {
+    type Error = StatelessExecutionError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let address = H160(address.0 .0);
+        match self.state.accounts.get(&address) {
+            Some(Some(account)) => Ok(Some(AccountInfo::new(
+                EvmU256::from_limbs(account.balance.0),
+                account.nonce,
+                B256::from(account.code_hash.0),
+                account.code.clone(),
+            ))),
+            Some(None) => Ok(None),
+            None => {
+                self.missing.entry(address).or_default();
+                Ok(None)
+            }
+        }
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.state
+            .accounts
+            .values()
+            .flatten()
+            .find(|account| account.code_hash.0 == code_hash.0)
+            .map(|account| account.code.clone())
+            .ok_or_else(|| {
+                StatelessExecutionError::MissingState(format!("code with hash {}", code_hash))
+            })
+    }
+
+    fn storage(&mut self, address: Address, index: EvmU256) -> Result<EvmU256, Self::Error> {
+        let address = H160(address.0 .0);
+        let slot = H256(index.to_be_bytes::<32>());
+        match self.state.accounts.get(&address) {
+            Some(Some(account)) => match account.storage.get(&slot) {
+                Some(value) => return Ok(EvmU256::from_limbs(value.0)),
+                None if H256(EMPTY_TRIE_ROOT) == account.storage_root => return Ok(EvmU256::ZERO),
+                None => {}
+            },
+            Some(None) => return Ok(EvmU256::ZERO),
+            None => {}
+        }
+        self.missing.entry(address).or_default().insert(slot);
+        Ok(EvmU256::ZERO)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        Err(StatelessExecutionError::Evm(format!(
+            "hash of block {} is not available in stateless execution",
+            number
+        )))
+    }
+}
+
+/// Verifies the account proof of an `eth_getProof` response against the state root, returning the
+/// `(nonce, balance, storage_root, code_hash)` of the account, or `None` if it does not exist.
+fn verify_account_proof(
+    state_root: H256,
+    address: H160,
+    response: &EIP1186ProofResponse,
+) -> Result<Option<(u64, U256, H256, H256)>, StatelessExecutionError> {
+    if response.address != address {
+        return Err(StatelessExecutionError::InvalidProof(format!(
+            "requested the proof of account {:?}, got {:?}",
+            address, response.address
+        )));
+    }
+    let Some(account) = verify_trie_proof(state_root, address.as_bytes(), &response.account_proof)?
+    else {
+        return Ok(None);
+    };
+
+    let invalid_account =
+        |e| StatelessExecutionError::InvalidProof(format!("invalid account {:?}: {}", address, e));
+    let rlp = Rlp::new(&account);
+    Ok(Some((
+        rlp.val_at(0).map_err(invalid_account)?,
+        rlp.val_at(1).map_err(invalid_account)?,
+        rlp.val_at(2).map_err(invalid_account)?,
+        rlp.val_at(3).map_err(invalid_account)?,
+    )))
+}
+
+/// Verifies a storage proof against the storage root of its account, returning the value of the slot.
+fn verify_storage_proof(
+    storage_root: H256,
+    storage_proof: &StorageProof,
+) -> Result<U256, StatelessExecutionError> {
+    match verify_trie_proof(
+        storage_root,
+        storage_key(storage_proof.key).as_bytes(),
+        &storage_proof.proof,
+    )? {
+        Some(value) => Rlp::new(&value).as_val().map_err(|e| {
+            StatelessExecutionError::InvalidProof(format!(
+                "invalid value of slot {:?}: {}",
+                storage_proof.key, e
+            ))
+        }),
+        None => Ok(U256::zero()),
+    }
+}
+
+/// Reference to a trie node from its parent: the hash of the node, or the node itself if its encoding is shorter
+/// than 32 bytes.
+enum NodeRef {
+    Hash(H256),
+    Inline(Vec<u8>),
+}
+
+impl NodeRef {
+    fn from_rlp(rlp: &Rlp) -> Result<Self, StatelessExecutionError> {
+        if rlp.is_list() {
+            return Ok(Self::Inline(rlp.as_raw().to_vec()));
+        }
+        let data = rlp.data().map_err(invalid_node)?;
+        if data.len() != 32 {
+            return Err(StatelessExecutionError::InvalidProof(format!(
+                "invalid node hash 0x{}",
+                hex::encode(data)
+            )));
+        }
+        Ok(Self::Hash(H256::from_slice(data)))
+    }
+}
+
+/// Walks a Merkle Patricia trie proof from the root, returning the value at `keccak256(key)`, or `None` if the
+/// proof shows that there is no such value.
+fn verify_trie_proof(
+    root: H256,
+    key: &[u8],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, StatelessExecutionError> {
+    if root.0 == EMPTY_TRIE_ROOT {
+        return Ok(None);
+    }
+
+    let path: Vec<u8> = keccak256(key)
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect();
+    let mut path = path.as_slice();
+    let mut proof_nodes = proof.iter();
+    let mut node_ref = NodeRef::Hash(root);
+    loop {
+        let node = match node_ref {
+            NodeRef::Hash(hash) => {
+                let node = proof_nodes.next().ok_or_else(|| {
+                    StatelessExecutionError::InvalidProof("proof is incomplete".to_string())
+                })?;
+                if H256(keccak256(node)) != hash {
+                    return Err(StatelessExecutionError::InvalidProof(format!(
+                        "node does not match hash {:?}",
+                        hash
+                    )));
+                }
+                node.to_vec()
+            }
+            NodeRef::Inline(node) => node,
+        };
+
+        let rlp = Rlp::new(&node);
+        match rlp.item_count().map_err(invalid_node)? {
+            // branch node
+            17 => {
+                let Some((&nibble, rest)) = path.split_first() else {
+                    // keys have the same length, so values are never stored in branch nodes
+                    return Err(StatelessExecutionError::InvalidProof(
+                        "proof ends in a branch node".to_string(),
+                    ));
+                };
+                let child = rlp.at(nibble as usize).map_err(invalid_node)?;
+                if child.is_empty() {
+                    return Ok(None);
+                }
+                node_ref = NodeRef::from_rlp(&child)?;
+                path = rest;
+            }
+            // extension or leaf node
+            2 => {
+                let encoded_path = rlp.at(0).map_err(invalid_node)?;
+                let (node_path, is_leaf) =
+                    decode_compact_path(encoded_path.data().map_err(invalid_node)?)?;
+                if is_leaf {
+                    if path != node_path.as_slice() {
+                        return Ok(None);
+                    }
+                    let value = rlp.at(1).map_err(invalid_node)?;
+                    return Ok(Some(value.data().map_err(invalid_node)?.to_vec()));
+                }
+                let Some(rest) = path.strip_prefix(node_path.as_slice()) else {
+                    return Ok(None);
+                };
+                node_ref = NodeRef::from_rlp(&rlp.at(1).map_err(invalid_node)?)?;
+                path = rest;
+            }
+            count => {
+                return Err(StatelessExecutionError::InvalidProof(format!(
+                    "invalid node with {} items",
+                    count
+                )))
+            }
+        }
+    }
+}
+
+/// Decodes the hex-prefix encoded path of a leaf or extension node, returning its nibbles and whether the node is
+/// a leaf.
+fn decode_compact_path(encoded: &[u8]) -> Result<(Vec<u8>, bool), StatelessExecutionError> {
+    let (&first, rest) = encoded
+        .split_first()
+        .ok_or_else(|| StatelessExecutionError::InvalidProof("empty node path".to_string()))?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(StatelessExecutionError::InvalidProof(format!(
+            "invalid node path flag {}",
+            flag
+        )));
+    }
+    let mut nibbles = Vec::with_capacity(2 * encoded.len());
+    // odd paths hold their first nibble in the flag byte, even paths pad it with a zero nibble
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    } else if first & 0x0f != 0 {
+        return Err(StatelessExecutionError::InvalidProof(
+            "non-zero padding nibble in node path".to_string(),
+        ));
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    Ok((nibbles, flag & 2 == 2))
+}
+
+/// Returns the storage slot proven by a storage proof, which holds it as a number.
+fn storage_key(key: U256) -> H256 {
+    let mut slot = H256::zero();
+    key.to_big_endian(slot.as_mut());
+    slot
+}
+
+fn invalid_node(e: ethers::utils::rlp::DecoderError) -> StatelessExecutionError {
+    StatelessExecutionError::InvalidProof(format!("invalid trie node: {}", e))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ethers::utils::rlp::{self, RlpStream};
+
+    use super::*;
+
+    /// Code that stops if storage slot 0 is not zero, and reverts otherwise.
+    const CHECK_SLOT_CODE: &str = "600054600a57600080fd5b00";
+
+    fn encode_compact_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 } + (nibbles.len() % 2) as u8;
+        let mut encoded = vec![flag << 4];
+        let mut nibbles = nibbles;
+        if nibbles.len() % 2 == 1 {
+            encoded[0] |= nibbles[0];
+            nibbles = &nibbles[1..];
+        }
+        encoded.extend(nibbles.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+        encoded
+    }
+
+    fn key_path(key: &[u8]) -> Vec<u8> {
+        keccak256(key)
+            .iter()
+            .flat_map(|byte| [byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    fn leaf_node(path: &[u8], value: Vec<u8>) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&encode_compact_path(path, true));
+        stream.append(&value);
+        stream.out().to_vec()
+    }
+
+    /// Returns the root of a trie holding a single value, and the proof of the value.
+    fn single_leaf_trie(key: &[u8], value: Vec<u8>) -> (H256, Vec<Bytes>) {
+        let node = leaf_node(&key_path(key), value);
+        (H256(keccak256(&node)), vec![node.into()])
+    }
+
+    /// Returns the state of a block with a single account at `address`, holding `code` and `slot_value` at storage
+    /// slot 0. The recorded state also proves that the zero address, used as caller, does not exist.
+    pub(crate) fn single_contract_state(
+        address: H160,
+        code: &[u8],
+        slot_value: U256,
+    ) -> (TrustedBlock, RecordedState) {
+        let slot = H256::zero();
+        let (storage_root, storage_proof) = if slot_value.is_zero() {
+            (H256(EMPTY_TRIE_ROOT), vec![])
+        } else {
+            single_leaf_trie(slot.as_bytes(), rlp::encode(&slot_value).to_vec())
+        };
+
+        let code_hash = H256(keccak256(code));
+        let mut account = RlpStream::new_list(4);
+        account
+            .append(&1u64)
+            .append(&U256::zero())
+            .append(&storage_root)
+            .append(&code_hash);
+        let (state_root, account_proof) =
+            single_leaf_trie(address.as_bytes(), account.out().to_vec());
+
+        let block = TrustedBlock {
+            number: 100,
+            timestamp: 1_700_000_000,
+            state_root,
+        };
+        let state = RecordedState {
+            block_number: block.number,
+            proofs: vec![
+                EIP1186ProofResponse {
+                    address,
+                    balance: U256::zero(),
+                    code_hash,
+                    nonce: 1u64.into(),
+                    storage_hash: storage_root,
+                    account_proof: account_proof.clone(),
+                    storage_proof: vec![StorageProof {
+                        key: U256::from_big_endian(slot.as_bytes()),
+                        proof: storage_proof,
+                        value: slot_value,
+                    }],
+                },
+                EIP1186ProofResponse {
+                    address: H160::zero(),
+                    balance: U256::zero(),
+                    code_hash: H256::zero(),
+                    nonce: 0u64.into(),
+                    storage_hash: H256::zero(),
+                    account_proof,
+                    storage_proof: vec![],
+                },
+            ],
+            codes: HashMap::from([(address, code.to_vec().into())]),
+        };
+        (block, state)
+    }
+
+    pub(crate) fn check_slot_code() -> Vec<u8> {
+        hex::decode(CHECK_SLOT_CODE).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_execute_call() {
+        let address = H160::repeat_byte(0x11);
+
+        let (block, state) = single_contract_state(address, &check_slot_code(), U256::one());
+        let execution = execute_call(&state, &block, address, vec![]).await.unwrap();
+        assert_eq!(execution.result, CallResult::Success(vec![]));
+        assert!(execution.gas_used > 21_000);
+
+        let (block, state) = single_contract_state(address, &check_slot_code(), U256::zero());
+        let execution = execute_call(&state, &block, address, vec![]).await.unwrap();
+        assert_eq!(execution.result, CallResult::Revert(vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_execute_call_with_recorded_state() {
+        let address = H160::repeat_byte(0x11);
+        let (block, state) = single_contract_state(address, &check_slot_code(), U256::one());
+
+        // recording a replay of the state records all of it
+        let recording = RecordingStateSource::new(state.clone(), block.number);
+        execute_call(&recording, &block, address, vec![])
+            .await
+            .unwrap();
+        let recorded = recording.recorded();
+        assert_eq!(recorded.codes, state.codes);
+        assert_eq!(recorded.proofs.len(), state.proofs.len());
+
+        // fixtures are stored as JSON
+        let json = serde_json::to_string(&recorded).unwrap();
+        let fixture: RecordedState = serde_json::from_str(&json).unwrap();
+        let execution = execute_call(&fixture, &block, address, vec![])
+            .await
+            .unwrap();
+        assert_eq!(execution.result, CallResult::Success(vec![]));
+
+        // state of another block
+        let other_block = TrustedBlock {
+            number: block.number + 1,
+            ..block
+        };
+        assert!(matches!(
+            execute_call(&fixture, &other_block, address, vec![]).await,
+            Err(StatelessExecutionError::MissingState(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_call_with_invalid_state() {
+        let address = H160::repeat_byte(0x11);
+        let (block, state) = single_contract_state(address, &check_slot_code(), U256::one());
+
+        // state of another root
+        let mut wrong_root = block.clone();
+        wrong_root.state_root.0[0] ^= 1;
+        assert!(matches!(
+            execute_call(&state, &wrong_root, address, vec![]).await,
+            Err(StatelessExecutionError::InvalidProof(_))
+        ));
+
+        // code not matching the code hash
+        let mut wrong_code = state.clone();
+        wrong_code.codes.insert(address, vec![0x00].into());
+        assert!(matches!(
+            execute_call(&wrong_code, &block, address, vec![]).await,
+            Err(StatelessExecutionError::CodeHashMismatch(_))
+        ));
+
+        // storage value not matching the storage root, even if the claimed value is right
+        let mut wrong_storage = state.clone();
+        let (_, other_proof) = single_leaf_trie(
+            H256::zero().as_bytes(),
+            rlp::encode(&U256::from(2)).to_vec(),
+        );
+        wrong_storage.proofs[0].storage_proof[0].proof = other_proof;
+        assert!(matches!(
+            execute_call(&wrong_storage, &block, address, vec![]).await,
+            Err(StatelessExecutionError::InvalidProof(_))
+        ));
+
+        // missing account
+        let mut missing_caller = state;
+        missing_caller.proofs.pop();
+        assert!(matches!(
+            execute_call(&missing_caller, &block, address, vec![]).await,
+            Err(StatelessExecutionError::MissingState(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_compact_path() {
+        for (path, is_leaf) in [
+            (vec![], false),
+            (vec![1], true),
+            (vec![1, 2], false),
+            (vec![1, 2, 3], true),
+        ] {
+            assert_eq!(
+                decode_compact_path(&encode_compact_path(&path, is_leaf)).unwrap(),
+                (path, is_leaf)
+            );
+        }
+
+        // even paths must pad the flag byte with a zero nibble
+        for flag in [0x00, 0x20] {
+            assert!(matches!(
+                decode_compact_path(&[flag | 0x01, 0x23]),
+                Err(StatelessExecutionError::InvalidProof(_))
+            ));
+        }
+        assert!(decode_compact_path(&[0x40]).is_err());
+        assert!(decode_compact_path(&[]).is_err());
+    }
+
+    #[test]
+    fn test_verify_trie_proof() {
+        // two keys whose paths diverge at the first nibble, below a branch node
+        let first_key = [0u8];
+        let second_key = (1..=u8::MAX)
+            .map(|byte| [byte])
+            .find(|key| key_path(key)[0] != key_path(&first_key)[0])
+            .unwrap();
+        let first_leaf = leaf_node(&key_path(&first_key)[1..], vec![1]);
+        let second_leaf = leaf_node(&key_path(&second_key)[1..], vec![2]);
+
+        let mut branch = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            if nibble == key_path(&first_key)[0] {
+                branch.append(&H256(keccak256(&first_leaf)));
+            } else if nibble == key_path(&second_key)[0] {
+                branch.append(&H256(keccak256(&second_leaf)));
+            } else {
+                branch.append_empty_data();
+            }
+        }
+        branch.append_empty_data();
+        let branch = branch.out().to_vec();
+        let root = H256(keccak256(&branch));
+
+        let first_proof: Vec<Bytes> = vec![branch.clone().into(), first_leaf.into()];
+        let second_proof: Vec<Bytes> = vec![branch.clone().into(), second_leaf.into()];
+        assert_eq!(
+            verify_trie_proof(root, &first_key, &first_proof).unwrap(),
+            Some(vec![1])
+        );
+        assert_eq!(
+            verify_trie_proof(root, &second_key, &second_proof).unwrap(),
+            Some(vec![2])
+        );
+
+        // a key under the same branch as the first one ends in a leaf with a different path
+        let sibling_key = (1..=u8::MAX)
+            .map(|byte| [byte])
+            .find(|key| key_path(key)[0] == key_path(&first_key)[0])
+            .unwrap();
+        assert_eq!(
+            verify_trie_proof(root, &sibling_key, &first_proof).unwrap(),
+            None
+        );
+        // a key under an empty child of the branch
+        let absent_key = (1..=u8::MAX)
+            .map(|byte| [byte])
+            .find(|key| {
+                key_path(key)[0] != key_path(&first_key)[0]
+                    && key_path(key)[0] != key_path(&second_key)[0]
+            })
+            .unwrap();
+        assert_eq!(
+            verify_trie_proof(root, &absent_key, &[branch.clone().into()]).unwrap(),
+            None
+        );
+
+        // the proof of the second key does not prove the first one
+        assert!(verify_trie_proof(root, &first_key, &second_proof).is_err());
+        // incomplete proof
+        assert!(verify_trie_proof(root, &first_key, &[branch.into()]).is_err());
+        assert_eq!(
+            verify_trie_proof(H256(EMPTY_TRIE_ROOT), &first_key, &[]).unwrap(),
+            None
+        );
+    }
+}
@@ -2,7 +2,14 @@ use crate::{core::BYTES_PER_SYMBOL, errors::ConversionError};
 use ark_bn254::Fr;
 use ark_ff::fields::PrimeField;
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ethers::{
+    contract::ContractError,
+    middleware::{signer::SignerMiddlewareError, SignerMiddleware},
+    providers::{Http, Provider},
+};
+use rust_eigenda_signers::signers::ethers::Signer as EthersSigner;
 use secrecy::{ExposeSecret, Secret};
+use serde::{Deserialize, Deserializer};
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -20,6 +27,23 @@ impl SecretUrl {
             inner: Secret::new(url.to_string()),
         }
     }
+
+    pub(crate) fn expose_secret(&self) -> &str {
+        self.inner.expose_secret()
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let url = String::deserialize(deserializer)?;
+        // the error does not include the URL, which may hold an API key
+        let url = Url::parse(&url)
+            .map_err(|e| serde::de::Error::custom(format!("invalid URL: {}", e)))?;
+        Ok(Self::new(url))
+    }
 }
 
 impl TryFrom<SecretUrl> for String {
@@ -39,6 +63,38 @@ impl PartialEq for SecretUrl {
 /// Converts an eval_poly to a coeff_poly, using the IFFT operation
 ///
 /// blob_length_symbols is required, to be able to choose the correct parameters when performing FFT
+/// Unwraps the error of a contract call made through a signer middleware, to be kept as the source of the errors of
+/// the caller. The middleware errors are generic over the signer, so they are unwrapped to the provider error, or
+/// replaced by their message for the errors that hold no other error.
+pub(crate) fn contract_error_source<S>(
+    e: ContractError<SignerMiddleware<Provider<Http>, EthersSigner<S>>>,
+) -> Box<dyn std::error::Error + Send + Sync>
+where
+    EthersSigner<S>: ethers::signers::Signer,
+{
+    match e {
+        ContractError::MiddlewareError { e } => middleware_error_source(e),
+        ContractError::ProviderError { e } => Box::new(e),
+        ContractError::DecodingError(e) => Box::new(e),
+        ContractError::AbiError(e) => Box::new(e),
+        ContractError::DetokenizationError(e) => Box::new(e),
+        e => e.to_string().into(),
+    }
+}
+
+/// Unwraps the error of a signer middleware, see [`contract_error_source`].
+pub(crate) fn middleware_error_source<S>(
+    e: SignerMiddlewareError<Provider<Http>, EthersSigner<S>>,
+) -> Box<dyn std::error::Error + Send + Sync>
+where
+    EthersSigner<S>: ethers::signers::Signer,
+{
+    match e {
+        SignerMiddlewareError::MiddlewareError(e) => Box::new(e),
+        e => e.to_string().into(),
+    }
+}
+
 pub(crate) fn eval_to_coeff_poly(
     eval_poly: Vec<Fr>,
     blob_length_symbols: usize,
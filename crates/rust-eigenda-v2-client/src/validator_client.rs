@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use tonic::transport::Channel;
+
+use crate::{
+    core::{BlobKey, BlobParams},
+    errors::ValidatorClientError,
+    generated::validator::{
+        retrieval_client::RetrievalClient as RpcRetrievalClient, ChunkEncodingFormat,
+        GetChunksRequest,
+    },
+};
+
+pub type OperatorId = [u8; 32];
+
+/// Range of chunks of a blob that a validator stores for a given quorum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidatorAssignment {
+    pub operator_id: OperatorId,
+    /// Address of the validator's retrieval gRPC service.
+    pub retrieval_socket: String,
+    /// Index of the first chunk assigned to the validator.
+    pub start_index: u32,
+    /// Number of consecutive chunks assigned to the validator.
+    pub num_chunks: u32,
+}
+
+/// Source of the validators that hold chunks of a blob, and of the chunks assigned to each of them.
+///
+/// Assignments depend on the operator stakes at the reference block number of the batch, so implementors
+/// are expected to read them from the EigenDA contracts (or from an indexer) at that block.
+#[async_trait::async_trait]
+pub trait ValidatorSource: Sync + Send + std::fmt::Debug {
+    /// Returns the chunk assignments of every validator in `quorum_id` for a blob encoded with `blob_params`.
+    async fn get_assignments(
+        &self,
+        reference_block_number: u32,
+        quorum_id: u8,
+        blob_params: &BlobParams,
+    ) -> Result<Vec<ValidatorAssignment>, ValidatorClientError>;
+}
+
+/// [`ValidatorSource`] backed by a fixed list of assignments per quorum.
+///
+/// Useful for private deployments with a known, static operator set.
+#[derive(Clone, Debug, Default)]
+pub struct StaticValidatorSource {
+    assignments: HashMap<u8, Vec<ValidatorAssignment>>,
+}
+
+impl StaticValidatorSource {
+    pub fn new(assignments: HashMap<u8, Vec<ValidatorAssignment>>) -> Self {
+        Self { assignments }
+    }
+}
+
+#[async_trait::async_trait]
+impl ValidatorSource for StaticValidatorSource {
+    async fn get_assignments(
+        &self,
+        _reference_block_number: u32,
+        quorum_id: u8,
+        _blob_params: &BlobParams,
+    ) -> Result<Vec<ValidatorAssignment>, ValidatorClientError> {
+        Ok(self
+            .assignments
+            .get(&quorum_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+pub struct ValidatorClientConfig {
+    pub max_grpc_message_size: usize,
+}
+
+/// [`ValidatorClient`] is a client for the retrieval service of EigenDA validators.
+///
+/// Connections are opened lazily and reused across requests to the same validator.
+/// This struct is a low level implementation and should not be used directly,
+/// use a high level abstraction to interact with it ([`ValidatorPayloadRetriever`]).
+pub struct ValidatorClient {
+    config: ValidatorClientConfig,
+    rpc_clients: HashMap<String, RpcRetrievalClient<Channel>>,
+}
+
+impl ValidatorClient {
+    pub fn new(config: ValidatorClientConfig) -> Result<Self, ValidatorClientError> {
+        if config.max_grpc_message_size == 0 {
+            return Err(ValidatorClientError::InvalidMaxGrpcMessageSize);
+        }
+
+        Ok(Self {
+            config,
+            rpc_clients: HashMap::new(),
+        })
+    }
+
+    /// Retrieves the chunks of a blob stored by a validator for the given quorum.
+    ///
+    /// Chunks are returned serialized in the gnark format, in the order of the validator's assignment.
+    pub async fn get_chunks(
+        &mut self,
+        retrieval_socket: &str,
+        blob_key: &BlobKey,
+        quorum_id: u8,
+    ) -> Result<Vec<Vec<u8>>, ValidatorClientError> {
+        let rpc_client = self.rpc_client(retrieval_socket).await?;
+        let res = rpc_client
+            .get_chunks(GetChunksRequest {
+                blob_key: blob_key.to_bytes().to_vec(),
+                quorum_id: quorum_id as u32,
+            })
+            .await?
+            .into_inner();
+
+        if res.chunk_encoding_format != ChunkEncodingFormat::Gnark as i32 {
+            return Err(ValidatorClientError::UnsupportedChunkEncoding(
+                res.chunk_encoding_format,
+            ));
+        }
+
+        Ok(res.chunks)
+    }
+
+    async fn rpc_client(
+        &mut self,
+        retrieval_socket: &str,
+    ) -> Result<&mut RpcRetrievalClient<Channel>, ValidatorClientError> {
+        if !self.rpc_clients.contains_key(retrieval_socket) {
+            let url = if retrieval_socket.contains("://") {
+                retrieval_socket.to_string()
+            } else {
+                format!("http://{}", retrieval_socket)
+            };
+            let endpoint = Channel::from_shared(url.clone())
+                .map_err(|_| ValidatorClientError::InvalidURI(url))?;
+            let channel = endpoint.connect().await?;
+            let rpc_client = RpcRetrievalClient::new(channel)
+                .max_decoding_message_size(self.config.max_grpc_message_size);
+            self.rpc_clients
+                .insert(retrieval_socket.to_string(), rpc_client);
+        }
+
+        // Safe unwrap: the client was inserted above if it was missing
+        Ok(self.rpc_clients.get_mut(retrieval_socket).unwrap())
+    }
+}
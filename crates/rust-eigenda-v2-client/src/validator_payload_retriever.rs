@@ -0,0 +1,554 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use rand::seq::SliceRandom;
+use rust_eigenda_v2_common::EigenDACert;
+use tokio::time::timeout;
+use tracing::{debug, debug_span, field, instrument, warn, Instrument};
+
+use crate::{
+    commitment_utils::{
+        generate_and_compare_blob_commitment, verify_blob_commitments, verify_frame,
+    },
+    core::{
+        frame::recover_blob_coefficients, Blob, BlobKey, BlobParams, EncodingParams, Frame,
+        Payload, PayloadDecodeMode, PayloadForm,
+    },
+    errors::{ConversionError, ValidatorPayloadRetrieverError},
+    relay_payload_retriever::compute_blob_key,
+    srs::Srs,
+    validator_client::{ValidatorAssignment, ValidatorClient, ValidatorSource},
+};
+
+pub struct ValidatorPayloadRetrieverConfig {
+    pub payload_form: PayloadForm,
+    pub retrieval_timeout_secs: Duration,
+    /// Encoding parameters of each blob version, as registered in the EigenDAThresholdRegistry.
+    pub blob_params: HashMap<u16, BlobParams>,
+    /// Whether to verify the length proof of the blob commitments. See
+    /// [`RelayPayloadRetrieverConfig::verify_length_proof`](crate::relay_payload_retriever::RelayPayloadRetrieverConfig::verify_length_proof).
+    pub verify_length_proof: bool,
+    /// How strictly retrieved blobs are validated when decoding them into payloads.
+    pub decode_mode: PayloadDecodeMode,
+}
+
+/// Provides the ability to get payloads directly from the EigenDA validators, without relying on relays.
+///
+/// Chunks are fetched from the validators holding them, and each chunk's KZG multiproof is verified against
+/// the blob commitment before being used. Once enough valid chunks are collected, the blob is reconstructed
+/// by Reed-Solomon decoding them.
+pub struct ValidatorPayloadRetriever {
+    srs: Arc<Srs>,
+    config: ValidatorPayloadRetrieverConfig,
+    validator_client: ValidatorClient,
+    validator_source: Box<dyn ValidatorSource>,
+}
+
+impl ValidatorPayloadRetriever {
+    /// Assembles a ValidatorPayloadRetriever from specified config, SRS, a validator client and the
+    /// source of validator assignments.
+    ///
+    /// The SRS must include the `[s^(2^i)]_2` points, since they are needed to verify chunks.
+    pub fn new(
+        config: ValidatorPayloadRetrieverConfig,
+        srs: Arc<Srs>,
+        validator_client: ValidatorClient,
+        validator_source: Box<dyn ValidatorSource>,
+    ) -> Result<Self, ValidatorPayloadRetrieverError> {
+        if srs.g2_power_of_2().is_none() {
+            return Err(ConversionError::SrsFile(
+                "g2_power_of_2 points are required to verify chunks".to_string(),
+            )
+            .into());
+        }
+        if config.verify_length_proof {
+            srs.check_length_proof_points()?;
+        }
+
+        Ok(ValidatorPayloadRetriever {
+            srs,
+            config,
+            validator_client,
+            validator_source,
+        })
+    }
+
+    // Fetches the chunks of the blob referenced by the certificate from the validators of each of its quorums,
+    // in random order, until enough valid chunks are collected to reconstruct it.
+    //
+    // The blob commitments are checked first (including the length proof, if enabled), and every chunk is verified
+    // against the blob commitment, so malicious validators can only withhold data.
+    // The reconstructed blob is decoded to yield the payload.
+    //
+    // This method does NOT verify the [`EigenDACert`] on chain: it is assumed that the input [`EigenDACert`] has already been
+    // verified prior to calling this method.
+    #[instrument(skip_all, fields(blob_key = field::Empty))]
+    pub async fn get_payload(
+        &mut self,
+        eigenda_cert: EigenDACert,
+    ) -> Result<Payload, ValidatorPayloadRetrieverError> {
+        let blob_key = compute_blob_key(&eigenda_cert)?;
+        tracing::Span::current().record("blob_key", field::display(blob_key.to_hex()));
+        let blob_header = &eigenda_cert
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header;
+        if blob_header.quorum_numbers.is_empty() {
+            return Err(ValidatorPayloadRetrieverError::InvalidCertificate(
+                "quorum count is zero".to_string(),
+            ));
+        }
+
+        verify_blob_commitments(
+            &self.srs,
+            &blob_header.commitment,
+            self.config.verify_length_proof,
+        )?;
+
+        let blob_length_symbols = blob_header.commitment.length as usize;
+        let blob_params = *self.config.blob_params.get(&blob_header.version).ok_or(
+            ValidatorPayloadRetrieverError::UnknownBlobVersion(blob_header.version),
+        )?;
+        let params = blob_params.encoding_params(blob_length_symbols)?;
+        let g2_tau_pow_n = self.srs.g2_tau_pow(params.chunk_length).ok_or(
+            ValidatorPayloadRetrieverError::MissingG2PowerOf2Point(params.chunk_length),
+        )?;
+        let g1_srs = self
+            .srs
+            .g1_points(blob_length_symbols.max(params.chunk_length))?;
+        let required_chunks = blob_length_symbols.div_ceil(params.chunk_length);
+        let reference_block_number = eigenda_cert.reference_block_number();
+
+        // valid frames, indexed by chunk index
+        let mut frames: BTreeMap<u32, Frame> = BTreeMap::new();
+        'quorums: for quorum_id in blob_header.quorum_numbers.iter() {
+            let mut assignments = self
+                .validator_source
+                .get_assignments(reference_block_number, *quorum_id, &blob_params)
+                .await?;
+            assignments.shuffle(&mut rand::thread_rng());
+
+            for assignment in assignments {
+                if frames.len() >= required_chunks {
+                    break 'quorums;
+                }
+                let end_index = assignment.start_index.saturating_add(assignment.num_chunks);
+                if (assignment.start_index..end_index).all(|index| frames.contains_key(&index)) {
+                    continue;
+                }
+
+                let span = debug_span!(
+                    "validator_attempt",
+                    operator_id = %hex::encode(assignment.operator_id),
+                    quorum = *quorum_id,
+                    chunks = assignment.num_chunks
+                );
+                let validator_frames = match self
+                    .retrieve_frames_with_timeout(&assignment, &blob_key, *quorum_id, &params)
+                    .instrument(span.clone())
+                    .await
+                {
+                    Ok(validator_frames) => validator_frames,
+                    Err(err) => {
+                        warn!(parent: &span, error = %err, "failed to retrieve chunks from validator");
+                        continue;
+                    }
+                };
+
+                for (chunk_index, frame) in validator_frames {
+                    if frames.contains_key(&chunk_index) {
+                        continue;
+                    }
+                    let coset_leader = params.coset_leader(chunk_index)?;
+                    let valid = verify_frame(
+                        &g1_srs,
+                        g2_tau_pow_n,
+                        blob_header.commitment.commitment,
+                        &frame,
+                        coset_leader,
+                    )
+                    .unwrap_or(false);
+                    if !valid {
+                        warn!(
+                            parent: &span,
+                            chunk_index,
+                            "chunk retrieved from validator does not match the blob commitment"
+                        );
+                        continue;
+                    }
+                    frames.insert(chunk_index, frame);
+                }
+            }
+        }
+
+        debug!(
+            chunks = frames.len(),
+            required_chunks,
+            symbol_count = blob_length_symbols,
+            "collected chunks from validators"
+        );
+        if frames.len() < required_chunks {
+            return Err(ValidatorPayloadRetrieverError::NotEnoughValidChunks(
+                frames.len(),
+                required_chunks,
+            ));
+        }
+
+        let frames: Vec<(u32, Frame)> = frames.into_iter().collect();
+        let coeff_polynomial = recover_blob_coefficients(&frames, &params, blob_length_symbols)?;
+        let blob = Blob {
+            coeff_polynomial,
+            blob_length_symbols,
+        };
+
+        let valid = generate_and_compare_blob_commitment(
+            &g1_srs,
+            blob.serialize(),
+            blob_header.commitment.commitment,
+        )?;
+        if !valid {
+            return Err(ValidatorPayloadRetrieverError::InvalidReconstructedBlob);
+        }
+
+        Ok(blob.to_payload_with_mode(self.config.payload_form, self.config.decode_mode)?)
+    }
+
+    /// Retrieves the chunks assigned to a validator, paired with their chunk index.
+    ///
+    /// Times out based on config's `retrieval_timeout_secs`.
+    ///
+    /// Returns [`ValidatorPayloadRetrieverError::RetrievalTimeout`] if the timeout is exceeded.
+    async fn retrieve_frames_with_timeout(
+        &mut self,
+        assignment: &ValidatorAssignment,
+        blob_key: &BlobKey,
+        quorum_id: u8,
+        params: &EncodingParams,
+    ) -> Result<Vec<(u32, Frame)>, ValidatorPayloadRetrieverError> {
+        let chunks = timeout(
+            self.config.retrieval_timeout_secs,
+            self.validator_client
+                .get_chunks(&assignment.retrieval_socket, blob_key, quorum_id),
+        )
+        .await
+        .map_err(|_| {
+            warn!(timeout = ?self.config.retrieval_timeout_secs, "validator request timed out");
+            ValidatorPayloadRetrieverError::RetrievalTimeout
+        })??;
+
+        if chunks.len() != assignment.num_chunks as usize {
+            return Err(ValidatorPayloadRetrieverError::UnexpectedChunkCount(
+                chunks.len(),
+                assignment.num_chunks as usize,
+            ));
+        }
+
+        chunks
+            .iter()
+            .zip(assignment.start_index..)
+            .map(|(chunk, chunk_index)| {
+                let frame = Frame::deserialize_gnark(chunk, params.chunk_length)?;
+                Ok((chunk_index, frame))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ark_bn254::{Fr, G1Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{BigInteger, Field, PrimeField};
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::{transport::Server, Request, Response, Status};
+
+    use crate::{
+        commitment_utils::g1_commitment_to_bytes,
+        core::frame::tests::encode_frames,
+        errors::{BlobCommitmentsError, RelayPayloadRetrieverError},
+        generated::validator::{
+            retrieval_server::{Retrieval, RetrievalServer},
+            ChunkEncodingFormat, GetChunksReply, GetChunksRequest, GetNodeInfoReply,
+            GetNodeInfoRequest,
+        },
+        relay_payload_retriever::{
+            tests::{
+                get_retriever_without_relays, get_test_eigenda_cert_for_payload,
+                get_test_eigenda_cert_with_false_length,
+            },
+            RelayPayloadRetriever, RelayPayloadRetrieverConfig, BLOB_VERSION_0_PARAMS,
+        },
+        tests::{get_insecure_test_srs, INSECURE_TEST_SRS_SECRET},
+        validator_client::{StaticValidatorSource, ValidatorClientConfig},
+    };
+
+    use super::*;
+
+    /// Blob params of the tests serving chunks: the blobs of the test payloads (8 symbols) are split into 8 chunks
+    /// of 4 symbols, 2 of which are enough to recover them.
+    const TEST_BLOB_PARAMS: BlobParams = BlobParams {
+        num_chunks: 8,
+        coding_rate: 4,
+    };
+
+    /// Returns the chunks of the blob of `payload`, with their multiproofs computed with the secret of the insecure
+    /// test SRS, as an EigenDA encoder would.
+    fn get_test_frames(payload: &Payload, payload_form: PayloadForm) -> Vec<(u32, Frame)> {
+        let blob = payload.to_blob(payload_form).unwrap();
+        let params = TEST_BLOB_PARAMS
+            .encoding_params(blob.blob_length_symbols)
+            .unwrap();
+        let mut coeffs = blob.coeff_polynomial.clone();
+        coeffs.resize(blob.blob_length_symbols, Fr::from(0));
+
+        let secret = Fr::from(INSECURE_TEST_SRS_SECRET);
+        let evaluate = |coeffs: &[Fr]| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(Fr::from(0u64), |acc, coeff| acc * secret + coeff)
+        };
+        let blob_evaluation = evaluate(&coeffs);
+        let chunk_length = params.chunk_length as u64;
+
+        encode_frames(&coeffs, &params)
+            .into_iter()
+            .map(|(chunk_index, mut frame)| {
+                // the proof is [(p(s) - I(s)) / (s^n - coset_leader^n)]_1, see `verify_frame`
+                let coset_leader = params.coset_leader(chunk_index).unwrap();
+                let quotient = (blob_evaluation - evaluate(&frame.coeffs))
+                    / (secret.pow([chunk_length]) - coset_leader.pow([chunk_length]));
+                frame.proof = (G1Affine::generator() * quotient).into_affine();
+                (chunk_index, frame)
+            })
+            .collect()
+    }
+
+    /// Serializes a frame in the gnark format, as returned by validators.
+    fn serialize_gnark(frame: &Frame) -> Vec<u8> {
+        let mut bytes = g1_commitment_to_bytes(&frame.proof).unwrap();
+        for coeff in &frame.coeffs {
+            bytes.extend(coeff.into_bigint().to_bytes_be());
+        }
+        bytes
+    }
+
+    /// Retrieval service of a validator, serving the same chunks for every blob and quorum.
+    struct MockValidator {
+        chunks: Vec<Vec<u8>>,
+    }
+
+    #[tonic::async_trait]
+    impl Retrieval for MockValidator {
+        async fn get_chunks(
+            &self,
+            _request: Request<GetChunksRequest>,
+        ) -> Result<Response<GetChunksReply>, Status> {
+            Ok(Response::new(GetChunksReply {
+                chunks: self.chunks.clone(),
+                chunk_encoding_format: ChunkEncodingFormat::Gnark as i32,
+            }))
+        }
+
+        async fn get_node_info(
+            &self,
+            _request: Request<GetNodeInfoRequest>,
+        ) -> Result<Response<GetNodeInfoReply>, Status> {
+            Err(Status::unimplemented("not served by the mock validator"))
+        }
+    }
+
+    /// Starts a validator serving the given frames, and returns its assignment in quorum 0.
+    async fn serve_frames(frames: &[(u32, Frame)]) -> ValidatorAssignment {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let retrieval_socket = listener.local_addr().unwrap().to_string();
+        let validator = MockValidator {
+            chunks: frames
+                .iter()
+                .map(|(_, frame)| serialize_gnark(frame))
+                .collect(),
+        };
+        tokio::spawn(
+            Server::builder()
+                .add_service(RetrievalServer::new(validator))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let mut operator_id = [0u8; 32];
+        operator_id[..4].copy_from_slice(&frames[0].0.to_be_bytes());
+        ValidatorAssignment {
+            operator_id,
+            retrieval_socket,
+            start_index: frames[0].0,
+            num_chunks: frames.len() as u32,
+        }
+    }
+
+    /// Returns a relay payload retriever without relays, falling back to validators with the given assignments in
+    /// quorum 0.
+    async fn get_retriever_with_validators(
+        payload_form: PayloadForm,
+        assignments: Vec<ValidatorAssignment>,
+    ) -> RelayPayloadRetriever {
+        let validator_retriever = ValidatorPayloadRetriever::new(
+            ValidatorPayloadRetrieverConfig {
+                payload_form,
+                retrieval_timeout_secs: Duration::from_secs(5),
+                blob_params: HashMap::from([(0, TEST_BLOB_PARAMS)]),
+                verify_length_proof: true,
+                decode_mode: PayloadDecodeMode::Strict,
+            },
+            get_insecure_test_srs().await,
+            ValidatorClient::new(ValidatorClientConfig {
+                max_grpc_message_size: 1 << 20,
+            })
+            .unwrap(),
+            Box::new(StaticValidatorSource::new(HashMap::from([(
+                0,
+                assignments,
+            )]))),
+        )
+        .unwrap();
+        get_retriever_without_relays(
+            RelayPayloadRetrieverConfig::builder()
+                .with_payload_form(payload_form)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .with_validator_fallback(validator_retriever)
+    }
+
+    /// Returns a validator retriever verifying length proofs, with no validator assigned to any quorum.
+    pub(crate) async fn get_test_validator_retriever(
+        payload_form: PayloadForm,
+    ) -> ValidatorPayloadRetriever {
+        ValidatorPayloadRetriever::new(
+            ValidatorPayloadRetrieverConfig {
+                payload_form,
+                retrieval_timeout_secs: Duration::from_secs(1),
+                blob_params: HashMap::from([(0, BLOB_VERSION_0_PARAMS)]),
+                verify_length_proof: true,
+                decode_mode: PayloadDecodeMode::Strict,
+            },
+            get_insecure_test_srs().await,
+            ValidatorClient::new(ValidatorClientConfig {
+                max_grpc_message_size: 1 << 20,
+            })
+            .unwrap(),
+            Box::new(StaticValidatorSource::new(HashMap::new())),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_false_length_is_rejected() {
+        let payload = Payload::new((0..100).collect());
+        let mut retriever = get_test_validator_retriever(PayloadForm::Coeff).await;
+
+        let length = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Coeff)
+            .blob_inclusion_info()
+            .blob_certificate
+            .blob_header
+            .commitment
+            .length;
+        for false_length in [length / 2, length * 2] {
+            let eigenda_cert =
+                get_test_eigenda_cert_with_false_length(&payload, PayloadForm::Coeff, false_length);
+            let res = retriever.get_payload(eigenda_cert).await;
+            assert!(matches!(
+                res,
+                Err(ValidatorPayloadRetrieverError::BlobCommitments(
+                    BlobCommitmentsError::InvalidLengthProof(length)
+                )) if length == false_length
+            ));
+        }
+
+        // the true length is accepted, and the chunks requested to the (missing) validators
+        let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Coeff);
+        let res = retriever.get_payload(eigenda_cert).await;
+        assert!(matches!(
+            res,
+            Err(ValidatorPayloadRetrieverError::NotEnoughValidChunks(0, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_payload_from_validators() {
+        let payload = Payload::new((0..100).collect());
+        for payload_form in [PayloadForm::Coeff, PayloadForm::Eval] {
+            let frames = get_test_frames(&payload, payload_form);
+            let assignments = vec![
+                serve_frames(&frames[..4]).await,
+                serve_frames(&frames[4..]).await,
+            ];
+            let mut retriever = get_retriever_with_validators(payload_form, assignments).await;
+
+            let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, payload_form);
+            let retrieved = retriever.get_payload(eigenda_cert).await.unwrap();
+            assert_eq!(retrieved.serialize(), payload.serialize());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tampered_frames_are_rejected() {
+        let payload = Payload::new((0..100).collect());
+        let frames = get_test_frames(&payload, PayloadForm::Coeff);
+        let mut tampered_frames = frames[..2].to_vec();
+        for (_, frame) in tampered_frames.iter_mut() {
+            frame.coeffs[0] += Fr::ONE;
+        }
+        let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Coeff);
+
+        // the honest validator holds only one chunk, so the tampered ones would be needed
+        let assignments = vec![
+            serve_frames(&tampered_frames).await,
+            serve_frames(&frames[2..3]).await,
+        ];
+        let mut retriever = get_retriever_with_validators(PayloadForm::Coeff, assignments).await;
+        let res = retriever.get_payload(eigenda_cert.clone()).await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::ValidatorRetrieval(
+                ValidatorPayloadRetrieverError::NotEnoughValidChunks(1, 2)
+            ))
+        ));
+
+        // with another honest chunk, the tampered ones are skipped
+        let assignments = vec![
+            serve_frames(&tampered_frames).await,
+            serve_frames(&frames[2..4]).await,
+        ];
+        let mut retriever = get_retriever_with_validators(PayloadForm::Coeff, assignments).await;
+        let retrieved = retriever.get_payload(eigenda_cert).await.unwrap();
+        assert_eq!(retrieved.serialize(), payload.serialize());
+    }
+
+    #[tokio::test]
+    async fn test_recover_from_minimum_chunks() {
+        let payload = Payload::new((0..100).collect());
+        let frames = get_test_frames(&payload, PayloadForm::Eval);
+        let eigenda_cert = get_test_eigenda_cert_for_payload(&payload, PayloadForm::Eval);
+
+        // exactly the 2 chunks needed
+        let assignments = vec![serve_frames(&frames[5..7]).await];
+        let mut retriever = get_retriever_with_validators(PayloadForm::Eval, assignments).await;
+        let retrieved = retriever.get_payload(eigenda_cert.clone()).await.unwrap();
+        assert_eq!(retrieved.serialize(), payload.serialize());
+
+        // one chunk short
+        let assignments = vec![serve_frames(&frames[5..6]).await];
+        let mut retriever = get_retriever_with_validators(PayloadForm::Eval, assignments).await;
+        let res = retriever.get_payload(eigenda_cert).await;
+        assert!(matches!(
+            res,
+            Err(RelayPayloadRetrieverError::ValidatorRetrieval(
+                ValidatorPayloadRetrieverError::NotEnoughValidChunks(1, 2)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,478 @@
+//! ABI encoding of the certs, as `abi.encode(cert)` in Solidity: the form the EigenDACertVerifier contracts and the
+//! rollup inbox contracts receive.
+use ark_bn254::{G1Affine, G2Affine};
+use ethabi::{ethereum_types::U256, ParamType, Token};
+
+use crate::{
+    commitment_utils::{
+        g1_point_from_coordinates, g1_point_to_coordinates, g2_point_from_coordinates,
+        g2_point_to_coordinates,
+    },
+    BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader, BlobInclusionInfo, EigenDACert,
+    EigenDACertError, EigenDACertV2, EigenDACertV3, EigenDACertVersion,
+    NonSignerStakesAndSignature, MAX_CERT_SIZE,
+};
+
+impl EigenDACertV2 {
+    /// ABI encodes the cert as the Solidity `EigenDACertV2` struct: `abi.encode(cert)`.
+    pub fn to_abi_bytes(&self) -> Vec<u8> {
+        ethabi::encode(&[Token::Tuple(vec![
+            blob_inclusion_info_token(&self.blob_inclusion_info),
+            batch_header_token(&self.batch_header),
+            non_signer_stakes_and_signature_token(&self.non_signer_stakes_and_signature),
+            Token::Bytes(self.signed_quorum_numbers.clone()),
+        ])])
+    }
+
+    /// Decodes an ABI encoded cert, see [`EigenDACertV2::to_abi_bytes`].
+    pub fn from_abi_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        let param = ParamType::Tuple(vec![
+            blob_inclusion_info_param(),
+            batch_header_param(),
+            non_signer_stakes_and_signature_param(),
+            ParamType::Bytes,
+        ]);
+        let [blob_inclusion_info, batch_header, non_signer_stakes, signed_quorum_numbers] =
+            tuple(decode(param, bytes)?)?;
+        Ok(EigenDACertV2 {
+            blob_inclusion_info: blob_inclusion_info_from_token(blob_inclusion_info)?,
+            batch_header: batch_header_from_token(batch_header)?,
+            non_signer_stakes_and_signature: non_signer_stakes_and_signature_from_token(
+                non_signer_stakes,
+            )?,
+            signed_quorum_numbers: bytes_from_token(signed_quorum_numbers)?,
+        })
+    }
+}
+
+impl EigenDACertV3 {
+    /// ABI encodes the cert as the Solidity `EigenDACertV3` struct: `abi.encode(cert)`, the input of
+    /// `checkDACert`.
+    pub fn to_abi_bytes(&self) -> Vec<u8> {
+        ethabi::encode(&[Token::Tuple(vec![
+            batch_header_token(&self.batch_header),
+            blob_inclusion_info_token(&self.blob_inclusion_info),
+            non_signer_stakes_and_signature_token(&self.non_signer_stakes_and_signature),
+            Token::Bytes(self.signed_quorum_numbers.clone()),
+        ])])
+    }
+
+    /// Decodes an ABI encoded cert, see [`EigenDACertV3::to_abi_bytes`].
+    pub fn from_abi_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        let param = ParamType::Tuple(vec![
+            batch_header_param(),
+            blob_inclusion_info_param(),
+            non_signer_stakes_and_signature_param(),
+            ParamType::Bytes,
+        ]);
+        let [batch_header, blob_inclusion_info, non_signer_stakes, signed_quorum_numbers] =
+            tuple(decode(param, bytes)?)?;
+        Ok(EigenDACertV3 {
+            batch_header: batch_header_from_token(batch_header)?,
+            blob_inclusion_info: blob_inclusion_info_from_token(blob_inclusion_info)?,
+            non_signer_stakes_and_signature: non_signer_stakes_and_signature_from_token(
+                non_signer_stakes,
+            )?,
+            signed_quorum_numbers: bytes_from_token(signed_quorum_numbers)?,
+        })
+    }
+}
+
+impl EigenDACert {
+    /// Transforms the EigenDACert into bytes: the version byte followed by the ABI encoded cert
+    pub fn to_abi_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.version().into()];
+        bytes.extend(match self {
+            EigenDACert::V2(cert) => cert.to_abi_bytes(),
+            EigenDACert::V3(cert) => cert.to_abi_bytes(),
+        });
+        bytes
+    }
+
+    /// Builds a new EigenDACert from bytes: the version byte followed by the ABI encoded cert
+    ///
+    /// Inputs longer than [`MAX_CERT_SIZE`] are rejected.
+    pub fn from_abi_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        if bytes.len() > MAX_CERT_SIZE {
+            return Err(EigenDACertError::AbiDecoding(format!(
+                "cert size ({}) exceeds the maximum of {} bytes",
+                bytes.len(),
+                MAX_CERT_SIZE
+            )));
+        }
+        let (&version, cert_bytes) = bytes
+            .split_first()
+            .ok_or_else(|| EigenDACertError::AbiDecoding("missing cert version".to_string()))?;
+        match EigenDACertVersion::try_from(version)? {
+            EigenDACertVersion::V2 => {
+                EigenDACertV2::from_abi_bytes(cert_bytes).map(EigenDACert::V2)
+            }
+            EigenDACertVersion::V3 => {
+                EigenDACertV3::from_abi_bytes(cert_bytes).map(EigenDACert::V3)
+            }
+        }
+    }
+}
+
+fn g1_point_param() -> ParamType {
+    ParamType::Tuple(vec![ParamType::Uint(256), ParamType::Uint(256)])
+}
+
+fn g2_point_param() -> ParamType {
+    let coordinate = ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2);
+    ParamType::Tuple(vec![coordinate.clone(), coordinate])
+}
+
+fn blob_inclusion_info_param() -> ParamType {
+    let blob_commitment = ParamType::Tuple(vec![
+        g1_point_param(),
+        g2_point_param(),
+        g2_point_param(),
+        ParamType::Uint(32),
+    ]);
+    let blob_header = ParamType::Tuple(vec![
+        ParamType::Uint(16),
+        ParamType::Bytes,
+        blob_commitment,
+        ParamType::FixedBytes(32),
+    ]);
+    let blob_certificate = ParamType::Tuple(vec![
+        blob_header,
+        ParamType::Bytes,
+        ParamType::Array(Box::new(ParamType::Uint(32))),
+    ]);
+    ParamType::Tuple(vec![
+        blob_certificate,
+        ParamType::Uint(32),
+        ParamType::Bytes,
+    ])
+}
+
+fn batch_header_param() -> ParamType {
+    ParamType::Tuple(vec![ParamType::FixedBytes(32), ParamType::Uint(32)])
+}
+
+fn non_signer_stakes_and_signature_param() -> ParamType {
+    let indices = ParamType::Array(Box::new(ParamType::Uint(32)));
+    ParamType::Tuple(vec![
+        indices.clone(),
+        ParamType::Array(Box::new(g1_point_param())),
+        ParamType::Array(Box::new(g1_point_param())),
+        g2_point_param(),
+        g1_point_param(),
+        indices.clone(),
+        indices.clone(),
+        ParamType::Array(Box::new(indices)),
+    ])
+}
+
+fn uint_token(value: impl Into<U256>) -> Token {
+    Token::Uint(value.into())
+}
+
+fn indices_token(indices: &[u32]) -> Token {
+    Token::Array(indices.iter().map(|&index| uint_token(index)).collect())
+}
+
+fn g1_point_token(point: &G1Affine) -> Token {
+    let [x, y] = g1_point_to_coordinates(point);
+    Token::Tuple(vec![
+        uint_token(U256::from_big_endian(&x)),
+        uint_token(U256::from_big_endian(&y)),
+    ])
+}
+
+fn g2_point_token(point: &G2Affine) -> Token {
+    let [x1, x0, y1, y0] = g2_point_to_coordinates(point);
+    let coordinate = |c1: [u8; 32], c0: [u8; 32]| {
+        Token::FixedArray(vec![
+            uint_token(U256::from_big_endian(&c1)),
+            uint_token(U256::from_big_endian(&c0)),
+        ])
+    };
+    Token::Tuple(vec![coordinate(x1, x0), coordinate(y1, y0)])
+}
+
+fn blob_inclusion_info_token(blob_inclusion_info: &BlobInclusionInfo) -> Token {
+    let blob_certificate = &blob_inclusion_info.blob_certificate;
+    let blob_header = &blob_certificate.blob_header;
+    let commitment = &blob_header.commitment;
+    let blob_commitment = Token::Tuple(vec![
+        g1_point_token(&commitment.commitment),
+        g2_point_token(&commitment.length_commitment),
+        g2_point_token(&commitment.length_proof),
+        uint_token(commitment.length),
+    ]);
+    let blob_header = Token::Tuple(vec![
+        uint_token(blob_header.version),
+        Token::Bytes(blob_header.quorum_numbers.clone()),
+        blob_commitment,
+        Token::FixedBytes(blob_header.payment_header_hash.to_vec()),
+    ]);
+    let blob_certificate = Token::Tuple(vec![
+        blob_header,
+        Token::Bytes(blob_certificate.signature.clone()),
+        indices_token(&blob_certificate.relay_keys),
+    ]);
+    Token::Tuple(vec![
+        blob_certificate,
+        uint_token(blob_inclusion_info.blob_index),
+        Token::Bytes(blob_inclusion_info.inclusion_proof.clone()),
+    ])
+}
+
+fn batch_header_token(batch_header: &BatchHeaderV2) -> Token {
+    Token::Tuple(vec![
+        Token::FixedBytes(batch_header.batch_root.to_vec()),
+        uint_token(batch_header.reference_block_number),
+    ])
+}
+
+fn non_signer_stakes_and_signature_token(params: &NonSignerStakesAndSignature) -> Token {
+    Token::Tuple(vec![
+        indices_token(&params.non_signer_quorum_bitmap_indices),
+        Token::Array(
+            params
+                .non_signer_pubkeys
+                .iter()
+                .map(g1_point_token)
+                .collect(),
+        ),
+        Token::Array(params.quorum_apks.iter().map(g1_point_token).collect()),
+        g2_point_token(&params.apk_g2),
+        g1_point_token(&params.sigma),
+        indices_token(&params.quorum_apk_indices),
+        indices_token(&params.total_stake_indices),
+        Token::Array(
+            params
+                .non_signer_stake_indices
+                .iter()
+                .map(|indices| indices_token(indices))
+                .collect(),
+        ),
+    ])
+}
+
+fn invalid(reason: impl Into<String>) -> EigenDACertError {
+    EigenDACertError::AbiDecoding(reason.into())
+}
+
+fn decode(param: ParamType, bytes: &[u8]) -> Result<Token, EigenDACertError> {
+    let mut tokens = ethabi::decode(&[param], bytes).map_err(|e| invalid(e.to_string()))?;
+    tokens.pop().ok_or_else(|| invalid("empty input"))
+}
+
+fn tuple<const N: usize>(token: Token) -> Result<[Token; N], EigenDACertError> {
+    match token {
+        Token::Tuple(tokens) | Token::FixedArray(tokens) => {
+            tokens.try_into().map_err(|tokens: Vec<Token>| {
+                invalid(format!("expected {} fields, got {}", N, tokens.len()))
+            })
+        }
+        token => Err(invalid(format!("expected a tuple, got {}", token))),
+    }
+}
+
+fn array(token: Token) -> Result<Vec<Token>, EigenDACertError> {
+    match token {
+        Token::Array(tokens) => Ok(tokens),
+        token => Err(invalid(format!("expected an array, got {}", token))),
+    }
+}
+
+fn uint_from_token(token: Token) -> Result<U256, EigenDACertError> {
+    match token {
+        Token::Uint(value) => Ok(value),
+        token => Err(invalid(format!("expected an uint, got {}", token))),
+    }
+}
+
+/// Reads an uint of a type narrower than 256 bits, rejecting values that overflow it.
+fn narrow_uint_from_token<T: TryFrom<U256>>(token: Token) -> Result<T, EigenDACertError> {
+    let value = uint_from_token(token)?;
+    T::try_from(value).map_err(|_| invalid(format!("uint {} overflows its type", value)))
+}
+
+fn indices_from_token(token: Token) -> Result<Vec<u32>, EigenDACertError> {
+    array(token)?
+        .into_iter()
+        .map(narrow_uint_from_token)
+        .collect()
+}
+
+fn bytes_from_token(token: Token) -> Result<Vec<u8>, EigenDACertError> {
+    match token {
+        Token::Bytes(bytes) => Ok(bytes),
+        token => Err(invalid(format!("expected bytes, got {}", token))),
+    }
+}
+
+fn bytes32_from_token(token: Token) -> Result<[u8; 32], EigenDACertError> {
+    match token {
+        Token::FixedBytes(bytes) => bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| invalid(format!("expected 32 bytes, got {}", bytes.len()))),
+        token => Err(invalid(format!("expected bytes32, got {}", token))),
+    }
+}
+
+fn coordinate_from_token(token: Token) -> Result<[u8; 32], EigenDACertError> {
+    let mut coordinate = [0u8; 32];
+    uint_from_token(token)?.to_big_endian(&mut coordinate);
+    Ok(coordinate)
+}
+
+fn g1_point_from_token(token: Token) -> Result<G1Affine, EigenDACertError> {
+    let [x, y] = tuple(token)?;
+    Ok(g1_point_from_coordinates(&[
+        coordinate_from_token(x)?,
+        coordinate_from_token(y)?,
+    ])?)
+}
+
+fn g2_point_from_token(token: Token) -> Result<G2Affine, EigenDACertError> {
+    let [x, y] = tuple(token)?;
+    let [x1, x0] = tuple(x)?;
+    let [y1, y0] = tuple(y)?;
+    Ok(g2_point_from_coordinates(&[
+        coordinate_from_token(x1)?,
+        coordinate_from_token(x0)?,
+        coordinate_from_token(y1)?,
+        coordinate_from_token(y0)?,
+    ])?)
+}
+
+fn blob_inclusion_info_from_token(token: Token) -> Result<BlobInclusionInfo, EigenDACertError> {
+    let [blob_certificate, blob_index, inclusion_proof] = tuple(token)?;
+    let [blob_header, signature, relay_keys] = tuple(blob_certificate)?;
+    let [version, quorum_numbers, commitment, payment_header_hash] = tuple(blob_header)?;
+    let [commitment, length_commitment, length_proof, length] = tuple(commitment)?;
+    Ok(BlobInclusionInfo {
+        blob_certificate: BlobCertificate {
+            blob_header: BlobHeader {
+                version: narrow_uint_from_token(version)?,
+                quorum_numbers: bytes_from_token(quorum_numbers)?,
+                commitment: BlobCommitments {
+                    commitment: g1_point_from_token(commitment)?,
+                    length_commitment: g2_point_from_token(length_commitment)?,
+                    length_proof: g2_point_from_token(length_proof)?,
+                    length: narrow_uint_from_token(length)?,
+                },
+                payment_header_hash: bytes32_from_token(payment_header_hash)?,
+            },
+            signature: bytes_from_token(signature)?,
+            relay_keys: indices_from_token(relay_keys)?,
+        },
+        blob_index: narrow_uint_from_token(blob_index)?,
+        inclusion_proof: bytes_from_token(inclusion_proof)?,
+    })
+}
+
+fn batch_header_from_token(token: Token) -> Result<BatchHeaderV2, EigenDACertError> {
+    let [batch_root, reference_block_number] = tuple(token)?;
+    Ok(BatchHeaderV2 {
+        batch_root: bytes32_from_token(batch_root)?,
+        reference_block_number: narrow_uint_from_token(reference_block_number)?,
+    })
+}
+
+fn non_signer_stakes_and_signature_from_token(
+    token: Token,
+) -> Result<NonSignerStakesAndSignature, EigenDACertError> {
+    let [bitmap_indices, pubkeys, apks, apk_g2, sigma, apk_indices, total_indices, stake_indices] =
+        tuple(token)?;
+    Ok(NonSignerStakesAndSignature {
+        non_signer_quorum_bitmap_indices: indices_from_token(bitmap_indices)?,
+        non_signer_pubkeys: array(pubkeys)?
+            .into_iter()
+            .map(g1_point_from_token)
+            .collect::<Result<_, _>>()?,
+        quorum_apks: array(apks)?
+            .into_iter()
+            .map(g1_point_from_token)
+            .collect::<Result<_, _>>()?,
+        apk_g2: g2_point_from_token(apk_g2)?,
+        sigma: g1_point_from_token(sigma)?,
+        quorum_apk_indices: indices_from_token(apk_indices)?,
+        total_stake_indices: indices_from_token(total_indices)?,
+        non_signer_stake_indices: array(stake_indices)?
+            .into_iter()
+            .map(indices_from_token)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{
+            decode_hex, get_test_eigenda_cert_v2, EIGENDA_CERT_V2_ABI_HEX, EIGENDA_CERT_V3_ABI_HEX,
+        },
+        EigenDACert, EigenDACertError, EigenDACertV2, EigenDACertV3,
+    };
+
+    #[test]
+    fn test_abi_regression_vectors() {
+        let cert_v2 = get_test_eigenda_cert_v2();
+        let expected_v2 = decode_hex(EIGENDA_CERT_V2_ABI_HEX);
+        assert_eq!(cert_v2.to_abi_bytes(), expected_v2);
+        assert_eq!(
+            EigenDACertV2::from_abi_bytes(&expected_v2).unwrap(),
+            cert_v2
+        );
+
+        let cert_v3: EigenDACertV3 = cert_v2.into();
+        let expected_v3 = decode_hex(EIGENDA_CERT_V3_ABI_HEX);
+        assert_eq!(cert_v3.to_abi_bytes(), expected_v3);
+        assert_eq!(
+            EigenDACertV3::from_abi_bytes(&expected_v3).unwrap(),
+            cert_v3
+        );
+    }
+
+    #[test]
+    fn test_versioned_abi_encoding() {
+        let cert_v2 = get_test_eigenda_cert_v2();
+        for cert in [
+            EigenDACert::V2(cert_v2.clone()),
+            EigenDACert::V3(cert_v2.into()),
+        ] {
+            let bytes = cert.to_abi_bytes();
+            assert_eq!(bytes[0], u8::from(cert.version()));
+            assert_eq!(EigenDACert::from_abi_bytes(&bytes).unwrap(), cert);
+        }
+        assert!(EigenDACert::from_abi_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_abi_decoding_rejects_malformed_certs() {
+        let expected = decode_hex(EIGENDA_CERT_V3_ABI_HEX);
+        assert!(matches!(
+            EigenDACertV3::from_abi_bytes(&expected[..expected.len() - 32]),
+            Err(EigenDACertError::AbiDecoding(_))
+        ));
+
+        // the reference block number is the word after the batch root, and must fit in an uint32
+        let mut overflowing = expected.clone();
+        overflowing[64 + 27] = 1;
+        assert!(matches!(
+            EigenDACertV3::from_abi_bytes(&overflowing),
+            Err(EigenDACertError::AbiDecoding(_))
+        ));
+
+        // the sigma X coordinate moved out of the curve
+        let cert = get_test_eigenda_cert_v2();
+        let mut off_curve = cert.to_abi_bytes();
+        let sigma_x =
+            super::g1_point_to_coordinates(&cert.non_signer_stakes_and_signature.sigma)[0];
+        let position = off_curve
+            .windows(32)
+            .position(|word| word == sigma_x)
+            .unwrap();
+        off_curve[position + 31] ^= 1;
+        assert!(matches!(
+            EigenDACertV2::from_abi_bytes(&off_curve),
+            Err(EigenDACertError::Conversion(_))
+        ));
+    }
+}
@@ -1,5 +1,5 @@
-use ark_bn254::{G1Affine, G2Affine};
-use ark_ff::{AdditiveGroup, Fp, Fp2, PrimeField, Zero};
+use ark_bn254::{Fq, G1Affine, G2Affine};
+use ark_ff::{AdditiveGroup, BigInteger, Fp, Fp2, PrimeField, Zero};
 use ark_serialize::CanonicalSerialize;
 use rust_kzg_bn254_primitives::helpers::{lexicographically_largest, read_g1_point_from_bytes_be};
 
@@ -8,11 +8,17 @@ use crate::ConversionError;
 const COMPRESSED_SMALLEST: u8 = 0b10 << 6;
 const COMPRESSED_LARGEST: u8 = 0b11 << 6;
 const COMPRESSED_INFINITY: u8 = 0b01 << 6;
+const G1_COMPRESSED_SIZE: usize = 32;
 const G2_COMPRESSED_SIZE: usize = 64;
 
 /// g1_commitment_from_bytes converts a byte slice to a G1Affine point.
 /// The points received are in compressed form.
 pub fn g1_commitment_from_bytes(bytes: &[u8]) -> Result<G1Affine, ConversionError> {
+    if bytes.len() != G1_COMPRESSED_SIZE {
+        return Err(ConversionError::G1Point(
+            "Invalid length for G1 Commitment".to_string(),
+        ));
+    }
     read_g1_point_from_bytes_be(bytes).map_err(|e| ConversionError::G1Point(e.to_string()))
 }
 
@@ -45,7 +51,7 @@ pub fn g1_commitment_to_bytes(point: &G1Affine) -> Result<Vec<u8>, ConversionErr
 
 /// g2_commitment_from_bytes converts a byte slice to a G2Affine point.
 pub fn g2_commitment_from_bytes(bytes: &[u8]) -> Result<G2Affine, ConversionError> {
-    if bytes.len() != 64 {
+    if bytes.len() != G2_COMPRESSED_SIZE {
         return Err(ConversionError::G2Point(
             "Invalid length for G2 Commitment".to_string(),
         ));
@@ -120,3 +126,90 @@ pub fn g2_commitment_to_bytes(point: &G2Affine) -> Result<Vec<u8>, ConversionErr
     bytes[0] |= mask;
     Ok(bytes)
 }
+
+/// Big endian coordinates of a G1 point, as in the Solidity `BN254.G1Point`: `[X, Y]`, being `[0, 0]` the point at
+/// infinity.
+pub fn g1_point_to_coordinates(point: &G1Affine) -> [[u8; 32]; 2] {
+    if point.infinity {
+        return [[0u8; 32]; 2];
+    }
+    [fq_to_bytes_be(&point.x), fq_to_bytes_be(&point.y)]
+}
+
+/// Reads a G1 point from its big endian coordinates, see [`g1_point_to_coordinates`].
+///
+/// Coordinates out of the field and points out of the curve or the subgroup are rejected.
+pub fn g1_point_from_coordinates(coordinates: &[[u8; 32]; 2]) -> Result<G1Affine, ConversionError> {
+    if coordinates
+        .iter()
+        .all(|coordinate| coordinate == &[0u8; 32])
+    {
+        return Ok(G1Affine::identity());
+    }
+    let invalid = |reason: &str| ConversionError::G1Point(reason.to_string());
+    let x = fq_from_bytes_be(&coordinates[0])
+        .ok_or_else(|| invalid("Coordinate is not in the field"))?;
+    let y = fq_from_bytes_be(&coordinates[1])
+        .ok_or_else(|| invalid("Coordinate is not in the field"))?;
+    let point = G1Affine::new_unchecked(x, y);
+    if !point.is_on_curve() {
+        return Err(invalid("Point is not on curve"));
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("Point is not on correct subgroup"));
+    }
+    Ok(point)
+}
+
+/// Big endian coordinates of a G2 point, as in the Solidity `BN254.G2Point`: `[X.c1, X.c0, Y.c1, Y.c0]`, being all
+/// zeros the point at infinity.
+pub fn g2_point_to_coordinates(point: &G2Affine) -> [[u8; 32]; 4] {
+    if point.infinity {
+        return [[0u8; 32]; 4];
+    }
+    [
+        fq_to_bytes_be(&point.x.c1),
+        fq_to_bytes_be(&point.x.c0),
+        fq_to_bytes_be(&point.y.c1),
+        fq_to_bytes_be(&point.y.c0),
+    ]
+}
+
+/// Reads a G2 point from its big endian coordinates, see [`g2_point_to_coordinates`].
+///
+/// Coordinates out of the field and points out of the curve or the subgroup are rejected.
+pub fn g2_point_from_coordinates(coordinates: &[[u8; 32]; 4]) -> Result<G2Affine, ConversionError> {
+    if coordinates
+        .iter()
+        .all(|coordinate| coordinate == &[0u8; 32])
+    {
+        return Ok(G2Affine::identity());
+    }
+    let invalid = |reason: &str| ConversionError::G2Point(reason.to_string());
+    let mut field_elements = [Fq::ZERO; 4];
+    for (element, coordinate) in field_elements.iter_mut().zip(coordinates) {
+        *element = fq_from_bytes_be(coordinate)
+            .ok_or_else(|| invalid("Coordinate is not in the field"))?;
+    }
+    let [x1, x0, y1, y0] = field_elements;
+    let point = G2Affine::new_unchecked(Fp2::new(x0, x1), Fp2::new(y0, y1));
+    if !point.is_on_curve() {
+        return Err(invalid("Point is not on curve"));
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("Point is not on correct subgroup"));
+    }
+    Ok(point)
+}
+
+fn fq_to_bytes_be(element: &Fq) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&element.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// Reads a field element, returning None if it is not lower than the modulus.
+fn fq_from_bytes_be(bytes: &[u8; 32]) -> Option<Fq> {
+    let element = Fq::from_be_bytes_mod_order(bytes);
+    (fq_to_bytes_be(&element) == *bytes).then_some(element)
+}
@@ -1,4 +1,5 @@
 use ark_bn254::{G1Affine, G2Affine};
+use bincode::Options;
 use serde::ser::Error;
 
 use crate::{
@@ -6,6 +7,10 @@ use crate::{
         g1_commitment_from_bytes, g1_commitment_to_bytes, g2_commitment_from_bytes,
         g2_commitment_to_bytes,
     },
+    json_encoding::{
+        hex_bytes, BlobCommitmentsJson, EigenDACertJson, EigenDACertJsonRef,
+        NonSignerStakesAndSignatureJson,
+    },
     ConversionError, EigenDACertError,
 };
 
@@ -59,6 +64,9 @@ impl serde::Serialize for BlobCommitments {
     where
         S: serde::Serializer,
     {
+        if serializer.is_human_readable() {
+            return BlobCommitmentsJson::from(self).serialize(serializer);
+        }
         BlobCommitmentsHelper::try_from(self)
             .map_err(|e| S::Error::custom(format!("Conversion failed: {}", e)))?
             .serialize(serializer)
@@ -70,16 +78,22 @@ impl<'de> serde::Deserialize<'de> for BlobCommitments {
     where
         D: serde::Deserializer<'de>,
     {
+        if deserializer.is_human_readable() {
+            let json = BlobCommitmentsJson::deserialize(deserializer)?;
+            return Self::try_from(json).map_err(serde::de::Error::custom);
+        }
         let helper = BlobCommitmentsHelper::deserialize(deserializer)?;
         Self::try_from(helper).map_err(serde::de::Error::custom)
     }
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct BlobHeader {
     pub version: u16,
     pub quorum_numbers: Vec<u8>,
     pub commitment: BlobCommitments,
+    #[serde(with = "hex_bytes")]
     pub payment_header_hash: [u8; 32],
 }
 
@@ -88,22 +102,28 @@ pub struct BlobHeader {
 /// is provided by the blob submitter (i.e. the blob header), and part is provided by the disperser (i.e. the relays).
 /// Validator nodes eventually sign the blob certificate once they are in custody of the required chunks
 /// (note that the signature is indirect; validators sign the hash of a Batch, which contains the blob certificate).
+#[serde(deny_unknown_fields)]
 pub struct BlobCertificate {
     pub blob_header: BlobHeader,
+    #[serde(with = "hex_bytes")]
     pub signature: Vec<u8>,
     pub relay_keys: Vec<u32>,
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 /// BlobInclusionInfo is the information needed to verify the inclusion of a blob in a batch.
+#[serde(deny_unknown_fields)]
 pub struct BlobInclusionInfo {
     pub blob_certificate: BlobCertificate,
     pub blob_index: u32,
+    #[serde(with = "hex_bytes")]
     pub inclusion_proof: Vec<u8>,
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct BatchHeaderV2 {
+    #[serde(with = "hex_bytes")]
     pub batch_root: [u8; 32],
     pub reference_block_number: u32,
 }
@@ -188,6 +208,9 @@ impl serde::Serialize for NonSignerStakesAndSignature {
     where
         S: serde::Serializer,
     {
+        if serializer.is_human_readable() {
+            return NonSignerStakesAndSignatureJson::from(self).serialize(serializer);
+        }
         NonSignerStakesAndSignatureHelper::try_from(self)
             .map_err(|e| S::Error::custom(format!("Conversion failed: {}", e)))?
             .serialize(serializer)
@@ -199,30 +222,318 @@ impl<'de> serde::Deserialize<'de> for NonSignerStakesAndSignature {
     where
         D: serde::Deserializer<'de>,
     {
+        if deserializer.is_human_readable() {
+            let json = NonSignerStakesAndSignatureJson::deserialize(deserializer)?;
+            return Self::try_from(json).map_err(serde::de::Error::custom);
+        }
         let helper = NonSignerStakesAndSignatureHelper::deserialize(deserializer)?;
         Self::try_from(helper).map_err(serde::de::Error::custom)
     }
 }
 
-/// Contains all data necessary to retrieve and validate a [`Blob`]
+/// Contains all data necessary to retrieve and validate a [`Blob`], in the format verified by
+/// `EigenDACertVerifier.verifyDACertV2`.
 ///
 /// This struct represents the composition of a EigenDA blob certificate, as it would exist in a rollup inbox.
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
-pub struct EigenDACert {
+#[serde(deny_unknown_fields)]
+pub struct EigenDACertV2 {
     pub blob_inclusion_info: BlobInclusionInfo,
     pub batch_header: BatchHeaderV2,
     pub non_signer_stakes_and_signature: NonSignerStakesAndSignature,
     pub signed_quorum_numbers: Vec<u8>,
 }
 
-impl EigenDACert {
-    /// Transforms the EigenDACert into bytes using bincode
+/// Cert in the format verified by `EigenDACertVerifier.checkDACert` of the V3 verifiers, which receive it ABI
+/// encoded. Holds the same data as [`EigenDACertV2`], with the batch header first.
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EigenDACertV3 {
+    pub batch_header: BatchHeaderV2,
+    pub blob_inclusion_info: BlobInclusionInfo,
+    pub non_signer_stakes_and_signature: NonSignerStakesAndSignature,
+    pub signed_quorum_numbers: Vec<u8>,
+}
+
+impl From<EigenDACertV2> for EigenDACertV3 {
+    fn from(cert: EigenDACertV2) -> Self {
+        EigenDACertV3 {
+            batch_header: cert.batch_header,
+            blob_inclusion_info: cert.blob_inclusion_info,
+            non_signer_stakes_and_signature: cert.non_signer_stakes_and_signature,
+            signed_quorum_numbers: cert.signed_quorum_numbers,
+        }
+    }
+}
+
+impl From<EigenDACertV3> for EigenDACertV2 {
+    fn from(cert: EigenDACertV3) -> Self {
+        EigenDACertV2 {
+            blob_inclusion_info: cert.blob_inclusion_info,
+            batch_header: cert.batch_header,
+            non_signer_stakes_and_signature: cert.non_signer_stakes_and_signature,
+            signed_quorum_numbers: cert.signed_quorum_numbers,
+        }
+    }
+}
+
+/// Version of an [`EigenDACert`], encoded as its leading byte.
+///
+/// The values are the version bytes of the eigenda-proxy commitments. Version 0 is the EigenDA v1 cert, which is
+/// not supported.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[repr(u8)]
+pub enum EigenDACertVersion {
+    V2 = 1,
+    V3 = 2,
+}
+
+impl From<EigenDACertVersion> for u8 {
+    fn from(version: EigenDACertVersion) -> Self {
+        version as u8
+    }
+}
+
+impl TryFrom<u8> for EigenDACertVersion {
+    type Error = EigenDACertError;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            1 => Ok(EigenDACertVersion::V2),
+            2 => Ok(EigenDACertVersion::V3),
+            version => Err(EigenDACertError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+/// Contains all data necessary to retrieve and validate a [`Blob`], in any of the supported cert versions.
+///
+/// This is the cert as it would exist in a rollup inbox: its bytes start with the version, so new versions can be
+/// decoded without migrating the existing certs.
+///
+/// Human readable serializers tag the cert with its version, as in `{"version": "V2", "cert": {...}}`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum EigenDACert {
+    V2(EigenDACertV2),
+    V3(EigenDACertV3),
+}
+
+/// Mirror of [`EigenDACert`] deriving the representation of binary serializers.
+#[derive(serde::Deserialize)]
+#[serde(rename = "EigenDACert")]
+enum EigenDACertHelper {
+    V2(EigenDACertV2),
+    V3(EigenDACertV3),
+}
+
+impl serde::Serialize for EigenDACert {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            return match self {
+                EigenDACert::V2(cert) => EigenDACertJsonRef::V2(cert),
+                EigenDACert::V3(cert) => EigenDACertJsonRef::V3(cert),
+            }
+            .serialize(serializer);
+        }
+        match self {
+            EigenDACert::V2(cert) => {
+                serializer.serialize_newtype_variant("EigenDACert", 0, "V2", cert)
+            }
+            EigenDACert::V3(cert) => {
+                serializer.serialize_newtype_variant("EigenDACert", 1, "V3", cert)
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EigenDACert {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return Ok(match EigenDACertJson::deserialize(deserializer)? {
+                EigenDACertJson::V2(cert) => EigenDACert::V2(cert),
+                EigenDACertJson::V3(cert) => EigenDACert::V3(cert),
+            });
+        }
+        Ok(match EigenDACertHelper::deserialize(deserializer)? {
+            EigenDACertHelper::V2(cert) => EigenDACert::V2(cert),
+            EigenDACertHelper::V3(cert) => EigenDACert::V3(cert),
+        })
+    }
+}
+
+impl From<EigenDACertV2> for EigenDACert {
+    fn from(cert: EigenDACertV2) -> Self {
+        EigenDACert::V2(cert)
+    }
+}
+
+impl From<EigenDACertV3> for EigenDACert {
+    fn from(cert: EigenDACertV3) -> Self {
+        EigenDACert::V3(cert)
+    }
+}
+
+/// Maximum size, in bytes, of a serialized [`EigenDACert`].
+pub const MAX_CERT_SIZE: usize = 1024 * 1024;
+
+/// Deserializes with the same options as `bincode::serialize`, but with the size limit of certs.
+///
+/// The bytes usually come from an untrusted source (e.g. L1 calldata), so inputs longer than [`MAX_CERT_SIZE`] are
+/// rejected, and no allocation can exceed it.
+fn deserialize_cert<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, EigenDACertError> {
+    if bytes.len() > MAX_CERT_SIZE {
+        return Err(EigenDACertError::SerializationError(format!(
+            "cert size ({}) exceeds the maximum of {} bytes",
+            bytes.len(),
+            MAX_CERT_SIZE
+        )));
+    }
+    bincode::options()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(MAX_CERT_SIZE as u64)
+        .deserialize(bytes)
+        .map_err(|e| EigenDACertError::SerializationError(e.to_string()))
+}
+
+/// First byte of the certs serialized before certs were versioned, which are V2 certs serialized with bincode.
+///
+/// They start with the blob version of their blob header, a little endian `u16`, and the only blob version
+/// deployed at the time was 0. No cert version is 0, so [`EigenDACert::from_bytes`] decodes them as V2 certs.
+pub const LEGACY_CERT_PREFIX: u8 = 0;
+
+impl EigenDACertV2 {
+    /// Transforms the EigenDACertV2 into bytes using bincode, without the version byte of [`EigenDACert::to_bytes`]
     pub fn to_bytes(&self) -> Result<Vec<u8>, EigenDACertError> {
         bincode::serialize(self).map_err(|e| EigenDACertError::SerializationError(e.to_string()))
     }
 
-    /// Builds a new EigenDACert from bytes using bincode
+    /// Builds a new EigenDACertV2 from bytes using bincode, without the version byte of [`EigenDACert::from_bytes`].
+    ///
+    /// Decodes the certs serialized before certs were versioned.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        deserialize_cert(bytes)
+    }
+}
+
+impl EigenDACertV3 {
+    /// Transforms the EigenDACertV3 into bytes using bincode, without the version byte of [`EigenDACert::to_bytes`]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EigenDACertError> {
+        bincode::serialize(self).map_err(|e| EigenDACertError::SerializationError(e.to_string()))
+    }
+
+    /// Builds a new EigenDACertV3 from bytes using bincode, without the version byte of [`EigenDACert::from_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        deserialize_cert(bytes)
+    }
+}
+
+impl EigenDACert {
+    /// Returns the version of the cert.
+    pub fn version(&self) -> EigenDACertVersion {
+        match self {
+            EigenDACert::V2(_) => EigenDACertVersion::V2,
+            EigenDACert::V3(_) => EigenDACertVersion::V3,
+        }
+    }
+
+    pub fn blob_inclusion_info(&self) -> &BlobInclusionInfo {
+        match self {
+            EigenDACert::V2(cert) => &cert.blob_inclusion_info,
+            EigenDACert::V3(cert) => &cert.blob_inclusion_info,
+        }
+    }
+
+    pub fn blob_inclusion_info_mut(&mut self) -> &mut BlobInclusionInfo {
+        match self {
+            EigenDACert::V2(cert) => &mut cert.blob_inclusion_info,
+            EigenDACert::V3(cert) => &mut cert.blob_inclusion_info,
+        }
+    }
+
+    pub fn batch_header(&self) -> &BatchHeaderV2 {
+        match self {
+            EigenDACert::V2(cert) => &cert.batch_header,
+            EigenDACert::V3(cert) => &cert.batch_header,
+        }
+    }
+
+    pub fn batch_header_mut(&mut self) -> &mut BatchHeaderV2 {
+        match self {
+            EigenDACert::V2(cert) => &mut cert.batch_header,
+            EigenDACert::V3(cert) => &mut cert.batch_header,
+        }
+    }
+
+    pub fn non_signer_stakes_and_signature(&self) -> &NonSignerStakesAndSignature {
+        match self {
+            EigenDACert::V2(cert) => &cert.non_signer_stakes_and_signature,
+            EigenDACert::V3(cert) => &cert.non_signer_stakes_and_signature,
+        }
+    }
+
+    pub fn non_signer_stakes_and_signature_mut(&mut self) -> &mut NonSignerStakesAndSignature {
+        match self {
+            EigenDACert::V2(cert) => &mut cert.non_signer_stakes_and_signature,
+            EigenDACert::V3(cert) => &mut cert.non_signer_stakes_and_signature,
+        }
+    }
+
+    pub fn signed_quorum_numbers(&self) -> &[u8] {
+        match self {
+            EigenDACert::V2(cert) => &cert.signed_quorum_numbers,
+            EigenDACert::V3(cert) => &cert.signed_quorum_numbers,
+        }
+    }
+
+    pub fn signed_quorum_numbers_mut(&mut self) -> &mut Vec<u8> {
+        match self {
+            EigenDACert::V2(cert) => &mut cert.signed_quorum_numbers,
+            EigenDACert::V3(cert) => &mut cert.signed_quorum_numbers,
+        }
+    }
+
+    /// Returns the reference block number of the batch of the cert.
+    pub fn reference_block_number(&self) -> u32 {
+        self.batch_header().reference_block_number
+    }
+
+    /// Transforms the EigenDACert into bytes: the version byte followed by the cert serialized with bincode
+    ///
+    /// This is the legacy encoding of this crate, see [`EigenDACert::to_rlp_bytes`] for the one of the eigenda-proxy.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EigenDACertError> {
+        let cert_bytes = match self {
+            EigenDACert::V2(cert) => cert.to_bytes()?,
+            EigenDACert::V3(cert) => cert.to_bytes()?,
+        };
+        let mut bytes = Vec::with_capacity(1 + cert_bytes.len());
+        bytes.push(self.version().into());
+        bytes.extend(cert_bytes);
+        Ok(bytes)
+    }
+
+    /// Builds a new EigenDACert from bytes: the version byte followed by the cert serialized with bincode
+    ///
+    /// The bytes usually come from an untrusted source (e.g. L1 calldata), so inputs longer than
+    /// [`MAX_CERT_SIZE`] are rejected, and no allocation can exceed it.
+    ///
+    /// Certs serialized before certs were versioned are also accepted, see [`LEGACY_CERT_PREFIX`].
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
-        bincode::deserialize(bytes).map_err(|e| EigenDACertError::SerializationError(e.to_string()))
+        let (&version, cert_bytes) = bytes.split_first().ok_or_else(|| {
+            EigenDACertError::SerializationError("missing cert version".to_string())
+        })?;
+        if version == LEGACY_CERT_PREFIX {
+            return EigenDACertV2::from_bytes(bytes).map(EigenDACert::V2);
+        }
+        match EigenDACertVersion::try_from(version)? {
+            EigenDACertVersion::V2 => EigenDACertV2::from_bytes(cert_bytes).map(EigenDACert::V2),
+            EigenDACertVersion::V3 => EigenDACertV3::from_bytes(cert_bytes).map(EigenDACert::V3),
+        }
     }
 }
@@ -3,6 +3,14 @@
 pub enum EigenDACertError {
     #[error("Serialization failed for EigenDA certificate {0}")]
     SerializationError(String),
+    #[error("Unsupported EigenDA certificate version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Invalid ABI encoded EigenDA certificate: {0}")]
+    AbiDecoding(String),
+    #[error("Invalid RLP encoded EigenDA certificate: {0}")]
+    RlpDecoding(String),
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
 }
 
 /// Errors specific to conversion
@@ -12,6 +20,8 @@ pub enum ConversionError {
     G1Point(String),
     #[error("Failed to parse G2 point: {0}")]
     G2Point(String),
+    #[error("Failed to parse hex: {0}")]
+    Hex(String),
     #[error(transparent)]
     ArkSerializationError(#[from] ark_serialize::SerializationError),
 }
@@ -0,0 +1,334 @@
+//! Human readable representation of the certs, used by serializers such as `serde_json`: every struct has named
+//! fields, and points and bytes are `0x` prefixed hex strings. Binary serializers such as bincode keep the compact
+//! representation the certs were always serialized with.
+//!
+//! The representation is described by the JSON Schema in `schema/eigenda_cert.schema.json`.
+use ark_bn254::{G1Affine, G2Affine};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commitment_utils::{
+        g1_point_from_coordinates, g1_point_to_coordinates, g2_point_from_coordinates,
+        g2_point_to_coordinates,
+    },
+    BlobCommitments, ConversionError, EigenDACertV2, EigenDACertV3, NonSignerStakesAndSignature,
+};
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub(crate) fn from_hex(value: &str) -> Result<Vec<u8>, ConversionError> {
+    let digits = value
+        .strip_prefix("0x")
+        .ok_or_else(|| ConversionError::Hex(format!("missing 0x prefix in {}", value)))?;
+    hex::decode(digits).map_err(|e| ConversionError::Hex(e.to_string()))
+}
+
+/// Serializes bytes as a hex string with human readable serializers, and as the bytes themselves otherwise.
+///
+/// Used with `#[serde(with = "hex_bytes")]` on the byte fields of the certs.
+pub(crate) mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{from_hex, to_hex};
+
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + Serialize,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&to_hex(bytes.as_ref()))
+        } else {
+            bytes.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return T::deserialize(deserializer);
+        }
+        let bytes = from_hex(&String::deserialize(deserializer)?).map_err(D::Error::custom)?;
+        let length = bytes.len();
+        T::try_from(bytes).map_err(|_| D::Error::custom(format!("unexpected length {}", length)))
+    }
+}
+
+/// Reads a point coordinate, which must be 32 bytes long.
+fn coordinate_from_hex(value: &str) -> Result<[u8; 32], ConversionError> {
+    from_hex(value)?.try_into().map_err(|bytes: Vec<u8>| {
+        ConversionError::Hex(format!("expected 32 bytes, got {}", bytes.len()))
+    })
+}
+
+/// A G1 point as its affine coordinates, being `(0, 0)` the point at infinity.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct G1PointJson {
+    x: String,
+    y: String,
+}
+
+impl From<&G1Affine> for G1PointJson {
+    fn from(point: &G1Affine) -> Self {
+        let [x, y] = g1_point_to_coordinates(point);
+        G1PointJson {
+            x: to_hex(&x),
+            y: to_hex(&y),
+        }
+    }
+}
+
+impl TryFrom<G1PointJson> for G1Affine {
+    type Error = ConversionError;
+
+    fn try_from(point: G1PointJson) -> Result<Self, Self::Error> {
+        g1_point_from_coordinates(&[
+            coordinate_from_hex(&point.x)?,
+            coordinate_from_hex(&point.y)?,
+        ])
+    }
+}
+
+/// An element of the quadratic extension field, `c0 + c1 * u`.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Fq2Json {
+    c0: String,
+    c1: String,
+}
+
+/// A G2 point as its affine coordinates, being all zeros the point at infinity.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct G2PointJson {
+    x: Fq2Json,
+    y: Fq2Json,
+}
+
+impl From<&G2Affine> for G2PointJson {
+    fn from(point: &G2Affine) -> Self {
+        let [x1, x0, y1, y0] = g2_point_to_coordinates(point);
+        G2PointJson {
+            x: Fq2Json {
+                c0: to_hex(&x0),
+                c1: to_hex(&x1),
+            },
+            y: Fq2Json {
+                c0: to_hex(&y0),
+                c1: to_hex(&y1),
+            },
+        }
+    }
+}
+
+impl TryFrom<G2PointJson> for G2Affine {
+    type Error = ConversionError;
+
+    fn try_from(point: G2PointJson) -> Result<Self, Self::Error> {
+        g2_point_from_coordinates(&[
+            coordinate_from_hex(&point.x.c1)?,
+            coordinate_from_hex(&point.x.c0)?,
+            coordinate_from_hex(&point.y.c1)?,
+            coordinate_from_hex(&point.y.c0)?,
+        ])
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BlobCommitmentsJson {
+    commitment: G1PointJson,
+    length_commitment: G2PointJson,
+    length_proof: G2PointJson,
+    length: u32,
+}
+
+impl From<&BlobCommitments> for BlobCommitmentsJson {
+    fn from(commitments: &BlobCommitments) -> Self {
+        BlobCommitmentsJson {
+            commitment: (&commitments.commitment).into(),
+            length_commitment: (&commitments.length_commitment).into(),
+            length_proof: (&commitments.length_proof).into(),
+            length: commitments.length,
+        }
+    }
+}
+
+impl TryFrom<BlobCommitmentsJson> for BlobCommitments {
+    type Error = ConversionError;
+
+    fn try_from(json: BlobCommitmentsJson) -> Result<Self, Self::Error> {
+        Ok(BlobCommitments {
+            commitment: json.commitment.try_into()?,
+            length_commitment: json.length_commitment.try_into()?,
+            length_proof: json.length_proof.try_into()?,
+            length: json.length,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct NonSignerStakesAndSignatureJson {
+    non_signer_quorum_bitmap_indices: Vec<u32>,
+    non_signer_pubkeys: Vec<G1PointJson>,
+    quorum_apks: Vec<G1PointJson>,
+    apk_g2: G2PointJson,
+    sigma: G1PointJson,
+    quorum_apk_indices: Vec<u32>,
+    total_stake_indices: Vec<u32>,
+    non_signer_stake_indices: Vec<Vec<u32>>,
+}
+
+impl From<&NonSignerStakesAndSignature> for NonSignerStakesAndSignatureJson {
+    fn from(params: &NonSignerStakesAndSignature) -> Self {
+        NonSignerStakesAndSignatureJson {
+            non_signer_quorum_bitmap_indices: params.non_signer_quorum_bitmap_indices.clone(),
+            non_signer_pubkeys: params.non_signer_pubkeys.iter().map(Into::into).collect(),
+            quorum_apks: params.quorum_apks.iter().map(Into::into).collect(),
+            apk_g2: (&params.apk_g2).into(),
+            sigma: (&params.sigma).into(),
+            quorum_apk_indices: params.quorum_apk_indices.clone(),
+            total_stake_indices: params.total_stake_indices.clone(),
+            non_signer_stake_indices: params.non_signer_stake_indices.clone(),
+        }
+    }
+}
+
+impl TryFrom<NonSignerStakesAndSignatureJson> for NonSignerStakesAndSignature {
+    type Error = ConversionError;
+
+    fn try_from(json: NonSignerStakesAndSignatureJson) -> Result<Self, Self::Error> {
+        Ok(NonSignerStakesAndSignature {
+            non_signer_quorum_bitmap_indices: json.non_signer_quorum_bitmap_indices,
+            non_signer_pubkeys: json
+                .non_signer_pubkeys
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            quorum_apks: json
+                .quorum_apks
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            apk_g2: json.apk_g2.try_into()?,
+            sigma: json.sigma.try_into()?,
+            quorum_apk_indices: json.quorum_apk_indices,
+            total_stake_indices: json.total_stake_indices,
+            non_signer_stake_indices: json.non_signer_stake_indices,
+        })
+    }
+}
+
+/// An [`crate::EigenDACert`] tagged with its version: `{"version": "V2", "cert": {...}}`.
+#[derive(Serialize)]
+#[serde(tag = "version", content = "cert")]
+pub(crate) enum EigenDACertJsonRef<'a> {
+    V2(&'a EigenDACertV2),
+    V3(&'a EigenDACertV3),
+}
+
+/// Owned counterpart of [`EigenDACertJsonRef`], to deserialize it.
+#[derive(Deserialize)]
+#[serde(tag = "version", content = "cert", deny_unknown_fields)]
+pub(crate) enum EigenDACertJson {
+    V2(EigenDACertV2),
+    V3(EigenDACertV3),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        tests::{get_test_eigenda_cert_v2, EIGENDA_CERT_SCHEMA, EIGENDA_CERT_V2_JSON},
+        BlobCommitments, EigenDACert, EigenDACertV2, EigenDACertV3,
+    };
+
+    #[test]
+    fn test_json_golden_vector() {
+        let cert = EigenDACert::V2(get_test_eigenda_cert_v2());
+        let golden: Value = serde_json::from_str(EIGENDA_CERT_V2_JSON).unwrap();
+        assert_eq!(serde_json::to_value(&cert).unwrap(), golden);
+        assert_eq!(serde_json::from_value::<EigenDACert>(golden).unwrap(), cert);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let cert_v2 = get_test_eigenda_cert_v2();
+        let cert_v3: EigenDACertV3 = cert_v2.clone().into();
+        for cert in [EigenDACert::V2(cert_v2.clone()), EigenDACert::V3(cert_v3)] {
+            let json = serde_json::to_string_pretty(&cert).unwrap();
+            assert_eq!(serde_json::from_str::<EigenDACert>(&json).unwrap(), cert);
+        }
+
+        let json = serde_json::to_string(&cert_v2).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EigenDACertV2>(&json).unwrap(),
+            cert_v2
+        );
+    }
+
+    #[test]
+    fn test_json_rejects_invalid_values() {
+        let commitment = get_test_eigenda_cert_v2()
+            .blob_inclusion_info
+            .blob_certificate
+            .blob_header
+            .commitment;
+        let json = serde_json::to_value(&commitment).unwrap();
+
+        let mut off_curve = json.clone();
+        off_curve["commitment"]["y"] = json!(format!("0x{:064x}", 3));
+        assert!(serde_json::from_value::<BlobCommitments>(off_curve).is_err());
+
+        let mut unprefixed = json.clone();
+        let x = json["commitment"]["x"].as_str().unwrap();
+        unprefixed["commitment"]["x"] = json!(x.trim_start_matches("0x"));
+        assert!(serde_json::from_value::<BlobCommitments>(unprefixed).is_err());
+
+        let mut short = json.clone();
+        short["commitment"]["x"] = json!("0x01");
+        assert!(serde_json::from_value::<BlobCommitments>(short).is_err());
+
+        let mut unknown_field = json;
+        unknown_field["length_in_bytes"] = json!(16);
+        assert!(serde_json::from_value::<BlobCommitments>(unknown_field).is_err());
+    }
+
+    #[test]
+    fn test_json_matches_schema() {
+        let schema: Value = serde_json::from_str(EIGENDA_CERT_SCHEMA).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let cert_v2 = get_test_eigenda_cert_v2();
+        for cert in [
+            EigenDACert::V2(cert_v2.clone()),
+            EigenDACert::V3(cert_v2.into()),
+        ] {
+            let value = serde_json::to_value(&cert).unwrap();
+            validator.validate(&value).unwrap();
+
+            // the schema is strict about the fields
+            let mut extra_field = value.clone();
+            extra_field["cert"]["batch_header"]["timestamp"] = json!(0);
+            assert!(!validator.is_valid(&extra_field));
+
+            // and about the hex encoding of the bytes
+            let root = value["cert"]["batch_header"]["batch_root"]
+                .as_str()
+                .unwrap();
+            let mut unprefixed = value.clone();
+            unprefixed["cert"]["batch_header"]["batch_root"] = json!(root.trim_start_matches("0x"));
+            assert!(!validator.is_valid(&unprefixed));
+            let mut not_hex = value.clone();
+            not_hex["cert"]["batch_header"]["batch_root"] = json!(format!("0x{}", "zz".repeat(32)));
+            assert!(!validator.is_valid(&not_hex));
+        }
+    }
+}
@@ -1,6 +1,82 @@
+mod abi_encoding;
 mod commitment_utils;
 mod eigenda_cert;
 mod errors;
+mod json_encoding;
+pub mod network;
+mod rlp_encoding;
 
 pub use eigenda_cert::*;
 pub use errors::*;
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::{
+        g1::{G1_GENERATOR_X, G1_GENERATOR_Y},
+        g2::{G2_GENERATOR_X, G2_GENERATOR_Y},
+        Fr, G1Affine, G2Affine,
+    };
+
+    use crate::{
+        BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader, BlobInclusionInfo,
+        EigenDACertV2, NonSignerStakesAndSignature,
+    };
+
+    /// Vectors of the cert returned by [`get_test_eigenda_cert_v2`], in the encodings used by the eigenda-proxy and
+    /// the Solidity contracts. They were produced by this crate, see `testdata/README.md`.
+    pub const EIGENDA_CERT_V2_RLP_HEX: &str = include_str!("../testdata/eigenda_cert_v2.rlp.hex");
+    pub const EIGENDA_CERT_V3_RLP_HEX: &str = include_str!("../testdata/eigenda_cert_v3.rlp.hex");
+    pub const EIGENDA_CERT_V2_ABI_HEX: &str = include_str!("../testdata/eigenda_cert_v2.abi.hex");
+    pub const EIGENDA_CERT_V3_ABI_HEX: &str = include_str!("../testdata/eigenda_cert_v3.abi.hex");
+    pub const EIGENDA_CERT_V2_JSON: &str = include_str!("../testdata/eigenda_cert_v2.json");
+    pub const EIGENDA_CERT_SCHEMA: &str = include_str!("../schema/eigenda_cert.schema.json");
+
+    pub fn decode_hex(vector: &str) -> Vec<u8> {
+        hex::decode(vector.trim()).unwrap()
+    }
+
+    fn g1_point(scalar: u64) -> G1Affine {
+        (G1Affine::new(G1_GENERATOR_X, G1_GENERATOR_Y) * Fr::from(scalar)).into()
+    }
+
+    /// A cert with every field set to a distinguishable value, with multiples of the generators as points.
+    pub fn get_test_eigenda_cert_v2() -> EigenDACertV2 {
+        let g2_generator = G2Affine::new(G2_GENERATOR_X, G2_GENERATOR_Y);
+        EigenDACertV2 {
+            blob_inclusion_info: BlobInclusionInfo {
+                blob_certificate: BlobCertificate {
+                    blob_header: BlobHeader {
+                        version: 0,
+                        quorum_numbers: vec![0, 1],
+                        commitment: BlobCommitments {
+                            commitment: g1_point(1),
+                            length_commitment: g2_generator,
+                            length_proof: -g2_generator,
+                            length: 16,
+                        },
+                        payment_header_hash: [0x11; 32],
+                    },
+                    signature: vec![0x22; 65],
+                    relay_keys: vec![0, 1, 300],
+                },
+                blob_index: 3,
+                inclusion_proof: [[0x33; 32], [0x44; 32]].concat(),
+            },
+            batch_header: BatchHeaderV2 {
+                batch_root: [0x55; 32],
+                reference_block_number: 3656235,
+            },
+            non_signer_stakes_and_signature: NonSignerStakesAndSignature {
+                non_signer_quorum_bitmap_indices: vec![11],
+                non_signer_pubkeys: vec![g1_point(2)],
+                quorum_apks: vec![g1_point(3), g1_point(4)],
+                apk_g2: g2_generator,
+                sigma: g1_point(5),
+                quorum_apk_indices: vec![1744, 2175],
+                total_stake_indices: vec![2305, 2440],
+                non_signer_stake_indices: vec![vec![12], vec![]],
+            },
+            signed_quorum_numbers: vec![0, 1],
+        }
+    }
+}
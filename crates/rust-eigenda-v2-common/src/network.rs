@@ -0,0 +1,17 @@
+//! Chain ids and disperser endpoints of the public EigenDA networks, shared by the v1 and v2 clients.
+//!
+//! The disperser URLs have no port: tonic connects to port 443 for `https` endpoints.
+
+pub const MAINNET_CHAIN_ID: u64 = 1;
+pub const HOLESKY_CHAIN_ID: u64 = 17000;
+pub const SEPOLIA_CHAIN_ID: u64 = 11155111;
+
+/// Disperser of the v1 client on mainnet.
+pub const MAINNET_V1_DISPERSER_RPC_URL: &str = "https://disperser.eigenda.xyz";
+/// Disperser of the v1 client on Holesky.
+pub const HOLESKY_V1_DISPERSER_RPC_URL: &str = "https://disperser-holesky.eigenda.xyz";
+/// Disperser of the v1 client on Sepolia.
+pub const SEPOLIA_V1_DISPERSER_RPC_URL: &str = "https://disperser-testnet-sepolia.eigenda.xyz";
+
+/// Disperser of the v2 client on Holesky.
+pub const HOLESKY_V2_DISPERSER_RPC_URL: &str = "https://disperser-testnet-holesky.eigenda.xyz";
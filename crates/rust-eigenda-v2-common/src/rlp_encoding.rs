@@ -0,0 +1,415 @@
+//! RLP encoding of the certs, as done by the eigenda-proxy with go-ethereum's `rlp` package: structs are lists,
+//! unsigned integers and big integers are minimal big endian strings and fixed size byte arrays are strings.
+use ark_bn254::{G1Affine, G2Affine};
+use rlp::{DecoderError, Rlp, RlpStream};
+
+use crate::{
+    commitment_utils::{
+        g1_point_from_coordinates, g1_point_to_coordinates, g2_point_from_coordinates,
+        g2_point_to_coordinates,
+    },
+    BatchHeaderV2, BlobCertificate, BlobCommitments, BlobHeader, BlobInclusionInfo, EigenDACert,
+    EigenDACertError, EigenDACertV2, EigenDACertV3, EigenDACertVersion,
+    NonSignerStakesAndSignature, MAX_CERT_SIZE,
+};
+
+impl EigenDACertV2 {
+    /// RLP encodes the cert as the eigenda-proxy does.
+    pub fn to_rlp_bytes(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        append_blob_inclusion_info(&mut stream, &self.blob_inclusion_info);
+        append_batch_header(&mut stream, &self.batch_header);
+        append_non_signer_stakes_and_signature(&mut stream, &self.non_signer_stakes_and_signature);
+        stream.append(&self.signed_quorum_numbers);
+        stream.out().to_vec()
+    }
+
+    /// Decodes a RLP encoded cert, see [`EigenDACertV2::to_rlp_bytes`].
+    pub fn from_rlp_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        let rlp = Rlp::new(bytes);
+        check_single_item(&rlp, bytes)?;
+        let [blob_inclusion_info, batch_header, non_signer_stakes, signed_quorum_numbers] =
+            list(&rlp)?;
+        Ok(EigenDACertV2 {
+            blob_inclusion_info: decode_blob_inclusion_info(&blob_inclusion_info)?,
+            batch_header: decode_batch_header(&batch_header)?,
+            non_signer_stakes_and_signature: decode_non_signer_stakes_and_signature(
+                &non_signer_stakes,
+            )?,
+            signed_quorum_numbers: signed_quorum_numbers.as_val().map_err(invalid)?,
+        })
+    }
+}
+
+impl EigenDACertV3 {
+    /// RLP encodes the cert as the eigenda-proxy does.
+    pub fn to_rlp_bytes(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        append_batch_header(&mut stream, &self.batch_header);
+        append_blob_inclusion_info(&mut stream, &self.blob_inclusion_info);
+        append_non_signer_stakes_and_signature(&mut stream, &self.non_signer_stakes_and_signature);
+        stream.append(&self.signed_quorum_numbers);
+        stream.out().to_vec()
+    }
+
+    /// Decodes a RLP encoded cert, see [`EigenDACertV3::to_rlp_bytes`].
+    pub fn from_rlp_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        let rlp = Rlp::new(bytes);
+        check_single_item(&rlp, bytes)?;
+        let [batch_header, blob_inclusion_info, non_signer_stakes, signed_quorum_numbers] =
+            list(&rlp)?;
+        Ok(EigenDACertV3 {
+            batch_header: decode_batch_header(&batch_header)?,
+            blob_inclusion_info: decode_blob_inclusion_info(&blob_inclusion_info)?,
+            non_signer_stakes_and_signature: decode_non_signer_stakes_and_signature(
+                &non_signer_stakes,
+            )?,
+            signed_quorum_numbers: signed_quorum_numbers.as_val().map_err(invalid)?,
+        })
+    }
+}
+
+impl EigenDACert {
+    /// Transforms the EigenDACert into bytes: the version byte followed by the RLP encoded cert.
+    ///
+    /// This is the cert the eigenda-proxy returns and posts to the rollup inbox.
+    pub fn to_rlp_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.version().into()];
+        bytes.extend(match self {
+            EigenDACert::V2(cert) => cert.to_rlp_bytes(),
+            EigenDACert::V3(cert) => cert.to_rlp_bytes(),
+        });
+        bytes
+    }
+
+    /// Builds a new EigenDACert from bytes: the version byte followed by the RLP encoded cert
+    ///
+    /// Inputs longer than [`MAX_CERT_SIZE`] are rejected.
+    pub fn from_rlp_bytes(bytes: &[u8]) -> Result<Self, EigenDACertError> {
+        if bytes.len() > MAX_CERT_SIZE {
+            return Err(EigenDACertError::RlpDecoding(format!(
+                "cert size ({}) exceeds the maximum of {} bytes",
+                bytes.len(),
+                MAX_CERT_SIZE
+            )));
+        }
+        let (&version, cert_bytes) = bytes
+            .split_first()
+            .ok_or_else(|| EigenDACertError::RlpDecoding("missing cert version".to_string()))?;
+        match EigenDACertVersion::try_from(version)? {
+            EigenDACertVersion::V2 => {
+                EigenDACertV2::from_rlp_bytes(cert_bytes).map(EigenDACert::V2)
+            }
+            EigenDACertVersion::V3 => {
+                EigenDACertV3::from_rlp_bytes(cert_bytes).map(EigenDACert::V3)
+            }
+        }
+    }
+}
+
+/// Appends a big integer as go-ethereum does: its big endian bytes without leading zeros.
+fn append_big_int(stream: &mut RlpStream, value: &[u8; 32]) {
+    let start = value
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(value.len());
+    stream.append(&value[start..].to_vec());
+}
+
+fn append_g1_point(stream: &mut RlpStream, point: &G1Affine) {
+    stream.begin_list(2);
+    for coordinate in g1_point_to_coordinates(point) {
+        append_big_int(stream, &coordinate);
+    }
+}
+
+fn append_g2_point(stream: &mut RlpStream, point: &G2Affine) {
+    let [x1, x0, y1, y0] = g2_point_to_coordinates(point);
+    stream.begin_list(2);
+    for [c1, c0] in [[x1, x0], [y1, y0]] {
+        stream.begin_list(2);
+        append_big_int(stream, &c1);
+        append_big_int(stream, &c0);
+    }
+}
+
+fn append_blob_inclusion_info(stream: &mut RlpStream, blob_inclusion_info: &BlobInclusionInfo) {
+    let blob_certificate = &blob_inclusion_info.blob_certificate;
+    let blob_header = &blob_certificate.blob_header;
+    let commitment = &blob_header.commitment;
+
+    stream.begin_list(3);
+    stream.begin_list(3);
+    stream.begin_list(4);
+    stream.append(&blob_header.version);
+    stream.append(&blob_header.quorum_numbers);
+    stream.begin_list(4);
+    append_g1_point(stream, &commitment.commitment);
+    append_g2_point(stream, &commitment.length_commitment);
+    append_g2_point(stream, &commitment.length_proof);
+    stream.append(&commitment.length);
+    stream.append(&blob_header.payment_header_hash.to_vec());
+    stream.append(&blob_certificate.signature);
+    stream.append_list::<u32, u32>(&blob_certificate.relay_keys);
+    stream.append(&blob_inclusion_info.blob_index);
+    stream.append(&blob_inclusion_info.inclusion_proof);
+}
+
+fn append_batch_header(stream: &mut RlpStream, batch_header: &BatchHeaderV2) {
+    stream.begin_list(2);
+    stream.append(&batch_header.batch_root.to_vec());
+    stream.append(&batch_header.reference_block_number);
+}
+
+fn append_non_signer_stakes_and_signature(
+    stream: &mut RlpStream,
+    params: &NonSignerStakesAndSignature,
+) {
+    stream.begin_list(8);
+    stream.append_list::<u32, u32>(&params.non_signer_quorum_bitmap_indices);
+    stream.begin_list(params.non_signer_pubkeys.len());
+    for pubkey in &params.non_signer_pubkeys {
+        append_g1_point(stream, pubkey);
+    }
+    stream.begin_list(params.quorum_apks.len());
+    for apk in &params.quorum_apks {
+        append_g1_point(stream, apk);
+    }
+    append_g2_point(stream, &params.apk_g2);
+    append_g1_point(stream, &params.sigma);
+    stream.append_list::<u32, u32>(&params.quorum_apk_indices);
+    stream.append_list::<u32, u32>(&params.total_stake_indices);
+    stream.begin_list(params.non_signer_stake_indices.len());
+    for indices in &params.non_signer_stake_indices {
+        stream.append_list::<u32, u32>(indices);
+    }
+}
+
+fn invalid(err: impl ToString) -> EigenDACertError {
+    EigenDACertError::RlpDecoding(err.to_string())
+}
+
+/// Rejects input with trailing bytes after the encoded cert.
+fn check_single_item(rlp: &Rlp, bytes: &[u8]) -> Result<(), EigenDACertError> {
+    let total = rlp.payload_info().map_err(invalid)?.total();
+    if total != bytes.len() {
+        return Err(invalid(format!(
+            "expected {} bytes, got {}",
+            total,
+            bytes.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Reads the items of a list, failing on malformed items instead of stopping at them as [`Rlp::iter`] does.
+fn items<'a>(rlp: &Rlp<'a>) -> Result<Vec<Rlp<'a>>, EigenDACertError> {
+    if !rlp.is_list() {
+        return Err(invalid(DecoderError::RlpExpectedToBeList));
+    }
+    let count = rlp.item_count().map_err(invalid)?;
+    (0..count).map(|i| rlp.at(i).map_err(invalid)).collect()
+}
+
+fn list<'a, const N: usize>(rlp: &Rlp<'a>) -> Result<[Rlp<'a>; N], EigenDACertError> {
+    items(rlp)?.try_into().map_err(|items: Vec<Rlp<'a>>| {
+        invalid(format!("expected {} items, got {}", N, items.len()))
+    })
+}
+
+fn decode_bytes32(rlp: &Rlp) -> Result<[u8; 32], EigenDACertError> {
+    let bytes: Vec<u8> = rlp.as_val().map_err(invalid)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| invalid(format!("expected 32 bytes, got {}", bytes.len())))
+}
+
+/// Reads a big integer, rejecting leading zeros and values wider than 256 bits as go-ethereum does.
+fn decode_big_int(rlp: &Rlp) -> Result<[u8; 32], EigenDACertError> {
+    let bytes: Vec<u8> = rlp.as_val().map_err(invalid)?;
+    if bytes.first() == Some(&0) {
+        return Err(invalid(DecoderError::RlpInvalidIndirection));
+    }
+    if bytes.len() > 32 {
+        return Err(invalid(DecoderError::RlpIsTooBig));
+    }
+    let mut value = [0u8; 32];
+    value[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(value)
+}
+
+fn decode_g1_point(rlp: &Rlp) -> Result<G1Affine, EigenDACertError> {
+    let [x, y] = list(rlp)?;
+    Ok(g1_point_from_coordinates(&[
+        decode_big_int(&x)?,
+        decode_big_int(&y)?,
+    ])?)
+}
+
+fn decode_g2_point(rlp: &Rlp) -> Result<G2Affine, EigenDACertError> {
+    let [x, y] = list(rlp)?;
+    let [x1, x0] = list(&x)?;
+    let [y1, y0] = list(&y)?;
+    Ok(g2_point_from_coordinates(&[
+        decode_big_int(&x1)?,
+        decode_big_int(&x0)?,
+        decode_big_int(&y1)?,
+        decode_big_int(&y0)?,
+    ])?)
+}
+
+fn decode_indices(rlp: &Rlp) -> Result<Vec<u32>, EigenDACertError> {
+    items(rlp)?
+        .iter()
+        .map(|index| index.as_val().map_err(invalid))
+        .collect()
+}
+
+fn decode_g1_points(rlp: &Rlp) -> Result<Vec<G1Affine>, EigenDACertError> {
+    items(rlp)?.iter().map(decode_g1_point).collect()
+}
+
+fn decode_blob_inclusion_info(rlp: &Rlp) -> Result<BlobInclusionInfo, EigenDACertError> {
+    let [blob_certificate, blob_index, inclusion_proof] = list(rlp)?;
+    let [blob_header, signature, relay_keys] = list(&blob_certificate)?;
+    let [version, quorum_numbers, commitment, payment_header_hash] = list(&blob_header)?;
+    let [commitment, length_commitment, length_proof, length] = list(&commitment)?;
+    Ok(BlobInclusionInfo {
+        blob_certificate: BlobCertificate {
+            blob_header: BlobHeader {
+                version: version.as_val().map_err(invalid)?,
+                quorum_numbers: quorum_numbers.as_val().map_err(invalid)?,
+                commitment: BlobCommitments {
+                    commitment: decode_g1_point(&commitment)?,
+                    length_commitment: decode_g2_point(&length_commitment)?,
+                    length_proof: decode_g2_point(&length_proof)?,
+                    length: length.as_val().map_err(invalid)?,
+                },
+                payment_header_hash: decode_bytes32(&payment_header_hash)?,
+            },
+            signature: signature.as_val().map_err(invalid)?,
+            relay_keys: decode_indices(&relay_keys)?,
+        },
+        blob_index: blob_index.as_val().map_err(invalid)?,
+        inclusion_proof: inclusion_proof.as_val().map_err(invalid)?,
+    })
+}
+
+fn decode_batch_header(rlp: &Rlp) -> Result<BatchHeaderV2, EigenDACertError> {
+    let [batch_root, reference_block_number] = list(rlp)?;
+    Ok(BatchHeaderV2 {
+        batch_root: decode_bytes32(&batch_root)?,
+        reference_block_number: reference_block_number.as_val().map_err(invalid)?,
+    })
+}
+
+fn decode_non_signer_stakes_and_signature(
+    rlp: &Rlp,
+) -> Result<NonSignerStakesAndSignature, EigenDACertError> {
+    let [bitmap_indices, pubkeys, apks, apk_g2, sigma, apk_indices, total_indices, stake_indices] =
+        list(rlp)?;
+    Ok(NonSignerStakesAndSignature {
+        non_signer_quorum_bitmap_indices: decode_indices(&bitmap_indices)?,
+        non_signer_pubkeys: decode_g1_points(&pubkeys)?,
+        quorum_apks: decode_g1_points(&apks)?,
+        apk_g2: decode_g2_point(&apk_g2)?,
+        sigma: decode_g1_point(&sigma)?,
+        quorum_apk_indices: decode_indices(&apk_indices)?,
+        total_stake_indices: decode_indices(&total_indices)?,
+        non_signer_stake_indices: items(&stake_indices)?
+            .iter()
+            .map(decode_indices)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rlp::{Rlp, RlpStream};
+
+    use super::{decode_big_int, decode_g1_point};
+    use crate::{
+        tests::{
+            decode_hex, get_test_eigenda_cert_v2, EIGENDA_CERT_V2_RLP_HEX, EIGENDA_CERT_V3_RLP_HEX,
+        },
+        EigenDACert, EigenDACertError, EigenDACertV2, EigenDACertV3,
+    };
+
+    #[test]
+    fn test_rlp_regression_vectors() {
+        let cert_v2 = get_test_eigenda_cert_v2();
+        let expected_v2 = decode_hex(EIGENDA_CERT_V2_RLP_HEX);
+        assert_eq!(cert_v2.to_rlp_bytes(), expected_v2);
+        assert_eq!(
+            EigenDACertV2::from_rlp_bytes(&expected_v2).unwrap(),
+            cert_v2
+        );
+
+        let cert_v3: EigenDACertV3 = cert_v2.into();
+        let expected_v3 = decode_hex(EIGENDA_CERT_V3_RLP_HEX);
+        assert_eq!(cert_v3.to_rlp_bytes(), expected_v3);
+        assert_eq!(
+            EigenDACertV3::from_rlp_bytes(&expected_v3).unwrap(),
+            cert_v3
+        );
+    }
+
+    #[test]
+    fn test_versioned_rlp_encoding() {
+        let cert_v2 = get_test_eigenda_cert_v2();
+        for cert in [
+            EigenDACert::V2(cert_v2.clone()),
+            EigenDACert::V3(cert_v2.into()),
+        ] {
+            let bytes = cert.to_rlp_bytes();
+            assert_eq!(bytes[0], u8::from(cert.version()));
+            assert_eq!(EigenDACert::from_rlp_bytes(&bytes).unwrap(), cert);
+        }
+
+        let mut bytes = decode_hex(EIGENDA_CERT_V2_RLP_HEX);
+        bytes.insert(0, 0);
+        assert!(matches!(
+            EigenDACert::from_rlp_bytes(&bytes),
+            Err(EigenDACertError::UnsupportedVersion(0))
+        ));
+        assert!(EigenDACert::from_rlp_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_rlp_decoding_rejects_malformed_certs() {
+        let expected = decode_hex(EIGENDA_CERT_V2_RLP_HEX);
+
+        let mut trailing = expected.clone();
+        trailing.push(0);
+        assert!(matches!(
+            EigenDACertV2::from_rlp_bytes(&trailing),
+            Err(EigenDACertError::RlpDecoding(_))
+        ));
+        assert!(matches!(
+            EigenDACertV2::from_rlp_bytes(&expected[..expected.len() - 1]),
+            Err(EigenDACertError::RlpDecoding(_))
+        ));
+        // the fields of a V2 cert are in a different order than the ones of a V3 cert
+        assert!(EigenDACertV3::from_rlp_bytes(&expected).is_err());
+    }
+
+    #[test]
+    fn test_rlp_point_decoding() {
+        // big integers with leading zeros are not canonical
+        assert!(decode_big_int(&Rlp::new(&[0x82, 0x00, 0x01])).is_err());
+        assert_eq!(decode_big_int(&Rlp::new(&[0x80])).unwrap(), [0u8; 32]);
+
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&1u8).append(&3u8);
+        let off_curve = stream.out().to_vec();
+        assert!(matches!(
+            decode_g1_point(&Rlp::new(&off_curve)),
+            Err(EigenDACertError::Conversion(_))
+        ));
+
+        let mut stream = RlpStream::new_list(2);
+        stream.append_empty_data().append_empty_data();
+        let infinity = stream.out().to_vec();
+        assert!(decode_g1_point(&Rlp::new(&infinity)).unwrap().infinity);
+    }
+}
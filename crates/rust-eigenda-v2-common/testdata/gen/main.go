@@ -0,0 +1,296 @@
+// Command gen writes the RLP and ABI encodings of the cert in ../eigenda_cert_v2.json, as produced by the eigenda Go
+// client, to the .rlp.hex and .abi.hex files next to it. See ../README.md for how to run it.
+//
+// The RLP encodings are rlp.EncodeToBytes of the coretypes certs, as posted by the eigenda-proxy. The ABI encodings
+// are abi.encode(cert) of the Solidity EigenDACertV2 and EigenDACertV3 structs, packed from the same values.
+package main
+
+import (
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"os"
+	"path/filepath"
+	"strings"
+
+	verifierV2 "github.com/Layr-Labs/eigenda/contracts/bindings/EigenDACertVerifierV2"
+	"github.com/Layr-Labs/eigenda/api/clients/v2/coretypes"
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/rlp"
+)
+
+type g1JSON struct {
+	X string `json:"x"`
+	Y string `json:"y"`
+}
+
+type fq2JSON struct {
+	C0 string `json:"c0"`
+	C1 string `json:"c1"`
+}
+
+type g2JSON struct {
+	X fq2JSON `json:"x"`
+	Y fq2JSON `json:"y"`
+}
+
+// certJSON is the JSON representation of the Rust crate, see ../../schema/eigenda_cert.schema.json.
+type certJSON struct {
+	Version string `json:"version"`
+	Cert    struct {
+		BlobInclusionInfo struct {
+			BlobCertificate struct {
+				BlobHeader struct {
+					Version       uint16   `json:"version"`
+					QuorumNumbers []uint16 `json:"quorum_numbers"`
+					Commitment    struct {
+						Commitment       g1JSON `json:"commitment"`
+						LengthCommitment g2JSON `json:"length_commitment"`
+						LengthProof      g2JSON `json:"length_proof"`
+						Length           uint32 `json:"length"`
+					} `json:"commitment"`
+					PaymentHeaderHash string `json:"payment_header_hash"`
+				} `json:"blob_header"`
+				Signature string   `json:"signature"`
+				RelayKeys []uint32 `json:"relay_keys"`
+			} `json:"blob_certificate"`
+			BlobIndex      uint32 `json:"blob_index"`
+			InclusionProof string `json:"inclusion_proof"`
+		} `json:"blob_inclusion_info"`
+		BatchHeader struct {
+			BatchRoot            string `json:"batch_root"`
+			ReferenceBlockNumber uint32 `json:"reference_block_number"`
+		} `json:"batch_header"`
+		NonSignerStakesAndSignature struct {
+			NonSignerQuorumBitmapIndices []uint32   `json:"non_signer_quorum_bitmap_indices"`
+			NonSignerPubkeys             []g1JSON   `json:"non_signer_pubkeys"`
+			QuorumApks                   []g1JSON   `json:"quorum_apks"`
+			ApkG2                        g2JSON     `json:"apk_g2"`
+			Sigma                        g1JSON     `json:"sigma"`
+			QuorumApkIndices             []uint32   `json:"quorum_apk_indices"`
+			TotalStakeIndices            []uint32   `json:"total_stake_indices"`
+			NonSignerStakeIndices        [][]uint32 `json:"non_signer_stake_indices"`
+		} `json:"non_signer_stakes_and_signature"`
+		SignedQuorumNumbers []uint16 `json:"signed_quorum_numbers"`
+	} `json:"cert"`
+}
+
+func main() {
+	dir := "."
+	if len(os.Args) > 1 {
+		dir = os.Args[1]
+	}
+	contents, err := os.ReadFile(filepath.Join(dir, "eigenda_cert_v2.json"))
+	check(err)
+	var parsed certJSON
+	check(json.Unmarshal(contents, &parsed))
+	if parsed.Version != "V2" {
+		panic(fmt.Sprintf("expected a V2 cert, got %s", parsed.Version))
+	}
+
+	certV2 := toCertV2(&parsed)
+	certV3 := certV2.ToV3()
+
+	write(dir, "eigenda_cert_v2.rlp.hex", encodeRLP(certV2))
+	write(dir, "eigenda_cert_v3.rlp.hex", encodeRLP(certV3))
+	write(dir, "eigenda_cert_v2.abi.hex", encodeABI(certV2Type(), certV2))
+	write(dir, "eigenda_cert_v3.abi.hex", encodeABI(certV3Type(), certV3))
+}
+
+func toCertV2(parsed *certJSON) *coretypes.EigenDACertV2 {
+	cert := &parsed.Cert
+	info := &cert.BlobInclusionInfo
+	header := &info.BlobCertificate.BlobHeader
+	nonSigner := &cert.NonSignerStakesAndSignature
+
+	return &coretypes.EigenDACertV2{
+		BlobInclusionInfo: verifierV2.EigenDATypesV2BlobInclusionInfo{
+			BlobCertificate: verifierV2.EigenDATypesV2BlobCertificate{
+				BlobHeader: verifierV2.EigenDATypesV2BlobHeaderV2{
+					Version:       header.Version,
+					QuorumNumbers: toBytes(header.QuorumNumbers),
+					Commitment: verifierV2.EigenDATypesV2BlobCommitment{
+						Commitment:       toG1(header.Commitment.Commitment),
+						LengthCommitment: toG2(header.Commitment.LengthCommitment),
+						LengthProof:      toG2(header.Commitment.LengthProof),
+						Length:           header.Commitment.Length,
+					},
+					PaymentHeaderHash: toBytes32(header.PaymentHeaderHash),
+				},
+				Signature: decodeHex(info.BlobCertificate.Signature),
+				RelayKeys: info.BlobCertificate.RelayKeys,
+			},
+			BlobIndex:      info.BlobIndex,
+			InclusionProof: decodeHex(info.InclusionProof),
+		},
+		BatchHeader: verifierV2.EigenDATypesV2BatchHeaderV2{
+			BatchRoot:            toBytes32(cert.BatchHeader.BatchRoot),
+			ReferenceBlockNumber: cert.BatchHeader.ReferenceBlockNumber,
+		},
+		NonSignerStakesAndSignature: verifierV2.EigenDATypesV1NonSignerStakesAndSignature{
+			NonSignerQuorumBitmapIndices: nonSigner.NonSignerQuorumBitmapIndices,
+			NonSignerPubkeys:             toG1s(nonSigner.NonSignerPubkeys),
+			QuorumApks:                   toG1s(nonSigner.QuorumApks),
+			ApkG2:                        toG2(nonSigner.ApkG2),
+			Sigma:                        toG1(nonSigner.Sigma),
+			QuorumApkIndices:             nonSigner.QuorumApkIndices,
+			TotalStakeIndices:            nonSigner.TotalStakeIndices,
+			NonSignerStakeIndices:        nonSigner.NonSignerStakeIndices,
+		},
+		SignedQuorumNumbers: toBytes(cert.SignedQuorumNumbers),
+	}
+}
+
+func encodeRLP(cert any) []byte {
+	bytes, err := rlp.EncodeToBytes(cert)
+	check(err)
+	return bytes
+}
+
+// encodeABI packs the cert as abi.encode(cert), matching the fields of the Go structs by name.
+func encodeABI(certType abi.Type, cert any) []byte {
+	bytes, err := abi.Arguments{{Type: certType}}.Pack(cert)
+	check(err)
+	return bytes
+}
+
+func certV2Type() abi.Type {
+	return newTuple(
+		tuple("blobInclusionInfo", blobInclusionInfoComponents()...),
+		tuple("batchHeader", batchHeaderComponents()...),
+		tuple("nonSignerStakesAndSignature", nonSignerStakesAndSignatureComponents()...),
+		field("signedQuorumNumbers", "bytes"),
+	)
+}
+
+func certV3Type() abi.Type {
+	return newTuple(
+		tuple("batchHeader", batchHeaderComponents()...),
+		tuple("blobInclusionInfo", blobInclusionInfoComponents()...),
+		tuple("nonSignerStakesAndSignature", nonSignerStakesAndSignatureComponents()...),
+		field("signedQuorumNumbers", "bytes"),
+	)
+}
+
+func blobInclusionInfoComponents() []abi.ArgumentMarshaling {
+	return []abi.ArgumentMarshaling{
+		tuple("blobCertificate",
+			tuple("blobHeader",
+				field("version", "uint16"),
+				field("quorumNumbers", "bytes"),
+				tuple("commitment",
+					tuple("commitment", g1Components()...),
+					tuple("lengthCommitment", g2Components()...),
+					tuple("lengthProof", g2Components()...),
+					field("length", "uint32"),
+				),
+				field("paymentHeaderHash", "bytes32"),
+			),
+			field("signature", "bytes"),
+			field("relayKeys", "uint32[]"),
+		),
+		field("blobIndex", "uint32"),
+		field("inclusionProof", "bytes"),
+	}
+}
+
+func batchHeaderComponents() []abi.ArgumentMarshaling {
+	return []abi.ArgumentMarshaling{
+		field("batchRoot", "bytes32"),
+		field("referenceBlockNumber", "uint32"),
+	}
+}
+
+func nonSignerStakesAndSignatureComponents() []abi.ArgumentMarshaling {
+	return []abi.ArgumentMarshaling{
+		field("nonSignerQuorumBitmapIndices", "uint32[]"),
+		{Name: "nonSignerPubkeys", Type: "tuple[]", Components: g1Components()},
+		{Name: "quorumApks", Type: "tuple[]", Components: g1Components()},
+		tuple("apkG2", g2Components()...),
+		tuple("sigma", g1Components()...),
+		field("quorumApkIndices", "uint32[]"),
+		field("totalStakeIndices", "uint32[]"),
+		field("nonSignerStakeIndices", "uint32[][]"),
+	}
+}
+
+func g1Components() []abi.ArgumentMarshaling {
+	return []abi.ArgumentMarshaling{field("X", "uint256"), field("Y", "uint256")}
+}
+
+func g2Components() []abi.ArgumentMarshaling {
+	return []abi.ArgumentMarshaling{field("X", "uint256[2]"), field("Y", "uint256[2]")}
+}
+
+func field(name string, typ string) abi.ArgumentMarshaling {
+	return abi.ArgumentMarshaling{Name: name, Type: typ}
+}
+
+func tuple(name string, components ...abi.ArgumentMarshaling) abi.ArgumentMarshaling {
+	return abi.ArgumentMarshaling{Name: name, Type: "tuple", Components: components}
+}
+
+func newTuple(components ...abi.ArgumentMarshaling) abi.Type {
+	typ, err := abi.NewType("tuple", "", components)
+	check(err)
+	return typ
+}
+
+func toG1(point g1JSON) verifierV2.BN254G1Point {
+	return verifierV2.BN254G1Point{X: toBig(point.X), Y: toBig(point.Y)}
+}
+
+func toG1s(points []g1JSON) []verifierV2.BN254G1Point {
+	converted := make([]verifierV2.BN254G1Point, len(points))
+	for i, point := range points {
+		converted[i] = toG1(point)
+	}
+	return converted
+}
+
+// toG2 orders the coordinates as the contracts do: c1 before c0.
+func toG2(point g2JSON) verifierV2.BN254G2Point {
+	return verifierV2.BN254G2Point{
+		X: [2]*big.Int{toBig(point.X.C1), toBig(point.X.C0)},
+		Y: [2]*big.Int{toBig(point.Y.C1), toBig(point.Y.C0)},
+	}
+}
+
+func toBig(value string) *big.Int {
+	integer, ok := new(big.Int).SetString(strings.TrimPrefix(value, "0x"), 16)
+	if !ok {
+		panic(fmt.Sprintf("invalid integer %s", value))
+	}
+	return integer
+}
+
+func toBytes(values []uint16) []byte {
+	bytes := make([]byte, len(values))
+	for i, value := range values {
+		bytes[i] = byte(value)
+	}
+	return bytes
+}
+
+func toBytes32(value string) [32]byte {
+	var bytes [32]byte
+	copy(bytes[:], decodeHex(value))
+	return bytes
+}
+
+func decodeHex(value string) []byte {
+	bytes, err := hex.DecodeString(strings.TrimPrefix(value, "0x"))
+	check(err)
+	return bytes
+}
+
+func write(dir string, name string, bytes []byte) {
+	check(os.WriteFile(filepath.Join(dir, name), []byte(hex.EncodeToString(bytes)+"\n"), 0o644))
+}
+
+func check(err error) {
+	if err != nil {
+		panic(err)
+	}
+}